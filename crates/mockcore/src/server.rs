@@ -78,7 +78,7 @@ impl Api for Server {
       network_active: true,
       networks: Vec::new(),
       relay_fee: Amount::from_sat(0),
-      incremental_fee: Amount::from_sat(0),
+      incremental_fee: Amount::from_sat(1000),
       local_addresses: Vec::new(),
       warnings: StringOrStringArray::String(String::new()),
     })
@@ -244,38 +244,74 @@ impl Api for Server {
   ) -> Result<Option<GetTxOutResult>, jsonrpc_core::Error> {
     let state = self.state();
 
-    let Some(value) = state.utxos.get(&OutPoint { txid, vout }) else {
+    let outpoint = OutPoint { txid, vout };
+
+    let Some(value) = state.utxos.get(&outpoint).copied().or_else(|| {
+      state
+        .mempool
+        .iter()
+        .find(|tx| tx.compute_txid() == txid)
+        .and_then(|tx| tx.output.get(usize::try_from(vout).unwrap()))
+        .filter(|_| {
+          !state
+            .mempool
+            .iter()
+            .flat_map(|tx| &tx.input)
+            .any(|txin| txin.previous_output == outpoint)
+        })
+        .map(|txout| txout.value)
+    }) else {
       return Ok(None);
     };
 
     let mut confirmations = None;
+    let mut script_pubkey = None;
 
     for (height, hash) in state.hashes.iter().enumerate() {
       for tx in &state.blocks[hash].txdata {
         if tx.compute_txid() == txid {
           confirmations = Some(state.hashes.len() - height);
+          script_pubkey = Some(
+            tx.output[usize::try_from(vout).unwrap()]
+              .script_pubkey
+              .clone(),
+          );
         }
       }
     }
 
+    let script_pubkey = script_pubkey.or_else(|| {
+      state
+        .mempool
+        .iter()
+        .find(|tx| tx.compute_txid() == txid)
+        .map(|tx| {
+          tx.output[usize::try_from(vout).unwrap()]
+            .script_pubkey
+            .clone()
+        })
+    });
+
     Ok(Some(GetTxOutResult {
       bestblock: BlockHash::all_zeros(),
       coinbase: false,
-      confirmations: confirmations.unwrap().try_into().unwrap(),
+      confirmations: confirmations.unwrap_or_default().try_into().unwrap(),
       script_pub_key: GetRawTransactionResultVoutScriptPubKey {
         asm: String::new(),
-        hex: Vec::new(),
+        hex: script_pubkey.unwrap_or_default().into_bytes(),
         req_sigs: None,
         type_: None,
         addresses: Vec::new(),
         address: None,
       },
-      value: *value,
+      value,
     }))
   }
 
   fn get_wallet_info(&self) -> Result<GetWalletInfoResult, jsonrpc_core::Error> {
-    if let Some(wallet_name) = self.state().loaded_wallets.first().cloned() {
+    let state = self.state();
+
+    if let Some(wallet_name) = state.loaded_wallets.first().cloned() {
       Ok(GetWalletInfoResult {
         avoid_reuse: None,
         balance: Amount::from_sat(0),
@@ -285,7 +321,7 @@ impl Api for Server {
         keypool_size: 0,
         keypool_size_hd_internal: 0,
         pay_tx_fee: Amount::from_sat(0),
-        private_keys_enabled: true,
+        private_keys_enabled: !state.watch_only(),
         scanning: None,
         tx_count: 0,
         unconfirmed_balance: Amount::from_sat(0),
@@ -335,12 +371,17 @@ impl Api for Server {
   fn create_wallet(
     &self,
     name: String,
-    _disable_private_keys: Option<bool>,
+    disable_private_keys: Option<bool>,
     _blank: Option<bool>,
     _passphrase: Option<String>,
     _avoid_reuse: Option<bool>,
   ) -> Result<LoadWalletResult, jsonrpc_core::Error> {
     self.state().wallets.insert(name.clone());
+
+    if disable_private_keys.unwrap_or_default() {
+      self.state().watch_only_wallets.insert(name.clone());
+    }
+
     Ok(LoadWalletResult {
       name,
       warning: None,
@@ -388,7 +429,12 @@ impl Api for Server {
 
     if output_value > input_value {
       for (value, outpoint) in utxos {
-        if state.locked.contains(&outpoint) {
+        if state.locked.contains(&outpoint)
+          || transaction
+            .input
+            .iter()
+            .any(|txin| txin.previous_output == outpoint)
+        {
           continue;
         }
 
@@ -542,6 +588,43 @@ impl Api for Server {
       }
     }
 
+    // replace conflicting transactions which signal replaceability, along
+    // with their descendants
+    let mut replaced = tx
+      .input
+      .iter()
+      .map(|txin| txin.previous_output)
+      .collect::<BTreeSet<OutPoint>>();
+
+    let mut descendants = false;
+
+    loop {
+      let (conflicting, mempool) = mem::take(&mut state.mempool)
+        .into_iter()
+        .partition::<Vec<Transaction>, _>(|mempool_tx| {
+          (descendants || mempool_tx.is_explicitly_rbf())
+            && mempool_tx
+              .input
+              .iter()
+              .any(|txin| replaced.contains(&txin.previous_output))
+        });
+
+      descendants = true;
+
+      state.mempool = mempool;
+
+      if conflicting.is_empty() {
+        break;
+      }
+
+      for conflicting in conflicting {
+        replaced.extend((0..conflicting.output.len()).map(|vout| OutPoint {
+          txid: conflicting.compute_txid(),
+          vout: vout.try_into().unwrap(),
+        }));
+      }
+    }
+
     state.mempool.push(tx.clone());
 
     Ok(tx.compute_txid().to_string())
@@ -625,18 +708,24 @@ impl Api for Server {
   ) -> Result<Value, jsonrpc_core::Error> {
     let state = self.state();
 
-    let Some(tx) = state.transactions.get(&txid) else {
+    let Some(tx) = state
+      .transactions
+      .get(&txid)
+      .or_else(|| state.mempool.iter().find(|tx| tx.compute_txid() == txid))
+    else {
       return Err(jsonrpc_core::Error::new(
         jsonrpc_core::types::error::ErrorCode::ServerError(-8),
       ));
     };
 
     let mut confirmations = None;
+    let mut block = None;
 
     'outer: for (height, hash) in state.hashes.iter().enumerate() {
-      for tx in &state.blocks[hash].txdata {
+      for (index, tx) in state.blocks[hash].txdata.iter().enumerate() {
         if tx.compute_txid() == txid {
           confirmations = Some(state.hashes.len() - height);
+          block = Some((height, *hash, index, state.blocks[hash].header.time));
           break 'outer;
         }
       }
@@ -646,13 +735,13 @@ impl Api for Server {
       serde_json::to_value(GetTransactionResult {
         info: WalletTxInfo {
           txid,
-          confirmations: confirmations.unwrap().try_into().unwrap(),
+          confirmations: confirmations.unwrap_or_default().try_into().unwrap(),
           time: 0,
           timereceived: 0,
-          blockhash: None,
-          blockindex: None,
-          blockheight: None,
-          blocktime: None,
+          blockhash: block.map(|(_height, hash, _index, _time)| hash),
+          blockindex: block.map(|(_height, _hash, index, _time)| index),
+          blockheight: block.map(|(height, _hash, _index, _time)| height.try_into().unwrap()),
+          blocktime: block.map(|(_height, _hash, _index, time)| time.into()),
           wallet_conflicts: Vec::new(),
           bip125_replaceable: Bip125Replaceable::Unknown,
         },
@@ -723,13 +812,149 @@ impl Api for Server {
         None => Err(Self::not_found()),
       }
     } else {
-      match state.transactions.get(&txid) {
+      match state
+        .transactions
+        .get(&txid)
+        .or_else(|| state.mempool.iter().find(|tx| tx.compute_txid() == txid))
+      {
         Some(tx) => Ok(Value::String(hex::encode(serialize(tx)))),
         None => Err(Self::not_found()),
       }
     }
   }
 
+  fn get_raw_mempool(&self) -> Result<Vec<Txid>, jsonrpc_core::Error> {
+    Ok(
+      self
+        .state()
+        .mempool
+        .iter()
+        .map(Transaction::compute_txid)
+        .collect(),
+    )
+  }
+
+  fn get_mempool_entry(&self, txid: Txid) -> Result<GetMempoolEntryResult, jsonrpc_core::Error> {
+    let state = self.state();
+
+    let Some(tx) = state.mempool.iter().find(|tx| tx.compute_txid() == txid) else {
+      return Err(Self::not_found());
+    };
+
+    let find = |txid: Txid| {
+      state
+        .transactions
+        .get(&txid)
+        .or_else(|| state.mempool.iter().find(|tx| tx.compute_txid() == txid))
+    };
+
+    let fee = |tx: &Transaction| {
+      tx.input
+        .iter()
+        .filter_map(|txin| {
+          find(txin.previous_output.txid)
+            .map(|prev| prev.output[usize::try_from(txin.previous_output.vout).unwrap()].value)
+        })
+        .sum::<Amount>()
+        .checked_sub(tx.output.iter().map(|txout| txout.value).sum())
+        .unwrap_or_default()
+    };
+
+    let spent_by = |txid: Txid| {
+      state
+        .mempool
+        .iter()
+        .filter(|tx| {
+          tx.input
+            .iter()
+            .any(|txin| txin.previous_output.txid == txid)
+        })
+        .collect::<Vec<&Transaction>>()
+    };
+
+    let depends = |tx: &Transaction| {
+      tx.input
+        .iter()
+        .filter_map(|txin| {
+          state
+            .mempool
+            .iter()
+            .find(|parent| parent.compute_txid() == txin.previous_output.txid)
+        })
+        .collect::<Vec<&Transaction>>()
+    };
+
+    let mut descendants = Vec::new();
+    let mut pending = vec![tx];
+
+    while let Some(tx) = pending.pop() {
+      if !descendants.contains(&tx) {
+        descendants.push(tx);
+        pending.extend(spent_by(tx.compute_txid()));
+      }
+    }
+
+    let mut ancestors = Vec::new();
+    let mut pending = vec![tx];
+
+    while let Some(tx) = pending.pop() {
+      if !ancestors.contains(&tx) {
+        ancestors.push(tx);
+        pending.extend(depends(tx));
+      }
+    }
+
+    let base = fee(tx);
+
+    Ok(GetMempoolEntryResult {
+      vsize: tx.vsize().try_into().unwrap(),
+      weight: Some(tx.weight().to_wu()),
+      time: 0,
+      height: (state.hashes.len() - 1).try_into().unwrap(),
+      descendant_count: descendants.len().try_into().unwrap(),
+      descendant_size: descendants
+        .iter()
+        .map(|tx| u64::try_from(tx.vsize()).unwrap())
+        .sum(),
+      ancestor_count: ancestors.len().try_into().unwrap(),
+      ancestor_size: ancestors
+        .iter()
+        .map(|tx| u64::try_from(tx.vsize()).unwrap())
+        .sum(),
+      wtxid: Txid::from_raw_hash(tx.compute_wtxid().to_raw_hash()),
+      fees: GetMempoolEntryResultFees {
+        base,
+        modified: base,
+        ancestor: ancestors.iter().map(|tx| fee(tx)).sum(),
+        descendant: descendants.iter().map(|tx| fee(tx)).sum(),
+      },
+      depends: depends(tx)
+        .into_iter()
+        .map(Transaction::compute_txid)
+        .collect(),
+      spent_by: spent_by(txid)
+        .into_iter()
+        .map(Transaction::compute_txid)
+        .collect(),
+      bip125_replaceable: true,
+      unbroadcast: Some(false),
+    })
+  }
+
+  fn get_address_info(
+    &self,
+    address: Address<NetworkUnchecked>,
+  ) -> Result<Value, jsonrpc_core::Error> {
+    let address = address.require_network(self.network).unwrap();
+
+    Ok(serde_json::json!({
+      "address": address.to_string(),
+      "scriptPubKey": address.script_pubkey().to_hex_string(),
+      "ismine": self.state().is_wallet_address(&address),
+      "labels": [],
+    }))
+  }
+
   fn list_unspent(
     &self,
     minconf: Option<usize>,
@@ -846,7 +1071,7 @@ impl Api for Server {
     &self,
     _label: Option<String>,
     count: Option<u16>,
-    _skip: Option<usize>,
+    skip: Option<usize>,
     _include_watchonly: Option<bool>,
   ) -> Result<Vec<ListTransactionResult>, jsonrpc_core::Error> {
     let state = self.state();
@@ -854,9 +1079,10 @@ impl Api for Server {
       state
         .transactions
         .iter()
-        .take(count.unwrap_or(u16::MAX).into())
         .map(|(txid, tx)| (*txid, tx))
         .chain(state.mempool.iter().map(|tx| (tx.compute_txid(), tx)))
+        .skip(skip.unwrap_or_default())
+        .take(count.unwrap_or(u16::MAX).into())
         .map(|(txid, tx)| ListTransactionResult {
           info: WalletTxInfo {
             confirmations: state.get_confirmations(tx),
@@ -969,10 +1195,13 @@ impl Api for Server {
     &self,
     psbt: String,
     sign: Option<bool>,
-    sighash_type: Option<()>,
+    sighash_type: Option<String>,
     bip32derivs: Option<bool>,
   ) -> Result<WalletProcessPsbtResult, jsonrpc_core::Error> {
-    assert!(sighash_type.is_none());
+    assert!(
+      matches!(sighash_type.as_deref(), None | Some("SINGLE|ANYONECANPAY")),
+      "walletprocesspsbt: sighash type {sighash_type:?} is not supported",
+    );
     assert!(bip32derivs.is_none());
 
     let mut psbt = Psbt::deserialize(
@@ -983,28 +1212,41 @@ impl Api for Server {
     .unwrap();
 
     for (i, txin) in psbt.unsigned_tx.input.iter().enumerate() {
-      psbt.inputs[i].witness_utxo = Some(
-        self
-          .state()
-          .transactions
-          .get(&txin.previous_output.txid)
-          .unwrap()
-          .output[txin.previous_output.vout as usize]
-          .clone(),
-      );
+      let state = self.state();
+
+      // inputs spending transactions which have not been broadcast, like
+      // inscription commit transactions, are left as is
+      if let Some(tx) = state
+        .transactions
+        .get(&txin.previous_output.txid)
+        .or_else(|| {
+          state
+            .mempool
+            .iter()
+            .find(|tx| tx.compute_txid() == txin.previous_output.txid)
+        })
+      {
+        psbt.inputs[i].witness_utxo = Some(tx.output[txin.previous_output.vout as usize].clone());
+      }
     }
 
     if let Some(sign) = sign {
-      if sign {
+      if sign && !self.state().watch_only() {
         for input in psbt.inputs.iter_mut() {
-          let address = Address::from_script(
-            &input.witness_utxo.as_ref().unwrap().script_pubkey,
-            self.network,
-          )
-          .unwrap();
+          let Some(witness_utxo) = &input.witness_utxo else {
+            continue;
+          };
+
+          let address = Address::from_script(&witness_utxo.script_pubkey, self.network).unwrap();
 
           if self.state().is_wallet_address(&address) {
-            input.final_script_witness = Some(Witness::from_slice(&[&[0; 64]]));
+            input.final_script_witness = Some(match sighash_type.as_deref() {
+              // signatures with a non-default sighash type end with the sighash byte
+              Some("SINGLE|ANYONECANPAY") => {
+                Witness::from_slice(&[[[0; 64].as_slice(), &[0x83]].concat()])
+              }
+              _ => Witness::from_slice(&[&[0; 64]]),
+            });
           }
         }
       }
@@ -1023,16 +1265,21 @@ impl Api for Server {
   ) -> Result<FinalizePsbtResult, jsonrpc_core::Error> {
     assert!(extract.is_none());
 
-    let mut transaction = Psbt::deserialize(
+    let psbt = Psbt::deserialize(
       &base64::engine::general_purpose::STANDARD
         .decode(psbt)
         .unwrap(),
     )
-    .unwrap()
-    .unsigned_tx;
+    .unwrap();
 
-    for input in &mut transaction.input {
-      if input.witness.is_empty() {
+    let mut transaction = psbt.unsigned_tx;
+
+    for (input, psbt_input) in transaction.input.iter_mut().zip(psbt.inputs) {
+      if let Some(witness) = psbt_input.final_script_witness {
+        input.witness = witness;
+      } else if let Some(signature) = psbt_input.tap_key_sig {
+        input.witness = Witness::p2tr_key_spend(&signature);
+      } else if input.witness.is_empty() {
         input.witness = Witness::from_slice(&[&[0; 64]]);
       }
     }
@@ -1066,7 +1313,7 @@ impl Api for Server {
 
         let txout = &tx.output[usize::try_from(input.previous_output.vout).unwrap()];
 
-        let address = Address::from_script(&txout.script_pubkey, Network::Bitcoin).unwrap();
+        let address = Address::from_script(&txout.script_pubkey, self.state().network).unwrap();
 
         if self.state().is_wallet_address(&address) {
           balance_change -= i64::try_from(txout.value.to_sat()).unwrap();
@@ -1074,7 +1321,10 @@ impl Api for Server {
       }
 
       for output in tx.output {
-        let address = Address::from_script(&output.script_pubkey, Network::Bitcoin).unwrap();
+        let Ok(address) = Address::from_script(&output.script_pubkey, self.state().network) else {
+          continue;
+        };
+
         if self.state().is_wallet_address(&address) {
           balance_change += i64::try_from(output.value.to_sat()).unwrap();
         }
@@ -19,6 +19,7 @@ pub struct State {
   pub version: usize,
   pub wallet: Wallet,
   pub wallets: BTreeSet<String>,
+  pub watch_only_wallets: BTreeSet<String>,
 }
 
 impl State {
@@ -49,9 +50,17 @@ impl State {
       version,
       wallet: Wallet::new(network),
       wallets: BTreeSet::new(),
+      watch_only_wallets: BTreeSet::new(),
     }
   }
 
+  pub(crate) fn watch_only(&self) -> bool {
+    self
+      .loaded_wallets
+      .first()
+      .is_some_and(|wallet| self.watch_only_wallets.contains(wallet))
+  }
+
   pub fn clear_wallet_addresses(&mut self) -> BTreeSet<Address> {
     mem::take(&mut self.receive_addresses)
       .into_iter()
@@ -4,6 +4,8 @@ use {
 };
 
 pub use crate::{
+  cursor::{Cursor, Order},
+  index::CollectionOrder,
   subcommand::decode::RawOutput as Decode,
   templates::{
     BlocksHtml as Blocks, RuneHtml as Rune, RunesHtml as Runes, StatusHtml as Status,
@@ -42,6 +44,12 @@ impl Block {
   }
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct BatchResponse {
+  pub body: serde_json::Value,
+  pub status: u16,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct BlockInfo {
   pub average_fee: u64,
@@ -75,6 +83,26 @@ pub struct BlockInfo {
   pub version: u32,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Collection {
+  pub children: Vec<InscriptionId>,
+  pub first_child_height: Option<u32>,
+  pub holders: Option<u64>,
+  pub last_child_height: Option<u32>,
+  pub more: bool,
+  pub order: CollectionOrder,
+  pub page: usize,
+  pub parent: InscriptionId,
+  pub parent_number: i32,
+  pub total_children: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct CollectionTraits {
+  pub parent: InscriptionId,
+  pub traits: BTreeMap<String, BTreeMap<String, u64>>,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Children {
   pub ids: Vec<InscriptionId>,
@@ -151,6 +179,12 @@ pub struct Inscriptions {
   pub page_index: u32,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Page<T> {
+  pub items: Vec<T>,
+  pub next: Option<Cursor>,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct UtxoRecursive {
   pub inscriptions: Option<Vec<InscriptionId>>,
@@ -227,6 +261,74 @@ pub struct SatInscription {
   pub id: Option<InscriptionId>,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct ModerationEntry {
+  pub reason: String,
+  pub timestamp: i64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModerationRequest {
+  pub reason: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneRecursive {
+  pub entry: RuneEntry,
+  pub id: RuneId,
+  pub max_supply: u128,
+  pub mintable: bool,
+  pub mints_remaining: Option<u128>,
+  pub parent: Option<InscriptionId>,
+  pub supply: u128,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Search {
+  pub inscriptions: Vec<InscriptionId>,
+  pub query: String,
+  pub runes: Vec<SpacedRune>,
+  pub sat: Option<ordinals::Sat>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Simulation {
+  pub burned_runes: Option<BTreeMap<SpacedRune, Pile>>,
+  pub fee: u64,
+  pub fee_inscriptions: Option<Vec<InscriptionId>>,
+  pub fee_sat_ranges: Option<Vec<(u64, u64)>>,
+  pub inscriptions: Vec<ParsedEnvelope>,
+  pub outputs: Vec<SimulatedOutput>,
+  pub runestone: Option<Artifact>,
+  pub txid: Txid,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SatFlow {
+  pub fee: u64,
+  pub segments: Vec<SatFlowSegment>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SatFlowSegment {
+  pub input: usize,
+  pub inscriptions: Option<Vec<InscriptionId>>,
+  pub output: Option<usize>,
+  pub rare_sats: Option<Vec<(ordinals::Sat, Rarity)>>,
+  pub sat_ranges: Option<Vec<(u64, u64)>>,
+  pub value: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SimulatedOutput {
+  pub address: Option<Address<NetworkUnchecked>>,
+  pub inscriptions: Option<Vec<InscriptionId>>,
+  pub runes: Option<BTreeMap<SpacedRune, Pile>>,
+  pub sat_ranges: Option<Vec<(u64, u64)>>,
+  pub script_pubkey: ScriptBuf,
+  pub value: u64,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct SatInscriptions {
   pub ids: Vec<InscriptionId>,
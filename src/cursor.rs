@@ -0,0 +1,115 @@
+use {
+  super::*,
+  base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine},
+};
+
+/// An opaque pagination cursor. Cursors point to the next item to be
+/// returned, by sequence number or rune ID, so that pages do not shift as new
+/// blocks are indexed.
+#[derive(Debug, PartialEq, Copy, Clone, DeserializeFromStr, SerializeDisplay)]
+pub enum Cursor {
+  RuneId(RuneId),
+  SequenceNumber(u32),
+}
+
+#[derive(Debug, PartialEq, Copy, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Order {
+  Newest,
+  Oldest,
+}
+
+impl Cursor {
+  pub(crate) fn rune_id(self) -> Result<RuneId> {
+    match self {
+      Self::RuneId(id) => Ok(id),
+      Self::SequenceNumber(_) => bail!("expected rune cursor"),
+    }
+  }
+
+  pub(crate) fn sequence_number(self) -> Result<u32> {
+    match self {
+      Self::RuneId(_) => bail!("expected sequence number cursor"),
+      Self::SequenceNumber(sequence_number) => Ok(sequence_number),
+    }
+  }
+}
+
+impl Display for Cursor {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let plain = match self {
+      Self::RuneId(id) => format!("r{id}"),
+      Self::SequenceNumber(sequence_number) => format!("s{sequence_number}"),
+    };
+
+    write!(f, "{}", URL_SAFE_NO_PAD.encode(plain))
+  }
+}
+
+impl FromStr for Cursor {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let plain = String::from_utf8(URL_SAFE_NO_PAD.decode(s).context("invalid cursor")?)
+      .context("invalid cursor")?;
+
+    if let Some(id) = plain.strip_prefix('r') {
+      Ok(Self::RuneId(id.parse().context("invalid cursor")?))
+    } else if let Some(sequence_number) = plain.strip_prefix('s') {
+      Ok(Self::SequenceNumber(
+        sequence_number.parse().context("invalid cursor")?,
+      ))
+    } else {
+      bail!("invalid cursor")
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn round_trip() {
+    #[track_caller]
+    fn case(cursor: Cursor) {
+      assert_eq!(cursor.to_string().parse::<Cursor>().unwrap(), cursor);
+    }
+
+    case(Cursor::SequenceNumber(0));
+    case(Cursor::SequenceNumber(u32::MAX));
+    case(Cursor::RuneId(RuneId { block: 1, tx: 0 }));
+    case(Cursor::RuneId(RuneId {
+      block: u64::MAX,
+      tx: u32::MAX,
+    }));
+  }
+
+  #[test]
+  fn cursors_are_url_safe() {
+    assert!(Cursor::SequenceNumber(u32::MAX)
+      .to_string()
+      .chars()
+      .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+  }
+
+  #[test]
+  fn invalid_cursors_are_rejected() {
+    assert!("".parse::<Cursor>().is_err());
+    assert!("!".parse::<Cursor>().is_err());
+    assert!(URL_SAFE_NO_PAD.encode("x1").parse::<Cursor>().is_err());
+    assert!(URL_SAFE_NO_PAD.encode("sfoo").parse::<Cursor>().is_err());
+  }
+
+  #[test]
+  fn order_deserializes_from_lowercase() {
+    assert_eq!(
+      serde_json::from_str::<Order>("\"newest\"").unwrap(),
+      Order::Newest
+    );
+    assert_eq!(
+      serde_json::from_str::<Order>("\"oldest\"").unwrap(),
+      Order::Oldest
+    );
+  }
+}
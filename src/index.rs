@@ -2,16 +2,17 @@ use {
   self::{
     entry::{
       Entry, HeaderValue, InscriptionEntry, InscriptionEntryValue, InscriptionIdValue,
-      OutPointValue, RuneEntryValue, RuneIdValue, SatPointValue, SatRange, TxidValue,
+      OutPointValue, RuneEntryValue, RuneEvent, RuneEventKind, RuneEventValue, RuneIdValue,
+      SatPointValue, SatRange, TxidValue,
     },
     event::Event,
-    lot::Lot,
     reorg::Reorg,
     updater::Updater,
     utxo_entry::{ParsedUtxoEntry, UtxoEntry, UtxoEntryBuf},
   },
   super::*,
   crate::{
+    cursor::Order,
     runes::MintError,
     subcommand::{find::FindRangeOutput, server::query},
     templates::StatusHtml,
@@ -32,42 +33,69 @@ use {
   std::{
     collections::HashMap,
     io::{BufWriter, Write},
+    ops::{Bound, Range},
     sync::Once,
   },
 };
 
-pub use self::entry::RuneEntry;
+pub use self::{collection::CollectionOrder, entry::RuneEntry};
 
+pub(crate) use self::{lot::Lot, updater::rune_updater::allocate as allocate_runes};
+
+mod collection;
 pub(crate) mod entry;
 pub mod event;
 mod fetcher;
 mod lot;
 mod reorg;
 mod rtx;
+pub(crate) mod search;
+mod simulation;
+pub(crate) mod traits;
 mod updater;
 mod utxo_entry;
 
 #[cfg(test)]
 pub(crate) mod testing;
 
-const SCHEMA_VERSION: u64 = 30;
+const SCHEMA_VERSION: u64 = 41;
+
+/// Number of most recent blocks for which rune events are retained.
+pub(crate) const RUNE_EVENT_BLOCKS: u32 = 10;
 
+/// Maximum number of mints of a single rune recorded per block, so that a
+/// popular mint does not fill the index with events.
+pub(crate) const RUNE_MINT_EVENTS_PER_BLOCK: usize = 100;
+
+define_multimap_table! { HEIGHT_TO_EVENT_RUNE_ID, u32, RuneIdValue }
+define_multimap_table! { RUNE_ID_AND_HEIGHT_TO_RUNE_EVENT, (RuneIdValue, u32), RuneEventValue }
 define_multimap_table! { SAT_TO_SEQUENCE_NUMBER, u64, u32 }
 define_multimap_table! { SEQUENCE_NUMBER_TO_CHILDREN, u32, u32 }
+define_multimap_table! { SEQUENCE_NUMBER_TO_TRANSFERRED_CHILDREN, u32, (u32, u32) }
+define_multimap_table! { TRAIT_TO_SEQUENCE_NUMBER, (u32, &str, &str), u32 }
+define_multimap_table! { SCRIPT_HASH_TO_HISTORY, &[u8; 32], (u32, u32, TxidValue) }
 define_multimap_table! { SCRIPT_PUBKEY_TO_OUTPOINT, &[u8], OutPointValue }
+define_multimap_table! { SEARCH_TERM_TO_RUNE_ID, &str, RuneIdValue }
+define_multimap_table! { SEARCH_TERM_TO_SEQUENCE_NUMBER, &str, u32 }
 define_table! { HEIGHT_TO_BLOCK_HEADER, u32, &HeaderValue }
 define_table! { HEIGHT_TO_LAST_SEQUENCE_NUMBER, u32, u32 }
 define_table! { HOME_INSCRIPTIONS, u32, InscriptionIdValue }
+define_table! { INSCRIPTION_ID_TO_MODERATION_ENTRY, InscriptionIdValue, (&str, i64) }
 define_table! { INSCRIPTION_ID_TO_SEQUENCE_NUMBER, InscriptionIdValue, u32 }
 define_table! { INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER, i32, u32 }
+define_table! { OUTPOINT_TO_HEIGHT, &OutPointValue, u32 }
 define_table! { OUTPOINT_TO_RUNE_BALANCES, &OutPointValue, &[u8] }
+define_table! { OUTPOINT_TO_SPENT_UTXO_ENTRY, &OutPointValue, &UtxoEntry }
 define_table! { OUTPOINT_TO_UTXO_ENTRY, &OutPointValue, &UtxoEntry }
 define_table! { RUNE_ID_TO_RUNE_ENTRY, RuneIdValue, RuneEntryValue }
+define_table! { RUNE_NUMBER_TO_RUNE_ID, u64, RuneIdValue }
 define_table! { RUNE_TO_RUNE_ID, u128, RuneIdValue }
 define_table! { SAT_TO_SATPOINT, u64, &SatPointValue }
+define_table! { SCRIPT_HASH_TO_SCRIPT_PUBKEY, &[u8; 32], &[u8] }
 define_table! { SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY, u32, InscriptionEntryValue }
 define_table! { SEQUENCE_NUMBER_TO_RUNE_ID, u32, RuneIdValue }
 define_table! { SEQUENCE_NUMBER_TO_SATPOINT, u32, &SatPointValue }
+define_table! { SEQUENCE_NUMBER_TO_TRANSFER_HEIGHT, u32, u32 }
 define_table! { STATISTIC_TO_COUNT, u64, u64 }
 define_table! { TRANSACTION_ID_TO_RUNE, &TxidValue, u128 }
 define_table! { TRANSACTION_ID_TO_TRANSACTION, &TxidValue, &[u8] }
@@ -92,6 +120,11 @@ pub(crate) enum Statistic {
   SatRanges = 14,
   UnboundInscriptions = 16,
   LastSavepointHeight = 17,
+  IndexSearch = 18,
+  IndexTraits = 19,
+  IndexTransfers = 20,
+  IndexSpentSats = 21,
+  IndexScriptHashes = 22,
 }
 
 impl Statistic {
@@ -203,7 +236,12 @@ pub struct Index {
   index_inscriptions: bool,
   index_runes: bool,
   index_sats: bool,
+  index_script_hashes: bool,
+  index_search: bool,
+  index_spent_sats: bool,
+  index_traits: bool,
   index_transactions: bool,
+  index_transfers: bool,
   path: PathBuf,
   settings: Settings,
   started: DateTime<Utc>,
@@ -306,22 +344,35 @@ impl Index {
         tx.set_durability(durability);
         tx.set_quick_repair(true);
 
+        tx.open_multimap_table(HEIGHT_TO_EVENT_RUNE_ID)?;
+        tx.open_multimap_table(RUNE_ID_AND_HEIGHT_TO_RUNE_EVENT)?;
         tx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
+        tx.open_multimap_table(SCRIPT_HASH_TO_HISTORY)?;
         tx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
+        tx.open_multimap_table(SEARCH_TERM_TO_RUNE_ID)?;
+        tx.open_multimap_table(SEARCH_TERM_TO_SEQUENCE_NUMBER)?;
         tx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
+        tx.open_multimap_table(SEQUENCE_NUMBER_TO_TRANSFERRED_CHILDREN)?;
+        tx.open_multimap_table(TRAIT_TO_SEQUENCE_NUMBER)?;
         tx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
         tx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
         tx.open_table(HOME_INSCRIPTIONS)?;
+        tx.open_table(INSCRIPTION_ID_TO_MODERATION_ENTRY)?;
         tx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
         tx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+        tx.open_table(OUTPOINT_TO_HEIGHT)?;
         tx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+        tx.open_table(OUTPOINT_TO_SPENT_UTXO_ENTRY)?;
         tx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
         tx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+        tx.open_table(RUNE_NUMBER_TO_RUNE_ID)?;
         tx.open_table(RUNE_TO_RUNE_ID)?;
         tx.open_table(SAT_TO_SATPOINT)?;
+        tx.open_table(SCRIPT_HASH_TO_SCRIPT_PUBKEY)?;
         tx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
         tx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
         tx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+        tx.open_table(SEQUENCE_NUMBER_TO_TRANSFER_HEIGHT)?;
         tx.open_table(TRANSACTION_ID_TO_RUNE)?;
         tx.open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?;
 
@@ -352,12 +403,42 @@ impl Index {
             u64::from(settings.index_sats_raw()),
           )?;
 
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexScriptHashes,
+            u64::from(settings.index_script_hashes_raw()),
+          )?;
+
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexSearch,
+            u64::from(settings.index_search_raw()),
+          )?;
+
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexSpentSats,
+            u64::from(settings.index_spent_sats_raw()),
+          )?;
+
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexTraits,
+            u64::from(settings.index_traits_raw()),
+          )?;
+
           Self::set_statistic(
             &mut statistics,
             Statistic::IndexTransactions,
             u64::from(settings.index_transactions_raw()),
           )?;
 
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexTransfers,
+            u64::from(settings.index_transfers_raw()),
+          )?;
+
           Self::set_statistic(&mut statistics, Statistic::Schema, SCHEMA_VERSION)?;
         }
 
@@ -370,6 +451,9 @@ impl Index {
           tx.open_table(RUNE_TO_RUNE_ID)?
             .insert(rune.store(), id.store())?;
 
+          tx.open_table(RUNE_NUMBER_TO_RUNE_ID)?
+            .insert(0, id.store())?;
+
           let mut statistics = tx.open_table(STATISTIC_TO_COUNT)?;
 
           Self::set_statistic(&mut statistics, Statistic::Runes, 1)?;
@@ -403,6 +487,14 @@ impl Index {
 
           tx.open_table(TRANSACTION_ID_TO_RUNE)?
             .insert(&etching.store(), rune.store())?;
+
+          if settings.index_search_raw() {
+            let mut search_term_to_rune_id = tx.open_multimap_table(SEARCH_TERM_TO_RUNE_ID)?;
+
+            for term in search::rune_terms(SpacedRune { rune, spacers: 128 }) {
+              search_term_to_rune_id.insert(term.as_str(), id.store())?;
+            }
+          }
         }
 
         tx.commit()?;
@@ -415,7 +507,12 @@ impl Index {
     let index_addresses;
     let index_runes;
     let index_sats;
+    let index_script_hashes;
+    let index_search;
+    let index_spent_sats;
+    let index_traits;
     let index_transactions;
+    let index_transfers;
     let index_inscriptions;
 
     {
@@ -425,7 +522,13 @@ impl Index {
       index_inscriptions = Self::is_statistic_set(&statistics, Statistic::IndexInscriptions)?;
       index_runes = Self::is_statistic_set(&statistics, Statistic::IndexRunes)?;
       index_sats = Self::is_statistic_set(&statistics, Statistic::IndexSats)?;
+      index_script_hashes =
+        index_addresses && Self::is_statistic_set(&statistics, Statistic::IndexScriptHashes)?;
+      index_search = Self::is_statistic_set(&statistics, Statistic::IndexSearch)?;
+      index_spent_sats = Self::is_statistic_set(&statistics, Statistic::IndexSpentSats)?;
+      index_traits = Self::is_statistic_set(&statistics, Statistic::IndexTraits)?;
       index_transactions = Self::is_statistic_set(&statistics, Statistic::IndexTransactions)?;
+      index_transfers = Self::is_statistic_set(&statistics, Statistic::IndexTransfers)?;
     }
 
     let genesis_block_coinbase_transaction =
@@ -453,7 +556,12 @@ impl Index {
       index_addresses,
       index_runes,
       index_sats,
+      index_script_hashes,
+      index_search,
+      index_spent_sats,
+      index_traits,
       index_transactions,
+      index_transfers,
       index_inscriptions,
       settings: settings.clone(),
       path,
@@ -507,6 +615,22 @@ impl Index {
     self.index_sats
   }
 
+  pub fn has_script_hash_index(&self) -> bool {
+    self.index_script_hashes
+  }
+
+  pub fn has_search_index(&self) -> bool {
+    self.index_search
+  }
+
+  pub fn has_traits_index(&self) -> bool {
+    self.index_traits
+  }
+
+  pub fn has_transfers_index(&self) -> bool {
+    self.index_transfers
+  }
+
   pub fn status(&self, json_api: bool) -> Result<StatusHtml> {
     let rtx = self.database.begin_read()?;
 
@@ -996,6 +1120,32 @@ impl Index {
     Ok((entries, more))
   }
 
+  pub fn runes_by_cursor(
+    &self,
+    cursor: Option<RuneId>,
+    order: Order,
+    page_size: usize,
+  ) -> Result<(Vec<(RuneId, RuneEntry)>, Option<RuneId>)> {
+    let rtx = self.database.begin_read()?;
+
+    let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+
+    Self::paginate_by_cursor(
+      rune_id_to_rune_entry
+        .range::<RuneIdValue>(Self::cursor_bounds(cursor.map(|id| id.store()), order))?,
+      cursor,
+      order,
+      page_size,
+      |result| Ok(RuneId::load(result?.0.value())),
+      |id| {
+        let entry = rune_id_to_rune_entry
+          .get(id.store())?
+          .ok_or_else(|| anyhow!("could not find rune entry for {id}"))?;
+        Ok(Some((id, RuneEntry::load(entry.value()))))
+      },
+    )
+  }
+
   pub fn encode_rune_balance(id: RuneId, balance: u128, buffer: &mut Vec<u8>) {
     varint::encode_to_vec(id.block.into(), buffer);
     varint::encode_to_vec(id.tx.into(), buffer);
@@ -1017,6 +1167,52 @@ impl Index {
     Ok(((id, balance), len))
   }
 
+  /// Returns up to `limit` events of rune `id` in the last
+  /// `RUNE_EVENT_BLOCKS` blocks for which `filter` returns `true`, most recent
+  /// first, along with the time of the block they occurred in.
+  pub(crate) fn get_rune_events(
+    &self,
+    id: RuneId,
+    limit: usize,
+    filter: impl Fn(&RuneEvent) -> bool,
+  ) -> Result<Vec<(u32, RuneEvent)>> {
+    let rtx = self.database.begin_read()?;
+
+    let height_to_block_header = rtx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
+    let rune_id_and_height_to_rune_event =
+      rtx.open_multimap_table(RUNE_ID_AND_HEIGHT_TO_RUNE_EVENT)?;
+
+    let mut events = Vec::new();
+
+    for result in rune_id_and_height_to_rune_event
+      .range((id.store(), 0)..=(id.store(), u32::MAX))?
+      .rev()
+    {
+      let (key, values) = result?;
+
+      let (_id, height) = key.value();
+
+      let time = height_to_block_header
+        .get(height)?
+        .map(|header| Header::load(*header.value()).time)
+        .unwrap_or_default();
+
+      for value in values {
+        if events.len() >= limit {
+          return Ok(events);
+        }
+
+        let event = RuneEvent::load(value?.value());
+
+        if filter(&event) {
+          events.push((time, event));
+        }
+      }
+    }
+
+    Ok(events)
+  }
+
   pub fn get_rune_balances_for_output(
     &self,
     outpoint: OutPoint,
@@ -1171,35 +1367,47 @@ impl Index {
     &self,
     page_size: usize,
     page_index: usize,
+    hidden: &BTreeSet<InscriptionId>,
   ) -> Result<(Vec<InscriptionId>, bool)> {
     let rtx = self.database.begin_read()?;
 
-    let sequence_number_to_inscription_entry =
-      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+    let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
 
-    let mut collections = rtx
-      .open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?
-      .iter()?
-      .skip(page_index.saturating_mul(page_size))
-      .take(page_size.saturating_add(1))
-      .map(|result| {
-        result
-          .and_then(|(parent, _children)| {
-            sequence_number_to_inscription_entry
-              .get(parent.value())
-              .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
-          })
-          .map_err(|err| err.into())
-      })
-      .collect::<Result<Vec<InscriptionId>>>()?;
+    Self::paginate(
+      rtx
+        .open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?
+        .iter()?
+        .map(|result| {
+          Self::load_visible_inscription_id(&sequence_number_to_entry, result?.0.value(), hidden)
+        }),
+      page_size,
+      page_index,
+    )
+  }
 
-    let more = collections.len() > page_size;
+  pub fn get_collections_by_cursor(
+    &self,
+    cursor: Option<u32>,
+    order: Order,
+    page_size: usize,
+    hidden: &BTreeSet<InscriptionId>,
+  ) -> Result<(Vec<InscriptionId>, Option<u32>)> {
+    let rtx = self.database.begin_read()?;
 
-    if more {
-      collections.pop();
-    }
+    let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
 
-    Ok((collections, more))
+    Self::paginate_by_cursor(
+      rtx
+        .open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?
+        .range::<u32>(Self::cursor_bounds(cursor, order))?,
+      cursor,
+      order,
+      page_size,
+      |result| Ok(result?.0.value()),
+      |sequence_number| {
+        Self::load_visible_inscription_id(&sequence_number_to_entry, sequence_number, hidden)
+      },
+    )
   }
 
   #[cfg(test)]
@@ -1218,7 +1426,7 @@ impl Index {
     };
 
     self
-      .get_children_by_sequence_number_paginated(sequence_number, usize::MAX, 0)
+      .get_children_by_sequence_number_paginated(sequence_number, usize::MAX, 0, &BTreeSet::new())
       .map(|(children, _more)| children)
   }
 
@@ -1270,65 +1478,175 @@ impl Index {
     sequence_number: u32,
     page_size: usize,
     page_index: usize,
+    hidden: &BTreeSet<InscriptionId>,
   ) -> Result<(Vec<InscriptionId>, bool)> {
     let rtx = self.database.begin_read()?;
 
     let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
 
-    let mut children = rtx
-      .open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?
-      .get(sequence_number)?
-      .skip(page_index * page_size)
-      .take(page_size.saturating_add(1))
-      .map(|result| {
-        result
-          .and_then(|sequence_number| {
-            sequence_number_to_entry
-              .get(sequence_number.value())
-              .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
-          })
-          .map_err(|err| err.into())
-      })
-      .collect::<Result<Vec<InscriptionId>>>()?;
+    Self::paginate(
+      rtx
+        .open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?
+        .get(sequence_number)?
+        .map(|result| {
+          Self::load_visible_inscription_id(&sequence_number_to_entry, result?.value(), hidden)
+        }),
+      page_size,
+      page_index,
+    )
+  }
+
+  /// Returns the traits of the children of the inscription with
+  /// `parent_sequence_number`, along with the number of children with each
+  /// trait value.
+  pub fn get_collection_traits(
+    &self,
+    parent_sequence_number: u32,
+  ) -> Result<BTreeMap<String, BTreeMap<String, u64>>> {
+    let rtx = self.database.begin_read()?;
 
-    let more = children.len() > page_size;
+    let trait_to_sequence_number = rtx.open_multimap_table(TRAIT_TO_SEQUENCE_NUMBER)?;
 
-    if more {
-      children.pop();
+    let mut traits = BTreeMap::<String, BTreeMap<String, u64>>::new();
+
+    for result in trait_to_sequence_number.range((parent_sequence_number, "", "")..)? {
+      let (key, children) = result?;
+
+      let (parent, key, value) = key.value();
+
+      if parent != parent_sequence_number {
+        break;
+      }
+
+      traits
+        .entry(key.into())
+        .or_default()
+        .insert(value.into(), children.len());
+    }
+
+    Ok(traits)
+  }
+
+  /// Returns the children of the inscription with `parent_sequence_number`
+  /// which match `traits`. Children must match at least one value of every
+  /// trait key.
+  pub fn get_children_with_traits_paginated(
+    &self,
+    parent_sequence_number: u32,
+    traits: &BTreeMap<String, BTreeSet<String>>,
+    page_size: usize,
+    page_index: usize,
+    hidden: &BTreeSet<InscriptionId>,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    let rtx = self.database.begin_read()?;
+
+    let trait_to_sequence_number = rtx.open_multimap_table(TRAIT_TO_SEQUENCE_NUMBER)?;
+    let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let mut matches: Option<BTreeSet<u32>> = None;
+
+    for (key, values) in traits {
+      let mut children = BTreeSet::new();
+
+      for value in values {
+        for child in
+          trait_to_sequence_number.get((parent_sequence_number, key.as_str(), value.as_str()))?
+        {
+          children.insert(child?.value());
+        }
+      }
+
+      matches = Some(match matches {
+        Some(matches) => matches.intersection(&children).copied().collect(),
+        None => children,
+      });
     }
 
-    Ok((children, more))
+    Self::paginate(
+      matches
+        .unwrap_or_default()
+        .into_iter()
+        .map(|sequence_number| {
+          Self::load_visible_inscription_id(&sequence_number_to_entry, sequence_number, hidden)
+        }),
+      page_size,
+      page_index,
+    )
+  }
+
+  pub fn get_children_by_sequence_number_cursor(
+    &self,
+    sequence_number: u32,
+    cursor: Option<u32>,
+    order: Order,
+    page_size: usize,
+    hidden: &BTreeSet<InscriptionId>,
+  ) -> Result<(Vec<InscriptionId>, Option<u32>)> {
+    let rtx = self.database.begin_read()?;
+
+    let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    Self::paginate_by_cursor(
+      rtx
+        .open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?
+        .get(sequence_number)?,
+      cursor,
+      order,
+      page_size,
+      |result| Ok(result?.value()),
+      |sequence_number| {
+        Self::load_visible_inscription_id(&sequence_number_to_entry, sequence_number, hidden)
+      },
+    )
   }
 
   pub fn get_parents_by_sequence_number_paginated(
     &self,
     parent_sequence_numbers: Vec<u32>,
     page_index: usize,
+    hidden: &BTreeSet<InscriptionId>,
   ) -> Result<(Vec<InscriptionId>, bool)> {
     const PAGE_SIZE: usize = 100;
     let rtx = self.database.begin_read()?;
 
     let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
 
-    let mut parents = parent_sequence_numbers
-      .iter()
-      .skip(page_index * PAGE_SIZE)
-      .take(PAGE_SIZE.saturating_add(1))
-      .map(|sequence_number| {
-        sequence_number_to_entry
-          .get(sequence_number)
-          .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
-          .map_err(|err| err.into())
-      })
-      .collect::<Result<Vec<InscriptionId>>>()?;
+    Self::paginate(
+      parent_sequence_numbers.into_iter().map(|sequence_number| {
+        Self::load_visible_inscription_id(&sequence_number_to_entry, sequence_number, hidden)
+      }),
+      PAGE_SIZE,
+      page_index,
+    )
+  }
 
-    let more_parents = parents.len() > PAGE_SIZE;
+  /// Unlike page numbers, which follow the order in which parents appear in
+  /// the child inscription, cursors list parents by sequence number.
+  pub fn get_parents_by_sequence_number_cursor(
+    &self,
+    mut parent_sequence_numbers: Vec<u32>,
+    cursor: Option<u32>,
+    order: Order,
+    page_size: usize,
+    hidden: &BTreeSet<InscriptionId>,
+  ) -> Result<(Vec<InscriptionId>, Option<u32>)> {
+    let rtx = self.database.begin_read()?;
 
-    if more_parents {
-      parents.pop();
-    }
+    let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
 
-    Ok((parents, more_parents))
+    parent_sequence_numbers.sort_unstable();
+    parent_sequence_numbers.dedup();
+
+    Self::paginate_by_cursor(
+      parent_sequence_numbers.into_iter(),
+      cursor,
+      order,
+      page_size,
+      Ok,
+      |sequence_number| {
+        Self::load_visible_inscription_id(&sequence_number_to_entry, sequence_number, hidden)
+      },
+    )
   }
 
   pub fn get_etching(&self, txid: Txid) -> Result<Option<SpacedRune>> {
@@ -1377,36 +1695,48 @@ impl Index {
     sat: Sat,
     page_size: u64,
     page_index: u64,
+    hidden: &BTreeSet<InscriptionId>,
   ) -> Result<(Vec<InscriptionId>, bool)> {
     let rtx = self.database.begin_read()?;
 
-    let sequence_number_to_inscription_entry =
-      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+    let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
 
-    let mut ids = rtx
-      .open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?
-      .get(&sat.n())?
-      .skip(page_index.saturating_mul(page_size).try_into().unwrap())
-      .take(page_size.saturating_add(1).try_into().unwrap())
-      .map(|result| {
-        result
-          .and_then(|sequence_number| {
-            let sequence_number = sequence_number.value();
-            sequence_number_to_inscription_entry
-              .get(sequence_number)
-              .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
-          })
-          .map_err(|err| err.into())
-      })
-      .collect::<Result<Vec<InscriptionId>>>()?;
+    Self::paginate(
+      rtx
+        .open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?
+        .get(&sat.n())?
+        .map(|result| {
+          Self::load_visible_inscription_id(&sequence_number_to_entry, result?.value(), hidden)
+        }),
+      page_size.try_into().unwrap(),
+      page_index.try_into().unwrap(),
+    )
+  }
 
-    let more = ids.len() > page_size.try_into().unwrap();
+  pub fn get_inscription_ids_by_sat_cursor(
+    &self,
+    sat: Sat,
+    cursor: Option<u32>,
+    order: Order,
+    page_size: usize,
+    hidden: &BTreeSet<InscriptionId>,
+  ) -> Result<(Vec<InscriptionId>, Option<u32>)> {
+    let rtx = self.database.begin_read()?;
 
-    if more {
-      ids.pop();
-    }
+    let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
 
-    Ok((ids, more))
+    Self::paginate_by_cursor(
+      rtx
+        .open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?
+        .get(&sat.n())?,
+      cursor,
+      order,
+      page_size,
+      |result| Ok(result?.value()),
+      |sequence_number| {
+        Self::load_visible_inscription_id(&sequence_number_to_entry, sequence_number, hidden)
+      },
+    )
   }
 
   pub fn get_inscription_id_by_sat_indexed(
@@ -1610,6 +1940,26 @@ impl Index {
     self.client.get_raw_transaction(&txid, None).into_option()
   }
 
+  /// Returns the hash of the block containing the transaction with `txid`,
+  /// or `None` if the transaction is unconfirmed or unknown.
+  pub(crate) fn get_transaction_block_hash(&self, txid: Txid) -> Result<Option<BlockHash>> {
+    if txid == self.genesis_block_coinbase_txid {
+      return self.block_hash(Some(0));
+    }
+
+    Ok(
+      self
+        .client
+        .get_raw_transaction_info(&txid, None)
+        .into_option()?
+        .and_then(|info| info.blockhash),
+    )
+  }
+
+  pub(crate) fn send_raw_transaction(&self, transaction: &Transaction) -> Result<Txid> {
+    Ok(self.client.send_raw_transaction(transaction)?)
+  }
+
   pub fn find(&self, sat: Sat) -> Result<Option<SatPoint>> {
     let sat = sat.0;
     let rtx = self.begin_read()?;
@@ -1717,6 +2067,20 @@ impl Index {
     )
   }
 
+  /// Returns the output at `outpoint` from Bitcoin Core's UTXO set, which,
+  /// unlike `get_transaction`, does not need a transaction index.
+  pub(crate) fn get_unspent_output(&self, outpoint: OutPoint) -> Result<Option<TxOut>> {
+    Ok(
+      self
+        .client
+        .get_tx_out(&outpoint.txid, outpoint.vout, Some(true))?
+        .map(|tx_out| TxOut {
+          value: tx_out.value,
+          script_pubkey: ScriptBuf::from_bytes(tx_out.script_pub_key.hex),
+        }),
+    )
+  }
+
   pub fn is_output_spent(&self, outpoint: OutPoint) -> Result<bool> {
     Ok(
       outpoint != OutPoint::null()
@@ -1803,28 +2167,77 @@ impl Index {
     &self,
     page_size: u32,
     page_index: u32,
+    hidden: &BTreeSet<InscriptionId>,
   ) -> Result<(Vec<InscriptionId>, bool)> {
     let rtx = self.database.begin_read()?;
 
     let sequence_number_to_inscription_entry =
       rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
 
-    let last = sequence_number_to_inscription_entry
+    let Some(last) = sequence_number_to_inscription_entry
       .iter()?
       .next_back()
       .map(|result| result.map(|(number, _entry)| number.value()))
       .transpose()?
-      .unwrap_or_default();
+    else {
+      return Ok((Vec::new(), false));
+    };
 
-    let start = last.saturating_sub(page_size.saturating_mul(page_index));
+    let inscription_id_to_sequence_number = rtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
 
-    let end = start.saturating_sub(page_size);
+    let mut hidden_sequence_numbers = BTreeSet::new();
 
-    let mut inscriptions = sequence_number_to_inscription_entry
-      .range(end..=start)?
+    for inscription_id in hidden {
+      if let Some(sequence_number) =
+        inscription_id_to_sequence_number.get(&inscription_id.store())?
+      {
+        hidden_sequence_numbers.insert(sequence_number.value());
+      }
+    }
+
+    // Seek to the first inscription of the page, which is preceded by
+    // `skip` visible inscriptions. Each hidden inscription between the
+    // candidate and the newest inscription pushes the candidate back by one,
+    // so this converges after at most as many steps as there are hidden
+    // inscriptions.
+    let skip = page_size.saturating_mul(page_index);
+
+    let mut start = last.checked_sub(skip);
+
+    loop {
+      let Some(candidate) = start else {
+        return Ok((Vec::new(), false));
+      };
+
+      let hidden_after = u32::try_from(hidden_sequence_numbers.range(candidate..).count()).unwrap();
+
+      let next = last.checked_sub(skip.saturating_add(hidden_after));
+
+      if next == start {
+        break;
+      }
+
+      start = next;
+    }
+
+    let mut inscriptions = Vec::new();
+
+    for result in sequence_number_to_inscription_entry
+      .range(..=start.unwrap())?
       .rev()
-      .map(|result| result.map(|(_number, entry)| InscriptionEntry::load(entry.value()).id))
-      .collect::<Result<Vec<InscriptionId>, StorageError>>()?;
+    {
+      let (sequence_number, entry) = result?;
+
+      if hidden_sequence_numbers.contains(&sequence_number.value()) {
+        continue;
+      }
+
+      inscriptions.push(InscriptionEntry::load(entry.value()).id);
+
+      if u32::try_from(inscriptions.len()).unwrap_or(u32::MAX) > page_size {
+        break;
+      }
+    }
 
     let more = u32::try_from(inscriptions.len()).unwrap_or(u32::MAX) > page_size;
 
@@ -1835,6 +2248,230 @@ impl Index {
     Ok((inscriptions, more))
   }
 
+  pub fn get_inscriptions_by_cursor(
+    &self,
+    cursor: Option<u32>,
+    order: Order,
+    page_size: usize,
+    hidden: &BTreeSet<InscriptionId>,
+  ) -> Result<(Vec<InscriptionId>, Option<u32>)> {
+    let rtx = self.database.begin_read()?;
+
+    let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    Self::paginate_by_cursor(
+      sequence_number_to_entry.range::<u32>(Self::cursor_bounds(cursor, order))?,
+      cursor,
+      order,
+      page_size,
+      |result| Ok(result?.0.value()),
+      |sequence_number| {
+        Self::load_visible_inscription_id(&sequence_number_to_entry, sequence_number, hidden)
+      },
+    )
+  }
+
+  /// Returns the ID of the inscription with `sequence_number`, or `None` if
+  /// it is `hidden`.
+  fn load_visible_inscription_id(
+    sequence_number_to_entry: &impl ReadableTable<u32, InscriptionEntryValue>,
+    sequence_number: u32,
+    hidden: &BTreeSet<InscriptionId>,
+  ) -> Result<Option<InscriptionId>> {
+    let inscription_id = Self::load_inscription_id(sequence_number_to_entry, sequence_number)?;
+
+    Ok((!hidden.contains(&inscription_id)).then_some(inscription_id))
+  }
+
+  /// Returns page `page_index` of the items in `items` which are not `None`,
+  /// along with whether or not there are more pages.
+  fn paginate<T>(
+    items: impl Iterator<Item = Result<Option<T>>>,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<T>, bool)> {
+    let mut page = items
+      .filter_map(Result::transpose)
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .collect::<Result<Vec<T>>>()?;
+
+    let more = page.len() > page_size;
+
+    if more {
+      page.pop();
+    }
+
+    Ok((page, more))
+  }
+
+  fn load_inscription_id(
+    sequence_number_to_entry: &impl ReadableTable<u32, InscriptionEntryValue>,
+    sequence_number: u32,
+  ) -> Result<InscriptionId> {
+    Ok(
+      InscriptionEntry::load(
+        sequence_number_to_entry
+          .get(sequence_number)?
+          .ok_or_else(|| {
+            anyhow!("could not find inscription for sequence number {sequence_number}")
+          })?
+          .value(),
+      )
+      .id,
+    )
+  }
+
+  /// Returns up to `limit` inscriptions and runes matching `query`, skipping
+  /// inscriptions for which `hidden` returns `true`.
+  pub fn search(
+    &self,
+    query: &str,
+    limit: usize,
+    hidden: impl Fn(InscriptionId) -> bool,
+  ) -> Result<(Vec<InscriptionId>, Vec<SpacedRune>)> {
+    let terms = search::terms(query).take(16).collect::<BTreeSet<String>>();
+
+    let rtx = self.database.begin_read()?;
+
+    let search_term_to_sequence_number = rtx.open_multimap_table(SEARCH_TERM_TO_SEQUENCE_NUMBER)?;
+    let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let inscriptions = Self::rank_search_results(
+      &terms,
+      |term| search_term_to_sequence_number.get(term),
+      |value| value.value(),
+    )?
+    .into_iter()
+    .map(|sequence_number| Self::load_inscription_id(&sequence_number_to_entry, sequence_number))
+    .filter(|result| {
+      result
+        .as_ref()
+        .map_or(true, |inscription_id| !hidden(*inscription_id))
+    })
+    .take(limit)
+    .collect::<Result<Vec<InscriptionId>>>()?;
+
+    let search_term_to_rune_id = rtx.open_multimap_table(SEARCH_TERM_TO_RUNE_ID)?;
+    let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+
+    let runes = Self::rank_search_results(
+      &terms,
+      |term| search_term_to_rune_id.get(term),
+      |value| RuneId::load(value.value()),
+    )?
+    .into_iter()
+    .take(limit)
+    .map(|id| {
+      Ok(
+        RuneEntry::load(
+          rune_id_to_rune_entry
+            .get(id.store())?
+            .ok_or_else(|| anyhow!("could not find rune entry for {id}"))?
+            .value(),
+        )
+        .spaced_rune,
+      )
+    })
+    .collect::<Result<Vec<SpacedRune>>>()?;
+
+    Ok((inscriptions, runes))
+  }
+
+  /// Ranks matches by the number of distinct query terms they contain,
+  /// breaking ties in favor of newer matches. To bound the cost of common
+  /// terms, only the newest postings of each term are considered.
+  fn rank_search_results<V: redb::Key + 'static, K: Copy + Ord>(
+    terms: &BTreeSet<String>,
+    postings: impl Fn(&str) -> Result<redb::MultimapValue<'_, V>, StorageError>,
+    key: impl Fn(redb::AccessGuard<V>) -> K,
+  ) -> Result<Vec<K>> {
+    const MAX_POSTINGS_PER_TERM: usize = 10_000;
+
+    let mut scores = BTreeMap::<K, usize>::new();
+
+    for term in terms {
+      for value in postings(term)?.rev().take(MAX_POSTINGS_PER_TERM) {
+        *scores.entry(key(value?)).or_default() += 1;
+      }
+    }
+
+    let mut results = scores.into_iter().collect::<Vec<(K, usize)>>();
+
+    results
+      .sort_by(|(a_key, a_score), (b_key, b_score)| b_score.cmp(a_score).then(b_key.cmp(a_key)));
+
+    Ok(results.into_iter().map(|(key, _)| key).collect())
+  }
+
+  fn cursor_bounds<K>(cursor: Option<K>, order: Order) -> (Bound<K>, Bound<K>) {
+    match (cursor, order) {
+      (None, _) => (Bound::Unbounded, Bound::Unbounded),
+      (Some(cursor), Order::Newest) => (Bound::Unbounded, Bound::Included(cursor)),
+      (Some(cursor), Order::Oldest) => (Bound::Included(cursor), Bound::Unbounded),
+    }
+  }
+
+  /// Returns up to `page_size` items starting at `cursor`, in the given
+  /// order, along with the key of the first item of the next page. `key`
+  /// extracts keys from `entries`, which must be sorted oldest first, and
+  /// `load` is only called for keys that are returned or skipped. Keys for
+  /// which `load` returns `None` are skipped.
+  fn paginate_by_cursor<I, K, T>(
+    entries: I,
+    cursor: Option<K>,
+    order: Order,
+    page_size: usize,
+    key: impl Fn(I::Item) -> Result<K>,
+    load: impl FnMut(K) -> Result<Option<T>>,
+  ) -> Result<(Vec<T>, Option<K>)>
+  where
+    I: DoubleEndedIterator,
+    K: Copy + PartialOrd,
+  {
+    match order {
+      Order::Newest => Self::take_page(
+        entries.rev().map(key),
+        |key| cursor.map_or(true, |cursor| key <= cursor),
+        page_size,
+        load,
+      ),
+      Order::Oldest => Self::take_page(
+        entries.map(key),
+        |key| cursor.map_or(true, |cursor| key >= cursor),
+        page_size,
+        load,
+      ),
+    }
+  }
+
+  fn take_page<K: Copy, T>(
+    keys: impl Iterator<Item = Result<K>>,
+    reached: impl Fn(K) -> bool,
+    page_size: usize,
+    mut load: impl FnMut(K) -> Result<Option<T>>,
+  ) -> Result<(Vec<T>, Option<K>)> {
+    let mut items = Vec::new();
+
+    for key in keys {
+      let key = key?;
+
+      if !reached(key) {
+        continue;
+      }
+
+      if items.len() == page_size {
+        return Ok((items, Some(key)));
+      }
+
+      if let Some(item) = load(key)? {
+        items.push(item);
+      }
+    }
+
+    Ok((items, None))
+  }
+
   pub fn get_inscriptions_in_block(&self, block_height: u32) -> Result<Vec<InscriptionId>> {
     let rtx = self.database.begin_read()?;
 
@@ -1865,6 +2502,51 @@ impl Index {
       .collect::<Result<Vec<InscriptionId>>>()
   }
 
+  pub fn get_inscriptions_in_block_by_cursor(
+    &self,
+    block_height: u32,
+    cursor: Option<u32>,
+    order: Order,
+    page_size: usize,
+    hidden: &BTreeSet<InscriptionId>,
+  ) -> Result<(Vec<InscriptionId>, Option<u32>)> {
+    let rtx = self.database.begin_read()?;
+
+    let height_to_last_sequence_number = rtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
+    let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let Some(newest_sequence_number) = height_to_last_sequence_number
+      .get(&block_height)?
+      .map(|ag| ag.value())
+    else {
+      return Ok((Vec::new(), None));
+    };
+
+    let oldest_sequence_number = height_to_last_sequence_number
+      .get(block_height.saturating_sub(1))?
+      .map(|ag| ag.value())
+      .unwrap_or(0);
+
+    let sequence_numbers = match (cursor, order) {
+      (None, _) => oldest_sequence_number..newest_sequence_number,
+      (Some(cursor), Order::Newest) => {
+        oldest_sequence_number..cursor.saturating_add(1).min(newest_sequence_number)
+      }
+      (Some(cursor), Order::Oldest) => cursor.max(oldest_sequence_number)..newest_sequence_number,
+    };
+
+    Self::paginate_by_cursor(
+      sequence_numbers,
+      cursor,
+      order,
+      page_size,
+      Ok,
+      |sequence_number| {
+        Self::load_visible_inscription_id(&sequence_number_to_entry, sequence_number, hidden)
+      },
+    )
+  }
+
   pub fn get_runes_in_block(&self, block_height: u64) -> Result<Vec<SpacedRune>> {
     let rtx = self.database.begin_read()?;
 
@@ -1932,20 +2614,80 @@ impl Index {
     )
   }
 
-  pub fn get_feed_inscriptions(&self, n: usize) -> Result<Vec<(u32, InscriptionId)>> {
-    Ok(
-      self
-        .database
-        .begin_read()?
-        .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
-        .iter()?
-        .rev()
-        .take(n)
-        .flat_map(|result| {
-          result.map(|(number, entry)| (number.value(), InscriptionEntry::load(entry.value()).id))
-        })
-        .collect(),
-    )
+  pub fn get_feed_inscriptions(&self, n: usize) -> Result<Vec<(u32, InscriptionId)>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+        .iter()?
+        .rev()
+        .take(n)
+        .flat_map(|result| {
+          result.map(|(number, entry)| (number.value(), InscriptionEntry::load(entry.value()).id))
+        })
+        .collect(),
+    )
+  }
+
+  /// Returns the number of inscription sequence numbers and rune numbers
+  /// which have been assigned.
+  pub(crate) fn get_sequence_number_counts(&self) -> Result<(u32, u32)> {
+    let rtx = self.database.begin_read()?;
+
+    let inscriptions = rtx
+      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+      .last()?
+      .map(|(sequence_number, _entry)| sequence_number.value() + 1)
+      .unwrap_or_default();
+
+    let runes = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?.len()?;
+
+    Ok((inscriptions, runes.try_into().unwrap()))
+  }
+
+  /// Returns the IDs of inscriptions with sequence numbers in `range`, along
+  /// with whether each inscription has children.
+  pub(crate) fn get_inscriptions_in_sequence_number_range(
+    &self,
+    range: Range<u32>,
+  ) -> Result<Vec<(InscriptionId, bool)>> {
+    let rtx = self.database.begin_read()?;
+
+    let sequence_number_to_children = rtx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
+
+    let mut inscriptions = Vec::new();
+
+    for result in rtx
+      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+      .range(range)?
+    {
+      let (sequence_number, entry) = result?;
+
+      inscriptions.push((
+        InscriptionEntry::load(entry.value()).id,
+        !sequence_number_to_children
+          .get(sequence_number.value())?
+          .is_empty(),
+      ));
+    }
+
+    Ok(inscriptions)
+  }
+
+  /// Returns the IDs of runes with rune numbers in `range`.
+  pub(crate) fn get_runes_in_number_range(&self, range: Range<u32>) -> Result<Vec<RuneId>> {
+    self
+      .database
+      .begin_read()?
+      .open_table(RUNE_NUMBER_TO_RUNE_ID)?
+      .range(u64::from(range.start)..u64::from(range.end))?
+      .map(|result| {
+        result
+          .map(|(_number, id)| RuneId::load(id.value()))
+          .map_err(|err| err.into())
+      })
+      .collect()
   }
 
   pub(crate) fn inscription_info(
@@ -2177,6 +2919,65 @@ impl Index {
     Ok(entry)
   }
 
+  pub(crate) fn get_moderation_entries(
+    &self,
+  ) -> Result<BTreeMap<InscriptionId, api::ModerationEntry>> {
+    self
+      .database
+      .begin_read()?
+      .open_table(INSCRIPTION_ID_TO_MODERATION_ENTRY)?
+      .iter()?
+      .map(|result| {
+        let (inscription_id, entry) = result?;
+        let (reason, timestamp) = entry.value();
+        Ok((
+          InscriptionId::load(inscription_id.value()),
+          api::ModerationEntry {
+            reason: reason.into(),
+            timestamp,
+          },
+        ))
+      })
+      .collect()
+  }
+
+  /// Returns `true` if the inscription was not already hidden.
+  pub(crate) fn insert_moderation_entry(
+    &self,
+    inscription_id: InscriptionId,
+    entry: &api::ModerationEntry,
+  ) -> Result<bool> {
+    let wtx = self.begin_write()?;
+
+    let inserted = wtx
+      .open_table(INSCRIPTION_ID_TO_MODERATION_ENTRY)?
+      .insert(
+        &inscription_id.store(),
+        (entry.reason.as_str(), entry.timestamp),
+      )?
+      .is_none();
+
+    Self::increment_statistic(&wtx, Statistic::Commits, 1)?;
+    wtx.commit()?;
+
+    Ok(inserted)
+  }
+
+  /// Returns `true` if the inscription was hidden.
+  pub(crate) fn remove_moderation_entry(&self, inscription_id: InscriptionId) -> Result<bool> {
+    let wtx = self.begin_write()?;
+
+    let removed = wtx
+      .open_table(INSCRIPTION_ID_TO_MODERATION_ENTRY)?
+      .remove(&inscription_id.store())?
+      .is_some();
+
+    Self::increment_statistic(&wtx, Statistic::Commits, 1)?;
+    wtx.commit()?;
+
+    Ok(removed)
+  }
+
   #[cfg(test)]
   fn assert_inscription_location(
     &self,
@@ -2293,11 +3094,18 @@ impl Index {
   }
 
   pub fn get_address_info(&self, address: &Address) -> Result<Vec<OutPoint>> {
+    self.get_outputs_for_script_pubkey(&address.script_pubkey())
+  }
+
+  pub(crate) fn get_outputs_for_script_pubkey(
+    &self,
+    script_pubkey: &Script,
+  ) -> Result<Vec<OutPoint>> {
     self
       .database
       .begin_read()?
       .open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?
-      .get(address.script_pubkey().as_bytes())?
+      .get(script_pubkey.as_bytes())?
       .map(|result| {
         result
           .map_err(|err| anyhow!(err))
@@ -2306,6 +3114,80 @@ impl Index {
       .collect()
   }
 
+  /// Returns the value of each of `outputs` recorded by the address index,
+  /// along with the height and header of the block in which it was received,
+  /// most recently received first.
+  pub(crate) fn get_received_outputs(
+    &self,
+    outputs: &[OutPoint],
+  ) -> Result<Vec<(OutPoint, u64, u32, Header)>> {
+    let rtx = self.database.begin_read()?;
+
+    let height_to_block_header = rtx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
+    let outpoint_to_height = rtx.open_table(OUTPOINT_TO_HEIGHT)?;
+    let outpoint_to_utxo_entry = rtx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
+
+    let mut received = Vec::new();
+
+    for outpoint in outputs {
+      let Some(height) = outpoint_to_height.get(&outpoint.store())? else {
+        continue;
+      };
+
+      let height = height.value();
+
+      let Some(header) = height_to_block_header.get(height)? else {
+        continue;
+      };
+
+      let Some(utxo_entry) = outpoint_to_utxo_entry.get(&outpoint.store())? else {
+        continue;
+      };
+
+      received.push((
+        *outpoint,
+        utxo_entry.value().parse(self).total_value(),
+        height,
+        Header::load(*header.value()),
+      ));
+    }
+
+    received.sort_by_key(|(outpoint, _value, height, _header)| cmp::Reverse((*height, *outpoint)));
+
+    Ok(received)
+  }
+
+  /// Returns the script pubkey whose SHA256 hash is `script_hash`, if an
+  /// output with that script pubkey has ever been indexed.
+  pub(crate) fn get_script_pubkey_for_script_hash(
+    &self,
+    script_hash: &[u8; 32],
+  ) -> Result<Option<ScriptBuf>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(SCRIPT_HASH_TO_SCRIPT_PUBKEY)?
+        .get(script_hash)?
+        .map(|script_pubkey| ScriptBuf::from_bytes(script_pubkey.value().to_vec())),
+    )
+  }
+
+  /// Returns the confirmed history of `script_hash` as (height, txid) pairs,
+  /// in block order.
+  pub(crate) fn get_script_hash_history(&self, script_hash: &[u8; 32]) -> Result<Vec<(u32, Txid)>> {
+    self
+      .database
+      .begin_read()?
+      .open_multimap_table(SCRIPT_HASH_TO_HISTORY)?
+      .get(script_hash)?
+      .map(|result| {
+        let (height, _tx_offset, txid) = result?.value();
+        Ok((height, Txid::load(txid)))
+      })
+      .collect()
+  }
+
   pub(crate) fn get_aggregated_rune_balances_for_outputs(
     &self,
     outputs: &Vec<OutPoint>,
@@ -3663,7 +4545,10 @@ mod tests {
 
       context.mine_blocks(1);
 
-      let (inscriptions, more) = context.index.get_inscriptions_paginated(100, 0).unwrap();
+      let (inscriptions, more) = context
+        .index
+        .get_inscriptions_paginated(100, 0, &BTreeSet::new())
+        .unwrap();
       assert_eq!(inscriptions, &[inscription_id]);
       assert!(!more);
     }
@@ -3690,12 +4575,58 @@ mod tests {
 
       assert_eq!(ids.len(), 100);
 
-      let (inscriptions, more) = context.index.get_inscriptions_paginated(100, 0).unwrap();
+      let (inscriptions, more) = context
+        .index
+        .get_inscriptions_paginated(100, 0, &BTreeSet::new())
+        .unwrap();
       assert_eq!(inscriptions, ids);
       assert!(more);
     }
   }
 
+  #[test]
+  fn get_latest_inscriptions_skips_hidden() {
+    for context in Context::configurations() {
+      context.mine_blocks(1);
+
+      let mut ids = Vec::new();
+
+      for i in 0..5 {
+        let txid = context.core.broadcast_tx(TransactionTemplate {
+          inputs: &[(i + 1, 0, 0, inscription("text/plain", "hello").to_witness())],
+          ..default()
+        });
+        context.mine_blocks(1);
+        ids.push(InscriptionId { txid, index: 0 });
+      }
+
+      ids.reverse();
+
+      let hidden = [ids[1], ids[3]]
+        .into_iter()
+        .collect::<BTreeSet<InscriptionId>>();
+
+      let pages = (0..4)
+        .map(|page_index| {
+          context
+            .index
+            .get_inscriptions_paginated(1, page_index, &hidden)
+            .unwrap()
+        })
+        .collect::<Vec<(Vec<InscriptionId>, bool)>>();
+
+      assert_eq!(
+        pages,
+        [
+          (vec![ids[0]], true),
+          (vec![ids[2]], true),
+          (vec![ids[4]], false),
+          (Vec::new(), false),
+        ]
+      );
+    }
+  }
+
   #[test]
   fn unrecognized_even_field_inscriptions_are_cursed_and_unbound() {
     for context in Context::configurations() {
@@ -4648,6 +5579,100 @@ mod tests {
     }
   }
 
+  #[test]
+  fn moderation_entries_are_not_rolled_back_by_reorgs() {
+    let mut context = Context::builder().build();
+
+    context.index.set_durability(redb::Durability::Immediate);
+
+    context.mine_blocks(1);
+
+    let entry = api::ModerationEntry {
+      reason: "spam".into(),
+      timestamp: 1,
+    };
+
+    assert!(context
+      .index
+      .insert_moderation_entry(inscription_id(1), &entry)
+      .unwrap());
+
+    context.mine_blocks(1);
+
+    assert!(context
+      .index
+      .insert_moderation_entry(inscription_id(2), &entry)
+      .unwrap());
+
+    context.core.invalidate_tip();
+    context.mine_blocks(2);
+
+    assert_eq!(
+      context.index.get_moderation_entries().unwrap(),
+      [
+        (inscription_id(1), entry.clone()),
+        (inscription_id(2), entry)
+      ]
+      .into(),
+    );
+  }
+
+  #[test]
+  fn moderation_entries_are_persisted() {
+    let tempdir = {
+      let context = Context::builder().build();
+
+      let entry = api::ModerationEntry {
+        reason: "spam".into(),
+        timestamp: 1,
+      };
+
+      assert!(context
+        .index
+        .insert_moderation_entry(inscription_id(1), &entry)
+        .unwrap());
+
+      assert!(!context
+        .index
+        .insert_moderation_entry(inscription_id(1), &entry)
+        .unwrap());
+
+      assert!(context
+        .index
+        .insert_moderation_entry(inscription_id(2), &entry)
+        .unwrap());
+
+      assert!(context
+        .index
+        .remove_moderation_entry(inscription_id(2))
+        .unwrap());
+
+      assert!(!context
+        .index
+        .remove_moderation_entry(inscription_id(2))
+        .unwrap());
+
+      context.tempdir
+    };
+
+    assert_eq!(
+      Context::builder()
+        .tempdir(tempdir)
+        .build()
+        .index
+        .get_moderation_entries()
+        .unwrap(),
+      [(
+        inscription_id(1),
+        api::ModerationEntry {
+          reason: "spam".into(),
+          timestamp: 1,
+        }
+      )]
+      .into(),
+    );
+  }
+
   #[test]
   fn recover_from_3_block_deep_and_consecutive_reorg() {
     for mut context in Context::configurations() {
@@ -6492,6 +7517,130 @@ mod tests {
     );
   }
 
+  #[test]
+  fn rune_events_are_pruned() {
+    const RUNE: u128 = 99246114928149462;
+
+    let context = Context::builder().arg("--index-runes").build();
+
+    let (txid, id) = context.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_rune_events(id, usize::MAX, |_| true)
+        .unwrap(),
+      [(
+        id.block.try_into().unwrap(),
+        RuneEvent {
+          amount: 0,
+          id,
+          kind: RuneEventKind::Etched,
+          txid,
+        }
+      )],
+    );
+
+    context.mine_blocks(RUNE_EVENT_BLOCKS.into());
+
+    assert_eq!(
+      context
+        .index
+        .get_rune_events(id, usize::MAX, |_| true)
+        .unwrap(),
+      []
+    );
+
+    let rtx = context.index.database.begin_read().unwrap();
+
+    assert!(rtx
+      .open_multimap_table(HEIGHT_TO_EVENT_RUNE_ID)
+      .unwrap()
+      .is_empty()
+      .unwrap());
+
+    assert!(rtx
+      .open_multimap_table(RUNE_ID_AND_HEIGHT_TO_RUNE_EVENT)
+      .unwrap()
+      .is_empty()
+      .unwrap());
+  }
+
+  #[test]
+  fn rune_mint_events_are_limited_per_block() {
+    const RUNE: u128 = 99246114928149462;
+
+    let context = Context::builder().arg("--index-runes").build();
+
+    let (_txid, id) = context.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          terms: Some(Terms {
+            amount: Some(1),
+            cap: Some(1000),
+            ..default()
+          }),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+    );
+
+    let start = usize::try_from(context.index.block_count().unwrap()).unwrap();
+
+    context.mine_blocks(u64::try_from(RUNE_MINT_EVENTS_PER_BLOCK).unwrap() + 1);
+
+    for i in 0..=RUNE_MINT_EVENTS_PER_BLOCK {
+      context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(start + i, 0, 0, Witness::new())],
+        op_return: Some(
+          Runestone {
+            mint: Some(id),
+            ..default()
+          }
+          .encipher(),
+        ),
+        ..default()
+      });
+    }
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context.index.rune(Rune(RUNE)).unwrap().unwrap().1.mints,
+      u128::try_from(RUNE_MINT_EVENTS_PER_BLOCK).unwrap() + 1,
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_rune_events(id, usize::MAX, |event| event.kind == RuneEventKind::Minted)
+        .unwrap()
+        .len(),
+      RUNE_MINT_EVENTS_PER_BLOCK,
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_rune_events(id, 5, |_| true)
+        .unwrap()
+        .len(),
+      5,
+    );
+  }
+
   #[test]
   fn rune_event_sender_channel() {
     const RUNE: u128 = 99246114928149462;
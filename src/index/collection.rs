@@ -0,0 +1,302 @@
+use {super::*, redb::ReadTransaction, std::collections::HashSet};
+
+/// The order in which the children of a collection are listed.
+#[derive(Debug, PartialEq, Clone, Default, DeserializeFromStr, SerializeDisplay)]
+pub enum CollectionOrder {
+  #[default]
+  Inscribed,
+  Trait(String),
+  Transferred,
+}
+
+impl Display for CollectionOrder {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Inscribed => write!(f, "inscribed"),
+      Self::Trait(key) => write!(f, "trait:{key}"),
+      Self::Transferred => write!(f, "transferred"),
+    }
+  }
+}
+
+impl FromStr for CollectionOrder {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "inscribed" => Ok(Self::Inscribed),
+      "transferred" => Ok(Self::Transferred),
+      _ => match s.strip_prefix("trait:") {
+        Some(key) if !key.is_empty() => Ok(Self::Trait(key.into())),
+        _ => bail!("invalid collection order `{s}`"),
+      },
+    }
+  }
+}
+
+impl Index {
+  /// Returns a page of the children of `parent` in `order`, along with
+  /// statistics about the collection. Holders are only counted on the first
+  /// page, and only if the index has an address index.
+  pub(crate) fn get_collection(
+    &self,
+    parent: InscriptionId,
+    order: &CollectionOrder,
+    page_size: usize,
+    page_index: usize,
+    hidden: &BTreeSet<InscriptionId>,
+  ) -> Result<Option<api::Collection>> {
+    let rtx = self.database.begin_read()?;
+
+    let inscription_id_to_sequence_number = rtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
+
+    let Some(parent_sequence_number) = inscription_id_to_sequence_number
+      .get(&parent.store())?
+      .map(|sequence_number| sequence_number.value())
+    else {
+      return Ok(None);
+    };
+
+    let mut hidden_sequence_numbers = HashSet::new();
+
+    for inscription_id in hidden {
+      if let Some(sequence_number) =
+        inscription_id_to_sequence_number.get(&inscription_id.store())?
+      {
+        hidden_sequence_numbers.insert(sequence_number.value());
+      }
+    }
+
+    let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let load_entry = |sequence_number: u32| -> Result<InscriptionEntry> {
+      Ok(InscriptionEntry::load(
+        sequence_number_to_entry
+          .get(sequence_number)?
+          .ok_or_else(|| {
+            anyhow!("missing inscription entry for sequence number {sequence_number}")
+          })?
+          .value(),
+      ))
+    };
+
+    let parent_entry = load_entry(parent_sequence_number)?;
+
+    let sequence_number_to_children = rtx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
+
+    let children = || -> Result<Box<dyn Iterator<Item = Result<u32>> + '_>> {
+      Ok(Box::new(
+        sequence_number_to_children
+          .get(parent_sequence_number)?
+          .map(|result| {
+            result
+              .map(|sequence_number| sequence_number.value())
+              .map_err(|err| err.into())
+          }),
+      ))
+    };
+
+    let total_children = sequence_number_to_children
+      .get(parent_sequence_number)?
+      .len();
+
+    let first_child_height = match children()?.next().transpose()? {
+      Some(sequence_number) => Some(load_entry(sequence_number)?.height),
+      None => None,
+    };
+
+    let last_child_height = match sequence_number_to_children
+      .get(parent_sequence_number)?
+      .next_back()
+      .transpose()?
+    {
+      Some(sequence_number) => Some(load_entry(sequence_number.value())?.height),
+      None => None,
+    };
+
+    let holders = if self.index_addresses && page_index == 0 {
+      Some(self.count_collection_holders(&rtx, children()?)?)
+    } else {
+      None
+    };
+
+    let sequence_number_to_transferred_children;
+
+    let ordered = match order {
+      CollectionOrder::Inscribed => children()?,
+      CollectionOrder::Transferred => {
+        ensure!(self.index_transfers, "this index has no transfers index");
+
+        sequence_number_to_transferred_children =
+          rtx.open_multimap_table(SEQUENCE_NUMBER_TO_TRANSFERRED_CHILDREN)?;
+
+        Box::new(
+          sequence_number_to_transferred_children
+            .get(parent_sequence_number)?
+            .rev()
+            .map(|result| {
+              result
+                .map(|value| value.value().1)
+                .map_err(|err| err.into())
+            }),
+        )
+      }
+      CollectionOrder::Trait(key) => Box::new(
+        self
+          .order_children_by_trait(
+            &rtx,
+            parent_sequence_number,
+            key,
+            children()?.collect::<Result<Vec<u32>>>()?,
+          )?
+          .into_iter()
+          .map(Ok),
+      ),
+    };
+
+    let mut page = Vec::new();
+
+    for sequence_number in ordered
+      .filter(|result| {
+        result.as_ref().map_or(true, |sequence_number| {
+          !hidden_sequence_numbers.contains(sequence_number)
+        })
+      })
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+    {
+      page.push(load_entry(sequence_number?)?.id);
+    }
+
+    let more = page.len() > page_size;
+
+    if more {
+      page.pop();
+    }
+
+    Ok(Some(api::Collection {
+      children: page,
+      first_child_height,
+      holders,
+      last_child_height,
+      more,
+      order: order.clone(),
+      page: page_index,
+      parent,
+      parent_number: parent_entry.inscription_number,
+      total_children,
+    }))
+  }
+
+  fn count_collection_holders(
+    &self,
+    rtx: &ReadTransaction,
+    children: impl Iterator<Item = Result<u32>>,
+  ) -> Result<u64> {
+    let sequence_number_to_satpoint = rtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+    let outpoint_to_utxo_entry = rtx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
+
+    let mut holders = HashSet::new();
+
+    for sequence_number in children {
+      let Some(satpoint) = sequence_number_to_satpoint.get(sequence_number?)? else {
+        continue;
+      };
+
+      let outpoint = SatPoint::load(*satpoint.value()).outpoint;
+
+      if Self::is_special_outpoint(outpoint) {
+        continue;
+      }
+
+      if let Some(utxo_entry) = outpoint_to_utxo_entry.get(&outpoint.store())? {
+        holders.insert(utxo_entry.value().parse(self).script_pubkey().to_vec());
+      }
+    }
+
+    Ok(holders.len().try_into().unwrap())
+  }
+
+  /// Orders children by the value of trait `key`, with numeric values first,
+  /// in numeric order, followed by other values in lexicographic order.
+  /// Children without the trait come last, in inscription order.
+  fn order_children_by_trait(
+    &self,
+    rtx: &ReadTransaction,
+    parent_sequence_number: u32,
+    key: &str,
+    children: Vec<u32>,
+  ) -> Result<Vec<u32>> {
+    let trait_to_sequence_number = rtx.open_multimap_table(TRAIT_TO_SEQUENCE_NUMBER)?;
+
+    let mut values = Vec::new();
+
+    for result in trait_to_sequence_number.range((parent_sequence_number, key, "")..)? {
+      let (trait_key, sequence_numbers) = result?;
+
+      let (parent, trait_key, value) = trait_key.value();
+
+      if parent != parent_sequence_number || trait_key != key {
+        break;
+      }
+
+      values.push((
+        value.to_string(),
+        sequence_numbers
+          .map(|result| {
+            result
+              .map(|sequence_number| sequence_number.value())
+              .map_err(|err| err.into())
+          })
+          .collect::<Result<Vec<u32>>>()?,
+      ));
+    }
+
+    values.sort_by(
+      |(a, _), (b, _)| match (a.parse::<f64>().ok(), b.parse::<f64>().ok()) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        (Some(_), None) => cmp::Ordering::Less,
+        (None, Some(_)) => cmp::Ordering::Greater,
+        (None, None) => a.cmp(b),
+      },
+    );
+
+    let mut seen = HashSet::new();
+    let mut ordered = Vec::with_capacity(children.len());
+
+    for sequence_number in values.into_iter().flat_map(|(_value, children)| children) {
+      if seen.insert(sequence_number) {
+        ordered.push(sequence_number);
+      }
+    }
+
+    for sequence_number in children {
+      if seen.insert(sequence_number) {
+        ordered.push(sequence_number);
+      }
+    }
+
+    Ok(ordered)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn collection_order_round_trip() {
+    #[track_caller]
+    fn case(s: &str, order: CollectionOrder) {
+      assert_eq!(s.parse::<CollectionOrder>().unwrap(), order);
+      assert_eq!(order.to_string(), s);
+    }
+
+    case("inscribed", CollectionOrder::Inscribed);
+    case("transferred", CollectionOrder::Transferred);
+    case("trait:eyes", CollectionOrder::Trait("eyes".into()));
+
+    assert!("trait:".parse::<CollectionOrder>().is_err());
+    assert!("newest".parse::<CollectionOrder>().is_err());
+  }
+}
@@ -287,6 +287,54 @@ impl Entry for RuneId {
   }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum RuneEventKind {
+  Burned,
+  Etched,
+  Minted,
+  Transferred { vout: u32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct RuneEvent {
+  pub(crate) amount: u128,
+  pub(crate) id: RuneId,
+  pub(crate) kind: RuneEventKind,
+  pub(crate) txid: Txid,
+}
+
+pub(super) type RuneEventValue = (RuneIdValue, TxidValue, u8, u32, u128);
+
+impl Entry for RuneEvent {
+  type Value = RuneEventValue;
+
+  fn load((id, txid, kind, vout, amount): Self::Value) -> Self {
+    Self {
+      amount,
+      id: RuneId::load(id),
+      kind: match kind {
+        0 => RuneEventKind::Burned,
+        1 => RuneEventKind::Etched,
+        2 => RuneEventKind::Minted,
+        3 => RuneEventKind::Transferred { vout },
+        _ => unreachable!(),
+      },
+      txid: Txid::load(txid),
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    let (kind, vout) = match self.kind {
+      RuneEventKind::Burned => (0, 0),
+      RuneEventKind::Etched => (1, 0),
+      RuneEventKind::Minted => (2, 0),
+      RuneEventKind::Transferred { vout } => (3, vout),
+    };
+
+    (self.id.store(), self.txid.store(), kind, vout, self.amount)
+  }
+}
+
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct InscriptionEntry {
   pub charms: u16,
@@ -516,6 +564,30 @@ mod tests {
     assert_eq!(InscriptionEntry::load(value), entry);
   }
 
+  #[test]
+  fn rune_event_entry() {
+    let txid = Txid::all_zeros();
+
+    for (kind, value) in [
+      (RuneEventKind::Burned, (0, 0)),
+      (RuneEventKind::Etched, (1, 0)),
+      (RuneEventKind::Minted, (2, 0)),
+      (RuneEventKind::Transferred { vout: 5 }, (3, 5)),
+    ] {
+      let event = RuneEvent {
+        amount: 6,
+        id: RuneId { block: 1, tx: 2 },
+        kind,
+        txid,
+      };
+
+      let value = ((1, 2), txid.store(), value.0, value.1, 6);
+
+      assert_eq!(event.store(), value);
+      assert_eq!(RuneEvent::load(value), event);
+    }
+  }
+
   #[test]
   fn inscription_id_entry() {
     let inscription_id = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdefi0"
@@ -62,11 +62,29 @@ impl Reorg {
 
     let mut wtx = index.begin_write()?;
 
+    // Moderation entries are not derived from the chain, so they are carried
+    // over instead of being rolled back with everything else.
+    let moderation_entries = index.get_moderation_entries()?;
+
     let oldest_savepoint =
       wtx.get_persistent_savepoint(wtx.list_persistent_savepoints()?.min().unwrap())?;
 
     wtx.restore_savepoint(&oldest_savepoint)?;
 
+    {
+      let mut inscription_id_to_moderation_entry =
+        wtx.open_table(INSCRIPTION_ID_TO_MODERATION_ENTRY)?;
+
+      inscription_id_to_moderation_entry.retain(|_, _| false)?;
+
+      for (inscription_id, entry) in &moderation_entries {
+        inscription_id_to_moderation_entry.insert(
+          &inscription_id.store(),
+          (entry.reason.as_str(), entry.timestamp),
+        )?;
+      }
+    }
+
     Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
     wtx.commit()?;
 
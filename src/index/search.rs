@@ -0,0 +1,187 @@
+use super::*;
+
+const MAX_BODY_BYTES: usize = 64 * 1024;
+const MAX_TERMS: usize = 256;
+const MAX_TERM_CHARS: usize = 32;
+
+pub(crate) fn terms(text: &str) -> impl Iterator<Item = String> + '_ {
+  text
+    .split(|c: char| !c.is_alphanumeric())
+    .filter(|term| !term.is_empty() && term.chars().count() <= MAX_TERM_CHARS)
+    .map(str::to_lowercase)
+}
+
+pub(crate) fn inscription_terms(inscription: &Inscription) -> BTreeSet<String> {
+  let mut text = String::new();
+
+  if let Some(body) = searchable_body(inscription) {
+    text.push_str(&body);
+  }
+
+  if let Some(metadata) = inscription.metadata() {
+    metadata_text(&metadata, &mut text);
+  }
+
+  let mut inscription_terms = BTreeSet::new();
+
+  for term in terms(&text) {
+    if inscription_terms.len() == MAX_TERMS {
+      break;
+    }
+
+    inscription_terms.insert(term);
+  }
+
+  inscription_terms
+}
+
+pub(crate) fn rune_terms(spaced_rune: SpacedRune) -> BTreeSet<String> {
+  let mut terms = terms(&spaced_rune.to_string()).collect::<BTreeSet<String>>();
+  terms.insert(spaced_rune.rune.to_string().to_lowercase());
+  terms
+}
+
+fn searchable_body(inscription: &Inscription) -> Option<String> {
+  if inscription.content_encoding().is_some() {
+    return None;
+  }
+
+  let content_type = inscription
+    .content_type()?
+    .split(';')
+    .next()
+    .unwrap()
+    .trim()
+    .to_lowercase();
+
+  if !content_type.starts_with("text/") && content_type != "application/json" {
+    return None;
+  }
+
+  let body = inscription.body()?;
+
+  let body = String::from_utf8_lossy(&body[..body.len().min(MAX_BODY_BYTES)]);
+
+  if content_type == "text/html" {
+    Some(strip_tags(&body))
+  } else {
+    Some(body.into_owned())
+  }
+}
+
+fn strip_tags(html: &str) -> String {
+  let mut text = String::with_capacity(html.len());
+  let mut in_tag = false;
+
+  for c in html.chars() {
+    match c {
+      '<' => in_tag = true,
+      '>' if in_tag => {
+        in_tag = false;
+        text.push(' ');
+      }
+      _ if !in_tag => text.push(c),
+      _ => {}
+    }
+  }
+
+  text
+}
+
+fn metadata_text(value: &Value, text: &mut String) {
+  match value {
+    Value::Text(s) => {
+      text.push(' ');
+      text.push_str(s);
+    }
+    Value::Array(values) => {
+      for value in values {
+        metadata_text(value, text);
+      }
+    }
+    Value::Map(entries) => {
+      for (key, value) in entries {
+        metadata_text(key, text);
+        metadata_text(value, text);
+      }
+    }
+    Value::Tag(_, value) => metadata_text(value, text),
+    _ => {}
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn terms_are_lowercased_and_split_on_non_alphanumerics() {
+    assert_eq!(
+      terms("Hello, World! foo-bar_baz").collect::<Vec<String>>(),
+      ["hello", "world", "foo", "bar", "baz"],
+    );
+  }
+
+  #[test]
+  fn long_terms_are_ignored() {
+    assert_eq!(
+      terms(&format!("a {} b", "x".repeat(33))).collect::<Vec<String>>(),
+      ["a", "b"],
+    );
+  }
+
+  #[test]
+  fn html_tags_are_stripped() {
+    assert_eq!(
+      inscription_terms(&inscription(
+        "text/html;charset=utf-8",
+        "<html><body class=foo>bar</body></html>"
+      )),
+      ["bar".into()].into(),
+    );
+  }
+
+  #[test]
+  fn binary_bodies_are_not_indexed() {
+    assert!(inscription_terms(&inscription("image/png", "foo")).is_empty());
+  }
+
+  #[test]
+  fn json_bodies_are_indexed() {
+    assert_eq!(
+      inscription_terms(&inscription("application/json", r#"{"foo":"bar"}"#)),
+      ["bar".into(), "foo".into()].into(),
+    );
+  }
+
+  #[test]
+  fn metadata_is_indexed() {
+    let mut metadata = Vec::new();
+    ciborium::into_writer(
+      &Value::Map(vec![(
+        Value::Text("Name".into()),
+        Value::Array(vec![Value::Text("Pepe".into()), Value::Integer(1.into())]),
+      )]),
+      &mut metadata,
+    )
+    .unwrap();
+
+    assert_eq!(
+      inscription_terms(&Inscription {
+        content_type: Some("image/png".into()),
+        body: Some(b"foo".into()),
+        metadata: Some(metadata),
+        ..default()
+      }),
+      ["name".into(), "pepe".into()].into(),
+    );
+  }
+
+  #[test]
+  fn rune_terms_include_full_name() {
+    assert_eq!(
+      rune_terms("UNCOMMON•GOODS".parse().unwrap()),
+      ["goods".into(), "uncommon".into(), "uncommongoods".into()].into(),
+    );
+  }
+}
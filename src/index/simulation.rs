@@ -0,0 +1,587 @@
+use {super::*, redb::ReadTransaction, std::collections::VecDeque, updater::rune_updater};
+
+impl Index {
+  /// Simulates the effect of `tx` against the current index, without
+  /// modifying it. `prevouts` must contain the output spent by each input.
+  ///
+  /// The simulation assumes that `tx` is the first transaction after the
+  /// coinbase in the next block. Etching commitments are not checked. Inputs
+  /// whose contents the index cannot vouch for, such as unconfirmed outputs,
+  /// are an error.
+  pub(crate) fn simulate(&self, tx: &Transaction, prevouts: &[TxOut]) -> Result<api::Simulation> {
+    assert_eq!(tx.input.len(), prevouts.len());
+
+    let rtx = self.database.begin_read()?;
+
+    let height = rtx
+      .open_table(HEIGHT_TO_BLOCK_HEADER)?
+      .range(0..)?
+      .next_back()
+      .transpose()?
+      .map(|(height, _header)| height.value() + 1)
+      .unwrap_or(0);
+
+    let outpoint_to_utxo_entry = rtx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let txid = tx.compute_txid();
+    let envelopes = ParsedEnvelope::from_transaction(tx);
+    let artifact = Runestone::decipher(tx);
+
+    let input_value = prevouts
+      .iter()
+      .map(|tx_out| tx_out.value.to_sat())
+      .sum::<u64>();
+
+    let output_values = tx
+      .output
+      .iter()
+      .map(|tx_out| tx_out.value.to_sat())
+      .collect::<Vec<u64>>();
+
+    let output_value = output_values.iter().sum::<u64>();
+
+    ensure!(
+      output_value <= input_value,
+      "output value {output_value} exceeds input value {input_value}"
+    );
+
+    let mut inscriptions = self.index_inscriptions.then(Vec::new);
+    let mut sat_ranges = self.index_sats.then(Vec::new);
+    let mut offset = 0;
+
+    for input in &tx.input {
+      self.verify_input(&outpoint_to_utxo_entry, input.previous_output, height)?;
+    }
+
+    for ((input, prevout), input_index) in tx.input.iter().zip(prevouts).zip(0..) {
+      let outpoint = input.previous_output;
+
+      if let Some(inscriptions) = &mut inscriptions {
+        for (satpoint, inscription_id) in self
+          .inscriptions_on_output(
+            &outpoint_to_utxo_entry,
+            &sequence_number_to_inscription_entry,
+            outpoint,
+          )?
+          .unwrap_or_default()
+        {
+          inscriptions.push((offset + satpoint.offset, inscription_id));
+        }
+
+        for (index, envelope) in envelopes.iter().enumerate() {
+          if envelope.input != input_index {
+            continue;
+          }
+
+          inscriptions.push((
+            envelope
+              .payload
+              .pointer()
+              .filter(|&pointer| pointer < output_value)
+              .unwrap_or(offset),
+            InscriptionId {
+              txid,
+              index: index.try_into().unwrap(),
+            },
+          ));
+        }
+      }
+
+      if let Some(ranges) = &mut sat_ranges {
+        match outpoint_to_utxo_entry.get(&outpoint.store())? {
+          Some(utxo_entry) => ranges.extend(
+            utxo_entry
+              .value()
+              .parse(self)
+              .sat_ranges()
+              .chunks_exact(11)
+              .map(|chunk| SatRange::load(chunk.try_into().unwrap())),
+          ),
+          None => sat_ranges = None,
+        }
+      }
+
+      offset += prevout.value.to_sat();
+    }
+
+    let mut output_inscriptions = inscriptions
+      .as_ref()
+      .map(|_| vec![Vec::new(); tx.output.len()]);
+    let mut fee_inscriptions = inscriptions.as_ref().map(|_| Vec::new());
+
+    if let Some(mut inscriptions) = inscriptions {
+      inscriptions.sort_by_key(|(offset, _inscription_id)| *offset);
+
+      for (offset, inscription_id) in inscriptions {
+        match Self::output_containing_offset(&output_values, offset) {
+          Some(vout) => output_inscriptions.as_mut().unwrap()[vout].push(inscription_id),
+          None => fee_inscriptions.as_mut().unwrap().push(inscription_id),
+        }
+      }
+    }
+
+    let (output_sat_ranges, fee_sat_ranges) = match sat_ranges {
+      Some(ranges) => {
+        let (outputs, fee) = assign_sat_ranges(&ranges, &output_values);
+        (Some(outputs), Some(fee))
+      }
+      None => (None, None),
+    };
+
+    let (output_runes, burned_runes) = if !self.index_runes {
+      (None, None)
+    } else if height < self.settings.first_rune_height() {
+      (
+        Some(vec![BTreeMap::new(); tx.output.len()]),
+        Some(BTreeMap::new()),
+      )
+    } else {
+      let (allocated, burned) = self.simulate_runes(&rtx, tx, height, artifact.as_ref())?;
+      (Some(allocated), Some(burned))
+    };
+
+    Ok(api::Simulation {
+      burned_runes,
+      fee: input_value - output_value,
+      fee_inscriptions,
+      fee_sat_ranges,
+      inscriptions: envelopes,
+      outputs: tx
+        .output
+        .iter()
+        .enumerate()
+        .map(|(vout, tx_out)| api::SimulatedOutput {
+          address: self
+            .settings
+            .chain()
+            .address_from_script(&tx_out.script_pubkey)
+            .ok()
+            .map(|address| address.as_unchecked().clone()),
+          inscriptions: output_inscriptions
+            .as_ref()
+            .map(|inscriptions| inscriptions[vout].clone()),
+          runes: output_runes.as_ref().map(|runes| runes[vout].clone()),
+          sat_ranges: output_sat_ranges
+            .as_ref()
+            .map(|sat_ranges| sat_ranges[vout].clone()),
+          script_pubkey: tx_out.script_pubkey.clone(),
+          value: tx_out.value.to_sat(),
+        })
+        .collect(),
+      runestone: artifact,
+      txid,
+    })
+  }
+
+  /// Returns the first-in-first-out flow of sats from the inputs of `tx` to
+  /// its outputs and fees, or `None` if the index has no sat index.
+  ///
+  /// The outputs spent by `tx` are read from the index, from the UTXO table if
+  /// they are unspent, and otherwise from the spent output table, which is
+  /// only populated if the index was built with `--index-spent-sats`. If any
+  /// are missing, `None` is returned.
+  pub(crate) fn get_sat_flow(&self, tx: &Transaction) -> Result<Option<api::SatFlow>> {
+    if !self.index_sats || tx.is_coinbase() {
+      return Ok(None);
+    }
+
+    let rtx = self.database.begin_read()?;
+
+    let outpoint_to_spent_utxo_entry = rtx.open_table(OUTPOINT_TO_SPENT_UTXO_ENTRY)?;
+    let outpoint_to_utxo_entry = rtx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let mut input_utxo_entries = Vec::new();
+
+    for input in &tx.input {
+      let outpoint = input.previous_output.store();
+
+      let Some(utxo_entry) = outpoint_to_utxo_entry
+        .get(&outpoint)?
+        .map(Ok)
+        .or_else(|| outpoint_to_spent_utxo_entry.get(&outpoint).transpose())
+        .transpose()?
+      else {
+        return Ok(None);
+      };
+
+      input_utxo_entries.push(utxo_entry);
+    }
+
+    let input_utxo_entries = input_utxo_entries
+      .iter()
+      .map(|utxo_entry| utxo_entry.value().parse(self))
+      .collect::<Vec<ParsedUtxoEntry>>();
+
+    let input_values = input_utxo_entries
+      .iter()
+      .map(|utxo_entry| utxo_entry.total_value())
+      .collect::<Vec<u64>>();
+
+    let output_values = tx
+      .output
+      .iter()
+      .map(|tx_out| tx_out.value.to_sat())
+      .collect::<Vec<u64>>();
+
+    let input_value = input_values.iter().sum::<u64>();
+    let output_value = output_values.iter().sum::<u64>();
+
+    if output_value > input_value {
+      return Ok(None);
+    }
+
+    let mut bounds = Vec::new();
+    let mut input_start = 0;
+
+    for (input, value) in input_values.iter().enumerate() {
+      let input_end = input_start + value;
+      let mut output_start = 0;
+
+      for (output, value) in output_values.iter().enumerate() {
+        let output_end = output_start + value;
+
+        let start = input_start.max(output_start);
+        let end = input_end.min(output_end);
+
+        if start < end {
+          bounds.push((input, Some(output), start, end));
+        }
+
+        output_start = output_end;
+      }
+
+      let start = input_start.max(output_value);
+
+      if start < input_end {
+        bounds.push((input, None, start, input_end));
+      }
+
+      input_start = input_end;
+    }
+
+    let mut ranges = Vec::new();
+    let mut inscriptions = Vec::new();
+    let mut offset = 0;
+
+    for (utxo_entry, value) in input_utxo_entries.iter().zip(&input_values) {
+      ranges.extend(
+        utxo_entry
+          .sat_ranges()
+          .chunks_exact(11)
+          .map(|chunk| SatRange::load(chunk.try_into().unwrap())),
+      );
+
+      if self.index_inscriptions {
+        for (sequence_number, inscription_offset) in utxo_entry.parse_inscriptions() {
+          let entry = InscriptionEntry::load(
+            sequence_number_to_inscription_entry
+              .get(sequence_number)?
+              .unwrap()
+              .value(),
+          );
+
+          inscriptions.push((offset + inscription_offset, entry.id));
+        }
+      }
+
+      offset += value;
+    }
+
+    if self.index_inscriptions {
+      let txid = tx.compute_txid();
+
+      for (index, envelope) in ParsedEnvelope::from_transaction(tx).iter().enumerate() {
+        let input_start = input_values
+          .iter()
+          .take(envelope.input.try_into().unwrap())
+          .sum::<u64>();
+
+        inscriptions.push((
+          envelope
+            .payload
+            .pointer()
+            .filter(|&pointer| pointer < output_value)
+            .unwrap_or(input_start),
+          InscriptionId {
+            txid,
+            index: index.try_into().unwrap(),
+          },
+        ));
+      }
+    }
+
+    let values = bounds
+      .iter()
+      .map(|(_, _, start, end)| end - start)
+      .collect::<Vec<u64>>();
+
+    let (assigned, _remaining) = assign_sat_ranges(&ranges, &values);
+
+    let segments = bounds
+      .into_iter()
+      .zip(assigned)
+      .map(
+        |((input, output, start, end), sat_ranges)| api::SatFlowSegment {
+          input,
+          inscriptions: self.index_inscriptions.then(|| {
+            inscriptions
+              .iter()
+              .filter(|(offset, _)| (start..end).contains(offset))
+              .map(|(_, inscription_id)| *inscription_id)
+              .collect()
+          }),
+          output,
+          rare_sats: Some(
+            sat_ranges
+              .iter()
+              .map(|(start, _end)| Sat(*start))
+              .filter(|sat| sat.rarity() > Rarity::Common)
+              .map(|sat| (sat, sat.rarity()))
+              .collect(),
+          ),
+          sat_ranges: Some(sat_ranges),
+          value: end - start,
+        },
+      )
+      .collect();
+
+    Ok(Some(api::SatFlow {
+      fee: input_value - output_value,
+      segments,
+    }))
+  }
+
+  /// Returns an error unless `outpoint` is an unspent output in a block which
+  /// the index has processed, so that the assets it holds are known.
+  fn verify_input(
+    &self,
+    outpoint_to_utxo_entry: &impl ReadableTable<&'static OutPointValue, &'static UtxoEntry>,
+    outpoint: OutPoint,
+    height: u32,
+  ) -> Result {
+    let utxo_entries = self.index_inscriptions || self.index_sats || self.index_addresses;
+
+    if utxo_entries && outpoint_to_utxo_entry.get(&outpoint.store())?.is_some() {
+      return Ok(());
+    }
+
+    let confirmations = self
+      .client
+      .get_tx_out(&outpoint.txid, outpoint.vout, Some(false))?
+      .map(|tx_out| tx_out.confirmations)
+      .unwrap_or_default();
+
+    ensure!(
+      confirmations > 0,
+      "cannot verify input {outpoint}: output is unconfirmed, spent, or does not exist"
+    );
+
+    let created = u32::try_from(self.client.get_block_count()? + 1)
+      .unwrap()
+      .saturating_sub(confirmations);
+
+    ensure!(
+      created < height,
+      "cannot verify input {outpoint}: block {created} has not been indexed"
+    );
+
+    // outputs created before the first indexed height cannot hold inscriptions
+    ensure!(
+      !utxo_entries || created < self.first_index_height,
+      "cannot verify input {outpoint}: output is not in index"
+    );
+
+    Ok(())
+  }
+
+  fn simulate_runes(
+    &self,
+    rtx: &ReadTransaction,
+    tx: &Transaction,
+    height: u32,
+    artifact: Option<&Artifact>,
+  ) -> Result<(Vec<BTreeMap<SpacedRune, Pile>>, BTreeMap<SpacedRune, Pile>)> {
+    let outpoint_to_balances = rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+    let id_to_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+    let rune_to_id = rtx.open_table(RUNE_TO_RUNE_ID)?;
+
+    let mut unallocated: HashMap<RuneId, Lot> = HashMap::new();
+
+    for input in &tx.input {
+      if let Some(balances) = outpoint_to_balances.get(&input.previous_output.store())? {
+        let buffer = balances.value();
+        let mut i = 0;
+        while i < buffer.len() {
+          let ((id, balance), len) = Index::decode_rune_balance(&buffer[i..]).unwrap();
+          i += len;
+          *unallocated.entry(id).or_default() += balance;
+        }
+      }
+    }
+
+    let mut etched = None;
+
+    if let Some(artifact) = artifact {
+      if let Some(id) = artifact.mint() {
+        if let Some(entry) = id_to_entry.get(&id.store())? {
+          if let Ok(amount) = RuneEntry::load(entry.value()).mintable(height.into()) {
+            *unallocated.entry(id).or_default() += amount;
+          }
+        }
+      }
+
+      let (rune, spacers, divisibility, symbol) = match artifact {
+        Artifact::Runestone(runestone) => match runestone.etching {
+          Some(etching) => (
+            Some(etching.rune),
+            etching.spacers.unwrap_or_default(),
+            etching.divisibility.unwrap_or_default(),
+            etching.symbol,
+          ),
+          None => (None, 0, 0, None),
+        },
+        Artifact::Cenotaph(cenotaph) => (cenotaph.etching.map(Some), 0, 0, None),
+      };
+
+      let id = RuneId {
+        block: height.into(),
+        tx: 1,
+      };
+
+      etched = match rune {
+        Some(Some(rune)) => (rune
+          >= Rune::minimum_at_height(self.settings.chain().network(), Height(height))
+          && !rune.is_reserved()
+          && rune_to_id.get(rune.0)?.is_none())
+        .then_some((id, rune, spacers, divisibility, symbol)),
+        Some(None) => Some((
+          id,
+          Rune::reserved(height.into(), id.tx),
+          spacers,
+          divisibility,
+          symbol,
+        )),
+        None => None,
+      };
+    }
+
+    let (allocated, burned) =
+      rune_updater::allocate(tx, artifact, unallocated, etched.map(|(id, ..)| id));
+
+    let pile = |id: RuneId, amount: Lot| -> Result<(SpacedRune, Pile)> {
+      if let Some((etched_id, rune, spacers, divisibility, symbol)) = etched {
+        if id == etched_id {
+          return Ok((
+            SpacedRune { rune, spacers },
+            Pile {
+              amount: amount.n(),
+              divisibility,
+              symbol,
+            },
+          ));
+        }
+      }
+
+      let entry = RuneEntry::load(id_to_entry.get(&id.store())?.unwrap().value());
+
+      Ok((entry.spaced_rune, entry.pile(amount.n())))
+    };
+
+    Ok((
+      allocated
+        .into_iter()
+        .map(|balances| {
+          balances
+            .into_iter()
+            .map(|(id, amount)| pile(id, amount))
+            .collect()
+        })
+        .collect::<Result<_>>()?,
+      burned
+        .into_iter()
+        .filter(|(_id, amount)| *amount > 0)
+        .map(|(id, amount)| pile(id, amount))
+        .collect::<Result<_>>()?,
+    ))
+  }
+
+  fn output_containing_offset(output_values: &[u64], offset: u64) -> Option<usize> {
+    let mut end = 0;
+
+    for (vout, value) in output_values.iter().enumerate() {
+      end += value;
+
+      if offset < end {
+        return Some(vout);
+      }
+    }
+
+    None
+  }
+}
+
+/// Assigns sat ranges to outputs in first-in-first-out order. Returns the
+/// ranges assigned to each output, and the remaining ranges, which are paid
+/// as fees.
+pub(crate) fn assign_sat_ranges(
+  ranges: &[(u64, u64)],
+  output_values: &[u64],
+) -> (Vec<Vec<(u64, u64)>>, Vec<(u64, u64)>) {
+  let mut ranges = ranges.iter().copied().collect::<VecDeque<(u64, u64)>>();
+
+  let mut outputs = Vec::new();
+
+  for value in output_values {
+    let mut remaining = *value;
+    let mut output = Vec::new();
+
+    while remaining > 0 {
+      let Some((start, end)) = ranges.pop_front() else {
+        break;
+      };
+
+      let size = end - start;
+
+      if size > remaining {
+        output.push((start, start + remaining));
+        ranges.push_front((start + remaining, end));
+        remaining = 0;
+      } else {
+        output.push((start, end));
+        remaining -= size;
+      }
+    }
+
+    outputs.push(output);
+  }
+
+  (outputs, ranges.into())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn sat_ranges_are_assigned_first_in_first_out() {
+    assert_eq!(
+      assign_sat_ranges(&[(0, 10), (100, 105)], &[3, 9, 2]),
+      (
+        vec![vec![(0, 3)], vec![(3, 10), (100, 102)], vec![(102, 104)]],
+        vec![(104, 105)],
+      ),
+    );
+  }
+
+  #[test]
+  fn outputs_with_insufficient_ranges_are_partially_assigned() {
+    assert_eq!(
+      assign_sat_ranges(&[(0, 2)], &[1, 3, 1]),
+      (vec![vec![(0, 1)], vec![(1, 2)], Vec::new()], Vec::new()),
+    );
+  }
+}
@@ -0,0 +1,114 @@
+use super::*;
+
+const MAX_KEY_BYTES: usize = 64;
+const MAX_TRAITS: usize = 64;
+const MAX_VALUE_BYTES: usize = 256;
+
+/// Returns the traits of an inscription, which are the text keys of its
+/// top-level metadata map whose values are scalars, along with those values
+/// formatted as text.
+pub(crate) fn inscription_traits(inscription: &Inscription) -> BTreeSet<(String, String)> {
+  let mut traits = BTreeSet::new();
+
+  let Some(Value::Map(entries)) = inscription.metadata() else {
+    return traits;
+  };
+
+  for (key, value) in entries {
+    if traits.len() == MAX_TRAITS {
+      break;
+    }
+
+    let Value::Text(key) = key else {
+      continue;
+    };
+
+    let Some(value) = scalar(&value) else {
+      continue;
+    };
+
+    if key.len() > MAX_KEY_BYTES || value.len() > MAX_VALUE_BYTES {
+      continue;
+    }
+
+    traits.insert((key, value));
+  }
+
+  traits
+}
+
+fn scalar(value: &Value) -> Option<String> {
+  match value {
+    Value::Text(text) => Some(text.clone()),
+    Value::Integer(integer) => Some(i128::from(*integer).to_string()),
+    Value::Bool(bool) => Some(bool.to_string()),
+    Value::Float(float) => Some(float.to_string()),
+    _ => None,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn traits(metadata: Value) -> BTreeSet<(String, String)> {
+    let mut buffer = Vec::new();
+    ciborium::into_writer(&metadata, &mut buffer).unwrap();
+
+    inscription_traits(&Inscription {
+      metadata: Some(buffer),
+      ..default()
+    })
+  }
+
+  #[test]
+  fn scalar_values_are_traits() {
+    assert_eq!(
+      traits(Value::Map(vec![
+        (Value::Text("eyes".into()), Value::Text("laser".into())),
+        (Value::Text("level".into()), Value::Integer(3.into())),
+        (Value::Text("rare".into()), Value::Bool(true)),
+        (Value::Text("size".into()), Value::Float(1.5)),
+      ])),
+      [
+        ("eyes".into(), "laser".into()),
+        ("level".into(), "3".into()),
+        ("rare".into(), "true".into()),
+        ("size".into(), "1.5".into()),
+      ]
+      .into(),
+    );
+  }
+
+  #[test]
+  fn non_scalar_values_and_non_text_keys_are_ignored() {
+    assert_eq!(
+      traits(Value::Map(vec![
+        (
+          Value::Text("attributes".into()),
+          Value::Array(vec![Value::Text("foo".into())])
+        ),
+        (Value::Integer(1.into()), Value::Text("bar".into())),
+        (Value::Text("name".into()), Value::Null),
+      ])),
+      BTreeSet::new(),
+    );
+  }
+
+  #[test]
+  fn metadata_which_is_not_a_map_has_no_traits() {
+    assert_eq!(traits(Value::Text("foo".into())), BTreeSet::new());
+    assert_eq!(inscription_traits(&Inscription::default()), BTreeSet::new());
+  }
+
+  #[test]
+  fn long_keys_and_values_are_ignored() {
+    assert_eq!(
+      traits(Value::Map(vec![
+        (Value::Text("a".repeat(65)), Value::Text("foo".into())),
+        (Value::Text("foo".into()), Value::Text("a".repeat(257))),
+      ])),
+      BTreeSet::new(),
+    );
+  }
+}
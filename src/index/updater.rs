@@ -1,6 +1,7 @@
 use {
   self::{inscription_updater::InscriptionUpdater, rune_updater::RuneUpdater},
   super::{fetcher::Fetcher, *},
+  bitcoin::hashes::sha256,
   futures::future::try_join_all,
   tokio::sync::{
     broadcast::{self, error::TryRecvError},
@@ -9,7 +10,7 @@ use {
 };
 
 mod inscription_updater;
-mod rune_updater;
+pub(super) mod rune_updater;
 
 pub(crate) struct BlockData {
   pub(crate) header: Header,
@@ -347,11 +348,16 @@ impl Updater<'_> {
     }
 
     if self.index.index_runes && self.height >= self.index.settings.first_rune_height() {
+      let mut height_to_event_rune_id = wtx.open_multimap_table(HEIGHT_TO_EVENT_RUNE_ID)?;
       let mut outpoint_to_rune_balances = wtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
       let mut rune_id_to_rune_entry = wtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+      let mut rune_number_to_rune_id = wtx.open_table(RUNE_NUMBER_TO_RUNE_ID)?;
+      let mut rune_id_and_height_to_rune_event =
+        wtx.open_multimap_table(RUNE_ID_AND_HEIGHT_TO_RUNE_EVENT)?;
       let mut rune_to_rune_id = wtx.open_table(RUNE_TO_RUNE_ID)?;
       let mut sequence_number_to_rune_id = wtx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
       let mut transaction_id_to_rune = wtx.open_table(TRANSACTION_ID_TO_RUNE)?;
+      let mut search_term_to_rune_id = wtx.open_multimap_table(SEARCH_TERM_TO_RUNE_ID)?;
 
       let runes = statistic_to_count
         .get(&Statistic::Runes.into())?
@@ -364,15 +370,23 @@ impl Updater<'_> {
         burned: HashMap::new(),
         client: &self.index.client,
         height: self.height,
+        height_to_event_rune_id: &mut height_to_event_rune_id,
         id_to_entry: &mut rune_id_to_rune_entry,
         inscription_id_to_sequence_number: &mut inscription_id_to_sequence_number,
+        mints: HashMap::new(),
         minimum: Rune::minimum_at_height(
           self.index.settings.chain().network(),
           Height(self.height),
         ),
+        number_to_id: &mut rune_number_to_rune_id,
         outpoint_to_balances: &mut outpoint_to_rune_balances,
+        rune_id_and_height_to_rune_event: &mut rune_id_and_height_to_rune_event,
         rune_to_id: &mut rune_to_rune_id,
         runes,
+        search_term_to_rune_id: self
+          .index
+          .index_search
+          .then_some(&mut search_term_to_rune_id),
         sequence_number_to_rune_id: &mut sequence_number_to_rune_id,
         statistic_to_count: &mut statistic_to_count,
         transaction_id_to_rune: &mut transaction_id_to_rune,
@@ -414,14 +428,24 @@ impl Updater<'_> {
     let mut home_inscriptions = wtx.open_table(HOME_INSCRIPTIONS)?;
     let mut inscription_number_to_sequence_number =
       wtx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+    let mut outpoint_to_height = wtx.open_table(OUTPOINT_TO_HEIGHT)?;
+    let mut outpoint_to_spent_utxo_entry = wtx.open_table(OUTPOINT_TO_SPENT_UTXO_ENTRY)?;
     let mut outpoint_to_utxo_entry = wtx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
     let mut sat_to_satpoint = wtx.open_table(SAT_TO_SATPOINT)?;
     let mut sat_to_sequence_number = wtx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
+    let mut script_hash_to_history = wtx.open_multimap_table(SCRIPT_HASH_TO_HISTORY)?;
     let mut script_pubkey_to_outpoint = wtx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
     let mut sequence_number_to_children = wtx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
     let mut sequence_number_to_inscription_entry =
       wtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+    let mut sequence_number_to_transfer_height =
+      wtx.open_table(SEQUENCE_NUMBER_TO_TRANSFER_HEIGHT)?;
+    let mut sequence_number_to_transferred_children =
+      wtx.open_multimap_table(SEQUENCE_NUMBER_TO_TRANSFERRED_CHILDREN)?;
     let mut transaction_id_to_transaction = wtx.open_table(TRANSACTION_ID_TO_TRANSACTION)?;
+    let mut search_term_to_sequence_number =
+      wtx.open_multimap_table(SEARCH_TERM_TO_SEQUENCE_NUMBER)?;
+    let mut trait_to_sequence_number = wtx.open_multimap_table(TRAIT_TO_SEQUENCE_NUMBER)?;
 
     let index_inscriptions = self.height >= self.index.settings.first_inscription_height()
       && self.index.index_inscriptions;
@@ -511,9 +535,25 @@ impl Updater<'_> {
       next_sequence_number,
       reward: Height(self.height).subsidy(),
       sat_to_sequence_number: &mut sat_to_sequence_number,
+      search_term_to_sequence_number: self
+        .index
+        .index_search
+        .then_some(&mut search_term_to_sequence_number),
       sequence_number_to_children: &mut sequence_number_to_children,
       sequence_number_to_entry: &mut sequence_number_to_inscription_entry,
+      sequence_number_to_transfer_height: self
+        .index
+        .index_transfers
+        .then_some(&mut sequence_number_to_transfer_height),
+      sequence_number_to_transferred_children: self
+        .index
+        .index_transfers
+        .then_some(&mut sequence_number_to_transferred_children),
       timestamp: block.header.time,
+      trait_to_sequence_number: self
+        .index
+        .index_traits
+        .then_some(&mut trait_to_sequence_number),
       transaction_buffer: Vec::new(),
       transaction_id_to_transaction: &mut transaction_id_to_transaction,
       unbound_inscriptions,
@@ -548,6 +588,10 @@ impl Updater<'_> {
           .map(|input| {
             let outpoint = input.previous_output.store();
 
+            if self.index.index_addresses {
+              outpoint_to_height.remove(&outpoint)?;
+            }
+
             let entry = if let Some(entry) = utxo_cache.remove(&OutPoint::load(outpoint)) {
               self.outputs_cached += 1;
               entry
@@ -578,6 +622,10 @@ impl Updater<'_> {
               entry
             };
 
+            if self.index.index_sats && self.index.index_spent_sats {
+              outpoint_to_spent_utxo_entry.insert(&outpoint, entry.as_ref())?;
+            }
+
             Ok(entry)
           })
           .collect::<Result<Vec<UtxoEntryBuf>>>()?
@@ -633,6 +681,19 @@ impl Updater<'_> {
         self.index_transaction_output_script_pubkeys(tx, &mut output_utxo_entries);
       }
 
+      if self.index.index_script_hashes {
+        let history = (self.height, u32::try_from(tx_offset).unwrap(), txid.store());
+
+        for script_pubkey in input_utxo_entries
+          .iter()
+          .map(|entry| entry.script_pubkey())
+          .chain(tx.output.iter().map(|txout| txout.script_pubkey.as_bytes()))
+        {
+          script_hash_to_history
+            .insert(sha256::Hash::hash(script_pubkey).as_byte_array(), history)?;
+        }
+      }
+
       if index_inscriptions {
         inscription_updater.index_inscriptions(
           tx,
@@ -646,8 +707,16 @@ impl Updater<'_> {
       }
 
       for (vout, output_utxo_entry) in output_utxo_entries.into_iter().enumerate() {
-        let vout = u32::try_from(vout).unwrap();
-        utxo_cache.insert(OutPoint { txid: *txid, vout }, output_utxo_entry);
+        let outpoint = OutPoint {
+          txid: *txid,
+          vout: u32::try_from(vout).unwrap(),
+        };
+
+        if self.index.index_addresses {
+          outpoint_to_height.insert(&outpoint.store(), self.height)?;
+        }
+
+        utxo_cache.insert(outpoint, output_utxo_entry);
       }
     }
 
@@ -828,6 +897,7 @@ impl Updater<'_> {
 
     {
       let mut outpoint_to_utxo_entry = wtx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
+      let mut script_hash_to_script_pubkey = wtx.open_table(SCRIPT_HASH_TO_SCRIPT_PUBKEY)?;
       let mut script_pubkey_to_outpoint = wtx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
       let mut sequence_number_to_satpoint = wtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
 
@@ -844,6 +914,13 @@ impl Updater<'_> {
         if self.index.index_addresses {
           let script_pubkey = utxo_entry.script_pubkey();
           script_pubkey_to_outpoint.insert(script_pubkey, &outpoint.store())?;
+
+          if self.index.index_script_hashes {
+            script_hash_to_script_pubkey.insert(
+              sha256::Hash::hash(script_pubkey).as_byte_array(),
+              script_pubkey,
+            )?;
+          }
         }
 
         if self.index.index_inscriptions {
@@ -28,6 +28,8 @@ enum Origin {
     hidden: bool,
     parents: Vec<InscriptionId>,
     reinscription: bool,
+    search_terms: BTreeSet<String>,
+    traits: BTreeSet<(String, String)>,
     unbound: bool,
     vindicated: bool,
   },
@@ -52,9 +54,15 @@ pub(super) struct InscriptionUpdater<'a, 'tx> {
   pub(super) transaction_buffer: Vec<u8>,
   pub(super) transaction_id_to_transaction: &'a mut Table<'tx, &'static TxidValue, &'static [u8]>,
   pub(super) sat_to_sequence_number: &'a mut MultimapTable<'tx, u64, u32>,
+  pub(super) search_term_to_sequence_number: Option<&'a mut MultimapTable<'tx, &'static str, u32>>,
   pub(super) sequence_number_to_children: &'a mut MultimapTable<'tx, u32, u32>,
   pub(super) sequence_number_to_entry: &'a mut Table<'tx, u32, InscriptionEntryValue>,
+  pub(super) sequence_number_to_transfer_height: Option<&'a mut Table<'tx, u32, u32>>,
+  pub(super) sequence_number_to_transferred_children:
+    Option<&'a mut MultimapTable<'tx, u32, (u32, u32)>>,
   pub(super) timestamp: u32,
+  pub(super) trait_to_sequence_number:
+    Option<&'a mut MultimapTable<'tx, (u32, &'static str, &'static str), u32>>,
   pub(super) unbound_inscriptions: u64,
 }
 
@@ -201,6 +209,16 @@ impl InscriptionUpdater<'_, '_> {
             hidden: inscription.payload.hidden(),
             parents: inscription.payload.parents(),
             reinscription: inscribed_offsets.contains_key(&offset),
+            search_terms: if self.search_term_to_sequence_number.is_some() {
+              search::inscription_terms(&inscription.payload)
+            } else {
+              BTreeSet::new()
+            },
+            traits: if self.trait_to_sequence_number.is_some() {
+              traits::inscription_traits(&inscription.payload)
+            } else {
+              BTreeSet::new()
+            },
             unbound: input_value == 0
               || curse == Some(Curse::UnrecognizedEvenField)
               || inscription.payload.unrecognized_even_field,
@@ -398,6 +416,29 @@ impl InscriptionUpdater<'_, '_> {
           )?;
         }
 
+        if let (Some(sequence_number_to_transfer_height), Some(transferred_children)) = (
+          &mut self.sequence_number_to_transfer_height,
+          &mut self.sequence_number_to_transferred_children,
+        ) {
+          let entry = InscriptionEntry::load(
+            self
+              .sequence_number_to_entry
+              .get(&sequence_number)?
+              .unwrap()
+              .value(),
+          );
+
+          let old_height = sequence_number_to_transfer_height
+            .insert(sequence_number, self.height)?
+            .map(|height| height.value())
+            .unwrap_or(entry.height);
+
+          for parent_sequence_number in entry.parents {
+            transferred_children.remove(parent_sequence_number, (old_height, sequence_number))?;
+            transferred_children.insert(parent_sequence_number, (self.height, sequence_number))?;
+          }
+        }
+
         if let Some(ref sender) = index.event_sender {
           sender.blocking_send(Event::InscriptionTransferred {
             block_height: self.height,
@@ -416,6 +457,8 @@ impl InscriptionUpdater<'_, '_> {
         hidden,
         parents,
         reinscription,
+        search_terms,
+        traits,
         unbound,
         vindicated,
       } => {
@@ -489,10 +532,26 @@ impl InscriptionUpdater<'_, '_> {
               .sequence_number_to_children
               .insert(parent_sequence_number, sequence_number)?;
 
+            if let Some(transferred_children) = &mut self.sequence_number_to_transferred_children {
+              transferred_children
+                .insert(parent_sequence_number, (self.height, sequence_number))?;
+            }
+
             Ok(parent_sequence_number)
           })
           .collect::<Result<Vec<u32>>>()?;
 
+        if let Some(trait_to_sequence_number) = &mut self.trait_to_sequence_number {
+          for parent_sequence_number in &parent_sequence_numbers {
+            for (key, value) in &traits {
+              trait_to_sequence_number.insert(
+                (*parent_sequence_number, key.as_str(), value.as_str()),
+                sequence_number,
+              )?;
+            }
+          }
+        }
+
         if let Some(ref sender) = index.event_sender {
           sender.blocking_send(Event::InscriptionCreated {
             block_height: self.height,
@@ -524,6 +583,12 @@ impl InscriptionUpdater<'_, '_> {
           .id_to_sequence_number
           .insert(&inscription_id.store(), sequence_number)?;
 
+        if let Some(search_term_to_sequence_number) = &mut self.search_term_to_sequence_number {
+          for term in &search_terms {
+            search_term_to_sequence_number.insert(term.as_str(), sequence_number)?;
+          }
+        }
+
         if !hidden {
           self
             .home_inscriptions
@@ -6,12 +6,18 @@ pub(super) struct RuneUpdater<'a, 'tx, 'client> {
   pub(super) client: &'client Client,
   pub(super) event_sender: Option<&'a mpsc::Sender<Event>>,
   pub(super) height: u32,
+  pub(super) height_to_event_rune_id: &'a mut MultimapTable<'tx, u32, RuneIdValue>,
   pub(super) id_to_entry: &'a mut Table<'tx, RuneIdValue, RuneEntryValue>,
   pub(super) inscription_id_to_sequence_number: &'a Table<'tx, InscriptionIdValue, u32>,
   pub(super) minimum: Rune,
+  pub(super) mints: HashMap<RuneId, usize>,
+  pub(super) number_to_id: &'a mut Table<'tx, u64, RuneIdValue>,
   pub(super) outpoint_to_balances: &'a mut Table<'tx, &'static OutPointValue, &'static [u8]>,
+  pub(super) rune_id_and_height_to_rune_event:
+    &'a mut MultimapTable<'tx, (RuneIdValue, u32), RuneEventValue>,
   pub(super) rune_to_id: &'a mut Table<'tx, u128, RuneIdValue>,
   pub(super) runes: u64,
+  pub(super) search_term_to_rune_id: Option<&'a mut MultimapTable<'tx, &'static str, RuneIdValue>>,
   pub(super) sequence_number_to_rune_id: &'a mut Table<'tx, u32, RuneIdValue>,
   pub(super) statistic_to_count: &'a mut Table<'tx, u64, u64>,
   pub(super) transaction_id_to_rune: &'a mut Table<'tx, &'static TxidValue, u128>,
@@ -23,13 +29,15 @@ impl RuneUpdater<'_, '_, '_> {
 
     let mut unallocated = self.unallocated(tx)?;
 
-    let mut allocated: Vec<HashMap<RuneId, Lot>> = vec![HashMap::new(); tx.output.len()];
+    let mut etched = None;
 
     if let Some(artifact) = &artifact {
       if let Some(id) = artifact.mint() {
         if let Some(amount) = self.mint(id)? {
           *unallocated.entry(id).or_default() += amount;
 
+          self.record(id, txid, RuneEventKind::Minted, amount.n())?;
+
           if let Some(sender) = self.event_sender {
             sender.blocking_send(Event::RuneMinted {
               block_height: self.height,
@@ -41,132 +49,19 @@ impl RuneUpdater<'_, '_, '_> {
         }
       }
 
-      let etched = self.etched(tx_index, tx, artifact)?;
-
-      if let Artifact::Runestone(runestone) = artifact {
-        if let Some((id, ..)) = etched {
-          *unallocated.entry(id).or_default() +=
-            runestone.etching.unwrap().premine.unwrap_or_default();
-        }
-
-        for Edict { id, amount, output } in runestone.edicts.iter().copied() {
-          let amount = Lot(amount);
-
-          // edicts with output values greater than the number of outputs
-          // should never be produced by the edict parser
-          let output = usize::try_from(output).unwrap();
-          assert!(output <= tx.output.len());
-
-          let id = if id == RuneId::default() {
-            let Some((id, ..)) = etched else {
-              continue;
-            };
-
-            id
-          } else {
-            id
-          };
-
-          let Some(balance) = unallocated.get_mut(&id) else {
-            continue;
-          };
-
-          let mut allocate = |balance: &mut Lot, amount: Lot, output: usize| {
-            if amount > 0 {
-              *balance -= amount;
-              *allocated[output].entry(id).or_default() += amount;
-            }
-          };
-
-          if output == tx.output.len() {
-            // find non-OP_RETURN outputs
-            let destinations = tx
-              .output
-              .iter()
-              .enumerate()
-              .filter_map(|(output, tx_out)| {
-                (!tx_out.script_pubkey.is_op_return()).then_some(output)
-              })
-              .collect::<Vec<usize>>();
-
-            if !destinations.is_empty() {
-              if amount == 0 {
-                // if amount is zero, divide balance between eligible outputs
-                let amount = *balance / destinations.len() as u128;
-                let remainder = usize::try_from(*balance % destinations.len() as u128).unwrap();
-
-                for (i, output) in destinations.iter().enumerate() {
-                  allocate(
-                    balance,
-                    if i < remainder { amount + 1 } else { amount },
-                    *output,
-                  );
-                }
-              } else {
-                // if amount is non-zero, distribute amount to eligible outputs
-                for output in destinations {
-                  allocate(balance, amount.min(*balance), output);
-                }
-              }
-            }
-          } else {
-            // Get the allocatable amount
-            let amount = if amount == 0 {
-              *balance
-            } else {
-              amount.min(*balance)
-            };
-
-            allocate(balance, amount, output);
-          }
-        }
-      }
+      etched = self.etched(tx_index, tx, artifact)?;
 
       if let Some((id, rune)) = etched {
         self.create_rune_entry(txid, artifact, id, rune)?;
       }
     }
 
-    let mut burned: HashMap<RuneId, Lot> = HashMap::new();
-
-    if let Some(Artifact::Cenotaph(_)) = artifact {
-      for (id, balance) in unallocated {
-        *burned.entry(id).or_default() += balance;
-      }
-    } else {
-      let pointer = artifact
-        .map(|artifact| match artifact {
-          Artifact::Runestone(runestone) => runestone.pointer,
-          Artifact::Cenotaph(_) => unreachable!(),
-        })
-        .unwrap_or_default();
-
-      // assign all un-allocated runes to the default output, or the first non
-      // OP_RETURN output if there is no default
-      if let Some(vout) = pointer
-        .map(|pointer| pointer.into_usize())
-        .inspect(|&pointer| assert!(pointer < allocated.len()))
-        .or_else(|| {
-          tx.output
-            .iter()
-            .enumerate()
-            .find(|(_vout, tx_out)| !tx_out.script_pubkey.is_op_return())
-            .map(|(vout, _tx_out)| vout)
-        })
-      {
-        for (id, balance) in unallocated {
-          if balance > 0 {
-            *allocated[vout].entry(id).or_default() += balance;
-          }
-        }
-      } else {
-        for (id, balance) in unallocated {
-          if balance > 0 {
-            *burned.entry(id).or_default() += balance;
-          }
-        }
-      }
-    }
+    let (allocated, burned) = allocate(
+      tx,
+      artifact.as_ref(),
+      unallocated,
+      etched.map(|(id, _rune)| id),
+    );
 
     // update outpoint balances
     let mut buffer: Vec<u8> = Vec::new();
@@ -175,14 +70,6 @@ impl RuneUpdater<'_, '_, '_> {
         continue;
       }
 
-      // increment burned balances
-      if tx.output[vout].script_pubkey.is_op_return() {
-        for (id, balance) in &balances {
-          *burned.entry(*id).or_default() += *balance;
-        }
-        continue;
-      }
-
       buffer.clear();
 
       let mut balances = balances.into_iter().collect::<Vec<(RuneId, Lot)>>();
@@ -198,6 +85,15 @@ impl RuneUpdater<'_, '_, '_> {
       for (id, balance) in balances {
         Index::encode_rune_balance(id, balance.n(), &mut buffer);
 
+        self.record(
+          id,
+          txid,
+          RuneEventKind::Transferred {
+            vout: outpoint.vout,
+          },
+          balance.n(),
+        )?;
+
         if let Some(sender) = self.event_sender {
           sender.blocking_send(Event::RuneTransferred {
             outpoint,
@@ -218,6 +114,8 @@ impl RuneUpdater<'_, '_, '_> {
     for (id, amount) in burned {
       *self.burned.entry(id).or_default() += amount;
 
+      self.record(id, txid, RuneEventKind::Burned, amount.n())?;
+
       if let Some(sender) = self.event_sender {
         sender.blocking_send(Event::RuneBurned {
           block_height: self.height,
@@ -232,6 +130,20 @@ impl RuneUpdater<'_, '_, '_> {
   }
 
   pub(super) fn update(self) -> Result {
+    if let Some(height) = self.height.checked_sub(RUNE_EVENT_BLOCKS) {
+      let ids = self
+        .height_to_event_rune_id
+        .remove_all(height)?
+        .map(|id| Ok(id?.value()))
+        .collect::<Result<Vec<RuneIdValue>, StorageError>>()?;
+
+      for id in ids {
+        self
+          .rune_id_and_height_to_rune_event
+          .remove_all((id, height))?;
+      }
+    }
+
     for (rune_id, burned) in self.burned {
       let mut entry = RuneEntry::load(self.id_to_entry.get(&rune_id.store())?.unwrap().value());
       entry.burned = entry.burned.checked_add(burned.n()).unwrap();
@@ -241,6 +153,35 @@ impl RuneUpdater<'_, '_, '_> {
     Ok(())
   }
 
+  fn record(&mut self, id: RuneId, txid: Txid, kind: RuneEventKind, amount: u128) -> Result {
+    if kind == RuneEventKind::Minted {
+      let mints = self.mints.entry(id).or_default();
+
+      if *mints >= RUNE_MINT_EVENTS_PER_BLOCK {
+        return Ok(());
+      }
+
+      *mints += 1;
+    }
+
+    self
+      .height_to_event_rune_id
+      .insert(self.height, id.store())?;
+
+    self.rune_id_and_height_to_rune_event.insert(
+      (id.store(), self.height),
+      RuneEvent {
+        amount,
+        id,
+        kind,
+        txid,
+      }
+      .store(),
+    )?;
+
+    Ok(())
+  }
+
   fn create_rune_entry(
     &mut self,
     txid: Txid,
@@ -256,6 +197,8 @@ impl RuneUpdater<'_, '_, '_> {
     let number = self.runes;
     self.runes += 1;
 
+    self.number_to_id.insert(number, id.store())?;
+
     self
       .statistic_to_count
       .insert(&Statistic::Runes.into(), self.runes)?;
@@ -308,6 +251,14 @@ impl RuneUpdater<'_, '_, '_> {
 
     self.id_to_entry.insert(id.store(), entry.store())?;
 
+    if let Some(search_term_to_rune_id) = &mut self.search_term_to_rune_id {
+      for term in search::rune_terms(entry.spaced_rune) {
+        search_term_to_rune_id.insert(term.as_str(), id.store())?;
+      }
+    }
+
+    self.record(id, txid, RuneEventKind::Etched, 0)?;
+
     if let Some(sender) = self.event_sender {
       sender.blocking_send(Event::RuneEtched {
         block_height: self.height,
@@ -489,3 +440,143 @@ impl RuneUpdater<'_, '_, '_> {
     Ok(unallocated)
   }
 }
+
+/// Allocates a transaction's unallocated rune balances to its outputs,
+/// according to its runestone's edicts and pointer. Returns the balances
+/// allocated to each non-OP_RETURN output, and the burned balances.
+pub(crate) fn allocate(
+  tx: &Transaction,
+  artifact: Option<&Artifact>,
+  mut unallocated: HashMap<RuneId, Lot>,
+  etched: Option<RuneId>,
+) -> (Vec<HashMap<RuneId, Lot>>, HashMap<RuneId, Lot>) {
+  let mut allocated: Vec<HashMap<RuneId, Lot>> = vec![HashMap::new(); tx.output.len()];
+
+  if let Some(Artifact::Runestone(runestone)) = artifact {
+    if let Some(id) = etched {
+      *unallocated.entry(id).or_default() += runestone.etching.unwrap().premine.unwrap_or_default();
+    }
+
+    for Edict { id, amount, output } in runestone.edicts.iter().copied() {
+      let amount = Lot(amount);
+
+      // edicts with output values greater than the number of outputs
+      // should never be produced by the edict parser
+      let output = usize::try_from(output).unwrap();
+      assert!(output <= tx.output.len());
+
+      let id = if id == RuneId::default() {
+        let Some(id) = etched else {
+          continue;
+        };
+
+        id
+      } else {
+        id
+      };
+
+      let Some(balance) = unallocated.get_mut(&id) else {
+        continue;
+      };
+
+      let mut allocate = |balance: &mut Lot, amount: Lot, output: usize| {
+        if amount > 0 {
+          *balance -= amount;
+          *allocated[output].entry(id).or_default() += amount;
+        }
+      };
+
+      if output == tx.output.len() {
+        // find non-OP_RETURN outputs
+        let destinations = tx
+          .output
+          .iter()
+          .enumerate()
+          .filter_map(|(output, tx_out)| (!tx_out.script_pubkey.is_op_return()).then_some(output))
+          .collect::<Vec<usize>>();
+
+        if !destinations.is_empty() {
+          if amount == 0 {
+            // if amount is zero, divide balance between eligible outputs
+            let amount = *balance / destinations.len() as u128;
+            let remainder = usize::try_from(*balance % destinations.len() as u128).unwrap();
+
+            for (i, output) in destinations.iter().enumerate() {
+              allocate(
+                balance,
+                if i < remainder { amount + 1 } else { amount },
+                *output,
+              );
+            }
+          } else {
+            // if amount is non-zero, distribute amount to eligible outputs
+            for output in destinations {
+              allocate(balance, amount.min(*balance), output);
+            }
+          }
+        }
+      } else {
+        // Get the allocatable amount
+        let amount = if amount == 0 {
+          *balance
+        } else {
+          amount.min(*balance)
+        };
+
+        allocate(balance, amount, output);
+      }
+    }
+  }
+
+  let mut burned: HashMap<RuneId, Lot> = HashMap::new();
+
+  if let Some(Artifact::Cenotaph(_)) = artifact {
+    for (id, balance) in unallocated {
+      *burned.entry(id).or_default() += balance;
+    }
+  } else {
+    let pointer = artifact
+      .map(|artifact| match artifact {
+        Artifact::Runestone(runestone) => runestone.pointer,
+        Artifact::Cenotaph(_) => unreachable!(),
+      })
+      .unwrap_or_default();
+
+    // assign all un-allocated runes to the default output, or the first non
+    // OP_RETURN output if there is no default
+    if let Some(vout) = pointer
+      .map(|pointer| pointer.into_usize())
+      .inspect(|&pointer| assert!(pointer < allocated.len()))
+      .or_else(|| {
+        tx.output
+          .iter()
+          .enumerate()
+          .find(|(_vout, tx_out)| !tx_out.script_pubkey.is_op_return())
+          .map(|(vout, _tx_out)| vout)
+      })
+    {
+      for (id, balance) in unallocated {
+        if balance > 0 {
+          *allocated[vout].entry(id).or_default() += balance;
+        }
+      }
+    } else {
+      for (id, balance) in unallocated {
+        if balance > 0 {
+          *burned.entry(id).or_default() += balance;
+        }
+      }
+    }
+  }
+
+  // runes allocated to OP_RETURN outputs are burned
+  for (balances, tx_out) in allocated.iter_mut().zip(&tx.output) {
+    if tx_out.script_pubkey.is_op_return() {
+      for (id, balance) in balances.drain() {
+        *burned.entry(id).or_default() += balance;
+      }
+    }
+  }
+
+  (allocated, burned)
+}
@@ -74,7 +74,7 @@ use {
     fs::{self, File},
     io::{self, BufReader, Cursor, Read},
     mem,
-    net::ToSocketAddrs,
+    net::{SocketAddr, ToSocketAddrs},
     path::{Path, PathBuf},
     process::{self, Command, Stdio},
     str::FromStr,
@@ -110,6 +110,7 @@ pub mod api;
 pub mod arguments;
 mod blocktime;
 pub mod chain;
+pub mod cursor;
 pub mod decimal;
 mod deserialize_from_str;
 mod error;
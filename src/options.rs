@@ -53,8 +53,33 @@ pub struct Options {
   pub(crate) index_runes: bool,
   #[arg(long, help = "Track location of all satoshis.")]
   pub(crate) index_sats: bool,
+  #[arg(
+    long,
+    help = "Index script hashes and their transaction histories, for serving the Electrum protocol. Requires --index-addresses."
+  )]
+  pub(crate) index_script_hashes: bool,
+  #[arg(
+    long,
+    help = "Index text of inscription content, inscription metadata, and rune names for full-text search."
+  )]
+  pub(crate) index_search: bool,
+  #[arg(
+    long,
+    help = "Keep the sat ranges of spent outputs, to show the sat flow of confirmed transactions. Requires --index-sats."
+  )]
+  pub(crate) index_spent_sats: bool,
+  #[arg(
+    long,
+    help = "Index top-level metadata traits of child inscriptions, for querying collections by trait."
+  )]
+  pub(crate) index_traits: bool,
   #[arg(long, help = "Store transactions in index.")]
   pub(crate) index_transactions: bool,
+  #[arg(
+    long,
+    help = "Track when inscriptions were last transferred, for ordering collections by transfer."
+  )]
+  pub(crate) index_transfers: bool,
   #[arg(long, help = "Run in integration test mode.")]
   pub(crate) integration_test: bool,
   #[clap(long, short, long, help = "Specify output format. [default: json]")]
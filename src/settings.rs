@@ -22,7 +22,12 @@ pub struct Settings {
   index_cache_size: Option<usize>,
   index_runes: bool,
   index_sats: bool,
+  index_script_hashes: bool,
+  index_search: bool,
+  index_spent_sats: bool,
+  index_traits: bool,
   index_transactions: bool,
+  index_transfers: bool,
   integration_test: bool,
   no_index_inscriptions: bool,
   server_password: Option<String>,
@@ -135,7 +140,12 @@ impl Settings {
       index_cache_size: self.index_cache_size.or(source.index_cache_size),
       index_runes: self.index_runes || source.index_runes,
       index_sats: self.index_sats || source.index_sats,
+      index_script_hashes: self.index_script_hashes || source.index_script_hashes,
+      index_search: self.index_search || source.index_search,
+      index_spent_sats: self.index_spent_sats || source.index_spent_sats,
+      index_traits: self.index_traits || source.index_traits,
       index_transactions: self.index_transactions || source.index_transactions,
+      index_transfers: self.index_transfers || source.index_transfers,
       integration_test: self.integration_test || source.integration_test,
       no_index_inscriptions: self.no_index_inscriptions || source.no_index_inscriptions,
       server_password: self.server_password.or(source.server_password),
@@ -171,7 +181,12 @@ impl Settings {
       index_cache_size: options.index_cache_size,
       index_runes: options.index_runes,
       index_sats: options.index_sats,
+      index_script_hashes: options.index_script_hashes,
+      index_search: options.index_search,
+      index_spent_sats: options.index_spent_sats,
+      index_traits: options.index_traits,
       index_transactions: options.index_transactions,
+      index_transfers: options.index_transfers,
       integration_test: options.integration_test,
       no_index_inscriptions: options.no_index_inscriptions,
       server_password: options.server_password,
@@ -259,7 +274,12 @@ impl Settings {
       index_cache_size: get_usize("INDEX_CACHE_SIZE")?,
       index_runes: get_bool("INDEX_RUNES"),
       index_sats: get_bool("INDEX_SATS"),
+      index_script_hashes: get_bool("INDEX_SCRIPT_HASHES"),
+      index_search: get_bool("INDEX_SEARCH"),
+      index_spent_sats: get_bool("INDEX_SPENT_SATS"),
+      index_traits: get_bool("INDEX_TRAITS"),
       index_transactions: get_bool("INDEX_TRANSACTIONS"),
+      index_transfers: get_bool("INDEX_TRANSFERS"),
       integration_test: get_bool("INTEGRATION_TEST"),
       no_index_inscriptions: get_bool("NO_INDEX_INSCRIPTIONS"),
       server_password: get_string("SERVER_PASSWORD"),
@@ -289,7 +309,12 @@ impl Settings {
       index_cache_size: None,
       index_runes: true,
       index_sats: true,
+      index_script_hashes: false,
+      index_search: false,
+      index_spent_sats: false,
+      index_traits: false,
       index_transactions: false,
+      index_transfers: false,
       integration_test: false,
       no_index_inscriptions: false,
       server_password: None,
@@ -363,7 +388,12 @@ impl Settings {
       }),
       index_runes: self.index_runes,
       index_sats: self.index_sats,
+      index_script_hashes: self.index_script_hashes,
+      index_search: self.index_search,
+      index_spent_sats: self.index_spent_sats,
+      index_traits: self.index_traits,
       index_transactions: self.index_transactions,
+      index_transfers: self.index_transfers,
       integration_test: self.integration_test,
       no_index_inscriptions: self.no_index_inscriptions,
       server_password: self.server_password,
@@ -547,14 +577,38 @@ impl Settings {
     self.index_sats
   }
 
+  pub fn index_script_hashes_raw(&self) -> bool {
+    self.index_script_hashes
+  }
+
+  pub fn index_search_raw(&self) -> bool {
+    self.index_search
+  }
+
+  pub fn index_spent_sats_raw(&self) -> bool {
+    self.index_spent_sats
+  }
+
+  pub fn index_traits_raw(&self) -> bool {
+    self.index_traits
+  }
+
   pub fn index_transactions_raw(&self) -> bool {
     self.index_transactions
   }
 
+  pub fn index_transfers_raw(&self) -> bool {
+    self.index_transfers
+  }
+
   pub fn integration_test(&self) -> bool {
     self.integration_test
   }
 
+  pub fn hidden(&self) -> impl Iterator<Item = InscriptionId> + '_ {
+    self.hidden.iter().flatten().copied()
+  }
+
   pub fn is_hidden(&self, inscription_id: InscriptionId) -> bool {
     self
       .hidden
@@ -1044,7 +1098,12 @@ mod tests {
       ("INDEX_ADDRESSES", "1"),
       ("INDEX_RUNES", "1"),
       ("INDEX_SATS", "1"),
+      ("INDEX_SCRIPT_HASHES", "1"),
+      ("INDEX_SEARCH", "1"),
+      ("INDEX_SPENT_SATS", "1"),
+      ("INDEX_TRAITS", "1"),
       ("INDEX_TRANSACTIONS", "1"),
+      ("INDEX_TRANSFERS", "1"),
       ("INTEGRATION_TEST", "1"),
       ("NO_INDEX_INSCRIPTIONS", "1"),
       ("SERVER_PASSWORD", "server password"),
@@ -1088,7 +1147,12 @@ mod tests {
         index_cache_size: Some(4),
         index_runes: true,
         index_sats: true,
+        index_script_hashes: true,
+        index_search: true,
+        index_spent_sats: true,
+        index_traits: true,
         index_transactions: true,
+        index_transfers: true,
         integration_test: true,
         no_index_inscriptions: true,
         server_password: Some("server password".into()),
@@ -1120,7 +1184,12 @@ mod tests {
           "--index-cache-size=4",
           "--index-runes",
           "--index-sats",
+          "--index-script-hashes",
+          "--index-search",
+          "--index-spent-sats",
+          "--index-traits",
           "--index-transactions",
+          "--index-transfers",
           "--index=index",
           "--integration-test",
           "--no-index-inscriptions",
@@ -1149,7 +1218,12 @@ mod tests {
         index_cache_size: Some(4),
         index_runes: true,
         index_sats: true,
+        index_script_hashes: true,
+        index_search: true,
+        index_spent_sats: true,
+        index_traits: true,
         index_transactions: true,
+        index_transfers: true,
         integration_test: true,
         no_index_inscriptions: true,
         server_password: Some("server password".into()),
@@ -2,24 +2,32 @@ use {
   self::{
     accept_encoding::AcceptEncoding,
     accept_json::AcceptJson,
+    chain_value::ChainValue,
     error::{OptionExt, ServerError, ServerResult},
+    moderation::Moderation,
   },
   super::*,
-  crate::templates::{
-    AddressHtml, BlockHtml, BlocksHtml, ChildrenHtml, ClockSvg, CollectionsHtml, HomeHtml,
-    InputHtml, InscriptionHtml, InscriptionsBlockHtml, InscriptionsHtml, OutputHtml, PageContent,
-    PageHtml, ParentsHtml, PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml,
-    PreviewMarkdownHtml, PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml,
-    PreviewVideoHtml, RareTxt, RuneHtml, RuneNotFoundHtml, RunesHtml, SatHtml, TransactionHtml,
+  crate::{
+    cursor::{Cursor, Order},
+    index::{entry::RuneEventKind, CollectionOrder},
+    templates::{
+      AddressHtml, BlockHtml, BlocksHtml, ChildrenHtml, ClockSvg, CollectionHtml, CollectionsHtml,
+      HomeHtml, InputHtml, InscriptionHtml, InscriptionsBlockHtml, InscriptionsHtml, OutputHtml,
+      PageContent, PageHtml, ParentsHtml, PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml,
+      PreviewImageHtml, PreviewMarkdownHtml, PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml,
+      PreviewUnknownHtml, PreviewVideoHtml, RareTxt, RuneHtml, RuneNotFoundHtml, RunesHtml,
+      SatHtml, SearchHtml, TransactionHtml,
+    },
   },
   axum::{
     extract::{DefaultBodyLimit, Extension, Json, Path, Query},
     http::{self, header, HeaderMap, HeaderName, HeaderValue, StatusCode, Uri},
     response::{IntoResponse, Redirect, Response},
-    routing::{get, post},
+    routing::{delete, get, post, put},
     Router,
   },
   axum_server::Handle,
+  bitcoin::Psbt,
   brotli::Decompressor,
   rust_embed::RustEmbed,
   rustls_acme::{
@@ -30,6 +38,7 @@ use {
   },
   std::{str, sync::Arc},
   tokio_stream::StreamExt,
+  tower::ServiceExt,
   tower_http::{
     compression::CompressionLayer,
     cors::{Any, CorsLayer},
@@ -42,10 +51,20 @@ pub use server_config::ServerConfig;
 
 mod accept_encoding;
 mod accept_json;
+mod chain_value;
+mod electrum;
 mod error;
+mod esplora;
+mod moderation;
+mod path_prefix;
 pub mod query;
 mod server_config;
 
+const FEED_ITEMS: usize = 300;
+const RECURSIVE_BATCH_LIMIT: usize = 100;
+// inscriptions with children have two URLs, and sitemaps hold at most 50,000
+const SITEMAP_CHUNK_SIZE: u32 = 25_000;
+
 enum SpawnConfig {
   Https(AxumAcceptor),
   Http,
@@ -68,6 +87,55 @@ pub(crate) enum OutputType {
   Runic,
 }
 
+#[derive(Deserialize)]
+pub(crate) struct BroadcastQuery {
+  #[serde(default)]
+  force: bool,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct CollectionQuery {
+  order: Option<CollectionOrder>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct RuneFeedQuery {
+  threshold: Option<Decimal>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct CursorQuery {
+  cursor: Option<Cursor>,
+  order: Option<Order>,
+}
+
+impl CursorQuery {
+  fn is_empty(&self) -> bool {
+    self.cursor.is_none() && self.order.is_none()
+  }
+
+  fn rune_id(&self) -> ServerResult<Option<RuneId>> {
+    self
+      .cursor
+      .map(Cursor::rune_id)
+      .transpose()
+      .map_err(|err| ServerError::BadRequest(err.to_string()))
+  }
+
+  fn sequence_number(&self) -> ServerResult<Option<u32>> {
+    self
+      .cursor
+      .map(Cursor::sequence_number)
+      .transpose()
+      .map_err(|err| ServerError::BadRequest(err.to_string()))
+  }
+}
+
+#[derive(Deserialize)]
+struct BatchQuery {
+  requests: String,
+}
+
 #[derive(Deserialize)]
 struct Search {
   query: String,
@@ -84,6 +152,38 @@ pub struct Server {
     help = "Listen on <ADDRESS> for incoming requests. [default: 0.0.0.0]"
   )]
   pub(crate) address: Option<String>,
+  #[arg(
+    long,
+    env = "ORD_SERVER_ADMIN_TOKEN",
+    help = "Enable admin endpoints, authenticated with bearer token <ADMIN_TOKEN>."
+  )]
+  pub(crate) admin_token: Option<String>,
+  #[arg(
+    long,
+    env = "ORD_SERVER_BROADCAST_TOKEN",
+    help = "Enable `POST /tx` transaction broadcast endpoint, authenticated with bearer token <BROADCAST_TOKEN>."
+  )]
+  pub(crate) broadcast_token: Option<String>,
+  #[arg(
+    long,
+    help = "Also serve the chain configured by config file <CHAIN_CONFIG>, under path prefix `/<CHAIN>`. May be repeated."
+  )]
+  pub(crate) chain_config: Vec<PathBuf>,
+  #[arg(
+    long,
+    help = "Route requests for host name <HOST> to <CHAIN>, which must be served. May be repeated. Argument is of the form `<CHAIN>=<HOST>`."
+  )]
+  pub(crate) chain_host: Vec<ChainValue<String>>,
+  #[arg(
+    long,
+    help = "Enable admin endpoints for <CHAIN>, authenticated with bearer token <TOKEN>, instead of <ADMIN_TOKEN>. May be repeated. Argument is of the form `<CHAIN>=<TOKEN>`."
+  )]
+  pub(crate) chain_admin_token: Vec<ChainValue<String>>,
+  #[arg(
+    long,
+    help = "Serve the Electrum protocol for <CHAIN> on <PORT>, which must be served. May be repeated. Argument is of the form `<CHAIN>=<PORT>`."
+  )]
+  pub(crate) chain_electrum_port: Vec<ChainValue<u16>>,
   #[arg(
     long,
     help = "Request ACME TLS certificate for <ACME_DOMAIN>. This ord instance must be reachable at <ACME_DOMAIN>:443 to respond to Let's Encrypt ACME challenges."
@@ -101,6 +201,16 @@ pub struct Server {
   pub(crate) decompress: bool,
   #[arg(long, env = "ORD_SERVER_DISABLE_JSON_API", help = "Disable JSON API.")]
   pub(crate) disable_json_api: bool,
+  #[arg(
+    long,
+    help = "Serve the Electrum protocol for the primary chain on <ELECTRUM_PORT>. Requires `--index-script-hashes`."
+  )]
+  pub(crate) electrum_port: Option<u16>,
+  #[arg(
+    long,
+    help = "Serve a subset of the Esplora REST API under `/esplora`."
+  )]
+  pub(crate) esplora: bool,
   #[arg(
     long,
     help = "Listen on <HTTP_PORT> for incoming HTTP requests. [default: 80]"
@@ -140,178 +250,223 @@ pub struct Server {
 impl Server {
   pub fn run(self, settings: Settings, index: Arc<Index>, handle: Handle) -> SubcommandResult {
     Runtime::new()?.block_on(async {
-      let index_clone = index.clone();
-      let integration_test = settings.integration_test();
+      let settings = Arc::new(settings);
+      let acme_domains = self.acme_domains()?;
 
-      let index_thread = thread::spawn(move || loop {
-        if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
-          break;
+      let mut chains = vec![(settings.clone(), index)];
+
+      for config in &self.chain_config {
+        let settings = Settings::merge(
+          Options {
+            config: Some(config.clone()),
+            ..default()
+          },
+          BTreeMap::new(),
+        )?;
+
+        if chains
+          .iter()
+          .any(|(served, _index)| served.chain() == settings.chain())
+        {
+          bail!("chain {} is served more than once", settings.chain());
         }
 
-        if !self.no_sync {
-          if let Err(error) = index_clone.update() {
-            log::warn!("Updating index: {error}");
-          }
+        let index = Arc::new(Index::open(&settings)?);
+
+        chains.push((Arc::new(settings), index));
+      }
+
+      let mut hosts = BTreeMap::new();
+
+      for ChainValue { chain, value: host } in &self.chain_host {
+        let host = host.to_lowercase();
+
+        if !chains
+          .iter()
+          .any(|(served, _index)| served.chain() == *chain)
+        {
+          bail!("cannot route host {host} to chain {chain}, which is not served");
         }
 
-        thread::sleep(if integration_test {
-          Duration::from_millis(100)
-        } else {
-          self.polling_interval.into()
-        });
+        if hosts.insert(host.clone(), *chain).is_some() {
+          bail!("host {host} is routed more than once");
+        }
+      }
+
+      let mut admin_tokens = Vec::new();
+
+      for ChainValue { chain, value: token } in &self.chain_admin_token {
+        if !chains
+          .iter()
+          .any(|(served, _index)| served.chain() == *chain)
+        {
+          bail!("cannot set admin token for chain {chain}, which is not served");
+        }
+
+        if admin_tokens
+          .iter()
+          .any(|(token_chain, _token)| token_chain == chain)
+        {
+          bail!("admin token for chain {chain} is set more than once");
+        }
+
+        admin_tokens.push((*chain, token.clone()));
+      }
+
+      let mut electrum_ports = self
+        .electrum_port
+        .map(|port| (settings.chain(), port))
+        .into_iter()
+        .collect::<Vec<(Chain, u16)>>();
+
+      for ChainValue { chain, value: port } in &self.chain_electrum_port {
+        if !chains
+          .iter()
+          .any(|(served, _index)| served.chain() == *chain)
+        {
+          bail!("cannot serve Electrum protocol for chain {chain}, which is not served");
+        }
+
+        if electrum_ports
+          .iter()
+          .any(|(port_chain, _port)| port_chain == chain)
+        {
+          bail!("Electrum port for chain {chain} is set more than once");
+        }
+
+        electrum_ports.push((*chain, *port));
+      }
+
+      for (chain, port) in electrum_ports {
+        let index = chains
+          .iter()
+          .find(|(served, _index)| served.chain() == chain)
+          .map(|(_settings, index)| index.clone())
+          .unwrap();
+
+        if !index.has_script_hash_index() {
+          bail!(
+            "serving the Electrum protocol for chain {chain} requires index created with `--index-addresses` and `--index-script-hashes` flags"
+          );
+        }
+
+        let addr = self.socket_addr(&settings, port)?;
+
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+
+        if !settings.integration_test() && !cfg!(test) {
+          eprintln!("Listening on tcp://{addr} for {chain} Electrum connections");
+        }
+
+        tokio::spawn(electrum::serve(
+          listener,
+          index,
+          if settings.integration_test() {
+            Duration::from_millis(100)
+          } else {
+            self.polling_interval.into()
+          },
+        ));
+      }
+
+      let indexes = chains
+        .iter()
+        .map(|(settings, index)| (settings.chain(), index.clone()))
+        .collect::<Vec<(Chain, Arc<Index>)>>();
+
+      let integration_test = settings.integration_test();
+
+      let index_thread = thread::spawn(move || {
+        thread::scope(|scope| {
+          for (chain, index) in &indexes {
+            scope.spawn(move || loop {
+              if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+                break;
+              }
+
+              if !self.no_sync {
+                if let Err(error) = index.update() {
+                  log::warn!("Updating {chain} index: {error}");
+                }
+              }
+
+              thread::sleep(if integration_test {
+                Duration::from_millis(100)
+              } else {
+                self.polling_interval.into()
+              });
+            });
+          }
+        })
       });
 
       INDEXER.lock().unwrap().replace(index_thread);
 
-      let settings = Arc::new(settings);
-      let acme_domains = self.acme_domains()?;
+      let mut routers = Vec::new();
+
+      for (chain_settings, index) in chains {
+        let chain = chain_settings.chain();
+
+        // chains without a host are only reachable under their path prefix
+        let domain = hosts
+          .iter()
+          .find(|(_host, host_chain)| **host_chain == chain)
+          .map(|(host, _chain)| host.clone())
+          .or_else(|| {
+            acme_domains.first().map(|domain| {
+              if chain == settings.chain() {
+                domain.clone()
+              } else {
+                format!("{domain}/{chain}")
+              }
+            })
+          });
 
-      let server_config = Arc::new(ServerConfig {
-        chain: settings.chain(),
-        csp_origin: self.csp_origin.clone(),
-        decompress: self.decompress,
-        domain: acme_domains.first().cloned(),
-        index_sats: index.has_sat_index(),
-        json_api_enabled: !self.disable_json_api,
-        proxy: self.proxy.clone(),
-      });
+        let admin_token = admin_tokens
+          .iter()
+          .find(|(token_chain, _token)| *token_chain == chain)
+          .map(|(_chain, token)| token.clone())
+          .or_else(|| self.admin_token.clone());
 
-      let router = Router::new()
-        .route("/", get(Self::home))
-        .route("/address/{address}", get(Self::address))
-        .route("/block/{query}", get(Self::block))
-        .route("/blockcount", get(Self::block_count))
-        .route("/blockhash", get(Self::block_hash))
-        .route("/blockhash/{height}", get(Self::block_hash_from_height))
-        .route("/blockheight", get(Self::block_height))
-        .route("/blocks", get(Self::blocks))
-        .route("/blocktime", get(Self::block_time))
-        .route("/bounties", get(Self::bounties))
-        .route("/children/{inscription_id}", get(Self::children))
-        .route(
-          "/children/{inscription_id}/{page}",
-          get(Self::children_paginated),
-        )
-        .route("/clock", get(Self::clock))
-        .route("/collections", get(Self::collections))
-        .route("/collections/{page}", get(Self::collections_paginated))
-        .route("/content/{inscription_id}", get(Self::content))
-        .route("/faq", get(Self::faq))
-        .route("/favicon.ico", get(Self::favicon))
-        .route("/feed.xml", get(Self::feed))
-        .route("/input/{block}/{transaction}/{input}", get(Self::input))
-        .route("/inscription/{inscription_query}", get(Self::inscription))
-        .route(
-          "/inscription/{inscription_query}/{child}",
-          get(Self::inscription_child),
-        )
-        .route("/inscriptions", get(Self::inscriptions))
-        .route("/inscriptions", post(Self::inscriptions_json))
-        .route("/inscriptions/{page}", get(Self::inscriptions_paginated))
-        .route(
-          "/inscriptions/block/{height}",
-          get(Self::inscriptions_in_block),
-        )
-        .route(
-          "/inscriptions/block/{height}/{page}",
-          get(Self::inscriptions_in_block_paginated),
-        )
-        .route("/install.sh", get(Self::install_script))
-        .route("/ordinal/{sat}", get(Self::ordinal))
-        .route("/output/{output}", get(Self::output))
-        .route("/outputs", post(Self::outputs))
-        .route("/outputs/{address}", get(Self::outputs_address))
-        .route("/parents/{inscription_id}", get(Self::parents))
-        .route(
-          "/parents/{inscription_id}/{page}",
-          get(Self::parents_paginated),
-        )
-        .route("/preview/{inscription_id}", get(Self::preview))
-        .route("/r/blockhash", get(Self::block_hash_json))
-        .route(
-          "/r/blockhash/{height}",
-          get(Self::block_hash_from_height_json),
-        )
-        .route("/r/blockheight", get(Self::block_height))
-        .route("/r/blocktime", get(Self::block_time))
-        .route("/r/blockinfo/{query}", get(Self::block_info))
-        .route(
-          "/r/inscription/{inscription_id}",
-          get(Self::inscription_recursive),
-        )
-        .route(
-          "/r/children/{inscription_id}",
-          get(Self::children_recursive),
-        )
-        .route(
-          "/r/children/{inscription_id}/{page}",
-          get(Self::children_recursive_paginated),
-        )
-        .route(
-          "/r/children/{inscription_id}/inscriptions",
-          get(Self::child_inscriptions_recursive),
-        )
-        .route(
-          "/r/children/{inscription_id}/inscriptions/{page}",
-          get(Self::child_inscriptions_recursive_paginated),
-        )
-        .route(
-          "/r/undelegated-content/{inscription_id}",
-          get(Self::undelegated_content),
-        )
-        .route("/r/metadata/{inscription_id}", get(Self::metadata))
-        .route("/r/parents/{inscription_id}", get(Self::parents_recursive))
-        .route(
-          "/r/parents/{inscription_id}/{page}",
-          get(Self::parents_recursive_paginated),
-        )
-        .route("/r/sat/{sat_number}", get(Self::sat_inscriptions))
-        .route(
-          "/r/sat/{sat_number}/{page}",
-          get(Self::sat_inscriptions_paginated),
-        )
-        .route(
-          "/r/sat/{sat_number}/at/{index}",
-          get(Self::sat_inscription_at_index),
-        )
-        .route("/r/utxo/{outpoint}", get(Self::utxo_recursive))
-        .route("/rare.txt", get(Self::rare_txt))
-        .route("/rune/{rune}", get(Self::rune))
-        .route("/runes", get(Self::runes))
-        .route("/runes/{page}", get(Self::runes_paginated))
-        .route("/sat/{sat}", get(Self::sat))
-        .route("/satpoint/{satpoint}", get(Self::satpoint))
-        .route("/search", get(Self::search_by_query))
-        .route("/search/{*query}", get(Self::search_by_path))
-        .route("/static/{*path}", get(Self::static_asset))
-        .route("/status", get(Self::status))
-        .route("/tx/{txid}", get(Self::transaction))
-        .route("/decode/{txid}", get(Self::decode))
-        .route("/update", get(Self::update))
-        .fallback(Self::fallback)
-        .layer(Extension(index))
-        .layer(Extension(server_config.clone()))
-        .layer(Extension(settings.clone()))
-        .layer(SetResponseHeaderLayer::if_not_present(
-          header::CONTENT_SECURITY_POLICY,
-          HeaderValue::from_static("default-src 'self'"),
-        ))
-        .layer(SetResponseHeaderLayer::overriding(
-          header::STRICT_TRANSPORT_SECURITY,
-          HeaderValue::from_static("max-age=31536000; includeSubDomains; preload"),
-        ))
-        .layer(
-          CorsLayer::new()
-            .allow_methods([http::Method::GET])
-            .allow_origin(Any),
-        )
-        .layer(CompressionLayer::new())
-        .with_state(server_config.clone());
+        routers.push((
+          chain,
+          self.chain_router(chain_settings, index, domain, admin_token)?,
+        ));
+      }
 
-      let router = if server_config.json_api_enabled {
-        router.layer(DefaultBodyLimit::disable())
-      } else {
+      let chain_router = |chain: Chain| {
+        routers
+          .iter()
+          .find(|(served, _router)| *served == chain)
+          .map(|(_chain, router)| router.clone())
+          .unwrap()
+      };
+
+      let mut router = chain_router(settings.chain());
+
+      for (chain, nested) in &routers {
+        if *chain != settings.chain() {
+          router = router.nest(&format!("/{chain}"), nested.clone());
+        }
+      }
+
+      let router = if hosts.is_empty() {
         router
+      } else {
+        let hosts = Arc::new(
+          hosts
+            .into_iter()
+            .map(|(host, chain)| (host, chain_router(chain)))
+            .collect::<BTreeMap<String, Router>>(),
+        );
+
+        Router::new().fallback_service(tower::service_fn(move |request: axum::extract::Request| {
+          Self::request_host(&request)
+            .and_then(|host| hosts.get(&host))
+            .unwrap_or(&router)
+            .clone()
+            .oneshot(request)
+        }))
       };
 
       let router = if let Some((username, password)) = settings.credentials() {
@@ -373,6 +528,259 @@ impl Server {
     })
   }
 
+  fn chain_router(
+    &self,
+    settings: Arc<Settings>,
+    index: Arc<Index>,
+    domain: Option<String>,
+    admin_token: Option<String>,
+  ) -> Result<Router> {
+    let moderation = Arc::new(Moderation::load(index.clone(), settings.clone())?);
+
+    let admin_router = if let Some(admin_token) = &admin_token {
+      Router::new()
+        .route("/admin/moderation", get(Self::moderation_entries))
+        .route(
+          "/admin/moderation/{inscription_id}",
+          put(Self::moderation_hide),
+        )
+        .route(
+          "/admin/moderation/{inscription_id}",
+          delete(Self::moderation_unhide),
+        )
+        .layer(ValidateRequestHeaderLayer::bearer(admin_token))
+    } else {
+      Router::new()
+    };
+
+    let broadcast_router = if let Some(broadcast_token) = &self.broadcast_token {
+      Router::new()
+        .route("/tx", post(Self::broadcast))
+        .layer(ValidateRequestHeaderLayer::bearer(broadcast_token))
+    } else {
+      Router::new()
+    };
+
+    let esplora_router = if self.esplora {
+      Router::new().nest("/esplora", esplora::router())
+    } else {
+      Router::new()
+    };
+
+    let server_config = Arc::new(ServerConfig {
+      chain: settings.chain(),
+      csp_origin: self.csp_origin.clone(),
+      decompress: self.decompress,
+      domain,
+      index_sats: index.has_sat_index(),
+      json_api_enabled: !self.disable_json_api,
+      proxy: self.proxy.clone(),
+    });
+
+    let router = Router::new()
+      .route("/", get(Self::home))
+      .route("/address/{address}", get(Self::address))
+      .route("/address/{address}/feed.xml", get(Self::address_feed))
+      .merge(admin_router)
+      .merge(broadcast_router)
+      .merge(esplora_router)
+      .route("/block/{query}", get(Self::block))
+      .route("/blockcount", get(Self::block_count))
+      .route("/blockhash", get(Self::block_hash))
+      .route("/blockhash/{height}", get(Self::block_hash_from_height))
+      .route("/blockheight", get(Self::block_height))
+      .route("/blocks", get(Self::blocks))
+      .route("/blocktime", get(Self::block_time))
+      .route("/bounties", get(Self::bounties))
+      .route("/children/{inscription_id}", get(Self::children))
+      .route(
+        "/children/{inscription_id}/feed.xml",
+        get(Self::children_feed),
+      )
+      .route(
+        "/children/{inscription_id}/{page}",
+        get(Self::children_paginated),
+      )
+      .route("/clock", get(Self::clock))
+      .route("/collection/{inscription_id}", get(Self::collection))
+      .route(
+        "/collection/{inscription_id}/{page}",
+        get(Self::collection_paginated),
+      )
+      .route(
+        "/collection/{inscription_id}/filter",
+        get(Self::collection_filter),
+      )
+      .route(
+        "/collection/{inscription_id}/filter/{page}",
+        get(Self::collection_filter_paginated),
+      )
+      .route(
+        "/collection/{inscription_id}/traits",
+        get(Self::collection_traits),
+      )
+      .route("/collections", get(Self::collections))
+      .route("/collections/{page}", get(Self::collections_paginated))
+      .route("/content/{inscription_id}", get(Self::content))
+      .route("/faq", get(Self::faq))
+      .route("/favicon.ico", get(Self::favicon))
+      .route("/feed.xml", get(Self::feed))
+      .route("/input/{block}/{transaction}/{input}", get(Self::input))
+      .route("/inscription/{inscription_query}", get(Self::inscription))
+      .route(
+        "/inscription/{inscription_query}/{child}",
+        get(Self::inscription_child),
+      )
+      .route("/inscriptions", get(Self::inscriptions))
+      .route("/inscriptions", post(Self::inscriptions_json))
+      .route("/inscriptions/{page}", get(Self::inscriptions_paginated))
+      .route(
+        "/inscriptions/block/{height}",
+        get(Self::inscriptions_in_block),
+      )
+      .route(
+        "/inscriptions/block/{height}/{page}",
+        get(Self::inscriptions_in_block_paginated),
+      )
+      .route("/install.sh", get(Self::install_script))
+      .route("/ordinal/{sat}", get(Self::ordinal))
+      .route("/output/{output}", get(Self::output))
+      .route("/outputs", post(Self::outputs))
+      .route("/outputs/{address}", get(Self::outputs_address))
+      .route("/parents/{inscription_id}", get(Self::parents))
+      .route(
+        "/parents/{inscription_id}/{page}",
+        get(Self::parents_paginated),
+      )
+      .route("/preview/{inscription_id}", get(Self::preview))
+      .merge(Self::recursive_router())
+      .route("/r/batch", get(Self::batch_recursive_get))
+      .route("/r/batch", post(Self::batch_recursive))
+      .route(
+        "/r/undelegated-content/{inscription_id}",
+        get(Self::undelegated_content),
+      )
+      .route("/rare.txt", get(Self::rare_txt))
+      .route("/rune/{rune}", get(Self::rune))
+      .route("/rune/{rune}/feed.xml", get(Self::rune_feed))
+      .route("/runes", get(Self::runes))
+      .route("/runes/{page}", get(Self::runes_paginated))
+      .route("/sat/{sat}", get(Self::sat))
+      .route("/satpoint/{satpoint}", get(Self::satpoint))
+      .route("/search", get(Self::search_by_query))
+      .route("/search/{*query}", get(Self::search_by_path))
+      .route("/sitemap.xml", get(Self::sitemap))
+      .route(
+        "/sitemap/inscriptions/{chunk}",
+        get(Self::sitemap_inscriptions),
+      )
+      .route("/sitemap/runes/{chunk}", get(Self::sitemap_runes))
+      .route("/static/{*path}", get(Self::static_asset))
+      .route("/status", get(Self::status))
+      .route("/tx/{txid}", get(Self::transaction))
+      .route("/decode", post(Self::decode_raw))
+      .route("/decode/{txid}", get(Self::decode))
+      .route("/update", get(Self::update))
+      .fallback(Self::fallback)
+      .layer(Extension(index))
+      .layer(Extension(moderation))
+      .layer(Extension(server_config.clone()))
+      .layer(Extension(settings.clone()))
+      .layer(axum::middleware::from_fn(path_prefix::prefix_links))
+      .layer(SetResponseHeaderLayer::if_not_present(
+        header::CONTENT_SECURITY_POLICY,
+        HeaderValue::from_static("default-src 'self'"),
+      ))
+      .layer(SetResponseHeaderLayer::overriding(
+        header::STRICT_TRANSPORT_SECURITY,
+        HeaderValue::from_static("max-age=31536000; includeSubDomains; preload"),
+      ))
+      .layer(
+        CorsLayer::new()
+          .allow_methods([http::Method::GET, http::Method::POST])
+          .allow_headers([header::CONTENT_TYPE])
+          .allow_origin(Any),
+      )
+      .layer(CompressionLayer::new())
+      .with_state(server_config.clone());
+
+    let router = if server_config.json_api_enabled {
+      router.layer(DefaultBodyLimit::disable())
+    } else {
+      router
+    };
+
+    Ok(router)
+  }
+
+  fn request_host(request: &axum::extract::Request) -> Option<String> {
+    let host = match request.uri().host() {
+      Some(host) => host,
+      None => request
+        .headers()
+        .get(header::HOST)?
+        .to_str()
+        .ok()?
+        .split(':')
+        .next()?,
+    };
+
+    Some(host.to_lowercase())
+  }
+
+  fn recursive_router() -> Router<Arc<ServerConfig>> {
+    Router::new()
+      .route("/r/blockhash", get(Self::block_hash_json))
+      .route(
+        "/r/blockhash/{height}",
+        get(Self::block_hash_from_height_json),
+      )
+      .route("/r/blockheight", get(Self::block_height))
+      .route("/r/blocktime", get(Self::block_time))
+      .route("/r/blockinfo/{query}", get(Self::block_info))
+      .route(
+        "/r/inscription/{inscription_id}",
+        get(Self::inscription_recursive),
+      )
+      .route(
+        "/r/children/{inscription_id}",
+        get(Self::children_recursive),
+      )
+      .route(
+        "/r/children/{inscription_id}/{page}",
+        get(Self::children_recursive_paginated),
+      )
+      .route(
+        "/r/children/{inscription_id}/inscriptions",
+        get(Self::child_inscriptions_recursive),
+      )
+      .route(
+        "/r/children/{inscription_id}/inscriptions/{page}",
+        get(Self::child_inscriptions_recursive_paginated),
+      )
+      .route("/r/metadata/{inscription_id}", get(Self::metadata))
+      .route("/r/parents/{inscription_id}", get(Self::parents_recursive))
+      .route(
+        "/r/parents/{inscription_id}/{page}",
+        get(Self::parents_recursive_paginated),
+      )
+      .route("/r/rune/{rune}", get(Self::rune_recursive))
+      .route(
+        "/r/rune/{rune}/balance/{outpoint}",
+        get(Self::rune_balance_recursive),
+      )
+      .route("/r/sat/{sat_number}", get(Self::sat_inscriptions))
+      .route(
+        "/r/sat/{sat_number}/{page}",
+        get(Self::sat_inscriptions_paginated),
+      )
+      .route(
+        "/r/sat/{sat_number}/at/{index}",
+        get(Self::sat_inscription_at_index),
+      )
+      .route("/r/utxo/{outpoint}", get(Self::utxo_recursive))
+  }
+
   fn spawn(
     &self,
     settings: &Settings,
@@ -381,21 +789,7 @@ impl Server {
     port: u16,
     config: SpawnConfig,
   ) -> Result<task::JoinHandle<io::Result<()>>> {
-    let address = match &self.address {
-      Some(address) => address.as_str(),
-      None => {
-        if cfg!(test) || settings.integration_test() {
-          "127.0.0.1"
-        } else {
-          "0.0.0.0"
-        }
-      }
-    };
-
-    let addr = (address, port)
-      .to_socket_addrs()?
-      .next()
-      .ok_or_else(|| anyhow!("failed to get socket addrs"))?;
+    let addr = self.socket_addr(settings, port)?;
 
     if !settings.integration_test() && !cfg!(test) {
       eprintln!(
@@ -445,13 +839,39 @@ impl Server {
   }
 
   fn acme_domains(&self) -> Result<Vec<String>> {
-    if !self.acme_domain.is_empty() {
-      Ok(self.acme_domain.clone())
+    let mut domains = if !self.acme_domain.is_empty() {
+      self.acme_domain.clone()
     } else {
-      Ok(vec![
-        System::host_name().ok_or(anyhow!("no hostname found"))?
-      ])
+      vec![System::host_name().ok_or(anyhow!("no hostname found"))?]
+    };
+
+    for ChainValue { value: host, .. } in &self.chain_host {
+      let host = host.to_lowercase();
+
+      if !domains.contains(&host) {
+        domains.push(host);
+      }
     }
+
+    Ok(domains)
+  }
+
+  fn socket_addr(&self, settings: &Settings, port: u16) -> Result<SocketAddr> {
+    let address = match &self.address {
+      Some(address) => address.as_str(),
+      None => {
+        if cfg!(test) || settings.integration_test() {
+          "127.0.0.1"
+        } else {
+          "0.0.0.0"
+        }
+      }
+    };
+
+    (address, port)
+      .to_socket_addrs()?
+      .next()
+      .ok_or_else(|| anyhow!("failed to get socket addrs"))
   }
 
   fn http_port(&self) -> Option<u16> {
@@ -567,11 +987,15 @@ impl Server {
   async fn sat(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
     Path(DeserializeFromStr(sat)): Path<DeserializeFromStr<Sat>>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult {
     task::block_in_place(|| {
-      let inscriptions = index.get_inscription_ids_by_sat(sat)?;
+      let mut inscriptions = index.get_inscription_ids_by_sat(sat)?;
+
+      inscriptions.retain(|inscription_id| !moderation.is_hidden(*inscription_id));
+
       let satpoint = index.rare_sat_satpoint(sat)?.or_else(|| {
         inscriptions.first().and_then(|&first_inscription_id| {
           index
@@ -825,6 +1249,7 @@ impl Server {
   async fn rune(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
     Path(DeserializeFromStr(rune_query)): Path<DeserializeFromStr<query::Rune>>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult {
@@ -835,15 +1260,7 @@ impl Server {
         ));
       }
 
-      let rune = match rune_query {
-        query::Rune::Spaced(spaced_rune) => spaced_rune.rune,
-        query::Rune::Id(rune_id) => index
-          .get_rune_by_id(rune_id)?
-          .ok_or_not_found(|| format!("rune {rune_id}"))?,
-        query::Rune::Number(number) => index
-          .get_rune_by_number(usize::try_from(number).unwrap())?
-          .ok_or_not_found(|| format!("rune number {number}"))?,
-      };
+      let rune = Self::resolve_rune(&index, rune_query)?;
 
       let Some((id, entry, parent)) = index.rune(rune)? else {
         return Ok(if accept_json {
@@ -861,6 +1278,8 @@ impl Server {
         });
       };
 
+      let parent = parent.filter(|parent| !moderation.is_hidden(*parent));
+
       let block_height = index.block_height()?.unwrap_or(Height(0));
 
       let mintable = entry.mintable((block_height.n() + 1).into()).is_ok();
@@ -886,22 +1305,126 @@ impl Server {
     })
   }
 
-  async fn runes(
-    Extension(server_config): Extension<Arc<ServerConfig>>,
-    Extension(index): Extension<Arc<Index>>,
-    accept_json: AcceptJson,
-  ) -> ServerResult<Response> {
-    Self::runes_paginated(
-      Extension(server_config),
-      Extension(index),
-      Path(0),
-      accept_json,
-    )
-    .await
+  fn resolve_rune(index: &Index, rune_query: query::Rune) -> ServerResult<Rune> {
+    Ok(match rune_query {
+      query::Rune::Spaced(spaced_rune) => spaced_rune.rune,
+      query::Rune::Id(rune_id) => index
+        .get_rune_by_id(rune_id)?
+        .ok_or_not_found(|| format!("rune {rune_id}"))?,
+      query::Rune::Number(number) => index
+        .get_rune_by_number(usize::try_from(number).unwrap())?
+        .ok_or_not_found(|| format!("rune number {number}"))?,
+    })
   }
 
-  async fn runes_paginated(
-    Extension(server_config): Extension<Arc<ServerConfig>>,
+  async fn rune_recursive(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
+    Path(DeserializeFromStr(rune_query)): Path<DeserializeFromStr<query::Rune>>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_rune_index() {
+        return Err(ServerError::NotFound(
+          "this server has no rune index".to_string(),
+        ));
+      }
+
+      let rune = Self::resolve_rune(&index, rune_query)?;
+
+      let (id, entry, parent) = index
+        .rune(rune)?
+        .ok_or_not_found(|| format!("rune {rune}"))?;
+
+      let parent = parent.filter(|parent| !moderation.is_hidden(*parent));
+
+      let block_height = index.block_height()?.unwrap_or(Height(0));
+
+      let mintable = entry.mintable((block_height.n() + 1).into()).is_ok();
+
+      Ok(
+        Json(api::RuneRecursive {
+          id,
+          max_supply: entry.max_supply(),
+          mintable,
+          mints_remaining: entry
+            .terms
+            .and_then(|terms| terms.cap)
+            .map(|cap| cap.saturating_sub(entry.mints)),
+          parent,
+          supply: entry.supply(),
+          entry,
+        })
+        .into_response(),
+      )
+    })
+  }
+
+  async fn rune_balance_recursive(
+    Extension(index): Extension<Arc<Index>>,
+    Path((DeserializeFromStr(rune_query), outpoint)): Path<(
+      DeserializeFromStr<query::Rune>,
+      OutPoint,
+    )>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_rune_index() {
+        return Err(ServerError::NotFound(
+          "this server has no rune index".to_string(),
+        ));
+      }
+
+      let rune = Self::resolve_rune(&index, rune_query)?;
+
+      let (_id, entry, _parent) = index
+        .rune(rune)?
+        .ok_or_not_found(|| format!("rune {rune}"))?;
+
+      let amount = index
+        .get_rune_balances_for_output(outpoint)?
+        .unwrap_or_default()
+        .get(&entry.spaced_rune)
+        .map(|pile| pile.amount)
+        .unwrap_or_default();
+
+      Ok(Json(entry.pile(amount)).into_response())
+    })
+  }
+
+  async fn runes(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Query(cursor_query): Query<CursorQuery>,
+    accept_json: AcceptJson,
+  ) -> ServerResult<Response> {
+    if accept_json.0 && !cursor_query.is_empty() {
+      return task::block_in_place(|| {
+        let (items, next) = index.runes_by_cursor(
+          cursor_query.rune_id()?,
+          cursor_query.order.unwrap_or(Order::Newest),
+          50,
+        )?;
+
+        Ok(
+          Json(api::Page {
+            items,
+            next: next.map(Cursor::RuneId),
+          })
+          .into_response(),
+        )
+      });
+    }
+
+    Self::runes_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path(0),
+      accept_json,
+    )
+    .await
+  }
+
+  async fn runes_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     Path(page_index): Path<usize>,
     AcceptJson(accept_json): AcceptJson,
@@ -937,14 +1460,14 @@ impl Server {
   async fn home(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
   ) -> ServerResult<PageHtml<HomeHtml>> {
     task::block_in_place(|| {
-      Ok(
-        HomeHtml {
-          inscriptions: index.get_home_inscriptions()?,
-        }
-        .page(server_config),
-      )
+      let mut inscriptions = index.get_home_inscriptions()?;
+
+      inscriptions.retain(|inscription_id| !moderation.is_hidden(*inscription_id));
+
+      Ok(HomeHtml { inscriptions }.page(server_config))
     })
   }
 
@@ -1095,11 +1618,14 @@ impl Server {
 
       let inscription_count = index.inscription_count(txid)?;
 
+      let sat_flow = index.get_sat_flow(&transaction)?;
+
       Ok(if accept_json {
         Json(api::Transaction {
           chain: server_config.chain,
           etching: index.get_etching(txid)?,
           inscription_count,
+          sat_flow,
           transaction,
           txid,
         })
@@ -1109,6 +1635,7 @@ impl Server {
           chain: server_config.chain,
           etching: index.get_etching(txid)?,
           inscription_count,
+          sat_flow,
           transaction,
           txid,
         }
@@ -1143,6 +1670,222 @@ impl Server {
     })
   }
 
+  async fn decode_raw(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    body: String,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !server_config.json_api_enabled {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+      }
+
+      let (transaction, prevouts) = Self::parse_raw_transaction(&index, &body)?;
+
+      Ok(Json(Self::simulate(&index, &transaction, &prevouts)?).into_response())
+    })
+  }
+
+  /// Simulates `transaction`. Failures, such as spending outputs which the
+  /// index does not know, are the client's fault.
+  fn simulate(
+    index: &Index,
+    transaction: &Transaction,
+    prevouts: &[TxOut],
+  ) -> ServerResult<api::Simulation> {
+    index
+      .simulate(transaction, prevouts)
+      .map_err(|err| ServerError::BadRequest(err.to_string()))
+  }
+
+  /// Parses a hex-encoded transaction, or a hex or base64-encoded PSBT, and
+  /// returns it along with the outputs spent by its inputs. Spent outputs are
+  /// fetched from the index, and only taken from the PSBT if the index does
+  /// not know them. PSBT outputs which do not match are rejected.
+  fn parse_raw_transaction(index: &Index, raw: &str) -> ServerResult<(Transaction, Vec<TxOut>)> {
+    let raw = raw.trim();
+
+    let bytes = hex::decode(raw)
+      .ok()
+      .or_else(|| base64_decode(raw).ok())
+      .ok_or_else(|| {
+        ServerError::BadRequest("request body must be hex or base64 encoded".into())
+      })?;
+
+    let (transaction, psbt_prevouts) = if let Ok(psbt) = Psbt::deserialize(&bytes) {
+      let prevouts = psbt
+        .inputs
+        .iter()
+        .zip(&psbt.unsigned_tx.input)
+        .map(|(input, txin)| Self::psbt_prevout(input, txin.previous_output))
+        .collect::<ServerResult<Vec<Option<TxOut>>>>()?;
+
+      (psbt.extract_tx_unchecked_fee_rate(), prevouts)
+    } else if let Ok(transaction) = consensus::encode::deserialize::<Transaction>(&bytes) {
+      let prevouts = vec![None; transaction.input.len()];
+      (transaction, prevouts)
+    } else {
+      return Err(ServerError::BadRequest(
+        "request body is not a valid transaction or PSBT".into(),
+      ));
+    };
+
+    let mut prevouts = Vec::new();
+
+    // Prevouts are always loaded from the index or Bitcoin Core's UTXO set if
+    // possible, since a PSBT may lie about them. PSBT prevouts are only used
+    // for outputs which neither knows about.
+    for (psbt_prevout, txin) in psbt_prevouts.into_iter().zip(&transaction.input) {
+      let outpoint = txin.previous_output;
+
+      let indexed = match index.get_transaction(outpoint.txid)? {
+        Some(transaction) => transaction
+          .output
+          .into_iter()
+          .nth(usize::try_from(outpoint.vout).unwrap()),
+        None => index.get_unspent_output(outpoint)?,
+      };
+
+      prevouts.push(match (indexed, psbt_prevout) {
+        (Some(indexed), Some(psbt_prevout)) if indexed != psbt_prevout => {
+          return Err(ServerError::BadRequest(format!(
+            "PSBT UTXO for input {outpoint} does not match output"
+          )));
+        }
+        (Some(prevout), _) | (None, Some(prevout)) => prevout,
+        (None, None) => {
+          return Err(ServerError::BadRequest(format!(
+            "output {outpoint} spent by transaction not found"
+          )));
+        }
+      });
+    }
+
+    let input_value = prevouts.iter().map(|txout| txout.value).sum::<Amount>();
+    let output_value = transaction
+      .output
+      .iter()
+      .map(|txout| txout.value)
+      .sum::<Amount>();
+
+    if output_value > input_value {
+      return Err(ServerError::BadRequest(format!(
+        "output value {output_value} exceeds input value {input_value}"
+      )));
+    }
+
+    Ok((transaction, prevouts))
+  }
+
+  /// Returns the UTXO of a PSBT input, after checking that its witness and
+  /// non-witness UTXOs agree with each other and with the spent outpoint.
+  fn psbt_prevout(input: &bitcoin::psbt::Input, outpoint: OutPoint) -> ServerResult<Option<TxOut>> {
+    let non_witness_utxo = match &input.non_witness_utxo {
+      Some(transaction) => {
+        if transaction.compute_txid() != outpoint.txid {
+          return Err(ServerError::BadRequest(format!(
+            "PSBT non-witness UTXO for input {outpoint} is not transaction {}",
+            outpoint.txid,
+          )));
+        }
+
+        Some(
+          transaction
+            .output
+            .get(usize::try_from(outpoint.vout).unwrap())
+            .cloned()
+            .ok_or_else(|| {
+              ServerError::BadRequest(format!(
+                "PSBT non-witness UTXO for input {outpoint} has no output {}",
+                outpoint.vout,
+              ))
+            })?,
+        )
+      }
+      None => None,
+    };
+
+    match (&input.witness_utxo, non_witness_utxo) {
+      (Some(witness_utxo), Some(non_witness_utxo)) if *witness_utxo != non_witness_utxo => {
+        Err(ServerError::BadRequest(format!(
+          "PSBT witness and non-witness UTXOs for input {outpoint} do not match"
+        )))
+      }
+      (Some(utxo), _) => Ok(Some(utxo.clone())),
+      (None, utxo) => Ok(utxo),
+    }
+  }
+
+  async fn broadcast(
+    Extension(index): Extension<Arc<Index>>,
+    Query(query): Query<BroadcastQuery>,
+    body: String,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let (transaction, prevouts) = Self::parse_raw_transaction(&index, &body)?;
+
+      if !query.force {
+        let simulation = Self::simulate(&index, &transaction, &prevouts)?;
+
+        let problems = Self::asset_safety_problems(&simulation);
+
+        if !problems.is_empty() {
+          return Err(ServerError::BadRequest(format!(
+            "refusing to broadcast transaction: {}. Use `?force=true` to broadcast anyway.",
+            problems.join(", "),
+          )));
+        }
+      }
+
+      Ok(
+        index
+          .send_raw_transaction(&transaction)?
+          .to_string()
+          .into_response(),
+      )
+    })
+  }
+
+  /// Returns descriptions of the ways in which a transaction would lose
+  /// inscriptions or runes, or could not be checked for losing them.
+  fn asset_safety_problems(simulation: &api::Simulation) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    if simulation.fee_inscriptions.is_none() {
+      problems.push("inscriptions cannot be checked without an inscription index".into());
+    }
+
+    if simulation.burned_runes.is_none() {
+      problems.push("runes cannot be checked without a rune index".into());
+    }
+
+    if let Some(Artifact::Cenotaph(_)) = simulation.runestone {
+      problems.push("runestone is a cenotaph".into());
+    }
+
+    for inscription_id in simulation.fee_inscriptions.iter().flatten() {
+      problems.push(format!(
+        "inscription {inscription_id} would be sent to fees"
+      ));
+    }
+
+    for output in &simulation.outputs {
+      if !output.script_pubkey.is_op_return() {
+        continue;
+      }
+
+      for inscription_id in output.inscriptions.iter().flatten() {
+        problems.push(format!("inscription {inscription_id} would be burned"));
+      }
+    }
+
+    for (rune, pile) in simulation.burned_runes.iter().flatten() {
+      problems.push(format!("{pile} of {rune} would be burned"));
+    }
+
+    problems
+  }
+
   async fn update(
     Extension(settings): Extension<Arc<Settings>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1160,9 +1903,16 @@ impl Server {
   async fn metadata(
     Extension(index): Extension<Arc<Index>>,
     Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
     Path(inscription_id): Path<InscriptionId>,
   ) -> ServerResult {
     task::block_in_place(|| {
+      if moderation.is_hidden(inscription_id) {
+        return Err(ServerError::NotFound(format!(
+          "inscription {inscription_id} not found"
+        )));
+      }
+
       let Some(inscription) = index.get_inscription_by_id(inscription_id)? else {
         return if let Some(proxy) = server_config.proxy.as_ref() {
           Self::proxy(proxy, &format!("r/metadata/{}", inscription_id))
@@ -1185,9 +1935,16 @@ impl Server {
   async fn inscription_recursive(
     Extension(index): Extension<Arc<Index>>,
     Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
     Path(inscription_id): Path<InscriptionId>,
   ) -> ServerResult {
     task::block_in_place(|| {
+      if moderation.is_hidden(inscription_id) {
+        return Err(ServerError::NotFound(format!(
+          "inscription {inscription_id} not found"
+        )));
+      }
+
       let Some(inscription) = index.get_inscription_by_id(inscription_id)? else {
         return if let Some(proxy) = server_config.proxy.as_ref() {
           Self::proxy(proxy, &format!("r/inscription/{}", inscription_id))
@@ -1256,6 +2013,102 @@ impl Server {
     })
   }
 
+  async fn batch_recursive_get(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(settings): Extension<Arc<Settings>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
+    Query(query): Query<BatchQuery>,
+  ) -> ServerResult {
+    Self::batch_recursive_inner(
+      index,
+      server_config,
+      settings,
+      moderation,
+      query
+        .requests
+        .split(',')
+        .filter(|request| !request.is_empty())
+        .map(str::to_string)
+        .collect(),
+    )
+    .await
+  }
+
+  async fn batch_recursive(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(settings): Extension<Arc<Settings>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
+    Json(requests): Json<Vec<String>>,
+  ) -> ServerResult {
+    Self::batch_recursive_inner(index, server_config, settings, moderation, requests).await
+  }
+
+  async fn batch_recursive_inner(
+    index: Arc<Index>,
+    server_config: Arc<ServerConfig>,
+    settings: Arc<Settings>,
+    moderation: Arc<Moderation>,
+    requests: Vec<String>,
+  ) -> ServerResult {
+    if requests.len() > RECURSIVE_BATCH_LIMIT {
+      return Err(ServerError::BadRequest(format!(
+        "batch of {} requests exceeds limit of {RECURSIVE_BATCH_LIMIT}",
+        requests.len()
+      )));
+    }
+
+    let router = Self::recursive_router()
+      .layer(Extension(index))
+      .layer(Extension(server_config.clone()))
+      .layer(Extension(settings))
+      .layer(Extension(moderation))
+      .with_state(server_config);
+
+    let mut responses = Vec::new();
+
+    for request in requests {
+      if !request.starts_with("/r/") {
+        return Err(ServerError::BadRequest(format!(
+          "batch request `{request}` is not a recursive endpoint"
+        )));
+      }
+
+      let request = http::Request::get(request.as_str())
+        .body(axum::body::Body::empty())
+        .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+      let response = match router.clone().oneshot(request).await {
+        Ok(response) => response,
+        Err(infallible) => match infallible {},
+      };
+
+      let status = response.status();
+
+      let json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .map(|content_type| content_type == "application/json")
+        .unwrap_or_default();
+
+      let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .map_err(|err| anyhow!(err))?;
+
+      responses.push(api::BatchResponse {
+        body: if json {
+          serde_json::from_slice(&body).map_err(|err| anyhow!(err))?
+        } else {
+          serde_json::Value::String(String::from_utf8_lossy(&body).into())
+        },
+        status: status.as_u16(),
+      });
+    }
+
+    Ok(Json(responses).into_response())
+  }
+
   async fn status(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1274,39 +2127,65 @@ impl Server {
   }
 
   async fn search_by_query(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
     Query(search): Query<Search>,
-  ) -> ServerResult<Redirect> {
-    Self::search(index, search.query).await
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    Self::search(server_config, index, moderation, search.query, accept_json).await
   }
 
   async fn search_by_path(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
     Path(search): Path<Search>,
-  ) -> ServerResult<Redirect> {
-    Self::search(index, search.query).await
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    Self::search(server_config, index, moderation, search.query, accept_json).await
   }
 
-  async fn search(index: Arc<Index>, query: String) -> ServerResult<Redirect> {
-    Self::search_inner(index, query).await
+  async fn search(
+    server_config: Arc<ServerConfig>,
+    index: Arc<Index>,
+    moderation: Arc<Moderation>,
+    query: String,
+    accept_json: bool,
+  ) -> ServerResult {
+    Self::search_inner(server_config, index, moderation, query, accept_json).await
   }
 
-  async fn search_inner(index: Arc<Index>, query: String) -> ServerResult<Redirect> {
+  async fn search_inner(
+    server_config: Arc<ServerConfig>,
+    index: Arc<Index>,
+    moderation: Arc<Moderation>,
+    query: String,
+    accept_json: bool,
+  ) -> ServerResult {
     task::block_in_place(|| {
       let query = query.trim();
 
+      let redirect = |path: String| Ok(Redirect::to(&path).into_response());
+
       if re::HASH.is_match(query) {
         if index.block_header(query.parse().unwrap())?.is_some() {
-          Ok(Redirect::to(&format!("/block/{query}")))
+          redirect(format!("/block/{query}"))
         } else {
-          Ok(Redirect::to(&format!("/tx/{query}")))
+          redirect(format!("/tx/{query}"))
         }
       } else if re::OUTPOINT.is_match(query) {
-        Ok(Redirect::to(&format!("/output/{query}")))
+        redirect(format!("/output/{query}"))
       } else if re::INSCRIPTION_ID.is_match(query) || re::INSCRIPTION_NUMBER.is_match(query) {
-        Ok(Redirect::to(&format!("/inscription/{query}")))
-      } else if re::SPACED_RUNE.is_match(query) {
-        Ok(Redirect::to(&format!("/rune/{query}")))
+        redirect(format!("/inscription/{query}"))
+      } else if re::SPACED_RUNE.is_match(query)
+        && (!index.has_search_index()
+          || match query.parse::<SpacedRune>() {
+            Ok(spaced_rune) => index.rune(spaced_rune.rune)?.is_some(),
+            Err(_) => false,
+          })
+      {
+        redirect(format!("/rune/{query}"))
       } else if re::RUNE_ID.is_match(query) {
         let id = query
           .parse::<RuneId>()
@@ -1314,13 +2193,79 @@ impl Server {
 
         let rune = index.get_rune_by_id(id)?.ok_or_not_found(|| "rune ID")?;
 
-        Ok(Redirect::to(&format!("/rune/{rune}")))
+        redirect(format!("/rune/{rune}"))
       } else if re::ADDRESS.is_match(query) {
-        Ok(Redirect::to(&format!("/address/{query}")))
+        redirect(format!("/address/{query}"))
       } else if re::SATPOINT.is_match(query) {
-        Ok(Redirect::to(&format!("/satpoint/{query}")))
+        redirect(format!("/satpoint/{query}"))
+      } else if index.has_search_index() && !Self::is_sat_notation(query) {
+        let (inscriptions, runes) = index.search(query, 100, |inscription_id| {
+          moderation.is_hidden(inscription_id)
+        })?;
+
+        let sat = query.parse::<Sat>().ok();
+
+        Ok(if accept_json {
+          Json(api::Search {
+            inscriptions,
+            query: query.into(),
+            runes,
+            sat,
+          })
+          .into_response()
+        } else {
+          SearchHtml {
+            inscriptions,
+            query: query.into(),
+            runes,
+            sat,
+          }
+          .page(server_config)
+          .into_response()
+        })
+      } else {
+        redirect(format!("/sat/{query}"))
+      }
+    })
+  }
+
+  /// Sat names look like words, so only sats written as numbers, decimals,
+  /// degrees, or percentiles bypass full-text search.
+  fn is_sat_notation(query: &str) -> bool {
+    query.parse::<Sat>().is_ok() && !query.chars().all(|c| c.is_ascii_lowercase())
+  }
+
+  async fn moderation_entries(Extension(moderation): Extension<Arc<Moderation>>) -> ServerResult {
+    Ok(Json(moderation.entries()).into_response())
+  }
+
+  async fn moderation_hide(
+    Extension(moderation): Extension<Arc<Moderation>>,
+    Path(inscription_id): Path<InscriptionId>,
+    Json(request): Json<api::ModerationRequest>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let status = if moderation.hide(inscription_id, request.reason)? {
+        StatusCode::CREATED
+      } else {
+        StatusCode::OK
+      };
+
+      Ok(status.into_response())
+    })
+  }
+
+  async fn moderation_unhide(
+    Extension(moderation): Extension<Arc<Moderation>>,
+    Path(inscription_id): Path<InscriptionId>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if moderation.unhide(inscription_id)? {
+        Ok(StatusCode::OK.into_response())
       } else {
-        Ok(Redirect::to(&format!("/sat/{query}")))
+        Err(ServerError::NotFound(format!(
+          "inscription {inscription_id} is not hidden"
+        )))
       }
     })
   }
@@ -1336,59 +2281,395 @@ impl Server {
   async fn feed(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
   ) -> ServerResult {
     task::block_in_place(|| {
-      let mut builder = rss::ChannelBuilder::default();
+      let mut builder = Self::feed_channel(&server_config, "Inscriptions".into());
 
-      let chain = server_config.chain;
-      match chain {
-        Chain::Mainnet => builder.title("Inscriptions".to_string()),
-        _ => builder.title(format!("Inscriptions – {chain:?}")),
-      };
+      for (number, id) in index.get_feed_inscriptions(FEED_ITEMS)? {
+        if moderation.is_hidden(id) {
+          continue;
+        }
 
-      builder.generator(Some("ord".to_string()));
-
-      for (number, id) in index.get_feed_inscriptions(300)? {
-        builder.item(
-          rss::ItemBuilder::default()
-            .title(Some(format!("Inscription {number}")))
-            .link(Some(format!("/inscription/{id}")))
-            .guid(Some(rss::Guid {
-              value: format!("/inscription/{id}"),
-              permalink: true,
-            }))
-            .build(),
-        );
+        builder.item(Self::feed_item(
+          format!("Inscription {number}"),
+          format!("/inscription/{id}"),
+          None,
+        ));
       }
 
-      Ok(
-        (
-          [
-            (header::CONTENT_TYPE, "application/rss+xml"),
-            (
-              header::CONTENT_SECURITY_POLICY,
-              "default-src 'unsafe-inline'",
-            ),
-          ],
-          builder.build().to_string(),
-        )
-          .into_response(),
-      )
+      Ok(Self::feed_response(builder))
     })
   }
 
-  async fn static_asset(Path(path): Path<String>) -> ServerResult {
-    let content = StaticAssets::get(if let Some(stripped) = path.strip_prefix('/') {
-      stripped
-    } else {
-      &path
-    })
-    .ok_or_not_found(|| format!("asset {path}"))?;
-
-    let mime = mime_guess::from_path(path).first_or_octet_stream();
-
-    Ok(
-      Response::builder()
+  async fn children_feed(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
+    Path(parent): Path<InscriptionId>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let entry = index
+        .get_inscription_entry(parent)?
+        .ok_or_not_found(|| format!("inscription {parent}"))?;
+
+      let mut builder = Self::feed_channel(
+        &server_config,
+        format!("Inscription {} Children", entry.inscription_number),
+      );
+
+      let (children, _next) = index.get_children_by_sequence_number_cursor(
+        entry.sequence_number,
+        None,
+        Order::Newest,
+        FEED_ITEMS,
+        &moderation.hidden(),
+      )?;
+
+      for child in children {
+        let entry = index
+          .get_inscription_entry(child)?
+          .ok_or_not_found(|| format!("inscription {child}"))?;
+
+        builder.item(Self::feed_item(
+          format!("Inscription {}", entry.inscription_number),
+          format!("/inscription/{child}"),
+          Some(entry.timestamp.into()),
+        ));
+      }
+
+      Ok(Self::feed_response(builder))
+    })
+  }
+
+  async fn address_feed(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
+    Path(address): Path<Address<NetworkUnchecked>>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_address_index() {
+        return Err(ServerError::NotFound(
+          "this server has no address index".to_string(),
+        ));
+      }
+
+      let address = address
+        .require_network(server_config.chain.network())
+        .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+      let mut builder = Self::feed_channel(&server_config, format!("Address {address}"));
+
+      let outputs = index.get_address_info(&address)?;
+
+      let mut items = Vec::new();
+
+      for (outpoint, value, _height, header) in index.get_received_outputs(&outputs)? {
+        let timestamp = Some(u64::from(header.time));
+
+        items.push(Self::feed_item(
+          format!("Received {value} sats"),
+          format!("/output/{outpoint}"),
+          timestamp,
+        ));
+
+        for inscription_id in index
+          .get_inscriptions_for_output(outpoint)?
+          .unwrap_or_default()
+        {
+          if moderation.is_hidden(inscription_id) {
+            continue;
+          }
+
+          let entry = index
+            .get_inscription_entry(inscription_id)?
+            .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
+
+          items.push(Self::feed_item(
+            format!("Received inscription {}", entry.inscription_number),
+            format!("/inscription/{inscription_id}"),
+            timestamp,
+          ));
+        }
+
+        for (rune, pile) in index
+          .get_rune_balances_for_output(outpoint)?
+          .unwrap_or_default()
+        {
+          let mut item = Self::feed_item(
+            format!("Received {pile} {rune}"),
+            format!("/output/{outpoint}"),
+            timestamp,
+          );
+
+          item.set_guid(rss::Guid {
+            value: format!("/output/{outpoint}#{rune}"),
+            permalink: false,
+          });
+
+          items.push(item);
+        }
+
+        if items.len() >= FEED_ITEMS {
+          break;
+        }
+      }
+
+      items.truncate(FEED_ITEMS);
+
+      builder.items(items);
+
+      Ok(Self::feed_response(builder))
+    })
+  }
+
+  async fn rune_feed(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(rune_query)): Path<DeserializeFromStr<query::Rune>>,
+    Query(query): Query<RuneFeedQuery>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_rune_index() {
+        return Err(ServerError::NotFound(
+          "this server has no rune index".to_string(),
+        ));
+      }
+
+      let rune = Self::resolve_rune(&index, rune_query)?;
+
+      let (id, entry, _parent) = index
+        .rune(rune)?
+        .ok_or_not_found(|| format!("rune {rune}"))?;
+
+      let threshold = query
+        .threshold
+        .map(|threshold| threshold.to_integer(entry.divisibility))
+        .transpose()
+        .map_err(|err| ServerError::BadRequest(err.to_string()))?
+        .unwrap_or_default();
+
+      let mut builder = Self::feed_channel(&server_config, format!("Rune {}", entry.spaced_rune));
+
+      let events = index.get_rune_events(id, FEED_ITEMS, |event| match event.kind {
+        RuneEventKind::Transferred { .. } => event.amount >= threshold,
+        _ => true,
+      })?;
+
+      for (time, event) in events {
+        let timestamp = Some(u64::from(time));
+
+        let txid = event.txid;
+
+        match event.kind {
+          RuneEventKind::Burned => builder.item(Self::feed_item(
+            format!("Burn of {} {}", entry.pile(event.amount), entry.spaced_rune),
+            format!("/tx/{txid}"),
+            timestamp,
+          )),
+          RuneEventKind::Etched => builder.item(Self::feed_item(
+            format!("Etching of {}", entry.spaced_rune),
+            format!("/tx/{txid}"),
+            timestamp,
+          )),
+          RuneEventKind::Minted => builder.item(Self::feed_item(
+            format!("Mint of {}", entry.spaced_rune),
+            format!("/tx/{txid}"),
+            timestamp,
+          )),
+          RuneEventKind::Transferred { vout } => builder.item(Self::feed_item(
+            format!(
+              "Transfer of {} {}",
+              entry.pile(event.amount),
+              entry.spaced_rune
+            ),
+            format!("/output/{}", OutPoint { txid, vout }),
+            timestamp,
+          )),
+        };
+      }
+
+      Ok(Self::feed_response(builder))
+    })
+  }
+
+  fn feed_channel(server_config: &ServerConfig, title: String) -> rss::ChannelBuilder {
+    let mut builder = rss::ChannelBuilder::default();
+
+    let chain = server_config.chain;
+    match chain {
+      Chain::Mainnet => builder.title(title),
+      _ => builder.title(format!("{title} – {chain:?}")),
+    };
+
+    builder.generator(Some("ord".to_string()));
+
+    builder
+  }
+
+  fn feed_item(title: String, link: String, timestamp: Option<u64>) -> rss::Item {
+    rss::ItemBuilder::default()
+      .title(Some(title))
+      .guid(Some(rss::Guid {
+        value: link.clone(),
+        permalink: true,
+      }))
+      .link(Some(link))
+      .pub_date(timestamp.map(|timestamp| crate::timestamp(timestamp).to_rfc2822()))
+      .build()
+  }
+
+  fn feed_response(builder: rss::ChannelBuilder) -> Response {
+    (
+      [
+        (header::CONTENT_TYPE, "application/rss+xml"),
+        (
+          header::CONTENT_SECURITY_POLICY,
+          "default-src 'unsafe-inline'",
+        ),
+      ],
+      builder.build().to_string(),
+    )
+      .into_response()
+  }
+
+  async fn sitemap(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let base = Self::sitemap_base(&server_config)?;
+
+      let (inscriptions, runes) = index.get_sequence_number_counts()?;
+
+      let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+        <sitemapindex xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+      );
+
+      for (kind, count) in [("inscriptions", inscriptions), ("runes", runes)] {
+        for chunk in 0..count.div_ceil(SITEMAP_CHUNK_SIZE) {
+          xml.push_str(&format!(
+            "  <sitemap><loc>{base}/sitemap/{kind}/{chunk}</loc></sitemap>\n"
+          ));
+        }
+      }
+
+      xml.push_str("</sitemapindex>\n");
+
+      Ok(Self::sitemap_response(xml))
+    })
+  }
+
+  async fn sitemap_inscriptions(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
+    Path(chunk): Path<u32>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let base = Self::sitemap_base(&server_config)?;
+
+      let inscriptions =
+        index.get_inscriptions_in_sequence_number_range(Self::sitemap_range(chunk)?)?;
+
+      if inscriptions.is_empty() {
+        return Err(ServerError::NotFound(format!(
+          "sitemap chunk {chunk} not found"
+        )));
+      }
+
+      let mut paths = Vec::new();
+
+      for (id, has_children) in inscriptions {
+        if moderation.is_hidden(id) {
+          continue;
+        }
+
+        paths.push(format!("/inscription/{id}"));
+
+        if has_children {
+          paths.push(format!("/collection/{id}"));
+        }
+      }
+
+      Ok(Self::sitemap_urlset(&base, paths))
+    })
+  }
+
+  async fn sitemap_runes(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(chunk): Path<u32>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let base = Self::sitemap_base(&server_config)?;
+
+      let runes = index.get_runes_in_number_range(Self::sitemap_range(chunk)?)?;
+
+      if runes.is_empty() {
+        return Err(ServerError::NotFound(format!(
+          "sitemap chunk {chunk} not found"
+        )));
+      }
+
+      Ok(Self::sitemap_urlset(
+        &base,
+        runes.into_iter().map(|id| format!("/rune/{id}")).collect(),
+      ))
+    })
+  }
+
+  /// Sitemap URLs must be absolute, so sitemaps are only served when the
+  /// chain has a domain.
+  fn sitemap_base(server_config: &ServerConfig) -> ServerResult<String> {
+    server_config
+      .domain
+      .as_ref()
+      .map(|domain| format!("https://{domain}"))
+      .ok_or_else(|| ServerError::NotFound("this server has no domain".into()))
+  }
+
+  fn sitemap_range(chunk: u32) -> ServerResult<std::ops::Range<u32>> {
+    let start = chunk
+      .checked_mul(SITEMAP_CHUNK_SIZE)
+      .ok_or_not_found(|| format!("sitemap chunk {chunk}"))?;
+
+    Ok(start..start.saturating_add(SITEMAP_CHUNK_SIZE))
+  }
+
+  fn sitemap_urlset(base: &str, paths: Vec<String>) -> Response {
+    let mut xml = String::from(
+      "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+      <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+
+    for path in paths {
+      xml.push_str(&format!("  <url><loc>{base}{path}</loc></url>\n"));
+    }
+
+    xml.push_str("</urlset>\n");
+
+    Self::sitemap_response(xml)
+  }
+
+  fn sitemap_response(xml: String) -> Response {
+    ([(header::CONTENT_TYPE, "application/xml")], xml).into_response()
+  }
+
+  async fn static_asset(Path(path): Path<String>) -> ServerResult {
+    let content = StaticAssets::get(if let Some(stripped) = path.strip_prefix('/') {
+      stripped
+    } else {
+      &path
+    })
+    .ok_or_not_found(|| format!("asset {path}"))?;
+
+    let mime = mime_guess::from_path(path).first_or_octet_stream();
+
+    Ok(
+      Response::builder()
         .header(header::CONTENT_TYPE, mime.as_ref())
         .body(content.data.into())
         .unwrap(),
@@ -1603,13 +2884,13 @@ impl Server {
 
   async fn content(
     Extension(index): Extension<Arc<Index>>,
-    Extension(settings): Extension<Arc<Settings>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Path(inscription_id): Path<InscriptionId>,
     accept_encoding: AcceptEncoding,
   ) -> ServerResult {
     task::block_in_place(|| {
-      if settings.is_hidden(inscription_id) {
+      if moderation.is_hidden(inscription_id) {
         return Ok(PreviewUnknownHtml.into_response());
       }
 
@@ -1640,13 +2921,13 @@ impl Server {
 
   async fn undelegated_content(
     Extension(index): Extension<Arc<Index>>,
-    Extension(settings): Extension<Arc<Settings>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Path(inscription_id): Path<InscriptionId>,
     accept_encoding: AcceptEncoding,
   ) -> ServerResult {
     task::block_in_place(|| {
-      if settings.is_hidden(inscription_id) {
+      if moderation.is_hidden(inscription_id) {
         return Ok(PreviewUnknownHtml.into_response());
       }
 
@@ -1734,13 +3015,13 @@ impl Server {
 
   async fn preview(
     Extension(index): Extension<Arc<Index>>,
-    Extension(settings): Extension<Arc<Settings>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Path(inscription_id): Path<InscriptionId>,
     accept_encoding: AcceptEncoding,
   ) -> ServerResult {
     task::block_in_place(|| {
-      if settings.is_hidden(inscription_id) {
+      if moderation.is_hidden(inscription_id) {
         return Ok(PreviewUnknownHtml.into_response());
       }
 
@@ -1919,17 +3200,47 @@ impl Server {
   async fn collections(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
+    Query(cursor_query): Query<CursorQuery>,
+    accept_json: AcceptJson,
   ) -> ServerResult {
-    Self::collections_paginated(Extension(server_config), Extension(index), Path(0)).await
+    if accept_json.0 && !cursor_query.is_empty() {
+      return task::block_in_place(|| {
+        let (items, next) = index.get_collections_by_cursor(
+          cursor_query.sequence_number()?,
+          cursor_query.order.unwrap_or(Order::Oldest),
+          100,
+          &moderation.hidden(),
+        )?;
+
+        Ok(
+          Json(api::Page {
+            items,
+            next: next.map(Cursor::SequenceNumber),
+          })
+          .into_response(),
+        )
+      });
+    }
+
+    Self::collections_paginated(
+      Extension(server_config),
+      Extension(index),
+      Extension(moderation),
+      Path(0),
+    )
+    .await
   }
 
   async fn collections_paginated(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
     Path(page_index): Path<usize>,
   ) -> ServerResult {
     task::block_in_place(|| {
-      let (collections, more_collections) = index.get_collections_paginated(100, page_index)?;
+      let (collections, more_collections) =
+        index.get_collections_paginated(100, page_index, &moderation.hidden())?;
 
       let prev = page_index.checked_sub(1);
 
@@ -1950,11 +3261,39 @@ impl Server {
   async fn children(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
     Path(inscription_id): Path<InscriptionId>,
+    Query(cursor_query): Query<CursorQuery>,
+    accept_json: AcceptJson,
   ) -> ServerResult {
+    if accept_json.0 && !cursor_query.is_empty() {
+      return task::block_in_place(|| {
+        let parent = index
+          .get_inscription_entry(inscription_id)?
+          .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
+
+        let (items, next) = index.get_children_by_sequence_number_cursor(
+          parent.sequence_number,
+          cursor_query.sequence_number()?,
+          cursor_query.order.unwrap_or(Order::Oldest),
+          100,
+          &moderation.hidden(),
+        )?;
+
+        Ok(
+          Json(api::Page {
+            items,
+            next: next.map(Cursor::SequenceNumber),
+          })
+          .into_response(),
+        )
+      });
+    }
+
     Self::children_paginated(
       Extension(server_config),
       Extension(index),
+      Extension(moderation),
       Path((inscription_id, 0)),
     )
     .await
@@ -1963,6 +3302,7 @@ impl Server {
   async fn children_paginated(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
     Path((parent, page)): Path<(InscriptionId, usize)>,
   ) -> ServerResult {
     task::block_in_place(|| {
@@ -1972,8 +3312,12 @@ impl Server {
 
       let parent_number = entry.inscription_number;
 
-      let (children, more_children) =
-        index.get_children_by_sequence_number_paginated(entry.sequence_number, 100, page)?;
+      let (children, more_children) = index.get_children_by_sequence_number_paginated(
+        entry.sequence_number,
+        100,
+        page,
+        &moderation.hidden(),
+      )?;
 
       let prev_page = page.checked_sub(1);
 
@@ -1993,53 +3337,267 @@ impl Server {
     })
   }
 
-  async fn children_recursive(
-    Extension(index): Extension<Arc<Index>>,
+  async fn collection(
     Extension(server_config): Extension<Arc<ServerConfig>>,
-    Path(inscription_id): Path<InscriptionId>,
+    Extension(index): Extension<Arc<Index>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
+    Path(parent): Path<InscriptionId>,
+    Query(query): Query<CollectionQuery>,
+    accept_json: AcceptJson,
   ) -> ServerResult {
-    Self::children_recursive_paginated(
-      Extension(index),
+    Self::collection_paginated(
       Extension(server_config),
-      Path((inscription_id, 0)),
+      Extension(index),
+      Extension(moderation),
+      Path((parent, 0)),
+      Query(query),
+      accept_json,
     )
     .await
   }
 
-  async fn children_recursive_paginated(
-    Extension(index): Extension<Arc<Index>>,
+  async fn collection_paginated(
     Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
     Path((parent, page)): Path<(InscriptionId, usize)>,
+    Query(query): Query<CollectionQuery>,
+    AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult {
     task::block_in_place(|| {
-      let Some(parent) = index.get_inscription_entry(parent)? else {
-        return if let Some(proxy) = server_config.proxy.as_ref() {
-          Self::proxy(proxy, &format!("r/children/{}/{}", parent, page))
+      let order = query.order.unwrap_or_default();
+
+      if matches!(order, CollectionOrder::Trait(_)) && !index.has_traits_index() {
+        return Err(ServerError::NotFound(
+          "this server has no traits index".to_string(),
+        ));
+      }
+
+      if order == CollectionOrder::Transferred && !index.has_transfers_index() {
+        return Err(ServerError::NotFound(
+          "this server has no transfers index".to_string(),
+        ));
+      }
+
+      let collection = index
+        .get_collection(parent, &order, 100, page, &moderation.hidden())?
+        .ok_or_not_found(|| format!("inscription {parent}"))?;
+
+      Ok(if accept_json {
+        Json(collection).into_response()
+      } else {
+        let traits = if index.has_traits_index() {
+          let entry = index
+            .get_inscription_entry(parent)?
+            .ok_or_not_found(|| format!("inscription {parent}"))?;
+
+          index
+            .get_collection_traits(entry.sequence_number)?
+            .into_keys()
+            .collect()
         } else {
-          Err(ServerError::NotFound(format!(
-            "inscription {} not found",
-            parent
-          )))
+          Vec::new()
         };
-      };
 
-      let parent_sequence_number = parent.sequence_number;
+        CollectionHtml {
+          collection,
+          traits,
+          transfers: index.has_transfers_index(),
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
 
-      let (ids, more) =
-        index.get_children_by_sequence_number_paginated(parent_sequence_number, 100, page)?;
+  async fn collection_traits(
+    Extension(index): Extension<Arc<Index>>,
+    Path(parent): Path<InscriptionId>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_traits_index() {
+        return Err(ServerError::NotFound(
+          "this server has no traits index".to_string(),
+        ));
+      }
 
-      Ok(Json(api::Children { ids, more, page }).into_response())
+      let entry = index
+        .get_inscription_entry(parent)?
+        .ok_or_not_found(|| format!("inscription {parent}"))?;
+
+      Ok(
+        Json(api::CollectionTraits {
+          parent,
+          traits: index.get_collection_traits(entry.sequence_number)?,
+        })
+        .into_response(),
+      )
     })
   }
 
-  async fn child_inscriptions_recursive(
+  async fn collection_filter(
     Extension(index): Extension<Arc<Index>>,
-    Path(inscription_id): Path<InscriptionId>,
+    Extension(moderation): Extension<Arc<Moderation>>,
+    Path(parent): Path<InscriptionId>,
+    Query(traits): Query<Vec<(String, String)>>,
   ) -> ServerResult {
-    Self::child_inscriptions_recursive_paginated(Extension(index), Path((inscription_id, 0))).await
+    Self::collection_filter_paginated(
+      Extension(index),
+      Extension(moderation),
+      Path((parent, 0)),
+      Query(traits),
+    )
+    .await
   }
 
-  async fn child_inscriptions_recursive_paginated(
+  async fn collection_filter_paginated(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
+    Path((parent, page)): Path<(InscriptionId, usize)>,
+    Query(traits): Query<Vec<(String, String)>>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_traits_index() {
+        return Err(ServerError::NotFound(
+          "this server has no traits index".to_string(),
+        ));
+      }
+
+      if traits.is_empty() {
+        return Err(ServerError::BadRequest(
+          "at least one trait must be given".into(),
+        ));
+      }
+
+      let entry = index
+        .get_inscription_entry(parent)?
+        .ok_or_not_found(|| format!("inscription {parent}"))?;
+
+      let mut filter = BTreeMap::<String, BTreeSet<String>>::new();
+
+      for (key, value) in traits {
+        filter.entry(key).or_default().insert(value);
+      }
+
+      let (ids, more) = index.get_children_with_traits_paginated(
+        entry.sequence_number,
+        &filter,
+        100,
+        page,
+        &moderation.hidden(),
+      )?;
+
+      Ok(Json(api::Children { ids, more, page }).into_response())
+    })
+  }
+
+  async fn children_recursive(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Path(inscription_id): Path<InscriptionId>,
+    Query(cursor_query): Query<CursorQuery>,
+  ) -> ServerResult {
+    if !cursor_query.is_empty() {
+      return task::block_in_place(|| {
+        let Some(parent) = index.get_inscription_entry(inscription_id)? else {
+          return Err(ServerError::NotFound(format!(
+            "inscription {inscription_id} not found"
+          )));
+        };
+
+        let (items, next) = index.get_children_by_sequence_number_cursor(
+          parent.sequence_number,
+          cursor_query.sequence_number()?,
+          cursor_query.order.unwrap_or(Order::Oldest),
+          100,
+          &BTreeSet::new(),
+        )?;
+
+        Ok(
+          Json(api::Page {
+            items,
+            next: next.map(Cursor::SequenceNumber),
+          })
+          .into_response(),
+        )
+      });
+    }
+
+    Self::children_recursive_paginated(
+      Extension(index),
+      Extension(server_config),
+      Path((inscription_id, 0)),
+    )
+    .await
+  }
+
+  async fn children_recursive_paginated(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Path((parent, page)): Path<(InscriptionId, usize)>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let Some(parent) = index.get_inscription_entry(parent)? else {
+        return if let Some(proxy) = server_config.proxy.as_ref() {
+          Self::proxy(proxy, &format!("r/children/{}/{}", parent, page))
+        } else {
+          Err(ServerError::NotFound(format!(
+            "inscription {} not found",
+            parent
+          )))
+        };
+      };
+
+      let parent_sequence_number = parent.sequence_number;
+
+      let (ids, more) = index.get_children_by_sequence_number_paginated(
+        parent_sequence_number,
+        100,
+        page,
+        &BTreeSet::new(),
+      )?;
+
+      Ok(Json(api::Children { ids, more, page }).into_response())
+    })
+  }
+
+  async fn child_inscriptions_recursive(
+    Extension(index): Extension<Arc<Index>>,
+    Path(inscription_id): Path<InscriptionId>,
+    Query(cursor_query): Query<CursorQuery>,
+  ) -> ServerResult {
+    if !cursor_query.is_empty() {
+      return task::block_in_place(|| {
+        let parent_sequence_number = index
+          .get_inscription_entry(inscription_id)?
+          .ok_or_not_found(|| format!("inscription {inscription_id}"))?
+          .sequence_number;
+
+        let (ids, next) = index.get_children_by_sequence_number_cursor(
+          parent_sequence_number,
+          cursor_query.sequence_number()?,
+          cursor_query.order.unwrap_or(Order::Oldest),
+          100,
+          &BTreeSet::new(),
+        )?;
+
+        Ok(
+          Json(api::Page {
+            items: ids
+              .into_iter()
+              .map(|id| Self::child_inscription_recursive(&index, id))
+              .collect::<ServerResult<Vec<api::ChildInscriptionRecursive>>>()?,
+            next: next.map(Cursor::SequenceNumber),
+          })
+          .into_response(),
+        )
+      });
+    }
+
+    Self::child_inscriptions_recursive_paginated(Extension(index), Path((inscription_id, 0))).await
+  }
+
+  async fn child_inscriptions_recursive_paginated(
     Extension(index): Extension<Arc<Index>>,
     Path((parent, page)): Path<(InscriptionId, usize)>,
   ) -> ServerResult {
@@ -2049,36 +3607,17 @@ impl Server {
         .ok_or_not_found(|| format!("inscription {parent}"))?
         .sequence_number;
 
-      let (ids, more) =
-        index.get_children_by_sequence_number_paginated(parent_sequence_number, 100, page)?;
+      let (ids, more) = index.get_children_by_sequence_number_paginated(
+        parent_sequence_number,
+        100,
+        page,
+        &BTreeSet::new(),
+      )?;
 
       let children = ids
         .into_iter()
-        .map(|inscription_id| {
-          let entry = index
-            .get_inscription_entry(inscription_id)
-            .unwrap()
-            .unwrap();
-
-          let satpoint = index
-            .get_inscription_satpoint_by_id(inscription_id)
-            .ok()
-            .flatten()
-            .unwrap();
-
-          api::ChildInscriptionRecursive {
-            charms: Charm::charms(entry.charms),
-            fee: entry.fee,
-            height: entry.height,
-            id: inscription_id,
-            number: entry.inscription_number,
-            output: satpoint.outpoint,
-            sat: entry.sat,
-            satpoint,
-            timestamp: timestamp(entry.timestamp.into()).timestamp(),
-          }
-        })
-        .collect();
+        .map(|inscription_id| Self::child_inscription_recursive(&index, inscription_id))
+        .collect::<ServerResult<Vec<api::ChildInscriptionRecursive>>>()?;
 
       Ok(
         Json(api::ChildInscriptions {
@@ -2091,14 +3630,61 @@ impl Server {
     })
   }
 
+  fn child_inscription_recursive(
+    index: &Index,
+    inscription_id: InscriptionId,
+  ) -> ServerResult<api::ChildInscriptionRecursive> {
+    let entry = index
+      .get_inscription_entry(inscription_id)?
+      .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
+
+    let satpoint = index
+      .get_inscription_satpoint_by_id(inscription_id)?
+      .ok_or_not_found(|| format!("inscription {inscription_id} satpoint"))?;
+
+    Ok(api::ChildInscriptionRecursive {
+      charms: Charm::charms(entry.charms),
+      fee: entry.fee,
+      height: entry.height,
+      id: inscription_id,
+      number: entry.inscription_number,
+      output: satpoint.outpoint,
+      sat: entry.sat,
+      satpoint,
+      timestamp: timestamp(entry.timestamp.into()).timestamp(),
+    })
+  }
+
   async fn inscriptions(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
+    Query(cursor_query): Query<CursorQuery>,
     accept_json: AcceptJson,
   ) -> ServerResult {
+    if accept_json.0 && !cursor_query.is_empty() {
+      return task::block_in_place(|| {
+        let (items, next) = index.get_inscriptions_by_cursor(
+          cursor_query.sequence_number()?,
+          cursor_query.order.unwrap_or(Order::Newest),
+          100,
+          &moderation.hidden(),
+        )?;
+
+        Ok(
+          Json(api::Page {
+            items,
+            next: next.map(Cursor::SequenceNumber),
+          })
+          .into_response(),
+        )
+      });
+    }
+
     Self::inscriptions_paginated(
       Extension(server_config),
       Extension(index),
+      Extension(moderation),
       Path(0),
       accept_json,
     )
@@ -2108,11 +3694,13 @@ impl Server {
   async fn inscriptions_paginated(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
     Path(page_index): Path<u32>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult {
     task::block_in_place(|| {
-      let (inscriptions, more) = index.get_inscriptions_paginated(100, page_index)?;
+      let (inscriptions, more) =
+        index.get_inscriptions_paginated(100, page_index, &moderation.hidden())?;
 
       let prev = page_index.checked_sub(1);
 
@@ -2140,12 +3728,35 @@ impl Server {
   async fn inscriptions_in_block(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
     Path(block_height): Path<u32>,
+    Query(cursor_query): Query<CursorQuery>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult {
+    if accept_json && !cursor_query.is_empty() {
+      return task::block_in_place(|| {
+        let (items, next) = index.get_inscriptions_in_block_by_cursor(
+          block_height,
+          cursor_query.sequence_number()?,
+          cursor_query.order.unwrap_or(Order::Oldest),
+          100,
+          &moderation.hidden(),
+        )?;
+
+        Ok(
+          Json(api::Page {
+            items,
+            next: next.map(Cursor::SequenceNumber),
+          })
+          .into_response(),
+        )
+      });
+    }
+
     Self::inscriptions_in_block_paginated(
       Extension(server_config),
       Extension(index),
+      Extension(moderation),
       Path((block_height, 0)),
       AcceptJson(accept_json),
     )
@@ -2155,6 +3766,7 @@ impl Server {
   async fn inscriptions_in_block_paginated(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
     Path((block_height, page_index)): Path<(u32, u32)>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult {
@@ -2167,6 +3779,7 @@ impl Server {
       let mut inscriptions = index
         .get_inscriptions_in_block(block_height)?
         .into_iter()
+        .filter(|inscription_id| !moderation.is_hidden(*inscription_id))
         .skip(page_index_usize.saturating_mul(page_size_usize))
         .take(page_size_usize.saturating_add(1))
         .collect::<Vec<InscriptionId>>();
@@ -2201,11 +3814,13 @@ impl Server {
   async fn parents(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
     Path(inscription_id): Path<InscriptionId>,
   ) -> ServerResult<Response> {
     Self::parents_paginated(
       Extension(server_config),
       Extension(index),
+      Extension(moderation),
       Path((inscription_id, 0)),
     )
     .await
@@ -2214,6 +3829,7 @@ impl Server {
   async fn parents_paginated(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(moderation): Extension<Arc<Moderation>>,
     Path((id, page)): Path<(InscriptionId, usize)>,
   ) -> ServerResult<Response> {
     task::block_in_place(|| {
@@ -2221,7 +3837,11 @@ impl Server {
         .get_inscription_entry(id)?
         .ok_or_not_found(|| format!("inscription {id}"))?;
 
-      let (parents, more) = index.get_parents_by_sequence_number_paginated(child.parents, page)?;
+      let (parents, more) = index.get_parents_by_sequence_number_paginated(
+        child.parents,
+        page,
+        &moderation.hidden(),
+      )?;
 
       let prev_page = page.checked_sub(1);
 
@@ -2244,7 +3864,32 @@ impl Server {
   async fn parents_recursive(
     Extension(index): Extension<Arc<Index>>,
     Path(inscription_id): Path<InscriptionId>,
+    Query(cursor_query): Query<CursorQuery>,
   ) -> ServerResult {
+    if !cursor_query.is_empty() {
+      return task::block_in_place(|| {
+        let child = index
+          .get_inscription_entry(inscription_id)?
+          .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
+
+        let (items, next) = index.get_parents_by_sequence_number_cursor(
+          child.parents,
+          cursor_query.sequence_number()?,
+          cursor_query.order.unwrap_or(Order::Oldest),
+          100,
+          &BTreeSet::new(),
+        )?;
+
+        Ok(
+          Json(api::Page {
+            items,
+            next: next.map(Cursor::SequenceNumber),
+          })
+          .into_response(),
+        )
+      });
+    }
+
     Self::parents_recursive_paginated(Extension(index), Path((inscription_id, 0))).await
   }
 
@@ -2257,7 +3902,8 @@ impl Server {
         .get_inscription_entry(inscription_id)?
         .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
 
-      let (ids, more) = index.get_parents_by_sequence_number_paginated(child.parents, page)?;
+      let (ids, more) =
+        index.get_parents_by_sequence_number_paginated(child.parents, page, &BTreeSet::new())?;
 
       let page_index =
         u32::try_from(page).map_err(|_| anyhow!("page index {} out of range", page))?;
@@ -2276,8 +3922,39 @@ impl Server {
   async fn sat_inscriptions(
     Extension(index): Extension<Arc<Index>>,
     Path(sat): Path<u64>,
-  ) -> ServerResult<Json<api::SatInscriptions>> {
-    Self::sat_inscriptions_paginated(Extension(index), Path((sat, 0))).await
+    Query(cursor_query): Query<CursorQuery>,
+  ) -> ServerResult {
+    if !cursor_query.is_empty() {
+      return task::block_in_place(|| {
+        if !index.has_sat_index() {
+          return Err(ServerError::NotFound(
+            "this server has no sat index".to_string(),
+          ));
+        }
+
+        let (items, next) = index.get_inscription_ids_by_sat_cursor(
+          Sat(sat),
+          cursor_query.sequence_number()?,
+          cursor_query.order.unwrap_or(Order::Oldest),
+          100,
+          &BTreeSet::new(),
+        )?;
+
+        Ok(
+          Json(api::Page {
+            items,
+            next: next.map(Cursor::SequenceNumber),
+          })
+          .into_response(),
+        )
+      });
+    }
+
+    Ok(
+      Self::sat_inscriptions_paginated(Extension(index), Path((sat, 0)))
+        .await?
+        .into_response(),
+    )
   }
 
   async fn sat_inscriptions_paginated(
@@ -2291,7 +3968,8 @@ impl Server {
         ));
       }
 
-      let (ids, more) = index.get_inscription_ids_by_sat_paginated(Sat(sat), 100, page)?;
+      let (ids, more) =
+        index.get_inscription_ids_by_sat_paginated(Sat(sat), 100, page, &BTreeSet::new())?;
 
       Ok(Json(api::SatInscriptions { ids, more, page }))
     })
@@ -2520,6 +4198,10 @@ mod tests {
       self.ord_flag("--index-sats")
     }
 
+    fn index_search(self) -> Self {
+      self.ord_flag("--index-search")
+    }
+
     fn redirect_http_to_https(self) -> Self {
       self.server_flag("--redirect-http-to-https")
     }
@@ -2921,6 +4603,17 @@ mod tests {
     assert_eq!(server.acme_domains().unwrap(), &["example.com"]);
   }
 
+  #[test]
+  fn acme_domains_include_chain_hosts() {
+    let (_, server) = parse_server_args(
+      "ord server --acme-domain example.com --chain-host signet=signet.example.com --chain-host regtest=example.com",
+    );
+    assert_eq!(
+      server.acme_domains().unwrap(),
+      &["example.com", "signet.example.com"]
+    );
+  }
+
   #[test]
   fn install_sh_redirects_to_github() {
     TestServer::new().assert_redirect(
@@ -2978,126 +4671,409 @@ mod tests {
   }
 
   #[test]
-  fn search_for_blockhash_returns_block() {
-    TestServer::new().assert_redirect(
-      "/search/000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f",
-      "/block/000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f",
-    );
-  }
-
-  #[test]
-  fn search_for_txid_returns_transaction() {
-    TestServer::new().assert_redirect(
-      "/search/0000000000000000000000000000000000000000000000000000000000000000",
-      "/tx/0000000000000000000000000000000000000000000000000000000000000000",
-    );
-  }
-
-  #[test]
-  fn search_for_outpoint_returns_output() {
-    TestServer::new().assert_redirect(
-      "/search/0000000000000000000000000000000000000000000000000000000000000000:0",
-      "/output/0000000000000000000000000000000000000000000000000000000000000000:0",
-    );
-  }
-
-  #[test]
-  fn search_for_inscription_id_returns_inscription() {
-    TestServer::new().assert_redirect(
-      "/search/0000000000000000000000000000000000000000000000000000000000000000i0",
-      "/inscription/0000000000000000000000000000000000000000000000000000000000000000i0",
-    );
-  }
-
-  #[test]
-  fn search_by_path_returns_rune() {
-    TestServer::new().assert_redirect("/search/ABCD", "/rune/ABCD");
-  }
-
-  #[test]
-  fn search_by_path_returns_spaced_rune() {
-    TestServer::new().assert_redirect("/search/AB•CD", "/rune/AB•CD");
-  }
-
-  #[test]
-  fn search_by_rune_id_returns_rune() {
+  fn full_text_search() {
     let server = TestServer::builder()
       .chain(Chain::Regtest)
       .index_runes()
+      .index_search()
       .build();
 
     server.mine_blocks(1);
 
-    let rune = Rune(RUNE);
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription("text/plain", "hello world").to_witness(),
+      )],
+      ..default()
+    });
 
-    server.assert_response_regex(format!("/rune/{rune}"), StatusCode::NOT_FOUND, ".*");
+    server.mine_blocks(1);
 
-    server.etch(
-      Runestone {
-        edicts: vec![Edict {
-          id: RuneId::default(),
-          amount: u128::MAX,
-          output: 0,
-        }],
-        etching: Some(Etching {
-          rune: Some(rune),
-          ..default()
-        }),
-        ..default()
-      },
-      1,
-      None,
-    );
+    let hello_world = InscriptionId { txid, index: 0 };
 
     server.mine_blocks(1);
 
-    server.assert_redirect("/search/8:1", "/rune/AAAAAAAAAAAAA");
-    server.assert_redirect("/search?query=8:1", "/rune/AAAAAAAAAAAAA");
-
-    server.assert_response_regex(
-      "/search/100000000000000000000:200000000000000000",
-      StatusCode::BAD_REQUEST,
-      ".*",
-    );
-  }
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        2,
+        0,
+        0,
+        inscription("text/html", "<p class=world>Hello, there!</p>").to_witness(),
+      )],
+      ..default()
+    });
 
-  #[test]
-  fn search_by_satpoint_returns_sat() {
-    let server = TestServer::builder()
-      .chain(Chain::Regtest)
-      .index_sats()
-      .build();
+    server.mine_blocks(1);
 
-    let txid = server.mine_blocks(1)[0].txdata[0].compute_txid();
+    let hello_there = InscriptionId { txid, index: 0 };
 
-    server.assert_redirect(
-      &format!("/search/{txid}:0:0"),
-      &format!("/satpoint/{txid}:0:0"),
+    pretty_assert_eq!(
+      server.get_json::<api::Search>("/search/hello%20world"),
+      api::Search {
+        inscriptions: vec![hello_world, hello_there],
+        query: "hello world".into(),
+        runes: Vec::new(),
+        sat: None,
+      }
     );
 
-    server.assert_redirect(
-      &format!("/search?query={txid}:0:0"),
-      &format!("/satpoint/{txid}:0:0"),
+    pretty_assert_eq!(
+      server.get_json::<api::Search>("/search?query=there"),
+      api::Search {
+        inscriptions: vec![hello_there],
+        query: "there".into(),
+        runes: Vec::new(),
+        sat: Some("there".parse().unwrap()),
+      }
     );
 
-    server.assert_redirect(
-      &format!("/satpoint/{txid}:0:0"),
-      &format!("/sat/{}", 50 * COIN_VALUE),
+    server.assert_response_regex(
+      "/search/hello",
+      StatusCode::OK,
+      format!(
+        ".*<title>Search: hello</title>.*<h1>Search results for hello</h1>.*\
+        <dd><a href=/sat/.*>hello</a></dd>.*<h2>Inscriptions</h2>.*{hello_there}.*{hello_world}.*",
+      ),
     );
 
-    server.assert_response_regex("/search/1:2:3", StatusCode::BAD_REQUEST, ".*");
+    server.assert_redirect("/search/100", "/inscription/100");
+    server.assert_redirect("/search/1.1", "/sat/1.1");
   }
 
   #[test]
-  fn satpoint_returns_sat_in_multiple_ranges() {
-    let server = TestServer::builder()
-      .chain(Chain::Regtest)
-      .index_sats()
+  fn full_text_search_skips_hidden_inscriptions() {
+    let core = mockcore::builder()
+      .network(Chain::Regtest.network())
       .build();
 
-    server.mine_blocks(1);
+    core.mine_blocks(1);
 
-    let split = TransactionTemplate {
+    let hidden = InscriptionId {
+      txid: core.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..default()
+      }),
+      index: 0,
+    };
+
+    core.mine_blocks(1);
+
+    let visible = InscriptionId {
+      txid: core.broadcast_tx(TransactionTemplate {
+        inputs: &[(2, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..default()
+      }),
+      index: 0,
+    };
+
+    core.mine_blocks(1);
+
+    let server = TestServer::builder()
+      .core(core)
+      .index_search()
+      .config(&format!("hidden: [{hidden}]"))
+      .build();
+
+    pretty_assert_eq!(
+      server.get_json::<api::Search>("/search/hello"),
+      api::Search {
+        inscriptions: vec![visible],
+        query: "hello".into(),
+        runes: Vec::new(),
+        sat: Some("hello".parse().unwrap()),
+      }
+    );
+
+    assert!(!server
+      .get("/search/hello")
+      .text()
+      .unwrap()
+      .contains(&hidden.to_string()));
+  }
+
+  #[test]
+  fn hidden_inscriptions_are_filtered_before_pagination() {
+    let core = mockcore::builder()
+      .network(Chain::Regtest.network())
+      .build();
+
+    core.mine_blocks(1);
+
+    let parent = InscriptionId {
+      txid: core.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..default()
+      }),
+      index: 0,
+    };
+
+    core.mine_blocks(1);
+
+    let mut builder = script::Builder::new();
+    for _ in 0..101 {
+      builder = Inscription {
+        content_type: Some("text/plain".into()),
+        body: Some("hello".into()),
+        parents: vec![parent.value()],
+        ..default()
+      }
+      .append_reveal_script_to_builder(builder);
+    }
+
+    let witness = Witness::from_slice(&[builder.into_bytes(), Vec::new()]);
+
+    let txid = core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, witness), (2, 1, 0, Default::default())],
+      ..default()
+    });
+
+    core.mine_blocks(1);
+
+    let children = (0..101)
+      .map(|index| InscriptionId { txid, index })
+      .collect::<Vec<InscriptionId>>();
+
+    let hidden = children[50];
+
+    let visible = children
+      .iter()
+      .copied()
+      .filter(|child| *child != hidden)
+      .collect::<Vec<InscriptionId>>();
+
+    let server = TestServer::builder()
+      .core(core)
+      .config(&format!("hidden: [{hidden}]"))
+      .build();
+
+    // Recursive endpoints are not filtered, so positions are the same on
+    // every instance.
+    pretty_assert_eq!(
+      server.get_json::<api::Children>(format!("/r/children/{parent}/1")),
+      api::Children {
+        ids: vec![children[100]],
+        more: false,
+        page: 1,
+      }
+    );
+
+    let collection = server.get_json::<api::Collection>(format!("/collection/{parent}"));
+
+    assert_eq!(collection.children, visible);
+    assert!(!collection.more);
+
+    let block = server.get_json::<api::Inscriptions>("/inscriptions/block/3");
+
+    assert_eq!(block.ids, visible);
+    assert!(!block.more);
+
+    let inscriptions = server.get_json::<api::Inscriptions>("/inscriptions");
+
+    assert_eq!(inscriptions.ids.len(), 100);
+    assert!(!inscriptions.ids.contains(&hidden));
+    assert!(inscriptions.more);
+
+    server.assert_response(
+      format!("/r/inscription/{hidden}"),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {hidden} not found"),
+    );
+
+    assert_eq!(
+      server
+        .get_json::<Vec<api::BatchResponse>>(format!("/r/batch?requests=/r/inscription/{hidden}"))
+        [0]
+        .status,
+      404,
+    );
+  }
+
+  #[test]
+  fn full_text_search_finds_runes() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .index_search()
+      .build();
+
+    server.mine_blocks(1);
+
+    let (_txid, id) = server.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          spacers: Some(1),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    let spaced_rune = server
+      .get_json::<api::Rune>(format!("/rune/{id}"))
+      .entry
+      .spaced_rune;
+
+    let name = spaced_rune.rune.to_string();
+
+    pretty_assert_eq!(
+      server.get_json::<api::Search>(format!("/search/{}", name.to_lowercase())),
+      api::Search {
+        inscriptions: Vec::new(),
+        query: name.to_lowercase(),
+        runes: vec![spaced_rune],
+        sat: None,
+      }
+    );
+
+    server.assert_redirect(
+      &format!("/search/{spaced_rune}"),
+      &format!("/rune/{spaced_rune}"),
+    );
+
+    server.assert_response_regex(
+      "/search/FOO",
+      StatusCode::OK,
+      ".*<p>No inscriptions or runes found.</p>.*",
+    );
+  }
+
+  #[test]
+  fn search_for_sat_notation_redirects_to_sat() {
+    TestServer::builder()
+      .index_search()
+      .build()
+      .assert_redirect("/search/1.1", "/sat/1.1");
+
+    TestServer::new().assert_redirect("/search/hello", "/sat/hello");
+  }
+
+  #[test]
+  fn search_for_blockhash_returns_block() {
+    TestServer::new().assert_redirect(
+      "/search/000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f",
+      "/block/000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f",
+    );
+  }
+
+  #[test]
+  fn search_for_txid_returns_transaction() {
+    TestServer::new().assert_redirect(
+      "/search/0000000000000000000000000000000000000000000000000000000000000000",
+      "/tx/0000000000000000000000000000000000000000000000000000000000000000",
+    );
+  }
+
+  #[test]
+  fn search_for_outpoint_returns_output() {
+    TestServer::new().assert_redirect(
+      "/search/0000000000000000000000000000000000000000000000000000000000000000:0",
+      "/output/0000000000000000000000000000000000000000000000000000000000000000:0",
+    );
+  }
+
+  #[test]
+  fn search_for_inscription_id_returns_inscription() {
+    TestServer::new().assert_redirect(
+      "/search/0000000000000000000000000000000000000000000000000000000000000000i0",
+      "/inscription/0000000000000000000000000000000000000000000000000000000000000000i0",
+    );
+  }
+
+  #[test]
+  fn search_by_path_returns_rune() {
+    TestServer::new().assert_redirect("/search/ABCD", "/rune/ABCD");
+  }
+
+  #[test]
+  fn search_by_path_returns_spaced_rune() {
+    TestServer::new().assert_redirect("/search/AB•CD", "/rune/AB•CD");
+  }
+
+  #[test]
+  fn search_by_rune_id_returns_rune() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    let rune = Rune(RUNE);
+
+    server.assert_response_regex(format!("/rune/{rune}"), StatusCode::NOT_FOUND, ".*");
+
+    server.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: u128::MAX,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          rune: Some(rune),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    server.mine_blocks(1);
+
+    server.assert_redirect("/search/8:1", "/rune/AAAAAAAAAAAAA");
+    server.assert_redirect("/search?query=8:1", "/rune/AAAAAAAAAAAAA");
+
+    server.assert_response_regex(
+      "/search/100000000000000000000:200000000000000000",
+      StatusCode::BAD_REQUEST,
+      ".*",
+    );
+  }
+
+  #[test]
+  fn search_by_satpoint_returns_sat() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+
+    let txid = server.mine_blocks(1)[0].txdata[0].compute_txid();
+
+    server.assert_redirect(
+      &format!("/search/{txid}:0:0"),
+      &format!("/satpoint/{txid}:0:0"),
+    );
+
+    server.assert_redirect(
+      &format!("/search?query={txid}:0:0"),
+      &format!("/satpoint/{txid}:0:0"),
+    );
+
+    server.assert_redirect(
+      &format!("/satpoint/{txid}:0:0"),
+      &format!("/sat/{}", 50 * COIN_VALUE),
+    );
+
+    server.assert_response_regex("/search/1:2:3", StatusCode::BAD_REQUEST, ".*");
+  }
+
+  #[test]
+  fn satpoint_returns_sat_in_multiple_ranges() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+
+    server.mine_blocks(1);
+
+    let split = TransactionTemplate {
       inputs: &[(1, 0, 0, Default::default())],
       outputs: 2,
       fee: 0,
@@ -3630,17 +5606,439 @@ mod tests {
   }
 
   #[test]
-  fn transactions_link_to_etching() {
+  fn transaction_sat_flow() {
     let server = TestServer::builder()
       .chain(Chain::Regtest)
-      .index_runes()
+      .index_sats()
+      .ord_flag("--index-spent-sats")
       .build();
 
     server.mine_blocks(1);
 
-    server.assert_response_regex(
-      "/runes",
-      StatusCode::OK,
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      outputs: 2,
+      fee: 1000,
+      ..default()
+    });
+
+    let inscription = InscriptionId { txid, index: 0 };
+
+    let start = 50 * COIN_VALUE;
+    let value = (50 * COIN_VALUE - 1000) / 2;
+
+    let segment = |output, start, end| api::SatFlowSegment {
+      input: 0,
+      inscriptions: Some(Vec::new()),
+      output,
+      rare_sats: Some(Vec::new()),
+      sat_ranges: Some(vec![(start, end)]),
+      value: end - start,
+    };
+
+    pretty_assert_eq!(
+      server
+        .index
+        .get_sat_flow(&server.core.mempool()[0])
+        .unwrap(),
+      Some(api::SatFlow {
+        fee: 1000,
+        segments: vec![
+          api::SatFlowSegment {
+            inscriptions: Some(vec![inscription]),
+            rare_sats: Some(vec![(Sat(start), Rarity::Uncommon)]),
+            ..segment(Some(0), start, start + value)
+          },
+          segment(Some(1), start + value, start + 2 * value),
+          segment(None, start + 2 * value, start + 50 * COIN_VALUE),
+        ],
+      }),
+    );
+
+    server.mine_blocks(1);
+
+    pretty_assert_eq!(
+      server
+        .get_json::<api::Transaction>(format!("/tx/{txid}"))
+        .sat_flow,
+      Some(api::SatFlow {
+        fee: 1000,
+        segments: vec![
+          api::SatFlowSegment {
+            inscriptions: Some(vec![inscription]),
+            rare_sats: Some(vec![(Sat(start), Rarity::Uncommon)]),
+            ..segment(Some(0), start, start + value)
+          },
+          segment(Some(1), start + value, start + 2 * value),
+          segment(None, start + 2 * value, start + 50 * COIN_VALUE),
+        ],
+      }),
+    );
+
+    server.assert_response_regex(
+      format!("/tx/{txid}"),
+      StatusCode::OK,
+      format!(
+        ".*<h2>Sat Flow</h2>.*\
+        <td><a href=/output/{txid}:0>0</a></td>\\s*\
+        <td>{value}</td>\\s*\
+        <td class=monospace>\\s*\
+        <a href=/sat/{start} class=uncommon>{start}</a>-<a href=/sat/{} class=common>{}</a>.*\
+        <td>fee</td>\\s*<td>1000</td>.*",
+        start + value - 1,
+        start + value - 1,
+      ),
+    );
+
+    assert_eq!(
+      TestServer::new()
+        .get_json::<api::Transaction>(format!(
+          "/tx/{}",
+          Chain::Mainnet
+            .genesis_block()
+            .coinbase()
+            .unwrap()
+            .compute_txid()
+        ))
+        .sat_flow,
+      None,
+    );
+  }
+
+  #[test]
+  fn transaction_sat_flow_requires_spent_sats_once_confirmed() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      fee: 1000,
+      ..default()
+    });
+
+    assert!(server
+      .get_json::<api::Transaction>(format!("/tx/{txid}"))
+      .sat_flow
+      .is_some());
+
+    server.mine_blocks(1);
+
+    assert_eq!(
+      server
+        .get_json::<api::Transaction>(format!("/tx/{txid}"))
+        .sat_flow,
+      None,
+    );
+  }
+
+  #[test]
+  fn esplora() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-addresses")
+      .server_flag("--esplora")
+      .build();
+
+    server.mine_blocks(1);
+
+    let address = Chain::Regtest
+      .address_from_script(&change(0).script_pubkey())
+      .unwrap();
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      fee: 1000,
+      recipient: Some(address.clone()),
+      ..default()
+    });
+
+    let block_hash = server.mine_blocks(1)[0].block_hash();
+
+    let inscription = InscriptionId { txid, index: 0 };
+
+    server.assert_response("/esplora/blocks/tip/height", StatusCode::OK, "2");
+
+    let transaction = server.core.tx_by_id(txid);
+
+    server.assert_response(
+      format!("/esplora/tx/{txid}/hex"),
+      StatusCode::OK,
+      &consensus::encode::serialize_hex(&transaction),
+    );
+
+    let esplora_transaction =
+      server.get_json::<esplora::Transaction>(format!("/esplora/tx/{txid}"));
+
+    pretty_assert_eq!(esplora_transaction.fee, Some(1000));
+    pretty_assert_eq!(esplora_transaction.txid, txid);
+    pretty_assert_eq!(
+      esplora_transaction.status,
+      esplora::Status {
+        block_hash: Some(block_hash),
+        block_height: Some(2),
+        block_time: Some(0),
+        confirmed: true,
+      }
+    );
+    pretty_assert_eq!(
+      esplora_transaction.vin[0].prevout.as_ref().unwrap().value,
+      50 * COIN_VALUE
+    );
+    pretty_assert_eq!(
+      esplora_transaction.vout,
+      vec![esplora::Output {
+        inscriptions: Some(vec![inscription]),
+        runes: None,
+        scriptpubkey: address.script_pubkey(),
+        scriptpubkey_address: Some(address.as_unchecked().clone()),
+        scriptpubkey_asm: address.script_pubkey().to_asm_string(),
+        scriptpubkey_type: "v0_p2wpkh".into(),
+        value: 50 * COIN_VALUE - 1000,
+      }],
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<Vec<esplora::Utxo>>(format!("/esplora/address/{address}/utxo")),
+      vec![esplora::Utxo {
+        inscriptions: Some(vec![inscription]),
+        runes: None,
+        status: esplora::Status {
+          block_hash: Some(block_hash),
+          block_height: Some(2),
+          block_time: Some(2),
+          confirmed: true,
+        },
+        txid,
+        value: 50 * COIN_VALUE - 1000,
+        vout: 0,
+      }],
+    );
+
+    let block = server.get_json::<esplora::Block>(format!("/esplora/block/{block_hash}"));
+
+    pretty_assert_eq!(block.id, block_hash);
+    pretty_assert_eq!(block.height, 2);
+    pretty_assert_eq!(block.tx_count, 2);
+
+    server.assert_response(
+      format!("/esplora/tx/{}", crate::test::txid(9)),
+      StatusCode::NOT_FOUND,
+      &format!("transaction {} not found", crate::test::txid(9)),
+    );
+  }
+
+  #[test]
+  fn electrum() {
+    use {
+      bitcoin::hashes::sha256,
+      std::io::{BufRead, Write},
+    };
+
+    let port = TcpListener::bind("127.0.0.1:0")
+      .unwrap()
+      .local_addr()
+      .unwrap()
+      .port();
+
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-addresses")
+      .ord_flag("--index-script-hashes")
+      .server_option("--electrum-port", &port.to_string())
+      .build();
+
+    server.mine_blocks(1);
+
+    let address = Chain::Regtest
+      .address_from_script(&change(0).script_pubkey())
+      .unwrap();
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      fee: 1000,
+      recipient: Some(address.clone()),
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let stream = std::net::TcpStream::connect(("127.0.0.1", port)).unwrap();
+
+    let mut reader = io::BufReader::new(stream.try_clone().unwrap());
+    let mut writer = stream;
+
+    let mut id = 0;
+
+    let mut call = |method: &str, params: serde_json::Value| {
+      id += 1;
+
+      writeln!(
+        writer,
+        "{}",
+        serde_json::json!({ "id": id, "method": method, "params": params })
+      )
+      .unwrap();
+
+      let mut line = String::new();
+      reader.read_line(&mut line).unwrap();
+
+      let response = serde_json::from_str::<serde_json::Value>(&line).unwrap();
+
+      assert_eq!(response["id"], id);
+
+      response
+    };
+
+    pretty_assert_eq!(
+      call("server.version", serde_json::json!(["test", "1.4"]))["result"],
+      serde_json::json!([format!("ord {}", env!("CARGO_PKG_VERSION")), "1.4"]),
+    );
+
+    let tip = server.index.block_hash(Some(2)).unwrap().unwrap();
+
+    pretty_assert_eq!(
+      call("blockchain.headers.subscribe", serde_json::json!([]))["result"],
+      serde_json::json!({
+        "height": 2,
+        "hex": consensus::encode::serialize_hex(&server.index.block_header(tip).unwrap().unwrap()),
+      }),
+    );
+
+    let mut script_hash = sha256::Hash::hash(address.script_pubkey().as_bytes()).to_byte_array();
+    script_hash.reverse();
+    let script_hash = hex::encode(script_hash);
+
+    pretty_assert_eq!(
+      serde_json::from_value::<Vec<electrum::Unspent>>(
+        call(
+          "blockchain.scripthash.listunspent",
+          serde_json::json!([script_hash])
+        )["result"]
+          .clone()
+      )
+      .unwrap(),
+      vec![electrum::Unspent {
+        height: 2,
+        inscriptions: Some(vec![InscriptionId { txid, index: 0 }]),
+        runes: None,
+        tx_hash: txid,
+        tx_pos: 0,
+        value: 50 * COIN_VALUE - 1000,
+      }],
+    );
+
+    pretty_assert_eq!(
+      call(
+        "blockchain.scripthash.get_balance",
+        serde_json::json!([script_hash])
+      )["result"],
+      serde_json::json!({ "confirmed": 50 * COIN_VALUE - 1000, "unconfirmed": 0 }),
+    );
+
+    pretty_assert_eq!(
+      call(
+        "blockchain.scripthash.get_history",
+        serde_json::json!([script_hash])
+      )["result"],
+      serde_json::json!([{ "height": 2, "tx_hash": txid }]),
+    );
+
+    pretty_assert_eq!(
+      call(
+        "blockchain.scripthash.subscribe",
+        serde_json::json!([script_hash])
+      )["result"],
+      sha256::Hash::hash(format!("{txid}:2:").as_bytes()).to_string(),
+    );
+
+    pretty_assert_eq!(
+      call(
+        "blockchain.scripthash.listunspent",
+        serde_json::json!([hex::encode([0; 32])])
+      )["result"],
+      serde_json::json!([]),
+    );
+
+    pretty_assert_eq!(
+      call("blockchain.transaction.get", serde_json::json!([txid]))["result"],
+      consensus::encode::serialize_hex(&server.core.tx_by_id(txid)),
+    );
+
+    let spend = Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: OutPoint { txid, vout: 0 },
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::MAX,
+        witness: Witness::new(),
+      }],
+      output: vec![TxOut {
+        value: Amount::from_sat(50 * COIN_VALUE - 2000),
+        script_pubkey: change(1).script_pubkey(),
+      }],
+    };
+
+    pretty_assert_eq!(
+      call(
+        "blockchain.transaction.broadcast",
+        serde_json::json!([consensus::encode::serialize_hex(&spend)])
+      )["result"],
+      spend.compute_txid().to_string(),
+    );
+
+    pretty_assert_eq!(
+      call("blockchain.unknown", serde_json::json!([]))["error"]["code"],
+      -32601,
+    );
+
+    server.mine_blocks(1);
+
+    let mut notifications = Vec::new();
+
+    for _ in 0..2 {
+      let mut line = String::new();
+      reader.read_line(&mut line).unwrap();
+      notifications.push(serde_json::from_str::<serde_json::Value>(&line).unwrap());
+    }
+
+    pretty_assert_eq!(notifications[0]["method"], "blockchain.headers.subscribe");
+    pretty_assert_eq!(notifications[0]["params"][0]["height"], 3);
+    pretty_assert_eq!(
+      notifications[1],
+      serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": "blockchain.scripthash.subscribe",
+        "params": [
+          script_hash,
+          sha256::Hash::hash(format!("{txid}:2:{}:3:", spend.compute_txid()).as_bytes())
+            .to_string(),
+        ],
+      }),
+    );
+  }
+
+  #[test]
+  fn esplora_requires_flag() {
+    TestServer::new().assert_response("/esplora/blocks/tip/height", StatusCode::NOT_FOUND, "");
+  }
+
+  #[test]
+  fn transactions_link_to_etching() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      "/runes",
+      StatusCode::OK,
       ".*<title>Runes</title>.*<h1>Runes</h1>\n<ul>\n</ul>.*",
     );
 
@@ -4867,7 +7265,7 @@ mod tests {
     server.assert_response_regex(
       format!("/preview/{inscription_id}"),
       StatusCode::OK,
-      format!(r".*<audio .*>\s*<source src=/content/{inscription_id}>.*"),
+      format!(r".*<audio .*>\s*<source src=\.\./content/{inscription_id}>.*"),
     );
   }
 
@@ -4887,7 +7285,7 @@ mod tests {
     server.assert_response_regex(
       format!("/preview/{inscription_id}"),
       StatusCode::OK,
-      format!(r".*src: url\(/content/{inscription_id}\).*"),
+      format!(r".*src: url\(\.\./content/{inscription_id}\).*"),
     );
   }
 
@@ -4953,7 +7351,7 @@ mod tests {
       format!("/preview/{inscription_id}"),
       StatusCode::OK,
       "default-src 'self' 'unsafe-inline'",
-      format!(r".*background-image: url\(/content/{inscription_id}\);.*"),
+      format!(r".*background-image: url\(\.\./content/{inscription_id}\);.*"),
     );
   }
 
@@ -5018,7 +7416,7 @@ mod tests {
     server.assert_response_regex(
       format!("/preview/{inscription_id}"),
       StatusCode::OK,
-      format!(r".*<video .*>\s*<source src=/content/{inscription_id}>.*"),
+      format!(r".*<video .*>\s*<source src=\.\./content/{inscription_id}>.*"),
     );
   }
 
@@ -5151,56 +7549,743 @@ mod tests {
   }
 
   #[test]
-  fn inscription_with_unknown_type_and_no_body_has_unknown_preview() {
-    let server = TestServer::builder()
-      .chain(Chain::Regtest)
-      .index_sats()
-      .build();
+  fn children_feed() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
     server.mine_blocks(1);
 
-    let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(
-        1,
-        0,
-        0,
-        Inscription {
-          content_type: Some("foo/bar".as_bytes().to_vec()),
-          body: None,
-          ..default()
-        }
-        .to_witness(),
-      )],
+    let parent_txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
       ..default()
     });
 
-    let inscription_id = InscriptionId { txid, index: 0 };
-
     server.mine_blocks(1);
 
-    server.assert_response(
-      format!("/preview/{inscription_id}"),
-      StatusCode::OK,
-      &fs::read_to_string("templates/preview-unknown.html").unwrap(),
-    );
-  }
-
-  #[test]
-  fn inscription_with_known_type_and_no_body_has_unknown_preview() {
-    let server = TestServer::builder()
-      .chain(Chain::Regtest)
-      .index_sats()
-      .build();
-    server.mine_blocks(1);
+    let parent = InscriptionId {
+      txid: parent_txid,
+      index: 0,
+    };
 
     let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(
-        1,
-        0,
-        0,
-        Inscription {
-          content_type: Some("image/png".as_bytes().to_vec()),
-          body: None,
-          ..default()
+      inputs: &[
+        (
+          2,
+          0,
+          0,
+          Inscription {
+            content_type: Some("text/plain".into()),
+            body: Some("hello".into()),
+            parents: vec![parent.value()],
+            ..default()
+          }
+          .to_witness(),
+        ),
+        (2, 1, 0, Default::default()),
+      ],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let child = InscriptionId { txid, index: 0 };
+
+    server.assert_response_regex(
+      format!("/children/{parent}/feed.xml"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Inscription 0 Children – Regtest</title>.*\
+        <item><title>Inscription 1</title><link>/inscription/{child}</link>.*<pubDate>.*</pubDate></item>\
+        </channel>.*"
+      ),
+    );
+
+    server.assert_response(
+      format!("/children/{child}/feed.xml"),
+      StatusCode::OK,
+      "<?xml version=\"1.0\" encoding=\"utf-8\"?><rss version=\"2.0\"><channel>\
+      <title>Inscription 1 Children – Regtest</title><link></link><description></description>\
+      <generator>ord</generator></channel></rss>",
+    );
+
+    server.assert_response(
+      format!("/children/{}/feed.xml", inscription_id(1)),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {} not found", inscription_id(1)),
+    );
+  }
+
+  #[test]
+  fn collection_traits() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-traits")
+      .build();
+
+    server.mine_blocks(1);
+
+    let parent_txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(4);
+
+    let parent = InscriptionId {
+      txid: parent_txid,
+      index: 0,
+    };
+
+    let child = |traits: &[(&str, Value)]| {
+      let mut metadata = Vec::new();
+      ciborium::into_writer(
+        &Value::Map(
+          traits
+            .iter()
+            .map(|(key, value)| (Value::Text((*key).into()), value.clone()))
+            .collect(),
+        ),
+        &mut metadata,
+      )
+      .unwrap();
+
+      Inscription {
+        content_type: Some("text/plain".into()),
+        body: Some("hello".into()),
+        metadata: Some(metadata),
+        parents: vec![parent.value()],
+        ..default()
+      }
+      .to_witness()
+    };
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (2, 1, 0, Default::default()),
+        (
+          3,
+          0,
+          0,
+          child(&[
+            ("eyes", Value::Text("laser".into())),
+            ("level", Value::Integer(1.into())),
+          ]),
+        ),
+        (
+          4,
+          0,
+          0,
+          child(&[
+            ("eyes", Value::Text("blue".into())),
+            ("level", Value::Integer(1.into())),
+          ]),
+        ),
+        (5, 0, 0, child(&[("eyes", Value::Text("laser".into()))])),
+      ],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let children = (0..3)
+      .map(|index| InscriptionId { txid, index })
+      .collect::<Vec<InscriptionId>>();
+
+    pretty_assert_eq!(
+      server.get_json::<api::CollectionTraits>(format!("/collection/{parent}/traits")),
+      api::CollectionTraits {
+        parent,
+        traits: [
+          (
+            "eyes".into(),
+            [("blue".into(), 1), ("laser".into(), 2)].into()
+          ),
+          ("level".into(), [("1".into(), 2)].into()),
+        ]
+        .into(),
+      }
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::Children>(format!("/collection/{parent}/filter?eyes=laser")),
+      api::Children {
+        ids: vec![children[0], children[2]],
+        more: false,
+        page: 0,
+      }
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::Children>(format!(
+        "/collection/{parent}/filter?eyes=laser&eyes=blue&level=1"
+      )),
+      api::Children {
+        ids: vec![children[0], children[1]],
+        more: false,
+        page: 0,
+      }
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::Children>(format!("/collection/{parent}/filter/1?eyes=laser")),
+      api::Children {
+        ids: Vec::new(),
+        more: false,
+        page: 1,
+      }
+    );
+
+    server.assert_response(
+      format!("/collection/{parent}/filter"),
+      StatusCode::BAD_REQUEST,
+      "at least one trait must be given",
+    );
+
+    server.assert_response(
+      format!("/collection/{}/traits", inscription_id(1)),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {} not found", inscription_id(1)),
+    );
+  }
+
+  #[test]
+  fn collection() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-addresses")
+      .ord_flag("--index-traits")
+      .ord_flag("--index-transfers")
+      .build();
+
+    server.mine_blocks(1);
+
+    let parent_txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(4);
+
+    let parent = InscriptionId {
+      txid: parent_txid,
+      index: 0,
+    };
+
+    let child = |level: u64| {
+      let mut metadata = Vec::new();
+      ciborium::into_writer(
+        &Value::Map(vec![(
+          Value::Text("level".into()),
+          Value::Integer(level.into()),
+        )]),
+        &mut metadata,
+      )
+      .unwrap();
+
+      Inscription {
+        content_type: Some("text/plain".into()),
+        body: Some("hello".into()),
+        metadata: Some(metadata),
+        parents: vec![parent.value()],
+        ..default()
+      }
+      .to_witness()
+    };
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (3, 0, 0, child(10)),
+        (4, 0, 0, child(9)),
+        (5, 0, 0, Default::default()),
+        (2, 1, 0, Default::default()),
+      ],
+      outputs: 4,
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let children = (0..2)
+      .map(|index| InscriptionId { txid, index })
+      .collect::<Vec<InscriptionId>>();
+
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(6, 1, 0, Default::default())],
+      recipient: Some(
+        Chain::Regtest
+          .address_from_script(&change(0).script_pubkey())
+          .unwrap(),
+      ),
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    pretty_assert_eq!(
+      server.get_json::<api::Collection>(format!("/collection/{parent}")),
+      api::Collection {
+        children: children.clone(),
+        first_child_height: Some(6),
+        holders: Some(2),
+        last_child_height: Some(6),
+        more: false,
+        order: CollectionOrder::Inscribed,
+        page: 0,
+        parent,
+        parent_number: 0,
+        total_children: 2,
+      }
+    );
+
+    pretty_assert_eq!(
+      server
+        .get_json::<api::Collection>(format!("/collection/{parent}?order=transferred"))
+        .children,
+      children,
+    );
+
+    pretty_assert_eq!(
+      server
+        .get_json::<api::Collection>(format!("/collection/{parent}?order=trait:level"))
+        .children,
+      vec![children[1], children[0]],
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::Collection>(format!("/collection/{parent}/1")),
+      api::Collection {
+        children: Vec::new(),
+        first_child_height: Some(6),
+        holders: None,
+        last_child_height: Some(6),
+        more: false,
+        order: CollectionOrder::Inscribed,
+        page: 1,
+        parent,
+        parent_number: 0,
+        total_children: 2,
+      }
+    );
+
+    server.assert_response_regex(
+      format!("/collection/{parent}?order=transferred"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Inscription 0 Collection</title>.*\
+        <dt>children</dt>\\s*<dd>2</dd>.*\
+        <dt>holders</dt>\\s*<dd>2</dd>.*\
+        <a href=/collection/{parent}>inscribed</a>\\s*transferred\\s*\
+        <a href=/collection/{parent}\\?order=trait%3Alevel>level</a>.*"
+      ),
+    );
+
+    server.assert_response(
+      format!("/collection/{parent}?order=newest"),
+      StatusCode::BAD_REQUEST,
+      "Failed to deserialize query string: order: invalid collection order `newest`",
+    );
+
+    server.assert_response(
+      format!("/collection/{}", inscription_id(1)),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {} not found", inscription_id(1)),
+    );
+  }
+
+  #[test]
+  fn collection_transferred_order_requires_transfers_index() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.assert_response(
+      format!("/collection/{}?order=transferred", inscription_id(1)),
+      StatusCode::NOT_FOUND,
+      "this server has no transfers index",
+    );
+  }
+
+  #[test]
+  fn collection_traits_require_traits_index() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.assert_response(
+      format!("/collection/{}/traits", inscription_id(1)),
+      StatusCode::NOT_FOUND,
+      "this server has no traits index",
+    );
+
+    server.assert_response(
+      format!("/collection/{}/filter?eyes=laser", inscription_id(1)),
+      StatusCode::NOT_FOUND,
+      "this server has no traits index",
+    );
+  }
+
+  #[test]
+  fn address_feed() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .ord_flag("--index-addresses")
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let address = Chain::Regtest
+      .address_from_script(
+        &server.index.get_transaction(txid).unwrap().unwrap().output[0].script_pubkey,
+      )
+      .unwrap();
+
+    server.assert_response_regex(
+      format!("/address/{address}/feed.xml"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Address {address} – Regtest</title>.*\
+        <item><title>Received \\d+ sats</title><link>/output/{txid}:0</link>.*<pubDate>.*</pubDate></item>\
+        <item><title>Received inscription 0</title><link>/inscription/{txid}i0</link>.*<pubDate>.*</pubDate></item>.*"
+      ),
+    );
+
+    server.assert_response(
+      "/address/bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4/feed.xml",
+      StatusCode::BAD_REQUEST,
+      "validation error",
+    );
+  }
+
+  #[test]
+  fn sitemap() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    let parent_txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let parent = InscriptionId {
+      txid: parent_txid,
+      index: 0,
+    };
+
+    let child_txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (
+          2,
+          0,
+          0,
+          Inscription {
+            content_type: Some("text/plain".into()),
+            body: Some("child".into()),
+            parents: vec![parent.value()],
+            ..default()
+          }
+          .to_witness(),
+        ),
+        (2, 1, 0, Default::default()),
+      ],
+      outputs: 2,
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let child = InscriptionId {
+      txid: child_txid,
+      index: 0,
+    };
+
+    let (_txid, id) = server.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    let base = format!("https://{}", System::host_name().unwrap());
+
+    server.assert_response(
+      "/sitemap.xml",
+      StatusCode::OK,
+      &format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <sitemap><loc>{base}/sitemap/inscriptions/0</loc></sitemap>
+  <sitemap><loc>{base}/sitemap/runes/0</loc></sitemap>
+</sitemapindex>
+"#
+      ),
+    );
+
+    server.assert_response(
+      "/sitemap/inscriptions/0",
+      StatusCode::OK,
+      &format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <url><loc>{base}/inscription/{parent}</loc></url>
+  <url><loc>{base}/collection/{parent}</loc></url>
+  <url><loc>{base}/inscription/{child}</loc></url>
+</urlset>
+"#
+      ),
+    );
+
+    server.assert_response(
+      "/sitemap/runes/0",
+      StatusCode::OK,
+      &format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <url><loc>{base}/rune/{id}</loc></url>
+</urlset>
+"#
+      ),
+    );
+
+    server.assert_response(
+      "/sitemap/inscriptions/1",
+      StatusCode::NOT_FOUND,
+      "sitemap chunk 1 not found",
+    );
+
+    server.assert_response_regex(
+      format!("/inscription/{child}"),
+      StatusCode::OK,
+      format!(
+        ".*<meta property=og:description content='Inscription 1 \\(text/plain\\), inscribed in block 3'>
+    <meta name=description content='.*'>
+    <meta property=og:image content='{base}/preview/{child}'>
+    <meta property=twitter:card content=summary_large_image>
+    <script type=application/ld\\+json>\\{{\"@context\":\"https://schema.org\",\"@type\":\"CreativeWork\",.*\"identifier\":\"{child}\",.*\\}}</script>
+.*"
+      ),
+    );
+  }
+
+  #[test]
+  fn hidden_inscriptions_are_not_used_as_previews() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .ord_flag("--index-sats")
+      .server_option("--admin-token", "secret")
+      .build();
+
+    server.mine_blocks(1);
+
+    let rune = Rune(RUNE);
+
+    let (txid, _id) = server.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(rune),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      Some(
+        Inscription {
+          content_type: Some("text/plain".into()),
+          body: Some("hello".into()),
+          rune: Some(rune.commitment()),
+          ..default()
+        }
+        .to_witness(),
+      ),
+    );
+
+    let parent = InscriptionId { txid, index: 0 };
+
+    let sat = server
+      .get_json::<api::Inscription>(format!("/inscription/{parent}"))
+      .sat
+      .unwrap();
+
+    let base = format!("https://{}", System::host_name().unwrap());
+
+    for path in [format!("/rune/{rune}"), format!("/sat/{sat}")] {
+      server.assert_response_regex(
+        &path,
+        StatusCode::OK,
+        format!(".*<meta property=og:image content='{base}/preview/{parent}'>.*"),
+      );
+    }
+
+    assert_eq!(
+      reqwest::blocking::Client::new()
+        .put(server.join_url(&format!("/admin/moderation/{parent}")))
+        .bearer_auth("secret")
+        .json(&api::ModerationRequest {
+          reason: "spam".into(),
+        })
+        .send()
+        .unwrap()
+        .status(),
+      StatusCode::CREATED,
+    );
+
+    for path in [format!("/rune/{rune}"), format!("/sat/{sat}")] {
+      server.assert_response_regex(
+        &path,
+        StatusCode::OK,
+        format!(
+          ".*<meta property=og:image content='{base}/static/favicon.png'>
+    <meta property=twitter:card content=summary>.*"
+        ),
+      );
+    }
+  }
+
+  #[test]
+  fn rune_feed() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    let rune = Rune(RUNE);
+
+    let (txid, id) = server.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: 1000,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          rune: Some(rune),
+          premine: Some(1000),
+          terms: Some(Terms {
+            amount: Some(10),
+            cap: Some(5),
+            ..default()
+          }),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    let block_count = usize::try_from(server.index.block_count().unwrap()).unwrap();
+
+    let mint = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(block_count - 1, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          mint: Some(id),
+          ..default()
+        }
+        .encipher(),
+      ),
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let transfer = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(block_count - 1, 1, 0, Witness::new())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/rune/{rune}/feed.xml"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Rune {rune} – Regtest</title>.*\
+        <item><title>Transfer of 1000\u{A0}¤ {rune}</title><link>/output/{transfer}:0</link>.*</item>\
+        <item><title>Mint of {rune}</title><link>/tx/{mint}</link>.*<pubDate>.*</pubDate></item>\
+        <item><title>Transfer of 10\u{A0}¤ {rune}</title><link>/output/{mint}:0</link>.*</item>\
+        <item><title>Etching of {rune}</title><link>/tx/{txid}</link>.*</item>\
+        <item><title>Transfer of 1000\u{A0}¤ {rune}</title><link>/output/{txid}:0</link>.*<pubDate>.*</pubDate></item>.*"
+      ),
+    );
+
+    server.assert_response_regex(
+      format!("/rune/{id}/feed.xml?threshold=1001"),
+      StatusCode::OK,
+      format!(".*<item><title>Mint of {rune}</title>.*</item></channel>.*"),
+    );
+
+    server.assert_response(
+      format!("/rune/{rune}/feed.xml?threshold=0.1"),
+      StatusCode::BAD_REQUEST,
+      "excessive precision",
+    );
+  }
+
+  #[test]
+  fn inscription_with_unknown_type_and_no_body_has_unknown_preview() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        Inscription {
+          content_type: Some("foo/bar".as_bytes().to_vec()),
+          body: None,
+          ..default()
+        }
+        .to_witness(),
+      )],
+      ..default()
+    });
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response(
+      format!("/preview/{inscription_id}"),
+      StatusCode::OK,
+      &fs::read_to_string("templates/preview-unknown.html").unwrap(),
+    );
+  }
+
+  #[test]
+  fn inscription_with_known_type_and_no_body_has_unknown_preview() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        Inscription {
+          content_type: Some("image/png".as_bytes().to_vec()),
+          body: None,
+          ..default()
         }
         .to_witness(),
       )],
@@ -5651,6 +8736,7 @@ next
 .*<a href=/inscription/.*><iframe .* src=/preview/.*></iframe></a>.*
     <div class=center>
       <a href=/children/{parent_inscription_id}>all \\(5\\)</a>
+      <a href=/collection/{parent_inscription_id}>collection</a>
     </div>.*"
       ),
     );
@@ -6473,6 +9559,543 @@ next
     );
   }
 
+  #[test]
+  fn rune_recursive_endpoints() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    let rune = Rune(RUNE);
+
+    let (txid, id) = server.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: 1000,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          divisibility: Some(1),
+          rune: Some(rune),
+          premine: Some(1000),
+          terms: Some(Terms {
+            amount: Some(10),
+            cap: Some(5),
+            ..default()
+          }),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    let entry = server.index.rune(rune).unwrap().unwrap().1;
+
+    pretty_assert_eq!(
+      server.get_json::<api::RuneRecursive>(format!("/r/rune/{rune}")),
+      api::RuneRecursive {
+        entry,
+        id,
+        max_supply: 1050,
+        mintable: true,
+        mints_remaining: Some(5),
+        parent: None,
+        supply: 1000,
+      }
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::RuneRecursive>(format!("/r/rune/{id}")),
+      server.get_json::<api::RuneRecursive>("/r/rune/0"),
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<Pile>(format!("/r/rune/{rune}/balance/{txid}:0")),
+      Pile {
+        amount: 1000,
+        divisibility: 1,
+        symbol: None,
+      }
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<Pile>(format!("/r/rune/{id}/balance/{txid}:1")),
+      Pile {
+        amount: 0,
+        divisibility: 1,
+        symbol: None,
+      }
+    );
+
+    server.assert_response(
+      "/r/rune/AAAAAAAAAAAAAA",
+      StatusCode::NOT_FOUND,
+      "rune AAAAAAAAAAAAAA not found",
+    );
+  }
+
+  #[test]
+  fn rune_recursive_endpoints_require_rune_index() {
+    TestServer::new().assert_response(
+      "/r/rune/AAAAAAAAAAAAA",
+      StatusCode::NOT_FOUND,
+      "this server has no rune index",
+    );
+  }
+
+  fn post_decode(server: &TestServer, body: &str) -> reqwest::blocking::Response {
+    server.index.update().unwrap();
+
+    reqwest::blocking::Client::new()
+      .post(server.join_url("/decode"))
+      .body(body.to_string())
+      .send()
+      .unwrap()
+  }
+
+  #[test]
+  fn decode_raw_transaction_allows_cross_origin_posts() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    let response = reqwest::blocking::Client::new()
+      .request(http::Method::OPTIONS, server.join_url("/decode"))
+      .header(header::ORIGIN, "https://example.com")
+      .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+      .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "content-type")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let allowed = |name| {
+      response
+        .headers()
+        .get(name)
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string()
+    };
+
+    assert!(allowed(header::ACCESS_CONTROL_ALLOW_METHODS).contains("POST"));
+    assert_eq!(
+      allowed(header::ACCESS_CONTROL_ALLOW_HEADERS),
+      "content-type"
+    );
+  }
+
+  #[test]
+  fn decode_raw_transaction_does_not_simulate_runes_before_first_rune_height() {
+    let server = TestServer::builder()
+      .chain(Chain::Mainnet)
+      .index_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    let transaction = Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: OutPoint {
+          txid: server.index.get_block_by_height(1).unwrap().unwrap().txdata[0].compute_txid(),
+          vout: 0,
+        },
+        ..default()
+      }],
+      output: vec![
+        TxOut {
+          value: Amount::from_sat(1000),
+          script_pubkey: change(0).script_pubkey(),
+        },
+        TxOut {
+          value: Amount::ZERO,
+          script_pubkey: Runestone {
+            etching: Some(Etching {
+              premine: Some(1000),
+              ..default()
+            }),
+            ..default()
+          }
+          .encipher(),
+        },
+      ],
+    };
+
+    let simulation = post_decode(&server, &consensus::encode::serialize_hex(&transaction))
+      .json::<api::Simulation>()
+      .unwrap();
+
+    assert!(matches!(simulation.runestone, Some(Artifact::Runestone(_))));
+
+    pretty_assert_eq!(simulation.burned_runes, Some(BTreeMap::new()));
+
+    pretty_assert_eq!(
+      simulation
+        .outputs
+        .into_iter()
+        .map(|output| output.runes.unwrap())
+        .collect::<Vec<BTreeMap<SpacedRune, Pile>>>(),
+      [BTreeMap::new(), BTreeMap::new()],
+    );
+  }
+
+  #[test]
+  fn decode_raw_transaction_simulates_inscription_transfer() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let outpoint = OutPoint { txid, vout: 0 };
+
+    let value = server.index.get_transaction(txid).unwrap().unwrap().output[0]
+      .value
+      .to_sat();
+
+    let sat_ranges = server.index.list(outpoint).unwrap().unwrap();
+
+    let transaction = Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: outpoint,
+        ..default()
+      }],
+      output: vec![
+        TxOut {
+          value: Amount::from_sat(1000),
+          script_pubkey: change(0).script_pubkey(),
+        },
+        TxOut {
+          value: Amount::from_sat(value - 2000),
+          script_pubkey: change(1).script_pubkey(),
+        },
+      ],
+    };
+
+    let response = post_decode(&server, &consensus::encode::serialize_hex(&transaction));
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let simulation = response.json::<api::Simulation>().unwrap();
+
+    let start = sat_ranges[0].0;
+
+    pretty_assert_eq!(
+      simulation,
+      api::Simulation {
+        burned_runes: None,
+        fee: 1000,
+        fee_inscriptions: Some(Vec::new()),
+        fee_sat_ranges: Some(vec![(start + value - 1000, start + value)]),
+        inscriptions: Vec::new(),
+        outputs: vec![
+          api::SimulatedOutput {
+            address: Some(
+              Chain::Regtest
+                .address_from_script(&change(0).script_pubkey())
+                .unwrap()
+                .as_unchecked()
+                .clone()
+            ),
+            inscriptions: Some(vec![InscriptionId { txid, index: 0 }]),
+            runes: None,
+            sat_ranges: Some(vec![(start, start + 1000)]),
+            script_pubkey: change(0).script_pubkey(),
+            value: 1000,
+          },
+          api::SimulatedOutput {
+            address: Some(
+              Chain::Regtest
+                .address_from_script(&change(1).script_pubkey())
+                .unwrap()
+                .as_unchecked()
+                .clone()
+            ),
+            inscriptions: Some(Vec::new()),
+            runes: None,
+            sat_ranges: Some(vec![(start + 1000, start + value - 1000)]),
+            script_pubkey: change(1).script_pubkey(),
+            value: value - 2000,
+          },
+        ],
+        runestone: None,
+        txid: transaction.compute_txid(),
+      }
+    );
+
+    let psbt = Psbt::from_unsigned_tx(transaction).unwrap();
+
+    let response = post_decode(&server, &base64_encode(&psbt.serialize()));
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    pretty_assert_eq!(response.json::<api::Simulation>().unwrap(), simulation);
+  }
+
+  #[test]
+  fn decode_raw_transaction_simulates_rune_transfer() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    let rune = Rune(RUNE);
+
+    let (txid, id) = server.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: 1000,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          rune: Some(rune),
+          premine: Some(1000),
+          terms: Some(Terms {
+            amount: Some(10),
+            cap: Some(5),
+            ..default()
+          }),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    let outpoint = OutPoint { txid, vout: 0 };
+
+    let value = server.index.get_transaction(txid).unwrap().unwrap().output[0]
+      .value
+      .to_sat();
+
+    let spaced_rune = SpacedRune { rune, spacers: 0 };
+
+    let pile = |amount| Pile {
+      amount,
+      divisibility: 0,
+      symbol: None,
+    };
+
+    let transaction = |runestone: Runestone| Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: outpoint,
+        ..default()
+      }],
+      output: vec![
+        TxOut {
+          value: Amount::from_sat(value / 2),
+          script_pubkey: change(0).script_pubkey(),
+        },
+        TxOut {
+          value: Amount::from_sat(value / 2),
+          script_pubkey: change(1).script_pubkey(),
+        },
+        TxOut {
+          value: Amount::ZERO,
+          script_pubkey: runestone.encipher(),
+        },
+      ],
+    };
+
+    let simulation = post_decode(
+      &server,
+      &consensus::encode::serialize_hex(&transaction(Runestone {
+        edicts: vec![Edict {
+          id,
+          amount: 400,
+          output: 1,
+        }],
+        mint: Some(id),
+        ..default()
+      })),
+    )
+    .json::<api::Simulation>()
+    .unwrap();
+
+    pretty_assert_eq!(simulation.burned_runes, Some(BTreeMap::new()));
+
+    pretty_assert_eq!(
+      simulation
+        .outputs
+        .into_iter()
+        .map(|output| output.runes.unwrap())
+        .collect::<Vec<BTreeMap<SpacedRune, Pile>>>(),
+      [
+        [(spaced_rune, pile(610))].into(),
+        [(spaced_rune, pile(400))].into(),
+        BTreeMap::new(),
+      ],
+    );
+
+    let simulation = post_decode(
+      &server,
+      &consensus::encode::serialize_hex(&transaction(Runestone {
+        edicts: vec![Edict {
+          id,
+          amount: 400,
+          output: 5,
+        }],
+        ..default()
+      })),
+    )
+    .json::<api::Simulation>()
+    .unwrap();
+
+    assert!(matches!(simulation.runestone, Some(Artifact::Cenotaph(_))));
+
+    pretty_assert_eq!(
+      simulation.burned_runes,
+      Some([(spaced_rune, pile(1000))].into()),
+    );
+  }
+
+  #[test]
+  fn decode_raw_transaction_checks_psbt_prevouts() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let spent = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let outpoint = OutPoint {
+      txid: spent,
+      vout: 0,
+    };
+
+    let prevout = server.index.get_transaction(spent).unwrap().unwrap().output[0].clone();
+
+    let unsigned_tx = |previous_output| Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output,
+        ..default()
+      }],
+      output: vec![TxOut {
+        value: Amount::from_sat(1000),
+        script_pubkey: change(0).script_pubkey(),
+      }],
+    };
+
+    let mut psbt = Psbt::from_unsigned_tx(unsigned_tx(outpoint)).unwrap();
+
+    psbt.inputs[0].witness_utxo = Some(TxOut {
+      value: prevout.value + Amount::from_sat(1),
+      script_pubkey: prevout.script_pubkey.clone(),
+    });
+
+    let response = post_decode(&server, &base64_encode(&psbt.serialize()));
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.text().unwrap(),
+      format!("PSBT UTXO for input {outpoint} does not match output"),
+    );
+
+    psbt.inputs[0].witness_utxo = Some(prevout.clone());
+
+    let response = post_decode(&server, &base64_encode(&psbt.serialize()));
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.json::<api::Simulation>().unwrap().fee,
+      prevout.value.to_sat() - 1000,
+    );
+
+    let mut psbt = Psbt::from_unsigned_tx(unsigned_tx(outpoint)).unwrap();
+
+    psbt.inputs[0].non_witness_utxo = Some(unsigned_tx(outpoint));
+
+    let response = post_decode(&server, &base64_encode(&psbt.serialize()));
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.text().unwrap(),
+      format!("PSBT non-witness UTXO for input {outpoint} is not transaction {spent}"),
+    );
+
+    let unknown = OutPoint {
+      txid: txid(1),
+      vout: 0,
+    };
+
+    let mut psbt = Psbt::from_unsigned_tx(unsigned_tx(unknown)).unwrap();
+
+    psbt.inputs[0].witness_utxo = Some(TxOut {
+      value: Amount::from_sat(2000),
+      script_pubkey: change(1).script_pubkey(),
+    });
+
+    let response = post_decode(&server, &base64_encode(&psbt.serialize()));
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.text().unwrap(),
+      format!("cannot verify input {unknown}: output is unconfirmed, spent, or does not exist"),
+    );
+  }
+
+  #[test]
+  fn decode_raw_transaction_errors() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    let response = post_decode(&server, "foo");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.text().unwrap(),
+      "request body must be hex or base64 encoded"
+    );
+
+    let response = post_decode(&server, "00");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.text().unwrap(),
+      "request body is not a valid transaction or PSBT"
+    );
+
+    let outpoint = OutPoint {
+      txid: txid(1),
+      vout: 0,
+    };
+
+    let response = post_decode(
+      &server,
+      &consensus::encode::serialize_hex(&Transaction {
+        version: Version(2),
+        lock_time: LockTime::ZERO,
+        input: vec![TxIn {
+          previous_output: outpoint,
+          ..default()
+        }],
+        output: Vec::new(),
+      }),
+    );
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.text().unwrap(),
+      format!("output {outpoint} spent by transaction not found"),
+    );
+  }
+
   #[test]
   fn utxo_recursive_endpoint_only_inscriptions() {
     let server = TestServer::builder().chain(Chain::Regtest).build();
@@ -6602,6 +10225,51 @@ next
       .is_none());
   }
 
+  #[test]
+  fn recursive_sat_endpoints_include_hidden_inscriptions() {
+    let core = mockcore::builder().network(Network::Regtest).build();
+
+    core.mine_blocks(1);
+
+    let mut ids = Vec::new();
+
+    for i in 0..3 {
+      let txid = core.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          i + 1,
+          if i == 0 { 0 } else { 1 },
+          0,
+          inscription("text/plain", "foo").to_witness(),
+        )],
+        ..default()
+      });
+
+      core.mine_blocks(1);
+
+      ids.push(InscriptionId { txid, index: 0 });
+    }
+
+    let server = TestServer::builder()
+      .core(core)
+      .chain(Chain::Regtest)
+      .index_sats()
+      .config(&format!("hidden: [{}]", ids[1]))
+      .build();
+
+    let sat_inscriptions = server.get_json::<api::SatInscriptions>("/r/sat/5000000000");
+
+    assert_eq!(sat_inscriptions.ids, ids);
+
+    for (i, id) in ids.iter().enumerate() {
+      assert_eq!(
+        server
+          .get_json::<api::SatInscription>(format!("/r/sat/5000000000/at/{i}"))
+          .id,
+        Some(*id),
+      );
+    }
+  }
+
   #[test]
   fn children_recursive_endpoint() {
     let server = TestServer::builder().chain(Chain::Regtest).build();
@@ -6692,59 +10360,285 @@ next
 
       inputs.push((i + 2, 1, 0, Witness::default()));
 
-      server.mine_blocks(1);
+      server.mine_blocks(1);
+    }
+
+    inputs.insert(
+      0,
+      (
+        112,
+        0,
+        0,
+        Inscription {
+          content_type: Some("text/plain".into()),
+          body: Some("hello".into()),
+          parents: parent_ids.iter().map(|id| id.value()).collect(),
+          ..default()
+        }
+        .to_witness(),
+      ),
+    );
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &inputs,
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    let first_parent_inscription_id = parent_ids[0];
+    let hundredth_parent_inscription_id = parent_ids[99];
+    let hundred_first_parent_inscription_id = parent_ids[100];
+    let hundred_eleventh_parent_inscription_id = parent_ids[110];
+
+    let parents_json = server.get_json::<api::Inscriptions>(format!("/r/parents/{inscription_id}"));
+
+    assert_eq!(parents_json.ids.len(), 100);
+    assert_eq!(parents_json.ids[0], first_parent_inscription_id);
+    assert_eq!(parents_json.ids[99], hundredth_parent_inscription_id);
+    assert!(parents_json.more);
+    assert_eq!(parents_json.page_index, 0);
+
+    let parents_json =
+      server.get_json::<api::Inscriptions>(format!("/r/parents/{inscription_id}/1"));
+
+    assert_eq!(parents_json.ids.len(), 11);
+    assert_eq!(parents_json.ids[0], hundred_first_parent_inscription_id);
+    assert_eq!(parents_json.ids[10], hundred_eleventh_parent_inscription_id);
+    assert!(!parents_json.more);
+    assert_eq!(parents_json.page_index, 1);
+  }
+
+  #[test]
+  fn child_inscriptions_recursive_endpoint() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let parent_txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    let parent_inscription_id = InscriptionId {
+      txid: parent_txid,
+      index: 0,
+    };
+
+    server.assert_response(
+      format!("/r/children/{parent_inscription_id}/inscriptions"),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {parent_inscription_id} not found"),
+    );
+
+    server.mine_blocks(1);
+
+    let child_inscriptions_json = server.get_json::<api::ChildInscriptions>(format!(
+      "/r/children/{parent_inscription_id}/inscriptions"
+    ));
+    assert_eq!(child_inscriptions_json.children.len(), 0);
+
+    let mut builder = script::Builder::new();
+    for _ in 0..111 {
+      builder = Inscription {
+        content_type: Some("text/plain".into()),
+        body: Some("hello".into()),
+        parents: vec![parent_inscription_id.value()],
+        unrecognized_even_field: false,
+        ..default()
+      }
+      .append_reveal_script_to_builder(builder);
+    }
+
+    let witness = Witness::from_slice(&[builder.into_bytes(), Vec::new()]);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, witness), (2, 1, 0, Default::default())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let first_child_inscription_id = InscriptionId { txid, index: 0 };
+    let hundredth_child_inscription_id = InscriptionId { txid, index: 99 };
+    let hundred_first_child_inscription_id = InscriptionId { txid, index: 100 };
+    let hundred_eleventh_child_inscription_id = InscriptionId { txid, index: 110 };
+
+    let child_inscriptions_json = server.get_json::<api::ChildInscriptions>(format!(
+      "/r/children/{parent_inscription_id}/inscriptions"
+    ));
+
+    assert_eq!(child_inscriptions_json.children.len(), 100);
+
+    assert_eq!(
+      child_inscriptions_json.children[0].id,
+      first_child_inscription_id
+    );
+    assert_eq!(child_inscriptions_json.children[0].number, 1); // parent is #0, 1st child is #1
+
+    assert_eq!(
+      child_inscriptions_json.children[99].id,
+      hundredth_child_inscription_id
+    );
+    assert_eq!(child_inscriptions_json.children[99].number, -99); // all but 1st child are cursed
+
+    assert!(child_inscriptions_json.more);
+    assert_eq!(child_inscriptions_json.page, 0);
+
+    let child_inscriptions_json = server.get_json::<api::ChildInscriptions>(format!(
+      "/r/children/{parent_inscription_id}/inscriptions/1"
+    ));
+
+    assert_eq!(child_inscriptions_json.children.len(), 11);
+
+    assert_eq!(
+      child_inscriptions_json.children[0].id,
+      hundred_first_child_inscription_id
+    );
+    assert_eq!(child_inscriptions_json.children[0].number, -100);
+
+    assert_eq!(
+      child_inscriptions_json.children[10].id,
+      hundred_eleventh_child_inscription_id
+    );
+    assert_eq!(child_inscriptions_json.children[10].number, -110);
+
+    assert!(!child_inscriptions_json.more);
+    assert_eq!(child_inscriptions_json.page, 1);
+  }
+
+  #[test]
+  fn children_recursive_cursor_pagination() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let parent_txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    let parent = InscriptionId {
+      txid: parent_txid,
+      index: 0,
+    };
+
+    server.mine_blocks(1);
+
+    let mut builder = script::Builder::new();
+    for _ in 0..111 {
+      builder = Inscription {
+        content_type: Some("text/plain".into()),
+        body: Some("hello".into()),
+        parents: vec![parent.value()],
+        ..default()
+      }
+      .append_reveal_script_to_builder(builder);
+    }
+
+    let witness = Witness::from_slice(&[builder.into_bytes(), Vec::new()]);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, witness), (2, 1, 0, Default::default())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let children = (0..111)
+      .map(|index| InscriptionId { txid, index })
+      .collect::<Vec<InscriptionId>>();
+
+    let first =
+      server.get_json::<api::Page<InscriptionId>>(format!("/r/children/{parent}?order=oldest"));
+
+    assert_eq!(first.items, children[..100]);
+
+    let next = first.next.unwrap();
+
+    let second = server.get_json::<api::Page<InscriptionId>>(format!(
+      "/r/children/{parent}?order=oldest&cursor={next}"
+    ));
+
+    assert_eq!(second.items, children[100..]);
+    assert_eq!(second.next, None);
+
+    let newest =
+      server.get_json::<api::Page<InscriptionId>>(format!("/r/children/{parent}?order=newest"));
+
+    assert_eq!(newest.items[0], children[110]);
+    assert_eq!(newest.items[99], children[11]);
+
+    let child_inscriptions = server.get_json::<api::Page<api::ChildInscriptionRecursive>>(format!(
+      "/r/children/{parent}/inscriptions?order=newest&cursor={next}"
+    ));
+
+    assert_eq!(child_inscriptions.items.len(), 100);
+    assert_eq!(child_inscriptions.items[0].id, children[100]);
+    assert_eq!(child_inscriptions.items[99].id, children[1]);
+
+    let last = server.get_json::<api::Page<api::ChildInscriptionRecursive>>(format!(
+      "/r/children/{parent}/inscriptions?order=newest&cursor={}",
+      child_inscriptions.next.unwrap(),
+    ));
+
+    assert_eq!(last.items.len(), 1);
+    assert_eq!(last.items[0].id, children[0]);
+    assert_eq!(last.next, None);
+  }
+
+  #[test]
+  fn inscriptions_cursor_pages_do_not_shift() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let mut builder = script::Builder::new();
+    for _ in 0..111 {
+      builder = inscription("text/plain", "hello").append_reveal_script_to_builder(builder);
     }
 
-    inputs.insert(
-      0,
-      (
-        112,
-        0,
-        0,
-        Inscription {
-          content_type: Some("text/plain".into()),
-          body: Some("hello".into()),
-          parents: parent_ids.iter().map(|id| id.value()).collect(),
-          ..default()
-        }
-        .to_witness(),
-      ),
-    );
+    let witness = Witness::from_slice(&[builder.into_bytes(), Vec::new()]);
 
     let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &inputs,
+      inputs: &[(1, 0, 0, witness)],
       ..default()
     });
 
     server.mine_blocks(1);
 
-    let inscription_id = InscriptionId { txid, index: 0 };
+    let first = server.get_json::<api::Page<InscriptionId>>("/inscriptions?order=newest");
 
-    let first_parent_inscription_id = parent_ids[0];
-    let hundredth_parent_inscription_id = parent_ids[99];
-    let hundred_first_parent_inscription_id = parent_ids[100];
-    let hundred_eleventh_parent_inscription_id = parent_ids[110];
+    assert_eq!(first.items.len(), 100);
+    assert_eq!(first.items[0], InscriptionId { txid, index: 110 });
 
-    let parents_json = server.get_json::<api::Inscriptions>(format!("/r/parents/{inscription_id}"));
+    let next = first.next.unwrap();
 
-    assert_eq!(parents_json.ids.len(), 100);
-    assert_eq!(parents_json.ids[0], first_parent_inscription_id);
-    assert_eq!(parents_json.ids[99], hundredth_parent_inscription_id);
-    assert!(parents_json.more);
-    assert_eq!(parents_json.page_index, 0);
+    let second =
+      server.get_json::<api::Page<InscriptionId>>(format!("/inscriptions?cursor={next}"));
 
-    let parents_json =
-      server.get_json::<api::Inscriptions>(format!("/r/parents/{inscription_id}/1"));
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "new").to_witness())],
+      ..default()
+    });
 
-    assert_eq!(parents_json.ids.len(), 11);
-    assert_eq!(parents_json.ids[0], hundred_first_parent_inscription_id);
-    assert_eq!(parents_json.ids[10], hundred_eleventh_parent_inscription_id);
-    assert!(!parents_json.more);
-    assert_eq!(parents_json.page_index, 1);
+    server.mine_blocks(1);
+
+    assert_eq!(
+      server.get_json::<api::Page<InscriptionId>>(format!("/inscriptions?cursor={next}")),
+      second,
+    );
+
+    assert_eq!(second.items.len(), 11);
+    assert_eq!(second.items[10], InscriptionId { txid, index: 0 });
+    assert_eq!(second.next, None);
+
+    let oldest = server.get_json::<api::Page<InscriptionId>>("/inscriptions?order=oldest");
+
+    assert_eq!(oldest.items[0], InscriptionId { txid, index: 0 });
   }
 
   #[test]
-  fn child_inscriptions_recursive_endpoint() {
+  fn children_collections_and_block_cursor_pagination() {
     let server = TestServer::builder().chain(Chain::Regtest).build();
     server.mine_blocks(1);
 
@@ -6753,31 +10647,19 @@ next
       ..default()
     });
 
-    let parent_inscription_id = InscriptionId {
+    let parent = InscriptionId {
       txid: parent_txid,
       index: 0,
     };
 
-    server.assert_response(
-      format!("/r/children/{parent_inscription_id}/inscriptions"),
-      StatusCode::NOT_FOUND,
-      &format!("inscription {parent_inscription_id} not found"),
-    );
-
     server.mine_blocks(1);
 
-    let child_inscriptions_json = server.get_json::<api::ChildInscriptions>(format!(
-      "/r/children/{parent_inscription_id}/inscriptions"
-    ));
-    assert_eq!(child_inscriptions_json.children.len(), 0);
-
     let mut builder = script::Builder::new();
     for _ in 0..111 {
       builder = Inscription {
         content_type: Some("text/plain".into()),
         body: Some("hello".into()),
-        parents: vec![parent_inscription_id.value()],
-        unrecognized_even_field: false,
+        parents: vec![parent.value()],
         ..default()
       }
       .append_reveal_script_to_builder(builder);
@@ -6792,52 +10674,188 @@ next
 
     server.mine_blocks(1);
 
-    let first_child_inscription_id = InscriptionId { txid, index: 0 };
-    let hundredth_child_inscription_id = InscriptionId { txid, index: 99 };
-    let hundred_first_child_inscription_id = InscriptionId { txid, index: 100 };
-    let hundred_eleventh_child_inscription_id = InscriptionId { txid, index: 110 };
+    let children = (0..111)
+      .map(|index| InscriptionId { txid, index })
+      .collect::<Vec<InscriptionId>>();
 
-    let child_inscriptions_json = server.get_json::<api::ChildInscriptions>(format!(
-      "/r/children/{parent_inscription_id}/inscriptions"
+    let first =
+      server.get_json::<api::Page<InscriptionId>>(format!("/children/{parent}?order=oldest"));
+
+    assert_eq!(first.items, children[..100]);
+
+    let second = server.get_json::<api::Page<InscriptionId>>(format!(
+      "/children/{parent}?cursor={}",
+      first.next.unwrap(),
     ));
 
-    assert_eq!(child_inscriptions_json.children.len(), 100);
+    assert_eq!(second.items, children[100..]);
+    assert_eq!(second.next, None);
+
+    let first = server.get_json::<api::Page<InscriptionId>>("/inscriptions/block/3?order=oldest");
+
+    assert_eq!(first.items, children[..100]);
+
+    let second = server.get_json::<api::Page<InscriptionId>>(format!(
+      "/inscriptions/block/3?cursor={}",
+      first.next.unwrap(),
+    ));
+
+    assert_eq!(second.items, children[100..]);
+    assert_eq!(second.next, None);
+
+    let newest = server.get_json::<api::Page<InscriptionId>>("/inscriptions/block/3?order=newest");
+
+    assert_eq!(newest.items[0], children[110]);
+    assert_eq!(newest.items[99], children[11]);
+
+    let last = server.get_json::<api::Page<InscriptionId>>(format!(
+      "/inscriptions/block/3?order=newest&cursor={}",
+      newest.next.unwrap(),
+    ));
 
     assert_eq!(
-      child_inscriptions_json.children[0].id,
-      first_child_inscription_id
+      last.items,
+      children[..11].iter().rev().copied().collect::<Vec<_>>()
     );
-    assert_eq!(child_inscriptions_json.children[0].number, 1); // parent is #0, 1st child is #1
+    assert_eq!(last.next, None);
 
     assert_eq!(
-      child_inscriptions_json.children[99].id,
-      hundredth_child_inscription_id
+      server.get_json::<api::Page<InscriptionId>>("/inscriptions/block/2?order=oldest"),
+      api::Page {
+        items: vec![parent],
+        next: None,
+      }
     );
-    assert_eq!(child_inscriptions_json.children[99].number, -99); // all but 1st child are cursed
 
-    assert!(child_inscriptions_json.more);
-    assert_eq!(child_inscriptions_json.page, 0);
+    assert_eq!(
+      server.get_json::<api::Page<InscriptionId>>("/collections?order=newest"),
+      api::Page {
+        items: vec![parent],
+        next: None,
+      }
+    );
+  }
 
-    let child_inscriptions_json = server.get_json::<api::ChildInscriptions>(format!(
-      "/r/children/{parent_inscription_id}/inscriptions/1"
-    ));
+  #[test]
+  fn parents_recursive_cursor_pagination() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(2);
 
-    assert_eq!(child_inscriptions_json.children.len(), 11);
+    let parent_a = InscriptionId {
+      txid: server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..default()
+      }),
+      index: 0,
+    };
+
+    server.mine_blocks(1);
+
+    let parent_b = InscriptionId {
+      txid: server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(2, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..default()
+      }),
+      index: 0,
+    };
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (
+          4,
+          0,
+          0,
+          Inscription {
+            content_type: Some("text/plain".into()),
+            body: Some("hello".into()),
+            parents: vec![parent_b.value(), parent_a.value()],
+            ..default()
+          }
+          .to_witness(),
+        ),
+        (3, 1, 0, Default::default()),
+        (4, 1, 0, Default::default()),
+      ],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let child = InscriptionId { txid, index: 0 };
 
     assert_eq!(
-      child_inscriptions_json.children[0].id,
-      hundred_first_child_inscription_id
+      server
+        .get_json::<api::Inscriptions>(format!("/r/parents/{child}"))
+        .ids,
+      [parent_b, parent_a],
     );
-    assert_eq!(child_inscriptions_json.children[0].number, -100);
 
     assert_eq!(
-      child_inscriptions_json.children[10].id,
-      hundred_eleventh_child_inscription_id
+      server.get_json::<api::Page<InscriptionId>>(format!("/r/parents/{child}?order=oldest")),
+      api::Page {
+        items: vec![parent_a, parent_b],
+        next: None,
+      }
     );
-    assert_eq!(child_inscriptions_json.children[10].number, -110);
 
-    assert!(!child_inscriptions_json.more);
-    assert_eq!(child_inscriptions_json.page, 1);
+    let cursor = Cursor::SequenceNumber(1);
+
+    assert_eq!(
+      server.get_json::<api::Page<InscriptionId>>(format!(
+        "/r/parents/{child}?order=newest&cursor={cursor}"
+      )),
+      api::Page {
+        items: vec![parent_b, parent_a],
+        next: None,
+      }
+    );
+
+    assert_eq!(
+      server.get_json::<api::Page<InscriptionId>>(format!(
+        "/r/parents/{child}?order=newest&cursor={}",
+        Cursor::SequenceNumber(0),
+      )),
+      api::Page {
+        items: vec![parent_a],
+        next: None,
+      }
+    );
+  }
+
+  #[test]
+  fn invalid_cursors_are_rejected() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    let rune_cursor = Cursor::RuneId(RuneId { block: 1, tx: 0 });
+
+    let response = reqwest::blocking::Client::new()
+      .get(server.join_url(&format!("/inscriptions?cursor={rune_cursor}")))
+      .header(header::ACCEPT, "application/json")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = reqwest::blocking::Client::new()
+      .get(server.join_url("/runes?cursor=foo"))
+      .header(header::ACCEPT, "application/json")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    assert_eq!(
+      server.get_json::<api::Page<(RuneId, RuneEntry)>>("/runes?order=oldest"),
+      api::Page {
+        items: Vec::new(),
+        next: None,
+      }
+    );
   }
 
   #[test]
@@ -7079,6 +11097,127 @@ next
     server_with_proxy.assert_response(format!("/content/{id}"), StatusCode::OK, "foo");
   }
 
+  #[test]
+  fn batch_recursive() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+
+    server.mine_blocks(1);
+
+    let mut metadata = Vec::new();
+    ciborium::into_writer("bar", &mut metadata).unwrap();
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        Inscription {
+          content_type: Some("text/plain".into()),
+          body: Some("foo".into()),
+          metadata: Some(metadata.clone()),
+          ..default()
+        }
+        .to_witness(),
+      )],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    let missing = InscriptionId { txid, index: 1 };
+
+    let requests = vec![
+      format!("/r/inscription/{id}"),
+      format!("/r/metadata/{id}"),
+      "/r/sat/5000000000".to_string(),
+      "/r/blockheight".to_string(),
+      format!("/r/inscription/{missing}"),
+    ];
+
+    let expected = vec![
+      api::BatchResponse {
+        body: serde_json::to_value(
+          server.get_json::<api::InscriptionRecursive>(format!("/r/inscription/{id}")),
+        )
+        .unwrap(),
+        status: 200,
+      },
+      api::BatchResponse {
+        body: serde_json::Value::String(hex::encode(&metadata)),
+        status: 200,
+      },
+      api::BatchResponse {
+        body: serde_json::to_value(api::SatInscriptions {
+          ids: vec![id],
+          more: false,
+          page: 0,
+        })
+        .unwrap(),
+        status: 200,
+      },
+      api::BatchResponse {
+        body: serde_json::Value::String("2".into()),
+        status: 200,
+      },
+      api::BatchResponse {
+        body: serde_json::Value::String(format!("inscription {missing} not found")),
+        status: 404,
+      },
+    ];
+
+    let response = reqwest::blocking::Client::new()
+      .post(server.join_url("/r/batch"))
+      .json(&requests)
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    pretty_assert_eq!(
+      response.json::<Vec<api::BatchResponse>>().unwrap(),
+      expected
+    );
+
+    pretty_assert_eq!(
+      server
+        .get_json::<Vec<api::BatchResponse>>(format!("/r/batch?requests={}", requests.join(","))),
+      expected
+    );
+  }
+
+  #[test]
+  fn batch_recursive_rejects_invalid_requests() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.assert_response(
+      "/r/batch?requests=/content/foo",
+      StatusCode::BAD_REQUEST,
+      "batch request `/content/foo` is not a recursive endpoint",
+    );
+
+    server.assert_response(
+      format!(
+        "/r/batch?requests={}",
+        vec!["/r/blockheight"; RECURSIVE_BATCH_LIMIT + 1].join(",")
+      ),
+      StatusCode::BAD_REQUEST,
+      &format!(
+        "batch of {} requests exceeds limit of {RECURSIVE_BATCH_LIMIT}",
+        RECURSIVE_BATCH_LIMIT + 1
+      ),
+    );
+
+    assert_eq!(
+      server.get_json::<Vec<api::BatchResponse>>("/r/batch?requests="),
+      Vec::new()
+    );
+  }
+
   #[test]
   fn metadata_proxy() {
     let server = TestServer::builder().chain(Chain::Regtest).build();
@@ -7429,6 +11568,466 @@ next
     );
   }
 
+  #[test]
+  fn inscriptions_can_be_hidden_with_admin_api() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_option("--admin-token", "secret")
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("image/png", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let inscription = InscriptionId { txid, index: 0 };
+
+    assert!(server
+      .get("/")
+      .text()
+      .unwrap()
+      .contains(&inscription.to_string()));
+
+    let client = reqwest::blocking::Client::new();
+
+    let url = server.join_url(&format!("/admin/moderation/{inscription}"));
+
+    assert_eq!(
+      client
+        .put(url.clone())
+        .json(&api::ModerationRequest {
+          reason: "spam".into(),
+        })
+        .send()
+        .unwrap()
+        .status(),
+      StatusCode::UNAUTHORIZED,
+    );
+
+    assert_eq!(
+      client
+        .put(url.clone())
+        .bearer_auth("secret")
+        .json(&api::ModerationRequest {
+          reason: "spam".into(),
+        })
+        .send()
+        .unwrap()
+        .status(),
+      StatusCode::CREATED,
+    );
+
+    let entries = client
+      .get(server.join_url("/admin/moderation"))
+      .bearer_auth("secret")
+      .send()
+      .unwrap()
+      .json::<BTreeMap<InscriptionId, api::ModerationEntry>>()
+      .unwrap();
+
+    assert_eq!(entries[&inscription].reason, "spam");
+
+    server.assert_response_regex(
+      format!("/content/{inscription}"),
+      StatusCode::OK,
+      PreviewUnknownHtml.to_string(),
+    );
+
+    server.assert_response_regex(
+      format!("/preview/{inscription}"),
+      StatusCode::OK,
+      PreviewUnknownHtml.to_string(),
+    );
+
+    assert!(!server
+      .get("/")
+      .text()
+      .unwrap()
+      .contains(&inscription.to_string()));
+
+    assert!(!server
+      .get("/feed.xml")
+      .text()
+      .unwrap()
+      .contains(&inscription.to_string()));
+
+    assert_eq!(
+      server.get_json::<api::Inscriptions>("/inscriptions").ids,
+      Vec::new(),
+    );
+
+    assert_eq!(
+      client
+        .delete(url.clone())
+        .bearer_auth("secret")
+        .send()
+        .unwrap()
+        .status(),
+      StatusCode::OK,
+    );
+
+    assert_eq!(
+      client
+        .delete(url)
+        .bearer_auth("secret")
+        .send()
+        .unwrap()
+        .status(),
+      StatusCode::NOT_FOUND,
+    );
+
+    server.assert_response(format!("/content/{inscription}"), StatusCode::OK, "hello");
+
+    assert_eq!(
+      server.get_json::<api::Inscriptions>("/inscriptions").ids,
+      vec![inscription],
+    );
+  }
+
+  #[test]
+  fn additional_chains_are_served_by_path_prefix_and_host() {
+    let signet = mockcore::builder().network(Network::Signet).build();
+
+    let tempdir = TempDir::new().unwrap();
+
+    let cookie_file = tempdir.path().join("cookie");
+    fs::write(&cookie_file, "username:password").unwrap();
+
+    let config = tempdir.path().join("signet.yaml");
+    fs::write(
+      &config,
+      format!(
+        "chain: signet\nbitcoin_rpc_url: {}\ncookie_file: {}\ndata_dir: {}\n",
+        signet.url(),
+        cookie_file.display(),
+        tempdir.path().display(),
+      ),
+    )
+    .unwrap();
+
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_option("--chain-config", config.to_str().unwrap())
+      .server_option("--chain-host", "signet=signet.example.com")
+      .server_option("--admin-token", "regtest-secret")
+      .server_option("--chain-admin-token", "signet=signet-secret")
+      .build();
+
+    let signet_genesis = Chain::Signet.genesis_block().block_hash().to_string();
+
+    for i in 0.. {
+      let response = server.get("/signet/blockhash/0");
+
+      if response.status() == StatusCode::OK {
+        assert_eq!(response.text().unwrap(), signet_genesis);
+        break;
+      }
+
+      assert!(i < 100, "signet index was not updated");
+
+      thread::sleep(Duration::from_millis(100));
+    }
+
+    server.assert_response(
+      "/blockhash/0",
+      StatusCode::OK,
+      &Chain::Regtest.genesis_block().block_hash().to_string(),
+    );
+
+    let response = reqwest::blocking::Client::new()
+      .get(server.join_url("/blockhash/0"))
+      .header(header::HOST, "Signet.Example.com:8080")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.text().unwrap(), signet_genesis);
+
+    let prefixed = server.get("/signet/blocks").text().unwrap();
+
+    assert!(prefixed.contains("<a href=/signet/block/0>"));
+    assert!(prefixed.contains("<a href=/signet title=home>"));
+    assert!(prefixed.contains("<form action=/signet/search method=get>"));
+    assert!(!prefixed.contains("href=/block/"));
+
+    assert_eq!(server.get("/signet").status(), StatusCode::OK);
+
+    server.assert_redirect("/signet/search?query=0", "/signet/inscription/0");
+    server.assert_redirect("/signet/search/0", "/signet/inscription/0");
+    server.assert_redirect("/search?query=0", "/inscription/0");
+    server.assert_redirect("/signet/ordinal/0", "/signet/sat/0");
+
+    let hosted = reqwest::blocking::Client::new()
+      .get(server.join_url("/blocks"))
+      .header(header::HOST, "signet.example.com")
+      .send()
+      .unwrap()
+      .text()
+      .unwrap();
+
+    assert!(hosted.contains("<a href=/block/0>"));
+    assert!(!hosted.contains("/signet/"));
+
+    let admin = |path: &str, token: &str| {
+      reqwest::blocking::Client::new()
+        .get(server.join_url(path))
+        .bearer_auth(token)
+        .send()
+        .unwrap()
+        .status()
+    };
+
+    assert_eq!(admin("/admin/moderation", "regtest-secret"), StatusCode::OK);
+    assert_eq!(
+      admin("/admin/moderation", "signet-secret"),
+      StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+      admin("/signet/admin/moderation", "signet-secret"),
+      StatusCode::OK
+    );
+    assert_eq!(
+      admin("/signet/admin/moderation", "regtest-secret"),
+      StatusCode::UNAUTHORIZED
+    );
+  }
+
+  #[test]
+  fn admin_endpoints_require_admin_token() {
+    TestServer::new().assert_response("/admin/moderation", StatusCode::NOT_FOUND, "");
+  }
+
+  #[test]
+  fn broadcast_refuses_to_lose_inscriptions() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .server_option("--broadcast-token", "foo")
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let value = server.index.get_transaction(txid).unwrap().unwrap().output[0]
+      .value
+      .to_sat();
+
+    let transaction = |output_value: u64| {
+      consensus::encode::serialize_hex(&Transaction {
+        version: Version(2),
+        lock_time: LockTime::ZERO,
+        input: vec![TxIn {
+          previous_output: OutPoint { txid, vout: 0 },
+          ..default()
+        }],
+        output: vec![TxOut {
+          value: Amount::from_sat(output_value),
+          script_pubkey: change(0).script_pubkey(),
+        }],
+      })
+    };
+
+    let broadcast = |path: &str, token: Option<&str>, body: String| {
+      let mut request = reqwest::blocking::Client::new()
+        .post(server.join_url(path))
+        .body(body);
+
+      if let Some(token) = token {
+        request = request.bearer_auth(token);
+      }
+
+      request.send().unwrap()
+    };
+
+    let response = broadcast("/tx", None, transaction(value));
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = broadcast("/tx", Some("bar"), transaction(value));
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = broadcast("/tx", Some("foo"), transaction(0));
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.text().unwrap(),
+      format!(
+        "refusing to broadcast transaction: inscription {txid}i0 would be sent to fees. \
+        Use `?force=true` to broadcast anyway."
+      ),
+    );
+
+    assert!(server.core.mempool().is_empty());
+
+    let response = broadcast("/tx", Some("foo"), transaction(value - 1000));
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let mempool = server.core.mempool();
+    assert_eq!(mempool.len(), 1);
+    assert_eq!(
+      response.text().unwrap(),
+      mempool[0].compute_txid().to_string()
+    );
+
+    let response = broadcast("/tx?force=true", Some("foo"), transaction(0));
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(server.core.mempool().len(), 2);
+  }
+
+  #[test]
+  fn broadcast_refuses_unchecked_transactions() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_option("--broadcast-token", "foo")
+      .build();
+
+    server.mine_blocks(1);
+
+    let coinbase = server.index.get_block_by_height(1).unwrap().unwrap().txdata[0].clone();
+
+    let transaction = |previous_output| {
+      consensus::encode::serialize_hex(&Transaction {
+        version: Version(2),
+        lock_time: LockTime::ZERO,
+        input: vec![TxIn {
+          previous_output,
+          ..default()
+        }],
+        output: vec![TxOut {
+          value: Amount::from_sat(1000),
+          script_pubkey: change(0).script_pubkey(),
+        }],
+      })
+    };
+
+    let broadcast = |body: String| {
+      reqwest::blocking::Client::new()
+        .post(server.join_url("/tx"))
+        .bearer_auth("foo")
+        .body(body)
+        .send()
+        .unwrap()
+    };
+
+    let response = broadcast(transaction(OutPoint {
+      txid: coinbase.compute_txid(),
+      vout: 0,
+    }));
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.text().unwrap(),
+      "refusing to broadcast transaction: runes cannot be checked without a rune index. \
+      Use `?force=true` to broadcast anyway.",
+    );
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      ..default()
+    });
+
+    let outpoint = OutPoint { txid, vout: 0 };
+
+    let response = broadcast(transaction(outpoint));
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.text().unwrap(),
+      format!("cannot verify input {outpoint}: output is unconfirmed, spent, or does not exist"),
+    );
+
+    assert_eq!(server.core.mempool().len(), 1);
+  }
+
+  #[test]
+  fn broadcast_refuses_cenotaphs() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .server_option("--broadcast-token", "foo")
+      .build();
+
+    let (txid, id) = server.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: 1000,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          premine: Some(1000),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    let value = server.index.get_transaction(txid).unwrap().unwrap().output[0]
+      .value
+      .to_sat();
+
+    let response = reqwest::blocking::Client::new()
+      .post(server.join_url("/tx"))
+      .bearer_auth("foo")
+      .body(consensus::encode::serialize_hex(&Transaction {
+        version: Version(2),
+        lock_time: LockTime::ZERO,
+        input: vec![TxIn {
+          previous_output: OutPoint { txid, vout: 0 },
+          ..default()
+        }],
+        output: vec![
+          TxOut {
+            value: Amount::from_sat(value),
+            script_pubkey: change(0).script_pubkey(),
+          },
+          TxOut {
+            value: Amount::ZERO,
+            script_pubkey: Runestone {
+              edicts: vec![Edict {
+                id,
+                amount: 1,
+                output: 5,
+              }],
+              ..default()
+            }
+            .encipher(),
+          },
+        ],
+      }))
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.text().unwrap(),
+      "refusing to broadcast transaction: runestone is a cenotaph, \
+      1000\u{A0}¤ of AAAAAAAAAAAAA would be burned. Use `?force=true` to broadcast anyway.",
+    );
+  }
+
+  #[test]
+  fn broadcast_endpoint_requires_broadcast_token() {
+    let server = TestServer::new();
+
+    let response = reqwest::blocking::Client::new()
+      .post(server.join_url("/tx"))
+      .body("00")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+  }
+
   #[test]
   fn update_endpoint_is_not_available_when_not_in_integration_test_mode() {
     let server = TestServer::builder().build();
@@ -0,0 +1,58 @@
+use super::*;
+
+/// A per-chain server option, of the form `<CHAIN>=<VALUE>`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ChainValue<T> {
+  pub(crate) chain: Chain,
+  pub(crate) value: T,
+}
+
+impl<T> FromStr for ChainValue<T>
+where
+  T: FromStr,
+  T::Err: std::error::Error + Send + Sync + 'static,
+{
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let (chain, value) = s
+      .split_once('=')
+      .ok_or_else(|| anyhow!("argument must be of the form `<CHAIN>=<VALUE>`"))?;
+
+    ensure!(!value.is_empty(), "value must not be empty");
+
+    Ok(Self {
+      chain: chain.parse()?,
+      value: value.parse()?,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn from_str() {
+    assert_eq!(
+      "signet=Secret".parse::<ChainValue<String>>().unwrap(),
+      ChainValue {
+        chain: Chain::Signet,
+        value: "Secret".to_string(),
+      }
+    );
+
+    assert_eq!(
+      "signet=50001".parse::<ChainValue<u16>>().unwrap(),
+      ChainValue {
+        chain: Chain::Signet,
+        value: 50001,
+      }
+    );
+
+    assert!("signet".parse::<ChainValue<String>>().is_err());
+    assert!("signet=".parse::<ChainValue<String>>().is_err());
+    assert!("foo=secret".parse::<ChainValue<String>>().is_err());
+    assert!("signet=foo".parse::<ChainValue<u16>>().is_err());
+  }
+}
@@ -0,0 +1,391 @@
+use {
+  super::*,
+  bitcoin::hashes::sha256,
+  serde::de::DeserializeOwned,
+  serde_json::{json, Value},
+  tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+  },
+};
+
+const MAX_SUBSCRIPTIONS: usize = 10_000;
+const PROTOCOL_VERSION: &str = "1.4";
+
+/// Serves a subset of the Electrum protocol, newline-delimited JSON-RPC over
+/// TCP, backed by the address and script hash indexes. Only confirmed
+/// transactions are indexed, so unconfirmed balances are always zero and
+/// histories never include mempool transactions.
+pub(super) async fn serve(
+  listener: TcpListener,
+  index: Arc<Index>,
+  polling_interval: Duration,
+) -> io::Result<()> {
+  loop {
+    let (stream, peer) = listener.accept().await?;
+
+    let index = index.clone();
+
+    tokio::spawn(async move {
+      if let Err(err) = Session::new(index).run(stream, polling_interval).await {
+        log::debug!("Electrum session with {peer} failed: {err}");
+      }
+    });
+  }
+}
+
+#[derive(Debug, Deserialize)]
+struct Request {
+  #[serde(default)]
+  id: Value,
+  method: String,
+  #[serde(default)]
+  params: Vec<Value>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Unspent {
+  pub(crate) height: u32,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub(crate) inscriptions: Option<Vec<InscriptionId>>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub(crate) runes: Option<BTreeMap<SpacedRune, Pile>>,
+  pub(crate) tx_hash: Txid,
+  pub(crate) tx_pos: u32,
+  pub(crate) value: u64,
+}
+
+enum RpcError {
+  Internal(Error),
+  InvalidParams(String),
+  MethodNotFound(String),
+}
+
+impl<T: Into<Error>> From<T> for RpcError {
+  fn from(error: T) -> Self {
+    Self::Internal(error.into())
+  }
+}
+
+impl RpcError {
+  fn to_json(&self) -> Value {
+    let (code, message) = match self {
+      Self::Internal(err) => (-32603, err.to_string()),
+      Self::InvalidParams(message) => (-32602, message.clone()),
+      Self::MethodNotFound(method) => (-32601, format!("unknown method `{method}`")),
+    };
+
+    json!({ "code": code, "message": message })
+  }
+}
+
+struct Session {
+  height: Option<u32>,
+  headers: bool,
+  index: Arc<Index>,
+  script_hashes: BTreeMap<String, ([u8; 32], Option<String>)>,
+}
+
+impl Session {
+  fn new(index: Arc<Index>) -> Self {
+    Self {
+      height: None,
+      headers: false,
+      index,
+      script_hashes: BTreeMap::new(),
+    }
+  }
+
+  async fn run(mut self, stream: TcpStream, polling_interval: Duration) -> Result {
+    self.height = self.index.block_height()?.map(|height| height.n());
+
+    let (reader, mut writer) = stream.into_split();
+
+    let mut lines = BufReader::new(reader).lines();
+
+    let mut interval = tokio::time::interval(polling_interval);
+
+    loop {
+      let messages = tokio::select! {
+        line = lines.next_line() => {
+          let Some(line) = line? else {
+            return Ok(());
+          };
+
+          if line.trim().is_empty() {
+            continue;
+          }
+
+          vec![task::block_in_place(|| self.respond(&line))]
+        }
+        _ = interval.tick() => match task::block_in_place(|| self.notifications()) {
+          Ok(notifications) => notifications,
+          Err(err) => {
+            log::warn!("failed to compute Electrum notifications: {err}");
+            continue;
+          }
+        },
+      };
+
+      for message in messages {
+        let mut line = serde_json::to_vec(&message)?;
+        line.push(b'\n');
+        writer.write_all(&line).await?;
+      }
+    }
+  }
+
+  fn respond(&mut self, line: &str) -> Value {
+    let request = match serde_json::from_str::<Request>(line) {
+      Ok(request) => request,
+      Err(err) => {
+        return json!({
+          "jsonrpc": "2.0",
+          "id": Value::Null,
+          "error": { "code": -32700, "message": err.to_string() },
+        })
+      }
+    };
+
+    match self.call(&request.method, &request.params) {
+      Ok(result) => json!({ "jsonrpc": "2.0", "id": request.id, "result": result }),
+      Err(err) => json!({ "jsonrpc": "2.0", "id": request.id, "error": err.to_json() }),
+    }
+  }
+
+  fn call(&mut self, method: &str, params: &[Value]) -> Result<Value, RpcError> {
+    match method {
+      "blockchain.block.header" => {
+        let height = param::<u32>(params, 0)?;
+
+        let hash = self
+          .index
+          .block_hash(Some(height))?
+          .ok_or_else(|| RpcError::InvalidParams(format!("no block at height {height}")))?;
+
+        Ok(self.header_hex(hash)?.into())
+      }
+      "blockchain.headers.subscribe" => {
+        self.headers = true;
+        Ok(self.tip()?.unwrap_or(Value::Null))
+      }
+      "blockchain.scripthash.get_balance" => {
+        let confirmed = self
+          .unspent(&script_hash(params)?.1)?
+          .iter()
+          .map(|unspent| unspent.value)
+          .sum::<u64>();
+
+        Ok(json!({ "confirmed": confirmed, "unconfirmed": 0 }))
+      }
+      "blockchain.scripthash.get_history" => Ok(
+        self
+          .history(&script_hash(params)?.1)?
+          .into_iter()
+          .map(|(height, txid)| json!({ "height": height, "tx_hash": txid }))
+          .collect::<Vec<Value>>()
+          .into(),
+      ),
+      "blockchain.scripthash.listunspent" => Ok(serde_json::to_value(
+        self.unspent(&script_hash(params)?.1)?,
+      )?),
+      "blockchain.scripthash.subscribe" => {
+        let (key, script_hash) = script_hash(params)?;
+
+        if !self.script_hashes.contains_key(&key) && self.script_hashes.len() >= MAX_SUBSCRIPTIONS {
+          return Err(RpcError::InvalidParams(format!(
+            "too many subscriptions, at most {MAX_SUBSCRIPTIONS} are allowed per session"
+          )));
+        }
+
+        let status = Self::status(&self.history(&script_hash)?);
+        self
+          .script_hashes
+          .insert(key, (script_hash, status.clone()));
+        Ok(status.into())
+      }
+      "blockchain.scripthash.unsubscribe" => Ok(
+        self
+          .script_hashes
+          .remove(&param::<String>(params, 0)?)
+          .is_some()
+          .into(),
+      ),
+      "blockchain.transaction.broadcast" => {
+        let transaction =
+          consensus::encode::deserialize_hex::<Transaction>(&param::<String>(params, 0)?)
+            .map_err(|err| RpcError::InvalidParams(format!("invalid transaction: {err}")))?;
+
+        Ok(
+          self
+            .index
+            .send_raw_transaction(&transaction)?
+            .to_string()
+            .into(),
+        )
+      }
+      "blockchain.transaction.get" => {
+        let txid = param::<Txid>(params, 0)?;
+
+        if params.get(1).and_then(Value::as_bool).unwrap_or_default() {
+          return Err(RpcError::InvalidParams(
+            "verbose transactions are not supported".into(),
+          ));
+        }
+
+        let transaction = self
+          .index
+          .get_transaction(txid)?
+          .ok_or_else(|| RpcError::InvalidParams(format!("transaction {txid} not found")))?;
+
+        Ok(consensus::encode::serialize_hex(&transaction).into())
+      }
+      "server.ping" => Ok(Value::Null),
+      "server.version" => Ok(json!([
+        format!("ord {}", env!("CARGO_PKG_VERSION")),
+        PROTOCOL_VERSION
+      ])),
+      _ => Err(RpcError::MethodNotFound(method.into())),
+    }
+  }
+
+  fn header_hex(&self, hash: BlockHash) -> Result<String> {
+    let header = self
+      .index
+      .block_header(hash)?
+      .ok_or_else(|| anyhow!("block header {hash} not found"))?;
+
+    Ok(consensus::encode::serialize_hex(&header))
+  }
+
+  fn tip(&self) -> Result<Option<Value>> {
+    let Some(height) = self.index.block_height()? else {
+      return Ok(None);
+    };
+
+    let Some(hash) = self.index.block_hash(Some(height.n()))? else {
+      return Ok(None);
+    };
+
+    Ok(Some(json!({
+      "height": height.n(),
+      "hex": self.header_hex(hash)?,
+    })))
+  }
+
+  /// Returns notifications for subscriptions which have changed since the last
+  /// block was indexed.
+  fn notifications(&mut self) -> Result<Vec<Value>> {
+    let height = self.index.block_height()?.map(|height| height.n());
+
+    if height == self.height {
+      return Ok(Vec::new());
+    }
+
+    let mut notifications = Vec::new();
+
+    if self.headers {
+      if let Some(tip) = self.tip()? {
+        notifications.push(json!({
+          "jsonrpc": "2.0",
+          "method": "blockchain.headers.subscribe",
+          "params": [tip],
+        }));
+      }
+    }
+
+    let mut changed = Vec::new();
+
+    for (key, (script_hash, status)) in &self.script_hashes {
+      let new = Self::status(&self.history(script_hash)?);
+
+      if *status != new {
+        changed.push((key.clone(), new));
+      }
+    }
+
+    for (key, status) in changed {
+      notifications.push(json!({
+        "jsonrpc": "2.0",
+        "method": "blockchain.scripthash.subscribe",
+        "params": [key, status],
+      }));
+
+      self.script_hashes.get_mut(&key).unwrap().1 = status;
+    }
+
+    self.height = height;
+
+    Ok(notifications)
+  }
+
+  /// Script hash status, the hash of the concatenated `tx_hash:height:`
+  /// history entries, or `None` if the script hash has no history.
+  fn status(history: &[(u32, Txid)]) -> Option<String> {
+    if history.is_empty() {
+      return None;
+    }
+
+    let mut preimage = String::new();
+
+    for (height, txid) in history {
+      preimage.push_str(&format!("{txid}:{height}:"));
+    }
+
+    Some(sha256::Hash::hash(preimage.as_bytes()).to_string())
+  }
+
+  fn history(&self, script_hash: &[u8; 32]) -> Result<Vec<(u32, Txid)>> {
+    self.index.get_script_hash_history(script_hash)
+  }
+
+  fn unspent(&self, script_hash: &[u8; 32]) -> Result<Vec<Unspent>> {
+    let Some(script_pubkey) = self.index.get_script_pubkey_for_script_hash(script_hash)? else {
+      return Ok(Vec::new());
+    };
+
+    let outputs = self.index.get_outputs_for_script_pubkey(&script_pubkey)?;
+
+    let mut unspent = Vec::new();
+
+    for (outpoint, value, height, _header) in self.index.get_received_outputs(&outputs)? {
+      unspent.push(Unspent {
+        height,
+        inscriptions: self.index.get_inscriptions_for_output(outpoint)?,
+        runes: self.index.get_rune_balances_for_output(outpoint)?,
+        tx_hash: outpoint.txid,
+        tx_pos: outpoint.vout,
+        value,
+      });
+    }
+
+    unspent.sort_by_key(|unspent| (unspent.height, unspent.tx_hash, unspent.tx_pos));
+
+    Ok(unspent)
+  }
+}
+
+/// Parses the script hash parameter, returning it as given and in internal
+/// byte order. Electrum script hashes are displayed in reverse byte order.
+fn script_hash(params: &[Value]) -> Result<(String, [u8; 32]), RpcError> {
+  let key = param::<String>(params, 0)?;
+
+  let mut script_hash: [u8; 32] = hex::decode(&key)
+    .ok()
+    .and_then(|bytes| bytes.try_into().ok())
+    .ok_or_else(|| RpcError::InvalidParams(format!("invalid script hash `{key}`")))?;
+
+  script_hash.reverse();
+
+  Ok((key, script_hash))
+}
+
+fn param<T: DeserializeOwned>(params: &[Value], i: usize) -> Result<T, RpcError> {
+  serde_json::from_value(
+    params
+      .get(i)
+      .cloned()
+      .ok_or_else(|| RpcError::InvalidParams(format!("missing parameter {i}")))?,
+  )
+  .map_err(|err| RpcError::InvalidParams(format!("invalid parameter {i}: {err}")))
+}
@@ -0,0 +1,345 @@
+use super::*;
+
+/// A subset of the Esplora REST API, served under `/esplora`, so that tools
+/// which speak Esplora can use an ord server. Outputs include ord's
+/// inscription and rune data as additional fields.
+pub(super) fn router<S: Clone + Send + Sync + 'static>() -> Router<S> {
+  Router::new()
+    .route("/address/{address}/utxo", get(address_utxo))
+    .route("/block/{hash}", get(block))
+    .route("/blocks/tip/height", get(tip_height))
+    .route("/tx/{txid}", get(transaction))
+    .route("/tx/{txid}/hex", get(transaction_hex))
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Block {
+  pub(crate) bits: u32,
+  pub(crate) difficulty: f64,
+  pub(crate) height: u32,
+  pub(crate) id: BlockHash,
+  pub(crate) mediantime: Option<u64>,
+  pub(crate) merkle_root: TxMerkleNode,
+  pub(crate) nonce: u32,
+  pub(crate) previousblockhash: Option<BlockHash>,
+  pub(crate) size: usize,
+  pub(crate) timestamp: u32,
+  pub(crate) tx_count: usize,
+  pub(crate) version: i32,
+  pub(crate) weight: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Input {
+  pub(crate) is_coinbase: bool,
+  pub(crate) prevout: Option<Output>,
+  pub(crate) scriptsig: ScriptBuf,
+  pub(crate) scriptsig_asm: String,
+  pub(crate) sequence: u32,
+  pub(crate) txid: Txid,
+  pub(crate) vout: u32,
+  pub(crate) witness: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Output {
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub(crate) inscriptions: Option<Vec<InscriptionId>>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub(crate) runes: Option<BTreeMap<SpacedRune, Pile>>,
+  pub(crate) scriptpubkey: ScriptBuf,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub(crate) scriptpubkey_address: Option<Address<NetworkUnchecked>>,
+  pub(crate) scriptpubkey_asm: String,
+  pub(crate) scriptpubkey_type: String,
+  pub(crate) value: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Status {
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub(crate) block_hash: Option<BlockHash>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub(crate) block_height: Option<u32>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub(crate) block_time: Option<u32>,
+  pub(crate) confirmed: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Transaction {
+  pub(crate) fee: Option<u64>,
+  pub(crate) locktime: u32,
+  pub(crate) size: usize,
+  pub(crate) status: Status,
+  pub(crate) txid: Txid,
+  pub(crate) version: i32,
+  pub(crate) vin: Vec<Input>,
+  pub(crate) vout: Vec<Output>,
+  pub(crate) weight: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Utxo {
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub(crate) inscriptions: Option<Vec<InscriptionId>>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub(crate) runes: Option<BTreeMap<SpacedRune, Pile>>,
+  pub(crate) status: Status,
+  pub(crate) txid: Txid,
+  pub(crate) value: u64,
+  pub(crate) vout: u32,
+}
+
+fn script_type(script: &Script) -> &'static str {
+  if script.is_p2pkh() {
+    "p2pkh"
+  } else if script.is_p2sh() {
+    "p2sh"
+  } else if script.is_p2wpkh() {
+    "v0_p2wpkh"
+  } else if script.is_p2wsh() {
+    "v0_p2wsh"
+  } else if script.is_p2tr() {
+    "v1_p2tr"
+  } else if script.is_op_return() {
+    "op_return"
+  } else if script.is_p2pk() {
+    "p2pk"
+  } else if script.is_multisig() {
+    "multisig"
+  } else if script.is_empty() {
+    "empty"
+  } else {
+    "unknown"
+  }
+}
+
+fn output(chain: Chain, tx_out: &TxOut) -> Output {
+  Output {
+    inscriptions: None,
+    runes: None,
+    scriptpubkey: tx_out.script_pubkey.clone(),
+    scriptpubkey_address: chain
+      .address_from_script(&tx_out.script_pubkey)
+      .ok()
+      .map(|address| address.as_unchecked().clone()),
+    scriptpubkey_asm: tx_out.script_pubkey.to_asm_string(),
+    scriptpubkey_type: script_type(&tx_out.script_pubkey).into(),
+    value: tx_out.value.to_sat(),
+  }
+}
+
+fn status(index: &Index, txid: Txid) -> Result<Status> {
+  let Some(block_hash) = index.get_transaction_block_hash(txid)? else {
+    return Ok(Status {
+      block_hash: None,
+      block_height: None,
+      block_time: None,
+      confirmed: false,
+    });
+  };
+
+  let info = index
+    .block_header_info(block_hash)?
+    .ok_or_else(|| anyhow!("block {block_hash} not found"))?;
+
+  Ok(Status {
+    block_hash: Some(block_hash),
+    block_height: Some(info.height.try_into().unwrap()),
+    block_time: Some(info.time.try_into().unwrap()),
+    confirmed: true,
+  })
+}
+
+async fn address_utxo(
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Extension(index): Extension<Arc<Index>>,
+  Path(address): Path<Address<NetworkUnchecked>>,
+) -> ServerResult {
+  task::block_in_place(|| {
+    if !index.has_address_index() {
+      return Err(ServerError::NotFound(
+        "this server has no address index".to_string(),
+      ));
+    }
+
+    let address = address
+      .require_network(server_config.chain.network())
+      .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+    let outputs = index.get_address_info(&address)?;
+
+    let mut utxos = Vec::new();
+
+    for (outpoint, value, height, header) in index.get_received_outputs(&outputs)? {
+      let Some((info, _tx_out)) = index.get_output_info(outpoint)? else {
+        continue;
+      };
+
+      utxos.push(Utxo {
+        inscriptions: info.inscriptions,
+        runes: info.runes,
+        status: Status {
+          block_hash: Some(header.block_hash()),
+          block_height: Some(height),
+          block_time: Some(header.time),
+          confirmed: true,
+        },
+        txid: outpoint.txid,
+        value,
+        vout: outpoint.vout,
+      });
+    }
+
+    Ok(Json(utxos).into_response())
+  })
+}
+
+async fn block(
+  Extension(index): Extension<Arc<Index>>,
+  Path(hash): Path<BlockHash>,
+) -> ServerResult {
+  task::block_in_place(|| {
+    let block = index
+      .get_block_by_hash(hash)?
+      .ok_or_not_found(|| format!("block {hash}"))?;
+
+    let info = index
+      .block_header_info(hash)?
+      .ok_or_not_found(|| format!("block {hash}"))?;
+
+    Ok(
+      Json(Block {
+        bits: block.header.bits.to_consensus(),
+        difficulty: info.difficulty,
+        height: info.height.try_into().unwrap(),
+        id: hash,
+        mediantime: info.median_time.map(|median_time| median_time as u64),
+        merkle_root: block.header.merkle_root,
+        nonce: block.header.nonce,
+        previousblockhash: (block.header.prev_blockhash != BlockHash::all_zeros())
+          .then_some(block.header.prev_blockhash),
+        size: block.total_size(),
+        timestamp: block.header.time,
+        tx_count: block.txdata.len(),
+        version: block.header.version.to_consensus(),
+        weight: block.weight().to_wu(),
+      })
+      .into_response(),
+    )
+  })
+}
+
+async fn tip_height(Extension(index): Extension<Arc<Index>>) -> ServerResult {
+  task::block_in_place(|| {
+    Ok(
+      index
+        .block_height()?
+        .ok_or_not_found(|| "tip")?
+        .to_string()
+        .into_response(),
+    )
+  })
+}
+
+async fn transaction(
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Extension(index): Extension<Arc<Index>>,
+  Path(txid): Path<Txid>,
+) -> ServerResult {
+  task::block_in_place(|| {
+    let transaction = index
+      .get_transaction(txid)?
+      .ok_or_not_found(|| format!("transaction {txid}"))?;
+
+    let chain = server_config.chain;
+
+    let is_coinbase = transaction.is_coinbase();
+
+    let mut vin = Vec::new();
+    let mut input_value = Some(0);
+
+    for input in &transaction.input {
+      let outpoint = input.previous_output;
+
+      let prevout = if is_coinbase {
+        None
+      } else {
+        index
+          .get_transaction(outpoint.txid)?
+          .and_then(|transaction| {
+            transaction
+              .output
+              .into_iter()
+              .nth(outpoint.vout.try_into().unwrap())
+          })
+      };
+
+      input_value = input_value
+        .zip(prevout.as_ref())
+        .map(|(input_value, prevout)| input_value + prevout.value.to_sat());
+
+      vin.push(Input {
+        is_coinbase,
+        prevout: prevout.as_ref().map(|prevout| output(chain, prevout)),
+        scriptsig: input.script_sig.clone(),
+        scriptsig_asm: input.script_sig.to_asm_string(),
+        sequence: input.sequence.0,
+        txid: outpoint.txid,
+        vout: outpoint.vout,
+        witness: input.witness.iter().map(hex::encode).collect(),
+      });
+    }
+
+    let mut vout = Vec::new();
+
+    for (tx_out, vout_index) in transaction.output.iter().zip(0..) {
+      let outpoint = OutPoint::new(txid, vout_index);
+
+      vout.push(Output {
+        inscriptions: index.get_inscriptions_for_output(outpoint)?,
+        runes: index.get_rune_balances_for_output(outpoint)?,
+        ..output(chain, tx_out)
+      });
+    }
+
+    let output_value = transaction
+      .output
+      .iter()
+      .map(|tx_out| tx_out.value.to_sat())
+      .sum::<u64>();
+
+    Ok(
+      Json(Transaction {
+        fee: if is_coinbase {
+          Some(0)
+        } else {
+          input_value.and_then(|input_value| input_value.checked_sub(output_value))
+        },
+        locktime: transaction.lock_time.to_consensus_u32(),
+        size: transaction.total_size(),
+        status: status(&index, txid)?,
+        txid,
+        version: transaction.version.0,
+        vin,
+        vout,
+        weight: transaction.weight().to_wu(),
+      })
+      .into_response(),
+    )
+  })
+}
+
+async fn transaction_hex(
+  Extension(index): Extension<Arc<Index>>,
+  Path(txid): Path<Txid>,
+) -> ServerResult {
+  task::block_in_place(|| {
+    let transaction = index
+      .get_transaction(txid)?
+      .ok_or_not_found(|| format!("transaction {txid}"))?;
+
+    Ok(consensus::encode::serialize_hex(&transaction).into_response())
+  })
+}
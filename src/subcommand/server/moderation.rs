@@ -0,0 +1,119 @@
+use {super::*, std::sync::RwLock};
+
+/// Inscriptions hidden by the operator, either with the `hidden` setting or
+/// through the admin API. Admin API changes are saved to the index, and a copy
+/// is kept in memory, so checks don't need a read transaction.
+pub(crate) struct Moderation {
+  entries: RwLock<BTreeMap<InscriptionId, api::ModerationEntry>>,
+  index: Arc<Index>,
+  settings: Arc<Settings>,
+}
+
+impl Moderation {
+  pub(crate) fn load(index: Arc<Index>, settings: Arc<Settings>) -> Result<Self> {
+    Ok(Self {
+      entries: RwLock::new(index.get_moderation_entries()?),
+      index,
+      settings,
+    })
+  }
+
+  pub(crate) fn is_hidden(&self, inscription_id: InscriptionId) -> bool {
+    self.settings.is_hidden(inscription_id)
+      || self.entries.read().unwrap().contains_key(&inscription_id)
+  }
+
+  /// Returns all hidden inscriptions, whether hidden with the `hidden`
+  /// setting or through the admin API.
+  pub(crate) fn hidden(&self) -> BTreeSet<InscriptionId> {
+    self
+      .settings
+      .hidden()
+      .chain(self.entries.read().unwrap().keys().copied())
+      .collect()
+  }
+
+  pub(crate) fn entries(&self) -> BTreeMap<InscriptionId, api::ModerationEntry> {
+    self.entries.read().unwrap().clone()
+  }
+
+  /// Returns `true` if the inscription was not already hidden.
+  pub(crate) fn hide(&self, inscription_id: InscriptionId, reason: String) -> Result<bool> {
+    let mut entries = self.entries.write().unwrap();
+
+    let entry = api::ModerationEntry {
+      reason,
+      timestamp: Utc::now().timestamp(),
+    };
+
+    let hidden = self.index.insert_moderation_entry(inscription_id, &entry)?;
+
+    entries.insert(inscription_id, entry);
+
+    Ok(hidden)
+  }
+
+  /// Returns `true` if the inscription was hidden.
+  pub(crate) fn unhide(&self, inscription_id: InscriptionId) -> Result<bool> {
+    let mut entries = self.entries.write().unwrap();
+
+    let unhidden = self.index.remove_moderation_entry(inscription_id)?;
+
+    entries.remove(&inscription_id);
+
+    Ok(unhidden)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::index::testing::Context};
+
+  #[test]
+  fn entries_are_saved_to_the_index() {
+    let context = Context::builder().build();
+
+    let index = Arc::new(context.index);
+
+    let moderation = Moderation::load(index.clone(), Arc::new(Settings::default())).unwrap();
+
+    assert!(!moderation.is_hidden(inscription_id(1)));
+    assert!(moderation.hide(inscription_id(1), "spam".into()).unwrap());
+    assert!(!moderation.hide(inscription_id(1), "spam".into()).unwrap());
+    assert!(moderation.is_hidden(inscription_id(1)));
+
+    let reloaded = Moderation::load(index.clone(), Arc::new(Settings::default())).unwrap();
+    assert!(reloaded.is_hidden(inscription_id(1)));
+    assert_eq!(reloaded.entries()[&inscription_id(1)].reason, "spam");
+
+    assert!(moderation.unhide(inscription_id(1)).unwrap());
+    assert!(!moderation.unhide(inscription_id(1)).unwrap());
+    assert!(!moderation.is_hidden(inscription_id(1)));
+    assert!(index.get_moderation_entries().unwrap().is_empty());
+  }
+
+  #[test]
+  fn hidden_includes_settings_and_entries() {
+    let context = Context::builder().build();
+
+    let moderation = Moderation::load(
+      Arc::new(context.index),
+      Arc::new(
+        Settings::from_env(
+          [("HIDDEN".into(), inscription_id(1).to_string())]
+            .into_iter()
+            .collect(),
+        )
+        .unwrap(),
+      ),
+    )
+    .unwrap();
+
+    moderation.hide(inscription_id(2), "spam".into()).unwrap();
+
+    assert_eq!(
+      moderation.hidden(),
+      [inscription_id(1), inscription_id(2)].into(),
+    );
+  }
+}
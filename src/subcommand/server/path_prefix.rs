@@ -0,0 +1,140 @@
+use {
+  super::*,
+  axum::{
+    body::Body,
+    extract::{NestedPath, Request},
+    middleware::Next,
+  },
+};
+
+lazy_static! {
+  static ref LINK: Regex = Regex::new(r#"\b((?:action|href|src)=['"]?)/([^/]|$)"#).unwrap();
+}
+
+/// Prefixes root-relative links in explorer pages and redirect locations
+/// served under a chain's path prefix. The bodies of responses which set their
+/// own content security policy, such as inscription content and previews, are
+/// passed through unchanged.
+pub(super) async fn prefix_links(request: Request, next: Next) -> Response {
+  let Some(prefix) = request
+    .extensions()
+    .get::<NestedPath>()
+    .map(|nested_path| nested_path.as_str().trim_end_matches('/').to_string())
+  else {
+    return next.run(request).await;
+  };
+
+  let mut response = next.run(request).await;
+
+  if let Some(location) = response
+    .headers()
+    .get(header::LOCATION)
+    .and_then(|location| location.to_str().ok())
+    .and_then(|location| prefix_location(location, &prefix))
+    .and_then(|location| HeaderValue::from_str(&location).ok())
+  {
+    response.headers_mut().insert(header::LOCATION, location);
+  }
+
+  let html = response
+    .headers()
+    .get(header::CONTENT_TYPE)
+    .and_then(|content_type| content_type.to_str().ok())
+    .is_some_and(|content_type| content_type.starts_with("text/html"));
+
+  if !html
+    || response
+      .headers()
+      .contains_key(header::CONTENT_SECURITY_POLICY)
+  {
+    return response;
+  }
+
+  let (mut parts, body) = response.into_parts();
+
+  let body = match axum::body::to_bytes(body, usize::MAX).await {
+    Ok(body) => body,
+    Err(err) => return ServerError::Internal(anyhow!(err)).into_response(),
+  };
+
+  parts.headers.remove(header::CONTENT_LENGTH);
+
+  Response::from_parts(
+    parts,
+    Body::from(rewrite(&String::from_utf8_lossy(&body), &prefix)),
+  )
+}
+
+fn prefix_location(location: &str, prefix: &str) -> Option<String> {
+  let rest = location.strip_prefix('/')?;
+
+  if rest.starts_with('/') {
+    return None;
+  }
+
+  // nested routers serve their root at the bare prefix, without a trailing slash
+  if rest.is_empty() || rest.starts_with(['?', '#']) {
+    Some(format!("{prefix}{rest}"))
+  } else {
+    Some(format!("{prefix}/{rest}"))
+  }
+}
+
+fn rewrite(html: &str, prefix: &str) -> String {
+  LINK
+    .replace_all(html, |captures: &regex::Captures| {
+      let rest = &captures[2];
+
+      // nested routers serve their root at the bare prefix, without a trailing slash
+      if rest.is_empty() || rest.starts_with([' ', '>', '\'', '"', '?', '#']) {
+        format!("{}{prefix}{rest}", &captures[1])
+      } else {
+        format!("{}{prefix}/{rest}", &captures[1])
+      }
+    })
+    .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn links_are_prefixed() {
+    assert_eq!(
+      rewrite(
+        "<a href=/ title=home></a><a href=/block/0>0</a><img src='/static/a.svg'>\
+        <form action=\"/search\">",
+        "/signet",
+      ),
+      "<a href=/signet title=home></a><a href=/signet/block/0>0</a>\
+      <img src='/signet/static/a.svg'><form action=\"/signet/search\">",
+    );
+  }
+
+  #[test]
+  fn locations_are_prefixed() {
+    assert_eq!(prefix_location("/", "/signet").unwrap(), "/signet");
+    assert_eq!(prefix_location("/?a=b", "/signet").unwrap(), "/signet?a=b");
+    assert_eq!(
+      prefix_location("/sat/0", "/signet").unwrap(),
+      "/signet/sat/0"
+    );
+    assert_eq!(prefix_location("//ordinals.com/", "/signet"), None);
+    assert_eq!(prefix_location("https://ordinals.com/", "/signet"), None);
+    assert_eq!(prefix_location("sat/0", "/signet"), None);
+  }
+
+  #[test]
+  fn other_links_are_not_prefixed() {
+    for html in [
+      "<a href=https://ordinals.com/>",
+      "<a href=//ordinals.com/>",
+      "<a href=block/0>",
+      "<meta content='/static/favicon.png'>",
+      "<p>href</p>",
+    ] {
+      assert_eq!(rewrite(html, "/signet"), html);
+    }
+  }
+}
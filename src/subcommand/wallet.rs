@@ -5,16 +5,23 @@ use {
   shared_args::SharedArgs,
 };
 
+pub mod accelerate;
 pub mod addresses;
 pub mod balance;
 mod batch_command;
+pub mod bump;
 pub mod burn;
 pub mod cardinals;
+pub mod consolidate_runes;
 pub mod create;
 pub mod dump;
+pub mod extract;
+pub mod finalize;
+pub mod history;
 pub mod inscribe;
 pub mod inscriptions;
 mod label;
+pub mod listing;
 pub mod mint;
 pub mod offer;
 pub mod outputs;
@@ -25,8 +32,10 @@ pub mod resume;
 pub mod runics;
 pub mod sats;
 pub mod send;
+pub mod send_many;
 mod shared_args;
 pub mod sign;
+pub mod sign_psbt;
 pub mod split;
 pub mod transactions;
 
@@ -48,26 +57,40 @@ pub(crate) struct WalletCommand {
 #[derive(Debug, Parser)]
 #[allow(clippy::large_enum_variant)]
 pub(crate) enum Subcommand {
+  #[command(about = "Accelerate unconfirmed transaction with child-pays-for-parent")]
+  Accelerate(accelerate::Accelerate),
   #[command(about = "Get wallet addresses")]
   Addresses,
   #[command(about = "Get wallet balance")]
   Balance,
   #[command(about = "Create inscriptions and runes")]
   Batch(batch_command::Batch),
+  #[command(about = "Bump fee of unconfirmed transaction")]
+  Bump(bump::Bump),
   #[command(about = "Burn an inscription")]
   Burn(burn::Burn),
   #[command(about = "List unspent cardinal outputs in wallet")]
   Cardinals,
+  #[command(about = "Consolidate runic outputs")]
+  ConsolidateRunes(consolidate_runes::ConsolidateRunes),
   #[command(about = "Create new wallet")]
   Create(create::Create),
   #[command(about = "Dump wallet descriptors")]
   Dump,
+  #[command(about = "Extract rare sats into their own outputs")]
+  Extract(extract::Extract),
+  #[command(about = "Finalize and broadcast transaction signed externally")]
+  Finalize(finalize::Finalize),
+  #[command(about = "Export wallet transaction history")]
+  History(history::History),
   #[command(about = "Create inscription")]
   Inscribe(inscribe::Inscribe),
   #[command(about = "List wallet inscriptions")]
   Inscriptions,
   #[command(about = "Export output labels")]
   Label,
+  #[command(subcommand, about = "Listing commands")]
+  Listing(listing::Listing),
   #[command(about = "Mint a rune")]
   Mint(mint::Mint),
   #[command(subcommand, about = "Offer commands")]
@@ -88,8 +111,12 @@ pub(crate) enum Subcommand {
   Sats(sats::Sats),
   #[command(about = "Send sat or inscription")]
   Send(send::Send),
+  #[command(about = "Send multiple assets to multiple recipients from a file")]
+  SendMany(send_many::SendMany),
   #[command(about = "Sign message")]
   Sign(sign::Sign),
+  #[command(about = "Sign PSBT after checking that no inscriptions or runes are lost")]
+  SignPsbt(sign_psbt::SignPsbt),
   #[command(about = "Split outputs")]
   Split(split::Split),
   #[command(about = "See wallet transactions")]
@@ -119,16 +146,23 @@ impl WalletCommand {
     )?;
 
     match self.subcommand {
+      Subcommand::Accelerate(accelerate) => accelerate.run(wallet),
       Subcommand::Addresses => addresses::run(wallet),
       Subcommand::Balance => balance::run(wallet),
       Subcommand::Batch(batch) => batch.run(wallet),
+      Subcommand::Bump(bump) => bump.run(wallet),
       Subcommand::Burn(burn) => burn.run(wallet),
       Subcommand::Cardinals => cardinals::run(wallet),
+      Subcommand::ConsolidateRunes(consolidate_runes) => consolidate_runes.run(wallet),
       Subcommand::Create(_) | Subcommand::Restore(_) => unreachable!(),
       Subcommand::Dump => dump::run(wallet),
+      Subcommand::Extract(extract) => extract.run(wallet),
+      Subcommand::Finalize(finalize) => finalize.run(wallet),
+      Subcommand::History(history) => history.run(wallet),
       Subcommand::Inscribe(inscribe) => inscribe.run(wallet),
       Subcommand::Inscriptions => inscriptions::run(wallet),
       Subcommand::Label => label::run(wallet),
+      Subcommand::Listing(listing) => listing.run(wallet),
       Subcommand::Mint(mint) => mint.run(wallet),
       Subcommand::Offer(offer) => offer.run(wallet),
       Subcommand::Outputs(outputs) => outputs.run(wallet),
@@ -138,7 +172,9 @@ impl WalletCommand {
       Subcommand::Runics => runics::run(wallet),
       Subcommand::Sats(sats) => sats.run(wallet),
       Subcommand::Send(send) => send.run(wallet),
+      Subcommand::SendMany(send_many) => send_many.run(wallet),
       Subcommand::Sign(sign) => sign.run(wallet),
+      Subcommand::SignPsbt(sign_psbt) => sign_psbt.run(wallet),
       Subcommand::Split(split) => split.run(wallet),
       Subcommand::Transactions(transactions) => transactions.run(wallet),
     }
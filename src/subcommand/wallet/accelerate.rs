@@ -0,0 +1,348 @@
+use {
+  super::*,
+  crate::{
+    index::{allocate_runes, Lot},
+    wallet::transaction_builder::{Target, TransactionBuilder},
+  },
+  std::collections::HashMap,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub parent: Txid,
+  pub txid: Txid,
+  pub psbt: String,
+  pub fee: u64,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Accelerate {
+  #[arg(long, help = "Don't sign or broadcast child transaction")]
+  dry_run: bool,
+  #[arg(long, help = "Bring package to fee rate of <FEE_RATE> sats/vB")]
+  fee_rate: FeeRate,
+  #[arg(help = "Accelerate unconfirmed transaction <TXID>")]
+  txid: Txid,
+}
+
+/// Inscriptions, along with their offsets, and runes in an output.
+type Contents = (Vec<(InscriptionId, u64)>, HashMap<RuneId, Lot>);
+
+impl Accelerate {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    let client = wallet.bitcoin_client();
+
+    let mempool = client.get_raw_mempool()?;
+
+    ensure! {
+      mempool.contains(&self.txid),
+      "transaction {} is not in mempool",
+      self.txid,
+    }
+
+    let entry = client.get_mempool_entry(&self.txid)?;
+
+    // the transaction being accelerated and its unconfirmed ancestors
+    let package_fee = entry.fees.ancestor;
+    let package_vsize = usize::try_from(entry.ancestor_size).unwrap();
+
+    ensure! {
+      self.fee_rate.fee(package_vsize) > package_fee,
+      "transaction {} already pays fee rate of at least {} sat/vB",
+      self.txid,
+      self.fee_rate,
+    }
+
+    let mut spent = BTreeSet::new();
+
+    for txid in &entry.spent_by {
+      spent.extend(
+        client
+          .get_raw_transaction(txid, None)?
+          .input
+          .iter()
+          .map(|txin| txin.previous_output),
+      );
+    }
+
+    let parent = client.get_raw_transaction(&self.txid, None)?;
+
+    let contents = Self::contents(&wallet, &mempool, &parent)?;
+
+    let mut candidates = Vec::new();
+    let mut runic = false;
+
+    for (vout, (output, (inscriptions, runes))) in parent.output.iter().zip(&contents).enumerate() {
+      let outpoint = OutPoint {
+        txid: self.txid,
+        vout: vout.try_into().unwrap(),
+      };
+
+      if output.script_pubkey.is_op_return()
+        || spent.contains(&outpoint)
+        || !wallet.is_mine(&output.script_pubkey)?
+      {
+        continue;
+      }
+
+      // runes would be moved to the first output of the child transaction
+      if !runes.is_empty() {
+        runic = true;
+        continue;
+      }
+
+      candidates.push((outpoint, output.clone(), inscriptions));
+    }
+
+    // prefer spending a cardinal output, so that no inscriptions move
+    let Some((outpoint, output, inscriptions)) = candidates
+      .iter()
+      .find(|(_outpoint, _output, inscriptions)| inscriptions.is_empty())
+      .or(candidates.first())
+      .cloned()
+    else {
+      if runic {
+        bail!(
+          "transaction {} has no unspent outputs belonging to wallet which do not contain runes",
+          self.txid
+        );
+      }
+
+      bail!(
+        "transaction {} has no unspent outputs belonging to wallet",
+        self.txid
+      );
+    };
+
+    let mut wallet_inscriptions = wallet.inscriptions().clone();
+
+    for (id, offset) in inscriptions {
+      wallet_inscriptions
+        .entry(SatPoint {
+          outpoint,
+          offset: *offset,
+        })
+        .or_default()
+        .push(*id);
+    }
+
+    let outgoing = SatPoint {
+      outpoint,
+      offset: inscriptions
+        .iter()
+        .map(|(_id, offset)| *offset)
+        .min()
+        .unwrap_or_default(),
+    };
+
+    let mut amounts = wallet
+      .utxos()
+      .iter()
+      .filter(|(outpoint, _txout)| !mempool.contains(&outpoint.txid))
+      .map(|(outpoint, txout)| (*outpoint, txout.clone()))
+      .collect::<BTreeMap<OutPoint, TxOut>>();
+
+    amounts.insert(outpoint, output);
+
+    let runic_outputs = wallet.get_runic_outputs()?.unwrap_or_default();
+
+    let recipient = wallet.get_change_address()?.script_pubkey();
+
+    let change = [wallet.get_change_address()?, wallet.get_change_address()?];
+
+    // the child's fee rate is raised until it pays for its own weight and the
+    // fee deficit of its unconfirmed ancestors
+    let mut fee_rate = self.fee_rate;
+
+    for _ in 0..4 {
+      let child = TransactionBuilder::new(
+        outgoing,
+        wallet_inscriptions.clone(),
+        amounts.clone(),
+        wallet.locked_utxos().clone().into_keys().collect(),
+        runic_outputs.clone(),
+        recipient.clone(),
+        change.clone(),
+        fee_rate,
+        Target::Postage,
+        wallet.chain().network(),
+      )
+      .build_transaction()?;
+
+      let vsize = Self::estimate_vsize(&child);
+
+      let fee = child
+        .input
+        .iter()
+        .map(|txin| amounts[&txin.previous_output].value)
+        .sum::<Amount>()
+        - child.output.iter().map(|txout| txout.value).sum::<Amount>();
+
+      let required = self.fee_rate.fee(package_vsize + vsize) - package_fee;
+
+      if fee >= required {
+        let (txid, psbt) = wallet.sign_and_broadcast(&child, self.dry_run, None)?;
+
+        return Ok(Some(Box::new(Output {
+          parent: self.txid,
+          txid,
+          psbt,
+          fee: fee.to_sat(),
+        })));
+      }
+
+      fee_rate = FeeRate::try_from((required.to_sat() + 1) as f64 / vsize as f64)?;
+    }
+
+    bail!("failed to build child transaction paying required fee")
+  }
+
+  /// Returns the inscriptions, along with their offsets, and runes in each
+  /// output of `transaction`, following unconfirmed inputs through their
+  /// transactions in `mempool`.
+  fn contents(
+    wallet: &Wallet,
+    mempool: &[Txid],
+    transaction: &Transaction,
+  ) -> Result<Vec<Contents>> {
+    let txid = transaction.compute_txid();
+
+    let output_value = transaction
+      .output
+      .iter()
+      .map(|txout| txout.value.to_sat())
+      .sum::<u64>();
+
+    let envelopes = ParsedEnvelope::from_transaction(transaction);
+
+    let mut inscriptions = Vec::new();
+    let mut unallocated = HashMap::<RuneId, Lot>::new();
+    let mut offset = 0;
+
+    for (input, txin) in transaction.input.iter().enumerate() {
+      let outpoint = txin.previous_output;
+      let vout = usize::try_from(outpoint.vout).unwrap();
+
+      let value = if mempool.contains(&outpoint.txid) {
+        let parent = wallet
+          .bitcoin_client()
+          .get_raw_transaction(&outpoint.txid, None)?;
+
+        let (output_inscriptions, output_runes) =
+          Self::contents(wallet, mempool, &parent)?.swap_remove(vout);
+
+        for (id, output_offset) in output_inscriptions {
+          inscriptions.push((id, offset + output_offset));
+        }
+
+        for (id, lot) in output_runes {
+          *unallocated.entry(id).or_default() += lot.0;
+        }
+
+        parent.output[vout].value.to_sat()
+      } else {
+        let output = wallet
+          .get_output(outpoint)?
+          .ok_or_else(|| anyhow!("input {outpoint} not found"))?;
+
+        for (spaced_rune, pile) in output.runes.iter().flatten() {
+          let (id, _entry, _parent) = wallet
+            .get_rune(spaced_rune.rune)?
+            .ok_or_else(|| anyhow!("rune `{spaced_rune}` has not been etched"))?;
+
+          *unallocated.entry(id).or_default() += pile.amount;
+        }
+
+        for id in output.inscriptions.unwrap_or_default() {
+          let inscription = wallet
+            .get_inscription(id)?
+            .ok_or_else(|| anyhow!("inscription {id} not found"))?;
+
+          inscriptions.push((id, offset + inscription.satpoint.offset));
+        }
+
+        output.value
+      };
+
+      for (index, envelope) in envelopes.iter().enumerate() {
+        if envelope.input == u32::try_from(input).unwrap() {
+          inscriptions.push((
+            InscriptionId {
+              txid,
+              index: index.try_into().unwrap(),
+            },
+            envelope
+              .payload
+              .pointer()
+              .filter(|&pointer| pointer < output_value)
+              .unwrap_or(offset),
+          ));
+        }
+      }
+
+      offset += value;
+    }
+
+    let artifact = Runestone::decipher(transaction);
+
+    let mut etched = None;
+
+    if let Some(Artifact::Runestone(runestone)) = &artifact {
+      if let Some(id) = runestone.mint {
+        if let Some((id, entry, _parent)) = wallet.get_rune_by_id(id)? {
+          if let Some(amount) = entry.terms.and_then(|terms| terms.amount) {
+            *unallocated.entry(id).or_default() += amount;
+          }
+        }
+      }
+
+      // runes etched by unconfirmed transactions do not have an ID yet
+      if runestone.etching.is_some() {
+        etched = Some(RuneId {
+          block: u64::MAX,
+          tx: u32::MAX,
+        });
+      }
+    }
+
+    let (allocated, _burned) = allocate_runes(transaction, artifact.as_ref(), unallocated, etched);
+
+    let mut outputs = allocated
+      .into_iter()
+      .map(|runes| {
+        (
+          Vec::new(),
+          runes
+            .into_iter()
+            .filter(|(_id, lot)| lot.0 > 0)
+            .collect::<HashMap<RuneId, Lot>>(),
+        )
+      })
+      .collect::<Vec<Contents>>();
+
+    for (id, mut offset) in inscriptions {
+      for (vout, output) in transaction.output.iter().enumerate() {
+        if offset < output.value.to_sat() {
+          outputs[vout].0.push((id, offset));
+          break;
+        }
+
+        offset -= output.value.to_sat();
+      }
+    }
+
+    Ok(outputs)
+  }
+
+  /// Estimates the virtual size of `transaction` once its inputs are signed,
+  /// assuming that they are all taproot key path spends.
+  fn estimate_vsize(transaction: &Transaction) -> usize {
+    let mut transaction = transaction.clone();
+
+    for txin in &mut transaction.input {
+      txin.witness = Witness::from_slice(&[&[0; 64]]);
+    }
+
+    transaction.vsize()
+  }
+}
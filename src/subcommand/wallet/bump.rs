@@ -0,0 +1,519 @@
+use {
+  super::*,
+  crate::wallet::transaction_builder::TransactionBuilder,
+  bitcoin::{
+    key::{PrivateKey, TweakedPublicKey},
+    secp256k1::{self, Keypair, Secp256k1},
+    sighash::{Prevouts, SighashCache, TapSighashType},
+    taproot::{ControlBlock, LeafVersion, Signature, TapLeafHash, TapNodeHash, TapTweakHash},
+  },
+  bitcoincore_rpc::bitcoincore_rpc_json::SignRawTransactionInput,
+  std::slice,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub original: Txid,
+  pub txid: Txid,
+  pub psbt: String,
+  pub fee: u64,
+  pub reveal: Option<Txid>,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Bump {
+  #[arg(long, help = "Don't sign or broadcast replacement transaction")]
+  dry_run: bool,
+  #[arg(
+    long,
+    help = "Use fee rate of <FEE_RATE> sats/vB for replacement transaction"
+  )]
+  fee_rate: FeeRate,
+  #[arg(help = "Replace unconfirmed transaction <TXID>")]
+  txid: Txid,
+}
+
+struct Reveal {
+  commit_input: usize,
+  prevouts: Vec<TxOut>,
+  transaction: Transaction,
+}
+
+impl Bump {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    let client = wallet.bitcoin_client();
+
+    let mempool = client.get_raw_mempool()?;
+
+    ensure! {
+      mempool.contains(&self.txid),
+      "transaction {} is not in mempool",
+      self.txid,
+    }
+
+    let original = client.get_raw_transaction(&self.txid, None)?;
+
+    if let Some(envelope) = ParsedEnvelope::from_transaction(&original).first() {
+      bail!(
+        "transaction {} is an inscription reveal, bump commit transaction {} instead",
+        self.txid,
+        original.input[usize::try_from(envelope.input).unwrap()]
+          .previous_output
+          .txid,
+      );
+    }
+
+    for (rune, entry) in wallet.pending_etchings()? {
+      ensure! {
+        entry.commit.compute_txid() != self.txid,
+        "transaction {} is the commit transaction of pending etching {rune}",
+        self.txid,
+      }
+    }
+
+    let children = client
+      .get_mempool_entry(&self.txid)?
+      .spent_by
+      .iter()
+      .map(|txid| client.get_raw_transaction(txid, None))
+      .collect::<Result<Vec<Transaction>, bitcoincore_rpc::Error>>()?;
+
+    let (prevouts, inscriptions) = Self::inputs(&wallet, &mempool, &original.input)?;
+
+    let reveal = match children.as_slice() {
+      [] => None,
+      [child] if !ParsedEnvelope::from_transaction(child).is_empty() => {
+        ensure! {
+          !wallet.signs_externally(),
+          "bumping inscription commit transactions is not supported with watch-only or multisig wallets",
+        }
+
+        let commit_input = child
+          .input
+          .iter()
+          .position(|txin| txin.previous_output.txid == self.txid)
+          .unwrap();
+
+        let mut prevouts = Vec::new();
+
+        for (input, txin) in child.input.iter().enumerate() {
+          if input == commit_input {
+            prevouts
+              .push(original.output[usize::try_from(txin.previous_output.vout).unwrap()].clone());
+          } else {
+            prevouts.append(&mut Self::inputs(&wallet, &mempool, slice::from_ref(txin))?.0);
+          }
+        }
+
+        Some(Reveal {
+          commit_input,
+          prevouts,
+          transaction: child.clone(),
+        })
+      }
+      [child, ..] => bail!(
+        "transaction {} has unconfirmed descendant {}",
+        self.txid,
+        child.compute_txid(),
+      ),
+    };
+
+    // the commit output must pay for the reveal at the new fee rate
+    let increase = reveal.as_ref().map(|reveal| {
+      let commit_txin = &reveal.transaction.input[reveal.commit_input];
+
+      let fee = reveal
+        .prevouts
+        .iter()
+        .map(|txout| txout.value)
+        .sum::<Amount>()
+        - reveal
+          .transaction
+          .output
+          .iter()
+          .map(|txout| txout.value)
+          .sum::<Amount>();
+
+      (
+        usize::try_from(commit_txin.previous_output.vout).unwrap(),
+        self
+          .fee_rate
+          .fee(reveal.transaction.vsize())
+          .checked_sub(fee)
+          .unwrap_or_default(),
+      )
+    });
+
+    let (replacement, replacement_prevouts) =
+      self.replace(&wallet, &mempool, &original, &prevouts, increase)?;
+
+    for (id, offset) in inscriptions {
+      ensure! {
+        Self::destination(&original, offset) == Self::destination(&replacement, offset),
+        "replacement transaction would move inscription {id}",
+      }
+    }
+
+    let fee = replacement_prevouts
+      .iter()
+      .map(|txout| txout.value)
+      .sum::<Amount>()
+      - replacement
+        .output
+        .iter()
+        .map(|txout| txout.value)
+        .sum::<Amount>();
+
+    let reveal = reveal
+      .map(|reveal| Self::rebuild_reveal(&wallet, reveal, &original, &replacement, self.dry_run))
+      .transpose()?;
+
+    let (txid, psbt) = wallet.sign_and_broadcast(&replacement, self.dry_run, None)?;
+
+    if let Some(reveal) = &reveal {
+      if !self.dry_run {
+        wallet.send_raw_transaction(reveal, None)?;
+      }
+    }
+
+    Ok(Some(Box::new(Output {
+      original: self.txid,
+      txid,
+      psbt,
+      fee: fee.to_sat(),
+      reveal: reveal.as_ref().map(Transaction::compute_txid),
+    })))
+  }
+
+  /// Returns the outputs spent by `input`, and the inscriptions they
+  /// contain along with their offsets from the start of the inputs.
+  fn inputs(
+    wallet: &Wallet,
+    mempool: &[Txid],
+    input: &[TxIn],
+  ) -> Result<(Vec<TxOut>, Vec<(InscriptionId, u64)>)> {
+    let mut prevouts = Vec::new();
+    let mut inscriptions = Vec::new();
+    let mut offset = 0;
+
+    for txin in input {
+      let outpoint = txin.previous_output;
+
+      ensure! {
+        !mempool.contains(&outpoint.txid),
+        "input {outpoint} is unconfirmed",
+      }
+
+      let output = wallet
+        .get_output(outpoint)?
+        .ok_or_else(|| anyhow!("input {outpoint} not found"))?;
+
+      for id in output.inscriptions.unwrap_or_default() {
+        let inscription = wallet
+          .get_inscription(id)?
+          .ok_or_else(|| anyhow!("inscription {id} not found"))?;
+
+        inscriptions.push((id, offset + inscription.satpoint.offset));
+      }
+
+      offset += output.value;
+
+      prevouts.push(TxOut {
+        value: Amount::from_sat(output.value),
+        script_pubkey: output.script_pubkey,
+      });
+    }
+
+    Ok((prevouts, inscriptions))
+  }
+
+  /// Builds a replacement for `original` paying the new fee rate, first by
+  /// reducing the value of a final change output, and otherwise by adding
+  /// cardinal inputs. Output `increase.0`, if any, is increased by `increase.1`.
+  fn replace(
+    &self,
+    wallet: &Wallet,
+    mempool: &[Txid],
+    original: &Transaction,
+    prevouts: &[TxOut],
+    increase: Option<(usize, Amount)>,
+  ) -> Result<(Transaction, Vec<TxOut>)> {
+    let input_value = prevouts.iter().map(|txout| txout.value).sum::<Amount>();
+
+    let output_value = original
+      .output
+      .iter()
+      .map(|txout| txout.value)
+      .sum::<Amount>();
+
+    let original_fee = input_value - output_value;
+
+    ensure! {
+      self.fee_rate.fee(original.vsize()) > original_fee,
+      "fee rate {} sat/vB is not higher than fee rate of transaction {}",
+      self.fee_rate,
+      self.txid,
+    }
+
+    let mut outputs = original.output.clone();
+
+    if let Some((vout, amount)) = increase {
+      outputs[vout].value += amount;
+    }
+
+    let change = outputs
+      .len()
+      .checked_sub(1)
+      .filter(|&vout| Some(vout) != increase.map(|(vout, _amount)| vout))
+      .filter(|&vout| !outputs[vout].script_pubkey.is_op_return())
+      .map(|vout| {
+        wallet
+          .is_mine(&outputs[vout].script_pubkey)
+          .map(|mine| mine.then_some(vout))
+      })
+      .transpose()?
+      .flatten();
+
+    if change.is_none() {
+      // a new change output would change the outputs runes are split between
+      if let Some(Artifact::Runestone(runestone)) = Runestone::decipher(original) {
+        ensure! {
+          runestone
+            .edicts
+            .iter()
+            .all(|edict| usize::try_from(edict.output).unwrap() != original.output.len()),
+          "cannot add change output to transaction {} with runestone edicts splitting runes between all outputs",
+          self.txid,
+        }
+      }
+    }
+
+    // the replacement must pay for its own bandwidth at the incremental relay
+    // fee rate on top of the fee of the original (BIP 125 rule 4)
+    #[allow(clippy::cast_precision_loss)]
+    let incremental_fee_rate = FeeRate::try_from(
+      wallet
+        .bitcoin_client()
+        .get_network_info()?
+        .incremental_fee
+        .to_sat() as f64
+        / 1000.0,
+    )?;
+
+    let mut amounts = wallet
+      .utxos()
+      .iter()
+      .filter(|(outpoint, _txout)| !mempool.contains(&outpoint.txid))
+      .map(|(outpoint, txout)| (*outpoint, txout.clone()))
+      .collect::<BTreeMap<OutPoint, TxOut>>();
+
+    amounts.extend(
+      original
+        .input
+        .iter()
+        .map(|txin| txin.previous_output)
+        .zip(prevouts.iter().cloned()),
+    );
+
+    let replacement = TransactionBuilder::replacement(
+      original
+        .input
+        .iter()
+        .map(|txin| txin.previous_output)
+        .collect(),
+      outputs,
+      wallet.inscriptions().clone(),
+      amounts.clone(),
+      wallet.locked_utxos().clone().into_keys().collect(),
+      wallet.get_runic_outputs()?.unwrap_or_default(),
+      [wallet.get_change_address()?, wallet.get_change_address()?],
+      self.fee_rate,
+      wallet.chain().network(),
+    )
+    .build_replacement(original.vsize(), change, original_fee, incremental_fee_rate)?;
+
+    let prevouts = replacement
+      .input
+      .iter()
+      .map(|txin| amounts[&txin.previous_output].clone())
+      .collect();
+
+    Ok((replacement, prevouts))
+  }
+
+  /// Returns the output and offset within that output of the sat at `offset`
+  /// in the inputs of `transaction`, or `None` if it is paid as fees.
+  fn destination(transaction: &Transaction, mut offset: u64) -> Option<(usize, u64)> {
+    for (vout, output) in transaction.output.iter().enumerate() {
+      if offset < output.value.to_sat() {
+        return Some((vout, offset));
+      }
+
+      offset -= output.value.to_sat();
+    }
+
+    None
+  }
+
+  /// Rebuilds `reveal` to spend the commit output of `commit`, re-signing the
+  /// commit input with the recovery key backed up when the commit was created.
+  fn rebuild_reveal(
+    wallet: &Wallet,
+    reveal: Reveal,
+    original: &Transaction,
+    commit: &Transaction,
+    dry_run: bool,
+  ) -> Result<Transaction> {
+    let Reveal {
+      commit_input,
+      mut prevouts,
+      transaction: mut reveal,
+    } = reveal;
+
+    let witness = &reveal.input[commit_input].witness;
+
+    let script = witness
+      .tapscript()
+      .ok_or_else(|| anyhow!("reveal transaction commit input is not a script path spend"))?
+      .to_owned();
+
+    let control_block = ControlBlock::decode(witness.last().unwrap())?;
+
+    let vout = usize::try_from(reveal.input[commit_input].previous_output.vout).unwrap();
+
+    reveal.input[commit_input].previous_output.txid = commit.compute_txid();
+    prevouts[commit_input] = commit.output[vout].clone();
+
+    for txin in &mut reveal.input {
+      txin.witness = Witness::new();
+    }
+
+    let secp256k1 = Secp256k1::new();
+
+    let key_pair = Self::recovery_key_pair(
+      wallet,
+      &secp256k1,
+      &original.output[vout].script_pubkey,
+      &control_block,
+      &script,
+    )?;
+
+    if dry_run {
+      return Ok(reveal);
+    }
+
+    let mut sighash_cache = SighashCache::new(&mut reveal);
+
+    let sighash = sighash_cache
+      .taproot_script_spend_signature_hash(
+        commit_input,
+        &Prevouts::All(&prevouts),
+        TapLeafHash::from_script(&script, LeafVersion::TapScript),
+        TapSighashType::Default,
+      )
+      .expect("signature hash should compute");
+
+    let signature = secp256k1.sign_schnorr(
+      &secp256k1::Message::from_digest_slice(sighash.as_ref())
+        .expect("should be cryptographically secure hash"),
+      &key_pair,
+    );
+
+    let witness = sighash_cache
+      .witness_mut(commit_input)
+      .expect("getting mutable witness reference should work");
+
+    witness.push(
+      Signature {
+        signature,
+        sighash_type: TapSighashType::Default,
+      }
+      .to_vec(),
+    );
+
+    witness.push(script);
+    witness.push(control_block.serialize());
+
+    if reveal.input.len() == 1 {
+      return Ok(reveal);
+    }
+
+    let result = wallet.bitcoin_client().sign_raw_transaction_with_wallet(
+      &reveal,
+      Some(
+        &commit
+          .output
+          .iter()
+          .enumerate()
+          .map(|(vout, output)| SignRawTransactionInput {
+            txid: commit.compute_txid(),
+            vout: vout.try_into().unwrap(),
+            script_pub_key: output.script_pubkey.clone(),
+            redeem_script: None,
+            amount: Some(output.value),
+          })
+          .collect::<Vec<SignRawTransactionInput>>(),
+      ),
+      None,
+    )?;
+
+    ensure!(
+      result.complete,
+      format!("Failed to sign reveal transaction: {:?}", result.errors)
+    );
+
+    Ok(result.transaction()?)
+  }
+
+  /// Finds the recovery key backed up for the commit output with
+  /// `script_pubkey`, and removes the taproot tweak, yielding the key which
+  /// signs the reveal script path spend.
+  fn recovery_key_pair(
+    wallet: &Wallet,
+    secp256k1: &Secp256k1<secp256k1::All>,
+    script_pubkey: &Script,
+    control_block: &ControlBlock,
+    script: &Script,
+  ) -> Result<Keypair> {
+    let descriptors = wallet
+      .bitcoin_client()
+      .call::<ListDescriptorsResult>("listdescriptors", &[serde_json::to_value(true)?])?
+      .descriptors;
+
+    let recovery_key = descriptors
+      .iter()
+      .filter_map(|descriptor| {
+        let wif = descriptor.desc.strip_prefix("rawtr(")?.split(')').next()?;
+        PrivateKey::from_wif(wif).ok()
+      })
+      .find(|private_key| {
+        let (x_only_public_key, _parity) = private_key.inner.x_only_public_key(secp256k1);
+        ScriptBuf::new_p2tr_tweaked(TweakedPublicKey::dangerous_assume_tweaked(
+          x_only_public_key,
+        )) == *script_pubkey
+      })
+      .ok_or_else(|| anyhow!("commit transaction recovery key not found in wallet"))?;
+
+    let mut merkle_root =
+      TapNodeHash::from(TapLeafHash::from_script(script, control_block.leaf_version));
+
+    for node in control_block.merkle_branch.iter() {
+      merkle_root = TapNodeHash::from_node_hashes(merkle_root, *node);
+    }
+
+    let tweak =
+      TapTweakHash::from_key_and_tweak(control_block.internal_key, Some(merkle_root)).to_scalar();
+
+    let key_pair = Keypair::from_secret_key(
+      secp256k1,
+      &recovery_key.inner.negate().add_tweak(&tweak)?.negate(),
+    );
+
+    ensure! {
+      key_pair.x_only_public_key().0 == control_block.internal_key,
+      "commit transaction recovery key does not match reveal transaction",
+    }
+
+    Ok(key_pair)
+  }
+}
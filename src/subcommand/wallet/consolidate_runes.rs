@@ -0,0 +1,414 @@
+use {
+  super::*,
+  crate::wallet::transaction_builder::TransactionBuilder,
+  bitcoin::Weight,
+  std::{cmp::Reverse, iter},
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct ConsolidateRunes {
+  #[arg(long, help = "Don't sign or broadcast transactions")]
+  pub(crate) dry_run: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
+  fee_rate: FeeRate,
+  #[arg(
+    long,
+    help = "Do not build transactions heavier than <MAX_WEIGHT> weight units. \
+    [default: 400000]",
+    value_name = "MAX_WEIGHT"
+  )]
+  pub(crate) max_weight: Option<u64>,
+  #[arg(
+    long,
+    default_value = "1",
+    help = "Consolidate runic outputs into <OUTPUTS> outputs, or <OUTPUTS> outputs per rune with \
+    `--per-rune`"
+  )]
+  pub(crate) outputs: usize,
+  #[arg(
+    long,
+    help = "Consolidate outputs of each rune separately. Outputs holding more than one rune are \
+    not consolidated"
+  )]
+  pub(crate) per_rune: bool,
+  #[arg(
+    long,
+    help = "Include <AMOUNT> postage with consolidated outputs. [default: 10000 sat]",
+    value_name = "AMOUNT"
+  )]
+  pub(crate) postage: Option<Amount>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub transactions: Vec<Consolidation>,
+  pub inputs: usize,
+  pub outputs: usize,
+  pub fee: u64,
+  pub fee_saved: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Consolidation {
+  pub txid: Txid,
+  pub psbt: String,
+  pub fee: u64,
+  pub inputs: usize,
+  pub outputs: usize,
+}
+
+impl ConsolidateRunes {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    ensure!(
+      wallet.has_rune_index(),
+      "`ord wallet consolidate-runes` requires index created with `--index-runes`",
+    );
+
+    ensure!(
+      self.outputs > 0,
+      "runic outputs must be consolidated into at least one output",
+    );
+
+    let max_weight = self.max_weight.unwrap_or(MAX_STANDARD_TX_WEIGHT.into());
+
+    let inscribed = wallet
+      .inscriptions()
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<HashSet<OutPoint>>();
+
+    let runic = wallet.get_runic_outputs()?.unwrap_or_default();
+
+    let mut ids = BTreeMap::new();
+    let mut balances = BTreeMap::<OutPoint, BTreeMap<RuneId, u128>>::new();
+
+    for output in &runic {
+      if inscribed.contains(output) {
+        continue;
+      }
+
+      let mut runes = BTreeMap::new();
+
+      for (spaced_rune, pile) in wallet
+        .get_runes_balances_in_output(output)?
+        .unwrap_or_default()
+      {
+        let id = match ids.get(&spaced_rune.rune) {
+          Some(id) => *id,
+          None => {
+            let (id, _entry, _parent) = wallet
+              .get_rune(spaced_rune.rune)?
+              .with_context(|| format!("rune `{}` has not been etched", spaced_rune.rune))?;
+            ids.insert(spaced_rune.rune, id);
+            id
+          }
+        };
+
+        runes.insert(id, pile.amount);
+      }
+
+      balances.insert(*output, runes);
+    }
+
+    let groups = if self.per_rune {
+      let mut groups = BTreeMap::<RuneId, Vec<OutPoint>>::new();
+
+      for (output, runes) in &balances {
+        if let [(id, _amount)] = runes.iter().collect::<Vec<_>>().as_slice() {
+          groups.entry(**id).or_default().push(*output);
+        }
+      }
+
+      groups.into_values().collect::<Vec<Vec<OutPoint>>>()
+    } else {
+      vec![balances.keys().copied().collect()]
+    };
+
+    let groups = groups
+      .into_iter()
+      .filter(|group| group.len() > self.outputs)
+      .collect::<Vec<Vec<OutPoint>>>();
+
+    ensure!(!groups.is_empty(), "no runic outputs to consolidate");
+
+    let mut cardinals = wallet
+      .utxos()
+      .iter()
+      .filter(|(outpoint, _txout)| {
+        !inscribed.contains(outpoint)
+          && !runic.contains(outpoint)
+          && !wallet.locked_utxos().contains_key(outpoint)
+      })
+      .map(|(outpoint, txout)| (*outpoint, txout.value))
+      .collect::<Vec<(OutPoint, Amount)>>();
+
+    cardinals.sort_by_key(|(_outpoint, value)| Reverse(*value));
+
+    let change = wallet.get_change_address()?.script_pubkey();
+
+    let mut used = HashSet::new();
+    let mut planned = Vec::new();
+
+    for group in groups {
+      // inputs are added to a transaction until its weight would exceed the
+      // maximum, assuming that it has the most outputs it might have
+      let scripts = vec![change.clone(); self.outputs];
+
+      let mut chunks = Vec::new();
+      let mut chunk = Vec::new();
+
+      for output in group {
+        chunk.push(output);
+
+        if chunk.len() > 1 {
+          let (_transaction, _fee, weight) = self.build(
+            &wallet, &chunk, &scripts, &change, &balances, &cardinals, &used,
+          )?;
+
+          if weight > max_weight {
+            chunk.pop();
+            chunks.push(chunk);
+            chunk = vec![output];
+          }
+        }
+      }
+
+      chunks.push(chunk);
+
+      // consolidating a single output would only move it
+      chunks.retain(|chunk| chunk.len() > 1);
+
+      for (i, chunk) in chunks.iter().enumerate() {
+        let outputs = if chunks.len() >= self.outputs {
+          1
+        } else {
+          self.outputs / chunks.len() + usize::from(i < self.outputs % chunks.len())
+        };
+
+        let scripts = (0..outputs)
+          .map(|_| Ok(wallet.get_change_address()?.script_pubkey()))
+          .collect::<Result<Vec<ScriptBuf>>>()?;
+
+        let (transaction, fee, weight) = self.build(
+          &wallet, chunk, &scripts, &change, &balances, &cardinals, &used,
+        )?;
+
+        ensure!(
+          weight <= max_weight,
+          "consolidation transaction weight {weight} over maximum weight {max_weight}",
+        );
+
+        used.extend(transaction.input.iter().map(|txin| txin.previous_output));
+
+        planned.push((transaction, fee, chunk.len(), outputs));
+      }
+    }
+
+    ensure!(!planned.is_empty(), "no runic outputs to consolidate");
+
+    let mut transactions = Vec::new();
+
+    for (transaction, fee, inputs, outputs) in planned {
+      let (txid, psbt) = wallet.sign_and_broadcast(&transaction, self.dry_run, None)?;
+
+      transactions.push(Consolidation {
+        txid,
+        psbt,
+        fee: fee.to_sat(),
+        inputs,
+        outputs,
+      });
+    }
+
+    let inputs = transactions
+      .iter()
+      .map(|consolidation| consolidation.inputs)
+      .sum::<usize>();
+
+    let outputs = transactions
+      .iter()
+      .map(|consolidation| consolidation.outputs)
+      .sum::<usize>();
+
+    Ok(Some(Box::new(Output {
+      fee: transactions
+        .iter()
+        .map(|consolidation| consolidation.fee)
+        .sum(),
+      fee_saved: self
+        .fee_rate
+        .fee((inputs - outputs) * TransactionBuilder::ADDITIONAL_INPUT_VBYTES)
+        .to_sat(),
+      inputs,
+      outputs,
+      transactions,
+    })))
+  }
+
+  /// Builds a transaction which consolidates the runes in `inputs` into
+  /// outputs with `scripts`, funded by the largest unused cardinal outputs if
+  /// the postage of `inputs` does not cover the fee, and returns it along with
+  /// its fee and estimated weight.
+  ///
+  /// The runestone is the first output, and allocates each rune evenly between
+  /// the consolidated outputs which follow it, with the last output receiving
+  /// any remainder.
+  #[allow(clippy::too_many_arguments)]
+  fn build(
+    &self,
+    wallet: &Wallet,
+    inputs: &[OutPoint],
+    scripts: &[ScriptBuf],
+    change: &ScriptBuf,
+    balances: &BTreeMap<OutPoint, BTreeMap<RuneId, u128>>,
+    cardinals: &[(OutPoint, Amount)],
+    used: &HashSet<OutPoint>,
+  ) -> Result<(Transaction, Amount, u64)> {
+    let mut totals = BTreeMap::<RuneId, u128>::new();
+
+    for input in inputs {
+      for (id, amount) in &balances[input] {
+        *totals.entry(*id).or_default() += amount;
+      }
+    }
+
+    let outputs = scripts.len();
+
+    let mut edicts = Vec::new();
+
+    for (id, total) in totals {
+      let share = total / u128::try_from(outputs).unwrap();
+
+      for output in 1..=outputs {
+        if output == outputs {
+          // an edict with amount zero allocates all remaining runes
+          edicts.push(Edict {
+            id,
+            amount: 0,
+            output: output.try_into().unwrap(),
+          });
+        } else if share > 0 {
+          edicts.push(Edict {
+            id,
+            amount: share,
+            output: output.try_into().unwrap(),
+          });
+        }
+      }
+    }
+
+    let runestone = Runestone {
+      edicts,
+      ..default()
+    };
+
+    let runestone_script_pubkey = runestone.encipher();
+
+    let size = runestone_script_pubkey.len();
+
+    ensure!(
+      size <= MAX_STANDARD_OP_RETURN_SIZE,
+      "runestone size {size} over maximum standard OP_RETURN size {MAX_STANDARD_OP_RETURN_SIZE}, \
+      use `--per-rune` to consolidate runes separately",
+    );
+
+    let postage = self.postage.unwrap_or(TARGET_POSTAGE);
+
+    let mut transaction = Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: inputs.iter().copied().map(Self::txin).collect(),
+      output: iter::once(TxOut {
+        value: Amount::ZERO,
+        script_pubkey: runestone_script_pubkey,
+      })
+      .chain(scripts.iter().map(|script_pubkey| TxOut {
+        value: postage,
+        script_pubkey: script_pubkey.clone(),
+      }))
+      .collect(),
+    };
+
+    let mut input_value = inputs
+      .iter()
+      .map(|input| wallet.utxos()[input].value)
+      .sum::<Amount>();
+
+    let output_value = postage * u64::try_from(outputs).unwrap();
+
+    let mut cardinals = cardinals
+      .iter()
+      .filter(|(outpoint, _value)| !used.contains(outpoint));
+
+    loop {
+      let weight = Self::estimate_weight(&transaction);
+
+      let fee = self
+        .fee_rate
+        .fee(weight.to_vbytes_ceil().try_into().unwrap());
+
+      if input_value >= output_value + fee {
+        let mut with_change = transaction.clone();
+
+        with_change.output.push(TxOut {
+          value: Amount::ZERO,
+          script_pubkey: change.clone(),
+        });
+
+        let weight_with_change = Self::estimate_weight(&with_change);
+
+        let fee_with_change = self
+          .fee_rate
+          .fee(weight_with_change.to_vbytes_ceil().try_into().unwrap());
+
+        let excess = input_value
+          .checked_sub(output_value + fee_with_change)
+          .unwrap_or_default();
+
+        let (transaction, fee, weight) = if excess >= change.minimal_non_dust() {
+          with_change.output.last_mut().unwrap().value = excess;
+          (with_change, fee_with_change, weight_with_change)
+        } else {
+          (transaction, input_value - output_value, weight)
+        };
+
+        assert_eq!(
+          Runestone::decipher(&transaction),
+          Some(Artifact::Runestone(runestone)),
+        );
+
+        return Ok((transaction, fee, weight.to_wu()));
+      }
+
+      let Some((outpoint, value)) = cardinals.next() else {
+        bail!(
+          "wallet does not contain enough cardinal UTXOs, please add additional funds to wallet."
+        );
+      };
+
+      transaction.input.push(Self::txin(*outpoint));
+      input_value += *value;
+    }
+  }
+
+  fn txin(previous_output: OutPoint) -> TxIn {
+    TxIn {
+      previous_output,
+      script_sig: ScriptBuf::new(),
+      sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+      witness: Witness::new(),
+    }
+  }
+
+  /// Estimates the weight of `transaction` once its inputs are signed,
+  /// assuming that they are all taproot key path spends.
+  fn estimate_weight(transaction: &Transaction) -> Weight {
+    let mut transaction = transaction.clone();
+
+    for txin in &mut transaction.input {
+      txin.witness = Witness::from_slice(&[&[0; 64]]);
+    }
+
+    transaction.weight()
+  }
+}
@@ -1,4 +1,4 @@
-use super::*;
+use {super::*, crate::wallet::Descriptor, bitcoincore_rpc::bitcoincore_rpc_json::Timestamp};
 
 pub(crate) fn run(wallet: Wallet) -> SubcommandResult {
   eprintln!(
@@ -8,9 +8,27 @@ pub(crate) fn run(wallet: Wallet) -> SubcommandResult {
 =========================================="
   );
 
-  Ok(Some(Box::new(
-    wallet
-      .bitcoin_client()
-      .call::<ListDescriptorsResult>("listdescriptors", &[serde_json::to_value(true)?])?,
-  )))
+  // watch-only wallets have no private keys of their own, but may hold
+  // recovery keys for commit transactions
+  let mut descriptors = wallet.bitcoin_client().call::<ListDescriptorsResult>(
+    "listdescriptors",
+    &[serde_json::to_value(!wallet.watch_only())?],
+  )?;
+
+  for recovery_key in wallet.recovery_keys()? {
+    let descriptor = format!("rawtr({})", recovery_key.to_wif());
+
+    let info = wallet.bitcoin_client().get_descriptor_info(&descriptor)?;
+
+    descriptors.descriptors.push(Descriptor {
+      desc: format!("{descriptor}#{}", info.checksum.unwrap_or_default()),
+      timestamp: Timestamp::Now,
+      active: false,
+      internal: Some(false),
+      range: None,
+      next: None,
+    });
+  }
+
+  Ok(Some(Box::new(descriptors)))
 }
@@ -0,0 +1,594 @@
+use {
+  super::*,
+  std::{cmp::Reverse, convert::Infallible, ops::Range},
+};
+
+#[derive(Debug, Clone)]
+pub(crate) enum Target {
+  Rarity(Rarity),
+  Sat(Sat),
+  Tsv(PathBuf),
+}
+
+impl FromStr for Target {
+  type Err = Infallible;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Ok(if let Ok(rarity) = s.parse() {
+      Self::Rarity(rarity)
+    } else if let Ok(sat) = s.parse() {
+      Self::Sat(sat)
+    } else {
+      Self::Tsv(s.into())
+    })
+  }
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Extract {
+  #[arg(long, help = "Don't sign or broadcast transaction")]
+  pub(crate) dry_run: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
+  fee_rate: FeeRate,
+  #[arg(
+    help = "Extract <TARGET> into its own output. <TARGET> may be a rarity, in which case all \
+    sats of that rarity or rarer are extracted, a sat, or a tab-separated value file whose first \
+    column lists sats to extract"
+  )]
+  target: Target,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub txid: Txid,
+  pub psbt: String,
+  pub fee: u64,
+  pub extracted: Vec<Extracted>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Extracted {
+  pub sat: Sat,
+  pub satpoint: SatPoint,
+}
+
+struct Split {
+  outpoint: OutPoint,
+  value: u64,
+  sats: Vec<(u64, Sat)>,
+  pieces: Vec<(Range<u64>, bool)>,
+}
+
+impl Extract {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    ensure!(
+      wallet.has_sat_index(),
+      "extract requires index created with `--index-sats` flag"
+    );
+
+    let ranges = wallet.get_wallet_sat_ranges()?;
+
+    let runic = wallet.get_runic_outputs()?.unwrap_or_default();
+
+    let mut outputs = BTreeMap::<OutPoint, Vec<(u64, Sat)>>::new();
+
+    for (sat, satpoint) in self.targets(&ranges)? {
+      ensure!(
+        !runic.contains(&satpoint.outpoint),
+        "cannot extract sat {sat} from runic output {}",
+        satpoint.outpoint,
+      );
+
+      outputs
+        .entry(satpoint.outpoint)
+        .or_default()
+        .push((satpoint.offset, sat));
+    }
+
+    let dust = wallet
+      .get_change_address()?
+      .script_pubkey()
+      .minimal_non_dust()
+      .to_sat();
+
+    let mut splits = Vec::new();
+
+    for (outpoint, mut sats) in outputs {
+      sats.sort();
+
+      let value = wallet.utxos()[&outpoint].value.to_sat();
+
+      let offsets = sats
+        .iter()
+        .map(|(offset, _sat)| *offset)
+        .collect::<Vec<u64>>();
+
+      let pieces = Self::pieces(value, &offsets, dust);
+
+      if pieces.len() == 1 {
+        continue;
+      }
+
+      for (satpoint, inscriptions) in wallet.inscriptions() {
+        if satpoint.outpoint != outpoint || offsets.contains(&satpoint.offset) {
+          continue;
+        }
+
+        if pieces
+          .iter()
+          .any(|(range, extracted)| *extracted && range.contains(&satpoint.offset))
+        {
+          bail!(
+            "cannot extract sats from output {outpoint} without also extracting inscription {} \
+            at {satpoint}",
+            inscriptions[0],
+          );
+        }
+      }
+
+      splits.push(Split {
+        outpoint,
+        value,
+        sats,
+        pieces,
+      });
+    }
+
+    ensure!(!splits.is_empty(), "sats are already in their own outputs");
+
+    // fees are paid with the last sats of the last input, so the output with
+    // the largest trailing change piece goes last, in case it can pay the fee
+    splits.sort_by_key(|split| match split.pieces.last() {
+      Some((range, false)) => range.end - range.start,
+      _ => 0,
+    });
+
+    let mut transaction = Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: Vec::new(),
+      output: Vec::new(),
+    };
+
+    let mut extracted = Vec::new();
+
+    for split in &splits {
+      transaction.input.push(Self::txin(split.outpoint));
+
+      let mut sats = split.sats.iter().peekable();
+
+      for (range, _extracted) in &split.pieces {
+        while let Some((offset, sat)) = sats.next_if(|(offset, _sat)| range.contains(offset)) {
+          extracted.push((*sat, transaction.output.len(), offset - range.start));
+        }
+
+        transaction.output.push(TxOut {
+          value: Amount::from_sat(range.end - range.start),
+          script_pubkey: wallet.get_change_address()?.script_pubkey(),
+        });
+      }
+    }
+
+    let fee = self.fund(&wallet, &ranges, &splits, &mut transaction, dust)?;
+
+    let (txid, psbt) = wallet.sign_and_broadcast(&transaction, self.dry_run, None)?;
+
+    Ok(Some(Box::new(Output {
+      txid,
+      psbt,
+      fee: fee.to_sat(),
+      extracted: extracted
+        .into_iter()
+        .map(|(sat, vout, offset)| Extracted {
+          sat,
+          satpoint: SatPoint {
+            outpoint: OutPoint {
+              txid,
+              vout: vout.try_into().unwrap(),
+            },
+            offset,
+          },
+        })
+        .collect(),
+    })))
+  }
+
+  /// Returns the sats to extract, along with their current location.
+  fn targets(&self, ranges: &[(OutPoint, Vec<(u64, u64)>)]) -> Result<Vec<(Sat, SatPoint)>> {
+    match &self.target {
+      Target::Rarity(rarity) => {
+        ensure!(*rarity > Rarity::Common, "common sats cannot be extracted");
+
+        let mut targets = Vec::new();
+
+        for (outpoint, sat_ranges) in ranges {
+          let mut offset = 0;
+          for (start, end) in sat_ranges {
+            for sat in Self::rare_sats(*start, *end) {
+              if sat.rarity() >= *rarity {
+                targets.push((
+                  sat,
+                  SatPoint {
+                    outpoint: *outpoint,
+                    offset: offset + sat.n() - start,
+                  },
+                ));
+              }
+            }
+            offset += end - start;
+          }
+        }
+
+        ensure!(
+          !targets.is_empty(),
+          "wallet contains no {rarity} or rarer sats"
+        );
+
+        Ok(targets)
+      }
+      Target::Sat(sat) => {
+        for (outpoint, sat_ranges) in ranges {
+          let mut offset = 0;
+          for (start, end) in sat_ranges {
+            if (*start..*end).contains(&sat.n()) {
+              return Ok(vec![(
+                *sat,
+                SatPoint {
+                  outpoint: *outpoint,
+                  offset: offset + sat.n() - start,
+                },
+              )]);
+            }
+            offset += end - start;
+          }
+        }
+
+        bail!("could not find sat `{sat}` in wallet outputs");
+      }
+      Target::Tsv(path) => {
+        let tsv = fs::read_to_string(path)
+          .with_context(|| format!("I/O error reading `{}`", path.display()))?;
+
+        let needles = sats::Sats::needles(&tsv)?;
+
+        let found = sats::Sats::find(&needles, ranges);
+
+        let lost = needles
+          .iter()
+          .filter(|(_sat, value)| !found.contains_key(*value))
+          .map(|(_sat, value)| *value)
+          .collect::<Vec<&str>>();
+
+        ensure!(
+          lost.is_empty(),
+          "could not find sats in wallet outputs: {}",
+          lost.join(", "),
+        );
+
+        Ok(
+          needles
+            .into_iter()
+            .map(|(sat, value)| (sat, found[value]))
+            .collect::<BTreeMap<Sat, SatPoint>>()
+            .into_iter()
+            .collect(),
+        )
+      }
+    }
+  }
+
+  /// Splits an output of `value` sats into change pieces, and extracted pieces
+  /// which contain the sats at `offsets` with as few other sats as possible.
+  /// Pieces are never smaller than `dust`, so gaps between sats that are too
+  /// small to be change are extracted along with them.
+  fn pieces(value: u64, offsets: &[u64], dust: u64) -> Vec<(Range<u64>, bool)> {
+    let mut extracted = Vec::<Range<u64>>::new();
+
+    for &offset in offsets {
+      let end = offset.saturating_add(dust).min(value);
+
+      let range = end.saturating_sub(dust).min(offset)..end;
+
+      match extracted.last_mut() {
+        Some(last) if range.start < last.end.saturating_add(dust) => {
+          last.start = last.start.min(range.start);
+          last.end = last.end.max(range.end);
+        }
+        _ => extracted.push(range),
+      }
+    }
+
+    if let Some(first) = extracted.first_mut() {
+      if first.start < dust {
+        first.start = 0;
+      }
+    }
+
+    if let Some(last) = extracted.last_mut() {
+      if value - last.end < dust {
+        last.end = value;
+      }
+    }
+
+    let mut pieces = Vec::new();
+    let mut start = 0;
+
+    for range in extracted {
+      if start < range.start {
+        pieces.push((start..range.start, false));
+      }
+
+      start = range.end;
+
+      pieces.push((range, true));
+    }
+
+    if start < value {
+      pieces.push((start..value, false));
+    }
+
+    pieces
+  }
+
+  /// Pays the fee for `transaction`, either from the trailing change piece of
+  /// the last split, if the sats that would be lost to fees contain no rare
+  /// sats or inscriptions, or else by adding cardinal inputs which contain
+  /// neither.
+  fn fund(
+    &self,
+    wallet: &Wallet,
+    ranges: &[(OutPoint, Vec<(u64, u64)>)],
+    splits: &[Split],
+    transaction: &mut Transaction,
+    dust: u64,
+  ) -> Result<Amount> {
+    let fee = self.fee_rate.fee(Self::estimate_vsize(transaction));
+
+    let last = splits.last().unwrap();
+
+    if let Some((range, false)) = last.pieces.last() {
+      if range.end - range.start >= fee.to_sat() + dust
+        && !Self::contains_rare_or_inscribed(
+          wallet,
+          ranges,
+          last.outpoint,
+          last.value - fee.to_sat()..last.value,
+        )
+      {
+        transaction.output.last_mut().unwrap().value -= fee;
+        return Ok(fee);
+      }
+    }
+
+    let inscribed = wallet
+      .inscriptions()
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<HashSet<OutPoint>>();
+
+    let runic = wallet.get_runic_outputs()?.unwrap_or_default();
+
+    let mut cardinals = ranges
+      .iter()
+      .filter(|(outpoint, sat_ranges)| {
+        wallet.utxos().contains_key(outpoint)
+          && !wallet.locked_utxos().contains_key(outpoint)
+          && !inscribed.contains(outpoint)
+          && !runic.contains(outpoint)
+          && !splits.iter().any(|split| split.outpoint == *outpoint)
+          && sat_ranges
+            .iter()
+            .all(|(start, end)| Self::rare_sats(*start, *end).next().is_none())
+      })
+      .map(|(outpoint, _sat_ranges)| (*outpoint, wallet.utxos()[outpoint].value))
+      .collect::<Vec<(OutPoint, Amount)>>();
+
+    cardinals.sort_by_key(|(_outpoint, value)| Reverse(*value));
+
+    let mut cardinals = cardinals.into_iter();
+
+    let mut input_value = Amount::ZERO;
+
+    loop {
+      transaction.output.push(TxOut {
+        value: Amount::ZERO,
+        script_pubkey: wallet.get_change_address()?.script_pubkey(),
+      });
+
+      let fee_with_change = self.fee_rate.fee(Self::estimate_vsize(transaction));
+
+      transaction.output.pop();
+
+      let fee = self.fee_rate.fee(Self::estimate_vsize(transaction));
+
+      if input_value >= fee_with_change + Amount::from_sat(dust) {
+        transaction.output.push(TxOut {
+          value: input_value - fee_with_change,
+          script_pubkey: wallet.get_change_address()?.script_pubkey(),
+        });
+        return Ok(fee_with_change);
+      }
+
+      if input_value >= fee {
+        return Ok(input_value);
+      }
+
+      let Some((outpoint, value)) = cardinals.next() else {
+        bail!(
+          "wallet does not contain enough cardinal UTXOs without rare sats, please add \
+          additional funds to wallet."
+        );
+      };
+
+      transaction.input.push(Self::txin(outpoint));
+      input_value += value;
+    }
+  }
+
+  /// Returns whether the sats at `offsets` in `outpoint` contain rare sats or
+  /// inscriptions.
+  fn contains_rare_or_inscribed(
+    wallet: &Wallet,
+    ranges: &[(OutPoint, Vec<(u64, u64)>)],
+    outpoint: OutPoint,
+    offsets: Range<u64>,
+  ) -> bool {
+    if wallet
+      .inscriptions()
+      .keys()
+      .any(|satpoint| satpoint.outpoint == outpoint && offsets.contains(&satpoint.offset))
+    {
+      return true;
+    }
+
+    let Some((_outpoint, sat_ranges)) = ranges.iter().find(|(output, _)| *output == outpoint)
+    else {
+      return true;
+    };
+
+    let mut offset = 0;
+
+    for (start, end) in sat_ranges {
+      let low = offsets.start.max(offset);
+      let high = offsets.end.min(offset + end - start);
+
+      if low < high
+        && Self::rare_sats(start + low - offset, start + high - offset)
+          .next()
+          .is_some()
+      {
+        return true;
+      }
+
+      offset += end - start;
+    }
+
+    false
+  }
+
+  /// Returns the sats in `start..end` which are not common, all of which are
+  /// the first sat of a block.
+  fn rare_sats(start: u64, end: u64) -> impl Iterator<Item = Sat> {
+    (Sat(start).height().n()..=Sat(end.saturating_sub(1).max(start)).height().n())
+      .map(|height| Height(height).starting_sat())
+      .filter(move |sat| (start..end).contains(&sat.n()))
+  }
+
+  fn txin(previous_output: OutPoint) -> TxIn {
+    TxIn {
+      previous_output,
+      script_sig: ScriptBuf::new(),
+      sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+      witness: Witness::new(),
+    }
+  }
+
+  /// Estimates the virtual size of `transaction` once its inputs are signed,
+  /// assuming that they are all taproot key path spends.
+  fn estimate_vsize(transaction: &Transaction) -> usize {
+    let mut transaction = transaction.clone();
+
+    for txin in &mut transaction.input {
+      txin.witness = Witness::from_slice(&[&[0; 64]]);
+    }
+
+    transaction.vsize()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[track_caller]
+  fn case(value: u64, offsets: &[u64], expected: &[(Range<u64>, bool)]) {
+    assert_eq!(Extract::pieces(value, offsets, 330), expected);
+  }
+
+  #[test]
+  fn pieces_sat_at_start() {
+    case(10_000, &[0], &[(0..330, true), (330..10_000, false)]);
+  }
+
+  #[test]
+  fn pieces_sat_in_middle() {
+    case(
+      10_000,
+      &[5_000],
+      &[
+        (0..5_000, false),
+        (5_000..5_330, true),
+        (5_330..10_000, false),
+      ],
+    );
+  }
+
+  #[test]
+  fn pieces_sat_near_start() {
+    case(10_000, &[100], &[(0..430, true), (430..10_000, false)]);
+  }
+
+  #[test]
+  fn pieces_sat_near_end() {
+    case(
+      10_000,
+      &[9_900],
+      &[(0..9_670, false), (9_670..10_000, true)],
+    );
+  }
+
+  #[test]
+  fn pieces_sats_close_together_share_piece() {
+    case(
+      10_000,
+      &[1_000, 1_200],
+      &[
+        (0..1_000, false),
+        (1_000..1_530, true),
+        (1_530..10_000, false),
+      ],
+    );
+  }
+
+  #[test]
+  fn pieces_gap_below_dust_is_extracted() {
+    case(
+      10_000,
+      &[1_000, 1_500],
+      &[
+        (0..1_000, false),
+        (1_000..1_830, true),
+        (1_830..10_000, false),
+      ],
+    );
+  }
+
+  #[test]
+  fn pieces_gap_above_dust_is_change() {
+    case(
+      10_000,
+      &[1_000, 2_000],
+      &[
+        (0..1_000, false),
+        (1_000..1_330, true),
+        (1_330..2_000, false),
+        (2_000..2_330, true),
+        (2_330..10_000, false),
+      ],
+    );
+  }
+
+  #[test]
+  fn pieces_small_output_is_one_piece() {
+    case(500, &[0], &[(0..500, true)]);
+  }
+
+  #[test]
+  fn rare_sats_in_range() {
+    assert_eq!(
+      Extract::rare_sats(0, 150 * COIN_VALUE).collect::<Vec<Sat>>(),
+      [Sat(0), Sat(50 * COIN_VALUE), Sat(100 * COIN_VALUE)],
+    );
+    assert_eq!(Extract::rare_sats(1, 50 * COIN_VALUE).next(), None);
+  }
+}
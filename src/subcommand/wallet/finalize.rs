@@ -0,0 +1,131 @@
+use {super::*, crate::wallet::entry::PlanEntry};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub txid: Txid,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub reveal: Option<Txid>,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Finalize {
+  #[arg(long, help = "Finalize and broadcast signed <PSBT>.")]
+  psbt: String,
+}
+
+impl Finalize {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    let psbt = base64_decode(&self.psbt).context("failed to base64 decode PSBT")?;
+
+    let psbt = Psbt::deserialize(&psbt).context("failed to deserialize PSBT")?;
+
+    let txid = psbt.unsigned_tx.compute_txid();
+
+    let Some(plan) = wallet.load_plan(txid)? else {
+      bail!("transaction {txid} was not planned by wallet");
+    };
+
+    ensure! {
+      psbt.unsigned_tx == plan.psbt.unsigned_tx,
+      "transaction {txid} does not match planned transaction",
+    }
+
+    // the commit transaction may have been broadcast by an earlier run which
+    // failed to broadcast the reveal transaction, in which case the commit
+    // inputs are already spent, so only the reveal transaction is sent
+    if !Self::is_broadcast(&wallet, txid) {
+      Self::finalize(&wallet, &plan, psbt, txid)?;
+    }
+
+    let reveal = plan
+      .reveal
+      .map(|reveal| {
+        let reveal_txid = reveal.compute_txid();
+
+        if Self::is_broadcast(&wallet, reveal_txid) {
+          return Ok(reveal_txid);
+        }
+
+        wallet.send_raw_transaction(&reveal, None).with_context(|| {
+          format!("failed to send reveal transaction after commit transaction {txid}")
+        })
+      })
+      .transpose()?;
+
+    wallet.clear_plan(txid)?;
+
+    Ok(Some(Box::new(Output { txid, reveal })))
+  }
+
+  fn is_broadcast(wallet: &Wallet, txid: Txid) -> bool {
+    wallet
+      .bitcoin_client()
+      .get_transaction(&txid, Some(true))
+      .is_ok()
+  }
+
+  fn finalize(wallet: &Wallet, plan: &PlanEntry, mut psbt: Psbt, txid: Txid) -> Result {
+    for (i, (txin, planned)) in psbt
+      .unsigned_tx
+      .input
+      .iter()
+      .zip(&plan.psbt.inputs)
+      .enumerate()
+    {
+      let outpoint = txin.previous_output;
+
+      // inputs finalized when planned, like the commit input of a reveal
+      // transaction, are signed by ord and not the external signer
+      if let Some(witness) = &planned.final_script_witness {
+        psbt.inputs[i].final_script_witness = Some(witness.clone());
+        continue;
+      }
+
+      ensure! {
+        wallet.utxos().contains_key(&outpoint),
+        "input {outpoint} is no longer unspent in wallet",
+      }
+
+      ensure! {
+        wallet.annotations_match(outpoint, planned)?,
+        "input {outpoint} inscriptions or runes changed since transaction was planned",
+      }
+
+      let input = &psbt.inputs[i];
+
+      ensure! {
+        input.final_script_witness.is_some()
+          || input.final_script_sig.is_some()
+          || input.tap_key_sig.is_some()
+          || !input.tap_script_sigs.is_empty()
+          || !input.partial_sigs.is_empty(),
+        "input {outpoint} is not signed",
+      }
+    }
+
+    let result = wallet
+      .bitcoin_client()
+      .finalize_psbt(&base64_encode(&psbt.serialize()), None)?;
+
+    ensure! {
+      result.complete,
+      "failed to finalize transaction {txid}",
+    }
+
+    let signed_tx = result
+      .hex
+      .ok_or_else(|| anyhow!("failed to finalize transaction {txid}"))?;
+
+    let signed_tx = Transaction::consensus_decode(&mut signed_tx.as_slice())
+      .context("unable to decode finalized transaction")?;
+
+    ensure! {
+      signed_tx.compute_txid() == txid,
+      "finalized transaction does not match planned transaction",
+    }
+
+    wallet.send_raw_transaction(&signed_tx, None)?;
+
+    Ok(())
+  }
+}
@@ -0,0 +1,495 @@
+use {
+  super::*,
+  crate::wallet::entry::AssetsEntry,
+  bitcoincore_rpc::json::GetTransactionResult,
+  std::{
+    collections::HashMap,
+    io::{ErrorKind, Write},
+    iter,
+    ops::Range,
+  },
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct History {
+  #[arg(long, help = "Print history as CSV.")]
+  csv: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub transaction: Txid,
+  pub height: Option<u32>,
+  pub timestamp: Option<u64>,
+  pub confirmations: i32,
+  pub received: u64,
+  pub sent: u64,
+  pub fee: Option<u64>,
+  pub inscriptions_received: Vec<Transfer>,
+  pub inscriptions_sent: Vec<Transfer>,
+  pub runes_received: BTreeMap<SpacedRune, Decimal>,
+  pub runes_sent: BTreeMap<SpacedRune, Decimal>,
+  pub labels: Vec<String>,
+  pub assets_unknown: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Transfer {
+  pub inscription: InscriptionId,
+  pub counterparty: Option<Address<NetworkUnchecked>>,
+}
+
+/// A transaction input or output, with the range of sats it occupies in the
+/// transaction, and whether it belongs to the wallet.
+struct Slot {
+  address: Option<Address<NetworkUnchecked>>,
+  mine: bool,
+  range: Range<u64>,
+}
+
+impl History {
+  const PAGE_SIZE: usize = 1000;
+
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    let client = wallet.bitcoin_client();
+
+    wallet.save_assets()?;
+
+    let mut txids = Vec::new();
+    let mut seen = HashSet::new();
+
+    for skip in (0..).step_by(Self::PAGE_SIZE) {
+      let page = client.list_transactions(None, Some(Self::PAGE_SIZE), Some(skip), None)?;
+
+      if page.is_empty() {
+        break;
+      }
+
+      for tx in page {
+        if seen.insert(tx.info.txid) {
+          txids.push(tx.info.txid);
+        }
+      }
+    }
+
+    seen.extend(wallet.utxos().keys().map(|outpoint| outpoint.txid));
+
+    wallet.prune_assets(&seen)?;
+
+    let mut transactions = BTreeMap::new();
+    for txid in txids {
+      let info = client.get_transaction(&txid, Some(true))?;
+      let transaction = info.transaction()?;
+      transactions.insert(txid, (info, transaction));
+    }
+
+    let mut mine = HashMap::new();
+    let mut output = Vec::new();
+
+    for (txid, (info, transaction)) in &transactions {
+      if let Some(entry) = Self::entry(&wallet, &transactions, &mut mine, *txid, info, transaction)?
+      {
+        output.push(((info.info.blockheight, info.info.blockindex), entry));
+      }
+    }
+
+    // unconfirmed transactions come last
+    output.sort_by_key(|((height, index), _entry)| {
+      (height.unwrap_or(u32::MAX), index.unwrap_or(usize::MAX))
+    });
+
+    let output = output
+      .into_iter()
+      .map(|(_position, entry)| entry)
+      .collect::<Vec<Output>>();
+
+    if self.csv {
+      let mut stdout = io::stdout().lock();
+
+      let result = iter::once(
+        "transaction,height,timestamp,confirmations,received,sent,fee,inscriptions_received,\
+        inscriptions_sent,runes_received,runes_sent,labels,assets_unknown"
+          .to_string(),
+      )
+      .chain(output.iter().map(Output::csv))
+      .try_for_each(|line| writeln!(stdout, "{line}"))
+      .and_then(|()| stdout.flush());
+
+      // a closed pipe, for example from `head`, is not an error
+      return match result {
+        Err(err) if err.kind() != ErrorKind::BrokenPipe => Err(err.into()),
+        _ => Ok(None),
+      };
+    }
+
+    Ok(Some(Box::new(output)))
+  }
+
+  /// Returns the history entry for `transaction`, or `None` if none of its
+  /// inputs or outputs belong to the wallet.
+  fn entry(
+    wallet: &Wallet,
+    transactions: &BTreeMap<Txid, (GetTransactionResult, Transaction)>,
+    mine: &mut HashMap<ScriptBuf, bool>,
+    txid: Txid,
+    info: &GetTransactionResult,
+    transaction: &Transaction,
+  ) -> Result<Option<Output>> {
+    let mut is_mine = |script_pubkey: &Script| -> Result<bool> {
+      if let Some(mine) = mine.get(script_pubkey) {
+        return Ok(*mine);
+      }
+
+      let is_mine = wallet.is_mine(script_pubkey)?;
+      mine.insert(script_pubkey.into(), is_mine);
+      Ok(is_mine)
+    };
+
+    let address = |script_pubkey: &Script| {
+      wallet
+        .chain()
+        .address_from_script(script_pubkey)
+        .ok()
+        .map(|address| address.as_unchecked().clone())
+    };
+
+    // sat ranges of inputs are only known if the values of all previous
+    // inputs are known
+    let mut involved = false;
+    let mut assets_unknown = false;
+    let mut inputs = Vec::new();
+    let mut inputs_known = true;
+    let mut all_inputs_mine = true;
+    let mut offset = 0;
+    let mut input_value = Amount::ZERO;
+    let mut value_in = Amount::ZERO;
+    let mut inscriptions_in = BTreeMap::new();
+    let mut runes_in = BTreeMap::<SpacedRune, Pile>::new();
+
+    for txin in &transaction.input {
+      let previous = if txin.previous_output.is_null() {
+        None
+      } else if let Some((_info, previous)) = transactions.get(&txin.previous_output.txid) {
+        Some(previous.clone())
+      } else {
+        wallet
+          .bitcoin_client()
+          .get_raw_transaction(&txin.previous_output.txid, None)
+          .ok()
+      };
+
+      let Some(txout) = previous.and_then(|previous| {
+        previous
+          .output
+          .into_iter()
+          .nth(txin.previous_output.vout.try_into().unwrap())
+      }) else {
+        inputs_known = false;
+        all_inputs_mine = false;
+        continue;
+      };
+
+      let mine = is_mine(&txout.script_pubkey)?;
+
+      let range = offset..offset + txout.value.to_sat();
+
+      if mine {
+        involved = true;
+        value_in += txout.value;
+
+        let assets = wallet
+          .load_assets(txin.previous_output)?
+          .unwrap_or_else(|| {
+            assets_unknown = true;
+            AssetsEntry::default()
+          });
+
+        for (inscription_offset, inscription) in assets.inscriptions {
+          inscriptions_in.insert(inscription, range.start + inscription_offset);
+        }
+
+        for (rune, pile) in assets.runes {
+          runes_in
+            .entry(rune)
+            .and_modify(|total| total.amount += pile.amount)
+            .or_insert(pile);
+        }
+      } else {
+        all_inputs_mine = false;
+      }
+
+      input_value += txout.value;
+      offset = range.end;
+
+      if inputs_known {
+        inputs.push(Slot {
+          address: address(&txout.script_pubkey),
+          mine,
+          range,
+        });
+      }
+    }
+
+    let mut outputs = Vec::new();
+    let mut offset = 0;
+    let mut output_value = Amount::ZERO;
+    let mut value_out = Amount::ZERO;
+    let mut inscriptions_out = BTreeMap::new();
+    let mut runes_out = BTreeMap::<SpacedRune, Pile>::new();
+
+    for (vout, txout) in transaction.output.iter().enumerate() {
+      let mine = is_mine(&txout.script_pubkey)?;
+
+      let range = offset..offset + txout.value.to_sat();
+
+      if mine {
+        involved = true;
+        value_out += txout.value;
+
+        let assets = wallet
+          .load_assets(OutPoint {
+            txid,
+            vout: vout.try_into().unwrap(),
+          })?
+          .unwrap_or_else(|| {
+            assets_unknown = true;
+            AssetsEntry::default()
+          });
+
+        for (inscription_offset, inscription) in assets.inscriptions {
+          inscriptions_out.insert(inscription, range.start + inscription_offset);
+        }
+
+        for (rune, pile) in assets.runes {
+          runes_out
+            .entry(rune)
+            .and_modify(|total| total.amount += pile.amount)
+            .or_insert(pile);
+        }
+      }
+
+      output_value += txout.value;
+      offset = range.end;
+
+      outputs.push(Slot {
+        address: address(&txout.script_pubkey),
+        mine,
+        range,
+      });
+    }
+
+    if !involved {
+      return Ok(None);
+    }
+
+    // the counterparty of a transfer is the owner of the input the inscription
+    // came from, or the output it went to, if they are not the wallet, and
+    // inscriptions created by the transaction have none
+    let counterparty = |slots: &[Slot], offset: u64| {
+      slots
+        .iter()
+        .find(|slot| slot.range.contains(&offset))
+        .filter(|slot| !slot.mine)
+        .and_then(|slot| slot.address.clone())
+    };
+
+    let inscriptions_received = inscriptions_out
+      .iter()
+      .filter(|(inscription, _offset)| !inscriptions_in.contains_key(inscription))
+      .map(|(inscription, offset)| Transfer {
+        inscription: *inscription,
+        counterparty: if inputs_known && inscription.txid != txid {
+          counterparty(&inputs, *offset)
+        } else {
+          None
+        },
+      })
+      .collect();
+
+    let inscriptions_sent = inscriptions_in
+      .iter()
+      .filter(|(inscription, _offset)| !inscriptions_out.contains_key(inscription))
+      .map(|(inscription, offset)| Transfer {
+        inscription: *inscription,
+        counterparty: if inputs_known {
+          counterparty(&outputs, *offset)
+        } else {
+          None
+        },
+      })
+      .collect();
+
+    let mut runes_received = BTreeMap::new();
+    let mut runes_sent = BTreeMap::new();
+
+    for rune in runes_in.keys().chain(runes_out.keys()) {
+      let pile_in = runes_in.get(rune);
+      let pile_out = runes_out.get(rune);
+
+      let divisibility = pile_in.or(pile_out).unwrap().divisibility;
+      let amount_in = pile_in.map(|pile| pile.amount).unwrap_or_default();
+      let amount_out = pile_out.map(|pile| pile.amount).unwrap_or_default();
+
+      if amount_out > amount_in {
+        runes_received.insert(
+          *rune,
+          Decimal {
+            value: amount_out - amount_in,
+            scale: divisibility,
+          },
+        );
+      } else if amount_in > amount_out {
+        runes_sent.insert(
+          *rune,
+          Decimal {
+            value: amount_in - amount_out,
+            scale: divisibility,
+          },
+        );
+      }
+    }
+
+    // the fee is only attributable to the wallet if it funded every input
+    let fee = all_inputs_mine
+      .then(|| input_value.checked_sub(output_value))
+      .flatten();
+
+    let received = value_out.checked_sub(value_in).unwrap_or_default();
+
+    let sent = value_in
+      .checked_sub(value_out + fee.unwrap_or_default())
+      .unwrap_or_default();
+
+    let mut labels = info
+      .details
+      .iter()
+      .filter_map(|detail| detail.label.clone())
+      .collect::<Vec<String>>();
+
+    labels.sort();
+    labels.dedup();
+
+    Ok(Some(Output {
+      transaction: txid,
+      height: info.info.blockheight,
+      timestamp: info.info.blocktime,
+      confirmations: info.info.confirmations,
+      received: received.to_sat(),
+      sent: sent.to_sat(),
+      fee: fee.map(|fee| fee.to_sat()),
+      inscriptions_received,
+      inscriptions_sent,
+      runes_received,
+      runes_sent,
+      labels,
+      assets_unknown,
+    }))
+  }
+}
+
+impl Output {
+  fn csv(&self) -> String {
+    fn optional<T: Display>(value: Option<T>) -> String {
+      value.map(|value| value.to_string()).unwrap_or_default()
+    }
+
+    fn transfers(transfers: &[Transfer]) -> String {
+      transfers
+        .iter()
+        .map(|transfer| match &transfer.counterparty {
+          Some(address) => format!(
+            "{}:{}",
+            transfer.inscription,
+            address.clone().assume_checked()
+          ),
+          None => transfer.inscription.to_string(),
+        })
+        .collect::<Vec<String>>()
+        .join(";")
+    }
+
+    fn runes(runes: &BTreeMap<SpacedRune, Decimal>) -> String {
+      runes
+        .iter()
+        .map(|(rune, amount)| format!("{rune}:{amount}"))
+        .collect::<Vec<String>>()
+        .join(";")
+    }
+
+    [
+      self.transaction.to_string(),
+      optional(self.height),
+      optional(self.timestamp),
+      self.confirmations.to_string(),
+      self.received.to_string(),
+      self.sent.to_string(),
+      optional(self.fee),
+      transfers(&self.inscriptions_received),
+      transfers(&self.inscriptions_sent),
+      runes(&self.runes_received),
+      runes(&self.runes_sent),
+      self.labels.join(";"),
+      self.assets_unknown.to_string(),
+    ]
+    .iter()
+    .map(|field| {
+      if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+      } else {
+        field.clone()
+      }
+    })
+    .collect::<Vec<String>>()
+    .join(",")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn csv() {
+    let output = Output {
+      transaction: txid(1),
+      height: Some(2),
+      timestamp: Some(3),
+      confirmations: 4,
+      received: 5,
+      sent: 0,
+      fee: None,
+      inscriptions_received: vec![Transfer {
+        inscription: inscription_id(1),
+        counterparty: Some(
+          "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+            .parse()
+            .unwrap(),
+        ),
+      }],
+      inscriptions_sent: Vec::new(),
+      runes_received: [(
+        SpacedRune {
+          rune: Rune(0),
+          spacers: 0,
+        },
+        Decimal {
+          value: 15,
+          scale: 1,
+        },
+      )]
+      .into(),
+      runes_sent: BTreeMap::new(),
+      labels: vec!["rent, \"june\"".into(), "gift".into()],
+      assets_unknown: false,
+    };
+
+    assert_eq!(
+      output.csv(),
+      format!(
+        "{},2,3,4,5,0,,{}:bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4,,A:1.5,,\"rent, \"\"june\"\";gift\",false",
+        txid(1),
+        inscription_id(1),
+      ),
+    );
+  }
+}
@@ -0,0 +1,46 @@
+use super::*;
+
+pub mod buy;
+pub mod create;
+
+#[derive(Debug, Parser)]
+pub(crate) enum Listing {
+  #[command(about = "Buy inscription listed for sale")]
+  Buy(buy::Buy),
+  #[command(about = "List inscription for sale")]
+  Create(create::Create),
+}
+
+impl Listing {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    match self {
+      Self::Buy(buy) => buy.run(wallet),
+      Self::Create(create) => create.run(wallet),
+    }
+  }
+}
+
+/// Listings are signed with `SIGHASH_SINGLE|ANYONECANPAY`, which commits the
+/// seller's signature to the seller's input and to the payment output at the
+/// same index, allowing the buyer to add inputs and outputs.
+pub(crate) const LISTING_SIGHASH_TYPE: u8 = 0x83;
+
+/// Returns the sighash type of the signature in a finalized input, or `None`
+/// if the input is not finalized. Taproot signatures without a sighash byte
+/// use `SIGHASH_DEFAULT`, which is returned as zero.
+pub(crate) fn sighash_type(input: &bitcoin::psbt::Input) -> Option<u8> {
+  let signature = match (&input.final_script_witness, &input.final_script_sig) {
+    (Some(witness), _) => witness.nth(0)?,
+    (None, Some(script_sig)) => match script_sig.instructions().next()?.ok()? {
+      script::Instruction::PushBytes(bytes) => bytes.as_bytes(),
+      script::Instruction::Op(_) => return None,
+    },
+    (None, None) => return None,
+  };
+
+  if signature.len() == 64 {
+    Some(0)
+  } else {
+    signature.last().copied()
+  }
+}
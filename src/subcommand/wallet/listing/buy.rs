@@ -0,0 +1,248 @@
+use super::*;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub fee: u64,
+  pub txid: Txid,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Buy {
+  #[arg(long, help = "Assert listing is for <AMOUNT>, excluding postage.")]
+  amount: Amount,
+  #[arg(long, help = "Don't sign or broadcast transaction")]
+  dry_run: bool,
+  #[arg(long, help = "<FEE_RATE> for purchase transaction.")]
+  fee_rate: FeeRate,
+  #[arg(long, help = "Assert listing is for <INSCRIPTION>")]
+  inscription: InscriptionId,
+  #[arg(long, help = "Buy <PSBT> listing")]
+  psbt: String,
+}
+
+impl Buy {
+  pub(crate) fn run(&self, wallet: Wallet) -> SubcommandResult {
+    let psbt = base64_decode(&self.psbt).context("failed to base64 decode PSBT")?;
+
+    let listing = Psbt::deserialize(&psbt).context("failed to deserialize PSBT")?;
+
+    ensure! {
+      listing.unsigned_tx.input.len() == 1 && listing.unsigned_tx.output.len() == 1,
+      "listing must contain exactly one input and one output",
+    }
+
+    let incoming = listing.unsigned_tx.input[0].previous_output;
+
+    ensure! {
+      !wallet.utxos().contains_key(&incoming),
+      "listing input {incoming} owned by wallet",
+    }
+
+    ensure! {
+      sighash_type(&listing.inputs[0]) == Some(LISTING_SIGHASH_TYPE),
+      "listing input {incoming} must be signed with SIGHASH_SINGLE|ANYONECANPAY",
+    }
+
+    let Some(output) = wallet.get_output(incoming)? else {
+      bail!("listing input {incoming} not found");
+    };
+
+    ensure! {
+      !output.spent,
+      "listing input {incoming} already spent",
+    }
+
+    if let Some(runes) = &output.runes {
+      ensure! {
+        runes.is_empty(),
+        "listing input {incoming} contains runes",
+      }
+    }
+
+    let Some(inscriptions) = output.inscriptions else {
+      bail!("index must have inscription index to buy listing");
+    };
+
+    ensure! {
+      inscriptions.len() <= 1,
+      "listing input {incoming} contains {} inscriptions", inscriptions.len(),
+    }
+
+    let Some(inscription) = inscriptions.into_iter().next() else {
+      bail!("listing input contains no inscriptions");
+    };
+
+    ensure! {
+      inscription == self.inscription,
+      "unexpected listed inscription {inscription}",
+    }
+
+    let postage = Amount::from_sat(output.value);
+
+    let payment = listing.unsigned_tx.output[0].clone();
+
+    ensure! {
+      payment.value == self.amount + postage,
+      "unexpected listing payment of {}, expected {} plus {} postage",
+      payment.value, self.amount, postage,
+    }
+
+    // The seller's input must be preceded by two padding inputs, so that the
+    // payment output lines up with it. The first output returns exactly the
+    // padding, so the inscription is received at offset zero of the second
+    // output rather than being paid to the seller.
+    let padding = Self::padding(&wallet)?;
+
+    let padding_value = padding
+      .iter()
+      .map(|outpoint| wallet.utxos()[outpoint].value)
+      .sum::<Amount>();
+
+    let mut input = padding
+      .iter()
+      .map(|outpoint| TxIn {
+        previous_output: *outpoint,
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+        witness: Witness::new(),
+      })
+      .collect::<Vec<TxIn>>();
+
+    input.push(listing.unsigned_tx.input[0].clone());
+
+    let tx = Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input,
+      output: vec![
+        TxOut {
+          value: padding_value,
+          script_pubkey: wallet.get_change_address()?.into(),
+        },
+        TxOut {
+          value: postage,
+          script_pubkey: wallet.get_change_address()?.into(),
+        },
+        payment,
+      ],
+    };
+
+    wallet.lock_non_cardinal_outputs()?;
+
+    let tx = fund_raw_transaction(wallet.bitcoin_client(), self.fee_rate, &tx)?;
+
+    let tx = Transaction::consensus_decode(&mut tx.as_slice())?;
+
+    ensure! {
+      tx.input.len() >= 3
+        && tx.output.len() >= 3
+        && tx.input[0].previous_output == padding[0]
+        && tx.input[1].previous_output == padding[1]
+        && tx.input[2] == listing.unsigned_tx.input[0]
+        && tx.output[0].value == padding_value
+        && tx.output[1].value == postage
+        && tx.output[2] == listing.unsigned_tx.output[0],
+      "funded transaction does not preserve listing input and output positions",
+    }
+
+    let mut input_value = postage;
+
+    for input in tx
+      .input
+      .iter()
+      .filter(|input| input.previous_output != incoming)
+    {
+      input_value += wallet
+        .utxos()
+        .get(&input.previous_output)
+        .ok_or_else(|| anyhow!("funding input {} not in wallet", input.previous_output))?
+        .value;
+    }
+
+    let fee = input_value
+      .checked_sub(tx.output.iter().map(|tx_out| tx_out.value).sum())
+      .ok_or_else(|| anyhow!("funded transaction outputs exceed inputs"))?;
+
+    let balance_change = wallet.simulate_transaction(&tx)?;
+
+    ensure! {
+      balance_change == -(self.amount + fee).to_signed()?,
+      "unexpected balance change of {balance_change}",
+    }
+
+    let mut psbt = Psbt::from_unsigned_tx(tx.clone())?;
+
+    psbt.inputs[2] = listing.inputs[0].clone();
+
+    let txid = if self.dry_run {
+      tx.compute_txid()
+    } else {
+      let signed_psbt = wallet
+        .bitcoin_client()
+        .wallet_process_psbt(&base64_encode(&psbt.serialize()), Some(true), None, None)?
+        .psbt;
+
+      let signed_tx = wallet
+        .bitcoin_client()
+        .finalize_psbt(&signed_psbt, None)?
+        .hex
+        .ok_or_else(|| anyhow!("unable to sign transaction"))?;
+
+      let signed_tx = Transaction::consensus_decode(&mut signed_tx.as_slice())
+        .context("unable to decode finalized transaction")?;
+
+      ensure! {
+        signed_tx.input.len() == tx.input.len(),
+        "signed transaction input length mismatch",
+      }
+
+      for (i, input) in signed_tx.input.iter().enumerate() {
+        if i == 2 {
+          ensure! {
+            Some(&input.witness) == listing.inputs[0].final_script_witness.as_ref()
+              || listing.inputs[0].final_script_witness.is_none(),
+            "listing input `{incoming}` signature changed after signing",
+          }
+        } else {
+          ensure! {
+            !input.witness.is_empty() || !input.script_sig.is_empty(),
+            "buyer input `{}` was not signed by wallet", input.previous_output,
+          }
+        }
+      }
+
+      wallet.send_raw_transaction(&signed_tx, None)?
+    };
+
+    Ok(Some(Box::new(Output {
+      fee: fee.to_sat(),
+      txid,
+    })))
+  }
+
+  /// Returns the wallet's two smallest cardinal outputs, to be spent as
+  /// padding.
+  fn padding(wallet: &Wallet) -> Result<[OutPoint; 2]> {
+    let inscribed = wallet
+      .inscriptions()
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<BTreeSet<OutPoint>>();
+
+    let runic = wallet.get_runic_outputs()?.unwrap_or_default();
+
+    let mut cardinal = wallet
+      .utxos()
+      .iter()
+      .filter(|(outpoint, _tx_out)| !inscribed.contains(outpoint) && !runic.contains(outpoint))
+      .map(|(outpoint, tx_out)| (tx_out.value, *outpoint))
+      .collect::<Vec<(Amount, OutPoint)>>();
+
+    cardinal.sort();
+
+    match cardinal.as_slice() {
+      [(_, first), (_, second), ..] => Ok([*first, *second]),
+      _ => bail!("wallet must contain two cardinal utxos to pad listing input"),
+    }
+  }
+}
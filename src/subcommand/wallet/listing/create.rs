@@ -0,0 +1,95 @@
+use {super::*, bitcoin::sighash::EcdsaSighashType};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub inscription: InscriptionId,
+  pub payment: u64,
+  pub psbt: String,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Create {
+  #[arg(long, help = "List <INSCRIPTION> for sale.")]
+  inscription: InscriptionId,
+  #[arg(
+    long,
+    help = "Sell for <AMOUNT>. The inscription's postage is added to the payment, so that the seller receives <AMOUNT>."
+  )]
+  amount: Amount,
+}
+
+impl Create {
+  pub(crate) fn run(&self, wallet: Wallet) -> SubcommandResult {
+    let Some(inscription) = wallet.inscription_info().get(&self.inscription).cloned() else {
+      bail!("inscription {} not in wallet", self.inscription);
+    };
+
+    let outgoing = inscription.satpoint.outpoint;
+
+    let Some(postage) = wallet.utxos().get(&outgoing).map(|tx_out| tx_out.value) else {
+      bail!(
+        "inscription {} output {outgoing} not in wallet",
+        self.inscription
+      );
+    };
+
+    if let Some(runes) = wallet.get_runes_balances_in_output(&outgoing)? {
+      ensure! {
+        runes.is_empty(),
+        "outgoing input {} contains runes", outgoing,
+      }
+    }
+
+    let Some(inscriptions) = wallet.get_inscriptions_in_output(&outgoing)? else {
+      bail!("index must have inscription index to create listing");
+    };
+
+    ensure! {
+      inscriptions == [self.inscription],
+      "outgoing input {} contains {} inscriptions", outgoing, inscriptions.len(),
+    }
+
+    let payment = self.amount + postage;
+
+    let tx = Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: outgoing,
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+        witness: Witness::new(),
+      }],
+      output: vec![TxOut {
+        value: payment,
+        script_pubkey: wallet.get_change_address()?.into(),
+      }],
+    };
+
+    let psbt = Psbt::from_unsigned_tx(tx)?;
+
+    let result = wallet
+      .bitcoin_client()
+      .call::<String>("utxoupdatepsbt", &[base64_encode(&psbt.serialize()).into()])?;
+
+    let result = wallet.bitcoin_client().wallet_process_psbt(
+      &result,
+      Some(true),
+      Some(EcdsaSighashType::SinglePlusAnyoneCanPay.into()),
+      None,
+    )?;
+
+    let psbt = Psbt::deserialize(&base64_decode(&result.psbt)?)?;
+
+    ensure! {
+      sighash_type(&psbt.inputs[0]) == Some(LISTING_SIGHASH_TYPE),
+      "failed to sign listing input with SIGHASH_SINGLE|ANYONECANPAY",
+    }
+
+    Ok(Some(Box::new(Output {
+      inscription: self.inscription,
+      payment: payment.to_sat(),
+      psbt: result.psbt,
+    })))
+  }
+}
@@ -20,6 +20,8 @@ pub struct Output {
   pub rune: SpacedRune,
   pub pile: Pile,
   pub mint: Txid,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub psbt: Option<String>,
 }
 
 impl Mint {
@@ -93,6 +95,24 @@ impl Mint {
     let unsigned_transaction =
       fund_raw_transaction(bitcoin_client, self.fee_rate, &unfunded_transaction)?;
 
+    let pile = Pile {
+      amount,
+      divisibility: rune_entry.divisibility,
+      symbol: rune_entry.symbol,
+    };
+
+    if wallet.signs_externally() {
+      let unsigned_transaction =
+        consensus::encode::deserialize::<Transaction>(&unsigned_transaction)?;
+
+      return Ok(Some(Box::new(Output {
+        rune: self.rune,
+        pile,
+        mint: unsigned_transaction.compute_txid(),
+        psbt: Some(wallet.plan_psbt(&Psbt::from_unsigned_tx(unsigned_transaction)?, None, true)?),
+      })));
+    }
+
     let signed_transaction = bitcoin_client
       .sign_raw_transaction_with_wallet(&unsigned_transaction, None, None)?
       .hex;
@@ -104,16 +124,15 @@ impl Mint {
       Some(Artifact::Runestone(runestone)),
     );
 
+    wallet.save_spent_assets(&signed_transaction)?;
+
     let transaction = bitcoin_client.send_raw_transaction(&signed_transaction)?;
 
     Ok(Some(Box::new(Output {
       rune: self.rune,
-      pile: Pile {
-        amount,
-        divisibility: rune_entry.divisibility,
-        symbol: rune_entry.symbol,
-      },
+      pile,
       mint: transaction,
+      psbt: None,
     })))
   }
 }
@@ -5,9 +5,9 @@ pub mod create;
 
 #[derive(Debug, Parser)]
 pub(crate) enum Offer {
-  #[command(about = "Accept offer to buy inscription")]
+  #[command(about = "Accept offer to buy inscription or runes")]
   Accept(accept::Accept),
-  #[command(about = "Create offer to buy inscription")]
+  #[command(about = "Create offer to buy inscription or runes")]
   Create(create::Create),
 }
 
@@ -1,4 +1,7 @@
-use super::*;
+use {
+  super::*,
+  crate::index::{allocate_runes, Lot},
+};
 
 #[derive(PartialEq)]
 enum Signature<'a> {
@@ -12,13 +15,21 @@ pub struct Output {
 }
 
 #[derive(Debug, Parser)]
+#[clap(
+group(
+  ArgGroup::new("outgoing")
+    .required(true)
+    .args(&["inscription", "rune"])))
+]
 pub(crate) struct Accept {
   #[arg(long, help = "Assert offer is for <AMOUNT>")]
   amount: Amount,
   #[arg(long, help = "Don't sign or broadcast transaction")]
   dry_run: bool,
   #[arg(long, help = "Assert offer is for <INSCRIPTION>")]
-  inscription: InscriptionId,
+  inscription: Option<InscriptionId>,
+  #[arg(long, help = "Assert offer is for <DECIMAL:RUNE>, e.g. `100:FOO`")]
+  rune: Option<Outgoing>,
   #[arg(long, help = "Accept <PSBT> offer")]
   psbt: String,
 }
@@ -46,31 +57,12 @@ impl Accept {
       bail!("PSBT contains no inputs owned by wallet");
     };
 
-    if let Some(runes) = wallet.get_runes_balances_in_output(&outgoing)? {
-      ensure! {
-        runes.is_empty(),
-        "outgoing input {} contains runes", outgoing,
+    match (self.inscription, &self.rune) {
+      (Some(inscription), None) => Self::check_inscription(&wallet, outgoing, inscription)?,
+      (None, Some(Outgoing::Rune { decimal, rune })) => {
+        Self::check_rune(&wallet, &psbt, index, *decimal, *rune)?
       }
-    }
-
-    let Some(inscriptions) = wallet.get_inscriptions_in_output(&outgoing)? else {
-      bail! {
-        "index must have inscription index to accept PSBT",
-      }
-    };
-
-    ensure! {
-      inscriptions.len() <= 1,
-      "outgoing input {} contains {} inscriptions", outgoing, inscriptions.len(),
-    }
-
-    let Some(inscription) = inscriptions.into_iter().next() else {
-      bail!("outgoing input contains no inscriptions");
-    };
-
-    ensure! {
-      inscription == self.inscription,
-      "unexpected outgoing inscription {inscription}",
+      _ => bail!("--rune must be a rune amount formatted as `DECIMAL:RUNE`"),
     }
 
     let balance_change = wallet.simulate_transaction(&psbt.unsigned_tx)?;
@@ -149,6 +141,168 @@ impl Accept {
     Ok(Some(Box::new(Output { txid })))
   }
 
+  fn check_inscription(wallet: &Wallet, outgoing: OutPoint, expected: InscriptionId) -> Result {
+    if let Some(runes) = wallet.get_runes_balances_in_output(&outgoing)? {
+      ensure! {
+        runes.is_empty(),
+        "outgoing input {} contains runes", outgoing,
+      }
+    }
+
+    let Some(inscriptions) = wallet.get_inscriptions_in_output(&outgoing)? else {
+      bail! {
+        "index must have inscription index to accept PSBT",
+      }
+    };
+
+    ensure! {
+      inscriptions.len() <= 1,
+      "outgoing input {} contains {} inscriptions", outgoing, inscriptions.len(),
+    }
+
+    let Some(inscription) = inscriptions.into_iter().next() else {
+      bail!("outgoing input contains no inscriptions");
+    };
+
+    ensure! {
+      inscription == expected,
+      "unexpected outgoing inscription {inscription}",
+    }
+
+    Ok(())
+  }
+
+  /// Checks that the offer's runestone sends exactly the offered amount of
+  /// `spaced_rune` from the seller's input to outputs not paying the seller,
+  /// and that no runes from the seller's input are burned or sent elsewhere.
+  fn check_rune(
+    wallet: &Wallet,
+    psbt: &Psbt,
+    index: usize,
+    decimal: Decimal,
+    spaced_rune: SpacedRune,
+  ) -> Result {
+    let outgoing = psbt.unsigned_tx.input[index].previous_output;
+
+    if let Some(inscriptions) = wallet.get_inscriptions_in_output(&outgoing)? {
+      ensure! {
+        inscriptions.is_empty(),
+        "outgoing input {} contains {} inscriptions", outgoing, inscriptions.len(),
+      }
+    }
+
+    let Some(balances) = wallet.get_runes_balances_in_output(&outgoing)? else {
+      bail!("index must have rune index to accept rune offer");
+    };
+
+    let (id, entry, _parent) = wallet
+      .get_rune(spaced_rune.rune)?
+      .with_context(|| format!("rune `{}` has not been etched", spaced_rune.rune))?;
+
+    let amount = decimal.to_integer(entry.divisibility)?;
+
+    let mut unallocated = BTreeMap::new();
+
+    for (rune, pile) in balances {
+      let (id, _entry, _parent) = wallet
+        .get_rune(rune.rune)?
+        .with_context(|| format!("rune `{rune}` has not been etched"))?;
+
+      unallocated.insert(id, pile.amount);
+    }
+
+    ensure! {
+      unallocated.get(&id).copied().unwrap_or_default() >= amount,
+      "outgoing input {outgoing} does not contain {} `{spaced_rune}`",
+      Pile {
+        amount,
+        divisibility: entry.divisibility,
+        symbol: entry.symbol,
+      },
+    }
+
+    for (i, input) in psbt.unsigned_tx.input.iter().enumerate() {
+      if i == index {
+        continue;
+      }
+
+      let Some(output) = wallet.get_output(input.previous_output)? else {
+        bail!("buyer input {} not found", input.previous_output);
+      };
+
+      ensure! {
+        output.runes.unwrap_or_default().is_empty(),
+        "buyer input {} contains runes", input.previous_output,
+      }
+    }
+
+    let artifact = Runestone::decipher(&psbt.unsigned_tx);
+
+    let runestone = match &artifact {
+      Some(Artifact::Runestone(runestone)) => runestone,
+      Some(Artifact::Cenotaph(_)) => bail!("offer runestone is a cenotaph"),
+      None => bail!("offer does not contain a runestone"),
+    };
+
+    ensure! {
+      runestone.etching.is_none() && runestone.mint.is_none(),
+      "offer runestone may not etch or mint runes",
+    }
+
+    let seller = &wallet.utxos()[&outgoing].script_pubkey;
+
+    let (allocated, burned) = allocate_runes(
+      &psbt.unsigned_tx,
+      artifact.as_ref(),
+      unallocated
+        .iter()
+        .map(|(id, amount)| (*id, Lot(*amount)))
+        .collect(),
+      None,
+    );
+
+    ensure! {
+      burned.is_empty(),
+      "offer burns runes from outgoing input {outgoing}",
+    }
+
+    for (rune_id, balance) in unallocated {
+      let mut sent = 0;
+      let mut returned = 0;
+
+      for (tx_out, allocated) in psbt.unsigned_tx.output.iter().zip(&allocated) {
+        let Some(allocated) = allocated.get(&rune_id).map(|lot| lot.0) else {
+          continue;
+        };
+
+        ensure! {
+          !tx_out.script_pubkey.is_op_return(),
+          "offer burns runes from outgoing input {outgoing}",
+        }
+
+        if tx_out.script_pubkey == *seller {
+          returned += allocated;
+        } else {
+          sent += allocated;
+        }
+      }
+
+      ensure! {
+        sent + returned == balance,
+        "offer burns runes from outgoing input {outgoing}",
+      }
+
+      let expected = if rune_id == id { amount } else { 0 };
+
+      ensure! {
+        sent == expected,
+        "offer sends {sent} units of rune {rune_id} from outgoing input {outgoing}, expected {expected}",
+      }
+    }
+
+    Ok(())
+  }
+
   fn psbt_signatures(psbt: &Psbt) -> Result<Vec<Option<Signature>>> {
     psbt
       .inputs
@@ -4,13 +4,30 @@ use super::*;
 pub struct Output {
   pub psbt: String,
   pub seller_address: Address<NetworkUnchecked>,
-  pub inscription: InscriptionId,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub inscription: Option<InscriptionId>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub rune: Option<Outgoing>,
 }
 
 #[derive(Debug, Parser)]
+#[clap(
+group(
+  ArgGroup::new("outgoing")
+    .required(true)
+    .args(&["inscription", "rune"])))
+]
 pub(crate) struct Create {
   #[arg(long, help = "<INSCRIPTION> to make offer for.")]
-  inscription: InscriptionId,
+  inscription: Option<InscriptionId>,
+  #[arg(
+    long,
+    requires = "utxo",
+    help = "<DECIMAL:RUNE> to make offer for, e.g. `100:FOO`."
+  )]
+  rune: Option<Outgoing>,
+  #[arg(long, help = "<UTXO> holding runes to make offer for.")]
+  utxo: Option<OutPoint>,
   #[arg(long, help = "<AMOUNT> to offer.")]
   amount: Amount,
   #[arg(long, help = "<FEE_RATE> for finalized transaction.")]
@@ -19,24 +36,67 @@ pub(crate) struct Create {
 
 impl Create {
   pub(crate) fn run(&self, wallet: Wallet) -> SubcommandResult {
+    let (tx, seller_address) = match (self.inscription, &self.rune, self.utxo) {
+      (Some(inscription), None, None) => self.inscription_offer(&wallet, inscription)?,
+      (None, Some(Outgoing::Rune { decimal, rune }), Some(utxo)) => {
+        self.rune_offer(&wallet, *decimal, *rune, utxo)?
+      }
+      (None, Some(_), _) => bail!("--rune must be a rune amount formatted as `DECIMAL:RUNE`"),
+      _ => bail!("--utxo may only be used with --rune"),
+    };
+
+    wallet.lock_non_cardinal_outputs()?;
+
+    let tx = fund_raw_transaction(wallet.bitcoin_client(), self.fee_rate, &tx)?;
+
+    let tx = Transaction::consensus_decode(&mut tx.as_slice())?;
+
+    let psbt = Psbt::from_unsigned_tx(tx)?;
+
+    let result = wallet
+      .bitcoin_client()
+      .call::<String>("utxoupdatepsbt", &[base64_encode(&psbt.serialize()).into()])?;
+
+    let result = wallet
+      .bitcoin_client()
+      .wallet_process_psbt(&result, Some(true), None, None)?;
+
+    ensure! {
+      !result.complete,
+      "PSBT unexpectedly complete after processing with wallet",
+    }
+
+    Ok(Some(Box::new(Output {
+      psbt: result.psbt,
+      inscription: self.inscription,
+      rune: self.rune.clone(),
+      seller_address: seller_address.into_unchecked(),
+    })))
+  }
+
+  fn inscription_offer(
+    &self,
+    wallet: &Wallet,
+    inscription_id: InscriptionId,
+  ) -> Result<(Transaction, Address)> {
     ensure!(
-      !wallet.inscription_info().contains_key(&self.inscription),
+      !wallet.inscription_info().contains_key(&inscription_id),
       "inscription {} already in wallet",
-      self.inscription
+      inscription_id
     );
 
-    let Some(inscription) = wallet.get_inscription(self.inscription)? else {
-      bail!("inscription {} does not exist", self.inscription);
+    let Some(inscription) = wallet.get_inscription(inscription_id)? else {
+      bail!("inscription {} does not exist", inscription_id);
     };
 
     let Some(postage) = inscription.value else {
-      bail!("inscription {} unbound", self.inscription);
+      bail!("inscription {} unbound", inscription_id);
     };
 
     let Some(seller_address) = inscription.address else {
       bail!(
         "inscription {} script pubkey not valid address",
-        self.inscription,
+        inscription_id,
       );
     };
 
@@ -68,31 +128,121 @@ impl Create {
       ],
     };
 
-    wallet.lock_non_cardinal_outputs()?;
+    Ok((tx, seller_address))
+  }
 
-    let tx = fund_raw_transaction(wallet.bitcoin_client(), self.fee_rate, &tx)?;
+  fn rune_offer(
+    &self,
+    wallet: &Wallet,
+    decimal: Decimal,
+    spaced_rune: SpacedRune,
+    utxo: OutPoint,
+  ) -> Result<(Transaction, Address)> {
+    ensure!(
+      wallet.has_rune_index(),
+      "creating rune offers requires index created with `--index-runes` flag",
+    );
 
-    let tx = Transaction::consensus_decode(&mut tx.as_slice())?;
+    ensure!(
+      !wallet.utxos().contains_key(&utxo),
+      "utxo {utxo} already in wallet",
+    );
 
-    let psbt = Psbt::from_unsigned_tx(tx)?;
+    let (id, entry, _parent) = wallet
+      .get_rune(spaced_rune.rune)?
+      .with_context(|| format!("rune `{}` has not been etched", spaced_rune.rune))?;
 
-    let result = wallet
-      .bitcoin_client()
-      .call::<String>("utxoupdatepsbt", &[base64_encode(&psbt.serialize()).into()])?;
+    let amount = decimal.to_integer(entry.divisibility)?;
 
-    let result = wallet
-      .bitcoin_client()
-      .wallet_process_psbt(&result, Some(true), None, None)?;
+    ensure!(amount > 0, "offer must be for a nonzero rune amount");
+
+    let Some(output) = wallet.get_output(utxo)? else {
+      bail!("utxo {utxo} does not exist");
+    };
+
+    ensure!(!output.spent, "utxo {utxo} already spent");
+
+    if let Some(inscriptions) = &output.inscriptions {
+      ensure!(
+        inscriptions.is_empty(),
+        "utxo {utxo} contains {} inscriptions",
+        inscriptions.len(),
+      );
+    }
+
+    let balance = output
+      .runes
+      .unwrap_or_default()
+      .into_iter()
+      .find(|(rune, _pile)| rune.rune == spaced_rune.rune)
+      .map(|(_rune, pile)| pile.amount)
+      .unwrap_or_default();
 
     ensure! {
-      !result.complete,
-      "PSBT unexpectedly complete after processing with wallet",
+      balance >= amount,
+      "utxo {utxo} holds {} `{}`, less than offered amount",
+      Pile {
+        amount: balance,
+        divisibility: entry.divisibility,
+        symbol: entry.symbol,
+      },
+      spaced_rune,
     }
 
-    Ok(Some(Box::new(Output {
-      psbt: result.psbt,
-      inscription: self.inscription,
-      seller_address: seller_address.into_unchecked(),
-    })))
+    let Some(seller_address) = output.address else {
+      bail!("utxo {utxo} script pubkey not valid address");
+    };
+
+    let seller_address = seller_address.require_network(wallet.chain().network())?;
+
+    // The offered amount is sent to the buyer, and any change, along with any
+    // other runes held by the seller's output, is returned to the seller in
+    // the payment output.
+    let mut edicts = vec![Edict {
+      amount,
+      id,
+      output: 0,
+    }];
+
+    if balance > amount {
+      edicts.push(Edict {
+        amount: balance - amount,
+        id,
+        output: 1,
+      });
+    }
+
+    let runestone = Runestone {
+      edicts,
+      pointer: Some(1),
+      ..default()
+    };
+
+    let tx = Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: utxo,
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+        witness: Witness::new(),
+      }],
+      output: vec![
+        TxOut {
+          value: TARGET_POSTAGE,
+          script_pubkey: wallet.get_change_address()?.into(),
+        },
+        TxOut {
+          value: self.amount + Amount::from_sat(output.value),
+          script_pubkey: seller_address.clone().into(),
+        },
+        TxOut {
+          value: Amount::from_sat(0),
+          script_pubkey: runestone.encipher(),
+        },
+      ],
+    };
+
+    Ok((tx, seller_address))
   }
 }
@@ -33,6 +33,8 @@ pub(crate) struct Restore {
 enum Source {
   Descriptor,
   Mnemonic,
+  Multisig,
+  Xpub,
 }
 
 impl Restore {
@@ -79,6 +81,42 @@ impl Restore {
             .0,
         )?;
       }
+      Source::Multisig => {
+        io::stdin().read_to_string(&mut buffer)?;
+
+        ensure!(
+          self.passphrase.is_none(),
+          "multisig descriptor does not take a passphrase"
+        );
+
+        Wallet::initialize_multisig(
+          name,
+          settings,
+          &buffer,
+          self
+            .timestamp
+            .unwrap_or(Timestamp(bitcoincore_rpc::json::Timestamp::Time(0)))
+            .0,
+        )?;
+      }
+      Source::Xpub => {
+        io::stdin().read_line(&mut buffer)?;
+
+        ensure!(
+          self.passphrase.is_none(),
+          "extended public key does not take a passphrase"
+        );
+
+        Wallet::initialize_watch_only(
+          name,
+          settings,
+          &buffer,
+          self
+            .timestamp
+            .unwrap_or(Timestamp(bitcoincore_rpc::json::Timestamp::Time(0)))
+            .0,
+        )?;
+      }
     }
 
     Ok(None)
@@ -88,7 +88,7 @@ impl Sats {
     }
   }
 
-  fn find(
+  pub(crate) fn find(
     needles: &[(Sat, &str)],
     ranges: &[(OutPoint, Vec<(u64, u64)>)],
   ) -> BTreeMap<String, SatPoint> {
@@ -131,7 +131,7 @@ impl Sats {
     results
   }
 
-  fn needles(tsv: &str) -> Result<Vec<(Sat, &str)>> {
+  pub(crate) fn needles(tsv: &str) -> Result<Vec<(Sat, &str)>> {
     let mut needles = tsv
       .lines()
       .enumerate()
@@ -0,0 +1,539 @@
+use {super::*, bitcoin::Weight, sendfile::Sendfile, std::cmp::Reverse};
+
+mod sendfile;
+
+#[derive(Debug, Parser)]
+pub(crate) struct SendMany {
+  #[arg(long, help = "Don't sign or broadcast transactions")]
+  pub(crate) dry_run: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
+  fee_rate: FeeRate,
+  #[arg(
+    long,
+    help = "Do not build transactions heavier than <MAX_WEIGHT> weight units. \
+    [default: 400000]",
+    value_name = "MAX_WEIGHT"
+  )]
+  pub(crate) max_weight: Option<u64>,
+  #[arg(
+    long,
+    help = "Target <AMOUNT> postage with sent inscriptions and sats. [default: 10000 sat]",
+    value_name = "AMOUNT"
+  )]
+  pub(crate) postage: Option<Amount>,
+  #[arg(
+    long,
+    help = "Send bitcoin, inscriptions, and sats to recipients defined in YAML <SEND_FILE>.",
+    value_name = "SEND_FILE"
+  )]
+  pub(crate) sends: PathBuf,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub completed: BTreeMap<usize, Txid>,
+  pub transactions: Vec<Sent>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Sent {
+  pub txid: Txid,
+  pub psbt: String,
+  pub fee: u64,
+  pub sends: Vec<usize>,
+}
+
+/// Sends which must be made in the same transaction. Sends of inscriptions
+/// and sats from the same output share a unit, which spends that output.
+/// Sends of bitcoin amounts each get a unit of their own, without an input.
+struct Unit {
+  sends: Vec<usize>,
+  input: Option<(OutPoint, Amount)>,
+  outputs: Vec<TxOut>,
+}
+
+impl SendMany {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    let sendfile = Sendfile::load(&self.sends, wallet.chain().network())?;
+
+    let max_weight = self.max_weight.unwrap_or(MAX_STANDARD_TX_WEIGHT.into());
+
+    let keys = sendfile.keys();
+
+    let mut completed = BTreeMap::new();
+    let mut stale = Vec::new();
+    let mut spent = HashSet::new();
+
+    // sends are recorded before their transaction is broadcast, so sends whose
+    // transaction is unknown to bitcoind were interrupted and must be retried
+    for (send, txid) in wallet.load_sends(&keys)?.into_iter().enumerate() {
+      let Some(txid) = txid else {
+        continue;
+      };
+
+      match wallet.bitcoin_client().get_transaction(&txid, None) {
+        Ok(result) if result.info.confirmations >= 0 => {
+          spent.extend(
+            result
+              .transaction()?
+              .input
+              .iter()
+              .map(|txin| txin.previous_output),
+          );
+          completed.insert(send, txid);
+        }
+        _ => stale.push(keys[send]),
+      }
+    }
+
+    if !stale.is_empty() && !self.dry_run {
+      wallet.clear_sends(&stale)?;
+    }
+
+    let pending = (0..sendfile.sends.len())
+      .filter(|send| !completed.contains_key(send))
+      .collect::<Vec<usize>>();
+
+    if pending.is_empty() {
+      return Ok(Some(Box::new(Output {
+        completed,
+        transactions: Vec::new(),
+      })));
+    }
+
+    let units = self.units(&wallet, &sendfile, &pending, &spent)?;
+
+    let inscribed = wallet
+      .inscriptions()
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<HashSet<OutPoint>>();
+
+    let runic = wallet.get_runic_outputs()?.unwrap_or_default();
+
+    let mut cardinals = wallet
+      .utxos()
+      .iter()
+      .filter(|(outpoint, _txout)| {
+        !inscribed.contains(outpoint)
+          && !runic.contains(outpoint)
+          && !spent.contains(outpoint)
+          && !wallet.locked_utxos().contains_key(outpoint)
+          && !units.iter().any(|unit| {
+            unit
+              .input
+              .is_some_and(|(input, _value)| input == **outpoint)
+          })
+      })
+      .map(|(outpoint, txout)| (*outpoint, txout.value))
+      .collect::<Vec<(OutPoint, Amount)>>();
+
+    cardinals.sort_by_key(|(_outpoint, value)| Reverse(*value));
+
+    let change = wallet.get_change_address()?.script_pubkey();
+
+    // units are added to a transaction until its weight would exceed the
+    // maximum, at which point a new transaction is started
+    let mut planned = Vec::new();
+    let mut used = HashSet::new();
+    let mut batch = Vec::new();
+    let mut funded = None;
+
+    for unit in &units {
+      batch.push(unit);
+
+      let (transaction, fee, weight) = self.fund(&batch, &cardinals, &used, &change)?;
+
+      if weight <= max_weight {
+        funded = Some((transaction, fee));
+        continue;
+      }
+
+      batch.pop();
+
+      let Some((transaction, fee)) = funded.take() else {
+        bail!(
+          "transaction for send {} has weight {weight} over maximum weight {max_weight}",
+          unit.sends[0],
+        );
+      };
+
+      used.extend(transaction.input.iter().map(|txin| txin.previous_output));
+
+      planned.push((transaction, fee, Self::sends(&batch)));
+
+      batch = vec![unit];
+
+      let (transaction, fee, weight) = self.fund(&batch, &cardinals, &used, &change)?;
+
+      ensure!(
+        weight <= max_weight,
+        "transaction for send {} has weight {weight} over maximum weight {max_weight}",
+        unit.sends[0],
+      );
+
+      funded = Some((transaction, fee));
+    }
+
+    let (transaction, fee) = funded.unwrap();
+
+    planned.push((transaction, fee, Self::sends(&batch)));
+
+    let mut transactions = Vec::new();
+
+    for (transaction, fee, sends) in planned {
+      if !self.dry_run {
+        wallet.save_sends(
+          &sends
+            .iter()
+            .map(|&send| keys[send])
+            .collect::<Vec<[u8; 32]>>(),
+          transaction.compute_txid(),
+        )?;
+      }
+
+      let (txid, psbt) = wallet.sign_and_broadcast(&transaction, self.dry_run, None)?;
+
+      transactions.push(Sent {
+        txid,
+        psbt,
+        fee: fee.to_sat(),
+        sends,
+      });
+    }
+
+    Ok(Some(Box::new(Output {
+      completed,
+      transactions,
+    })))
+  }
+
+  /// Groups `pending` sends into units, ordered by their first send.
+  fn units(
+    &self,
+    wallet: &Wallet,
+    sendfile: &Sendfile,
+    pending: &[usize],
+    spent: &HashSet<OutPoint>,
+  ) -> Result<Vec<Unit>> {
+    let inscription_info = wallet.inscription_info();
+
+    let runic = wallet.get_runic_outputs()?.unwrap_or_default();
+
+    let mut units = Vec::new();
+
+    let mut outputs = BTreeMap::<OutPoint, Vec<(u64, usize)>>::new();
+
+    for &send in pending {
+      let recipient = &sendfile.sends[send];
+
+      let satpoint = match recipient.asset {
+        Outgoing::Amount(amount) => {
+          let script_pubkey = recipient.address.script_pubkey();
+
+          let threshold = script_pubkey.minimal_non_dust();
+
+          ensure!(
+            amount >= threshold,
+            "send {send} amount {amount} below dust threshold {threshold}",
+          );
+
+          units.push(Unit {
+            sends: vec![send],
+            input: None,
+            outputs: vec![TxOut {
+              value: amount,
+              script_pubkey,
+            }],
+          });
+
+          continue;
+        }
+        Outgoing::InscriptionId(id) => {
+          inscription_info
+            .get(&id)
+            .ok_or_else(|| anyhow!("inscription {id} not found"))?
+            .satpoint
+        }
+        Outgoing::Sat(sat) => wallet.find_sat_in_outputs(sat)?,
+        Outgoing::SatPoint(satpoint) => {
+          ensure!(
+            !wallet.inscriptions().contains_key(&satpoint),
+            "inscriptions must be sent by inscription ID",
+          );
+          satpoint
+        }
+        Outgoing::Rune { .. } => unreachable!(),
+      };
+
+      let outpoint = satpoint.outpoint;
+
+      let Some(txout) = wallet
+        .utxos()
+        .get(&outpoint)
+        .filter(|_| !spent.contains(&outpoint))
+      else {
+        bail!("output {outpoint} for send {send} not found in wallet");
+      };
+
+      ensure!(
+        satpoint.offset < txout.value.to_sat(),
+        "satpoint {satpoint} for send {send} is past end of output",
+      );
+
+      ensure!(
+        !runic.contains(&outpoint),
+        "output {outpoint} for send {send} contains runes",
+      );
+
+      outputs
+        .entry(outpoint)
+        .or_default()
+        .push((satpoint.offset, send));
+    }
+
+    for (outpoint, sends) in outputs {
+      units.push(self.split(wallet, sendfile, outpoint, sends)?);
+    }
+
+    units.sort_by_key(|unit| unit.sends[0]);
+
+    Ok(units)
+  }
+
+  /// Splits `outpoint` into outputs which send the sats at each offset in
+  /// `sends` with postage, returning the rest of its value to the wallet.
+  fn split(
+    &self,
+    wallet: &Wallet,
+    sendfile: &Sendfile,
+    outpoint: OutPoint,
+    mut sends: Vec<(u64, usize)>,
+  ) -> Result<Unit> {
+    sends.sort();
+
+    for pair in sends.windows(2) {
+      ensure!(
+        pair[0].0 != pair[1].0,
+        "sends {} and {} both send satpoint {}",
+        pair[0].1.min(pair[1].1),
+        pair[0].1.max(pair[1].1),
+        SatPoint {
+          outpoint,
+          offset: pair[0].0,
+        },
+      );
+    }
+
+    let value = wallet.utxos()[&outpoint].value.to_sat();
+
+    let postage = self.postage.unwrap_or(TARGET_POSTAGE).to_sat();
+
+    let dust = wallet
+      .get_change_address()?
+      .script_pubkey()
+      .minimal_non_dust()
+      .to_sat();
+
+    let mut ranges = Vec::new();
+    let mut start = 0;
+
+    for (i, &(offset, send)) in sends.iter().enumerate() {
+      if offset - start >= dust {
+        ranges.push((start, offset, None));
+        start = offset;
+      }
+
+      let next = sends.get(i + 1).map(|(offset, _send)| *offset);
+
+      let mut end = next.unwrap_or(value).min(offset.saturating_add(postage));
+
+      if next.is_none() && value - end < dust {
+        end = value;
+      }
+
+      let threshold = sendfile.sends[send]
+        .address
+        .script_pubkey()
+        .minimal_non_dust();
+
+      ensure!(
+        Amount::from_sat(end - start) >= threshold,
+        "send {send} output value {} below dust threshold {threshold}",
+        Amount::from_sat(end - start),
+      );
+
+      ranges.push((start, end, Some(send)));
+
+      start = end;
+    }
+
+    if start < value {
+      ranges.push((start, value, None));
+    }
+
+    for (satpoint, inscriptions) in wallet.inscriptions() {
+      if satpoint.outpoint != outpoint
+        || sends
+          .iter()
+          .any(|(offset, _send)| *offset == satpoint.offset)
+      {
+        continue;
+      }
+
+      if let Some((_start, _end, Some(send))) = ranges
+        .iter()
+        .find(|(start, end, _send)| (*start..*end).contains(&satpoint.offset))
+      {
+        bail!(
+          "send {send} would also send inscription {} at {satpoint}",
+          inscriptions[0],
+        );
+      }
+    }
+
+    let mut outputs = Vec::new();
+
+    for (start, end, send) in ranges {
+      outputs.push(TxOut {
+        value: Amount::from_sat(end - start),
+        script_pubkey: match send {
+          Some(send) => sendfile.sends[send].address.script_pubkey(),
+          None => wallet.get_change_address()?.script_pubkey(),
+        },
+      });
+    }
+
+    let mut sends = sends
+      .into_iter()
+      .map(|(_offset, send)| send)
+      .collect::<Vec<usize>>();
+
+    sends.sort();
+
+    Ok(Unit {
+      sends,
+      input: Some((outpoint, Amount::from_sat(value))),
+      outputs,
+    })
+  }
+
+  /// Builds a transaction making the sends in `units`, funded by the largest
+  /// unused cardinal outputs, and returns it along with its fee and estimated
+  /// weight.
+  ///
+  /// Inputs and outputs of units which spend an output come first and in the
+  /// same order, so that the sats of each input flow to its unit's outputs.
+  fn fund(
+    &self,
+    units: &[&Unit],
+    cardinals: &[(OutPoint, Amount)],
+    used: &HashSet<OutPoint>,
+    change: &ScriptBuf,
+  ) -> Result<(Transaction, Amount, u64)> {
+    let mut transaction = Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: Vec::new(),
+      output: Vec::new(),
+    };
+
+    let mut input_value = Amount::ZERO;
+
+    for unit in units.iter().filter(|unit| unit.input.is_some()) {
+      let (outpoint, value) = unit.input.unwrap();
+      transaction.input.push(Self::txin(outpoint));
+      transaction.output.extend(unit.outputs.iter().cloned());
+      input_value += value;
+    }
+
+    for unit in units.iter().filter(|unit| unit.input.is_none()) {
+      transaction.output.extend(unit.outputs.iter().cloned());
+    }
+
+    let output_value = transaction
+      .output
+      .iter()
+      .map(|txout| txout.value)
+      .sum::<Amount>();
+
+    let mut cardinals = cardinals
+      .iter()
+      .filter(|(outpoint, _value)| !used.contains(outpoint));
+
+    loop {
+      let weight = Self::estimate_weight(&transaction);
+
+      let fee = self
+        .fee_rate
+        .fee(weight.to_vbytes_ceil().try_into().unwrap());
+
+      if input_value >= output_value + fee {
+        let mut with_change = transaction.clone();
+
+        with_change.output.push(TxOut {
+          value: Amount::ZERO,
+          script_pubkey: change.clone(),
+        });
+
+        let weight_with_change = Self::estimate_weight(&with_change);
+
+        let fee_with_change = self
+          .fee_rate
+          .fee(weight_with_change.to_vbytes_ceil().try_into().unwrap());
+
+        let excess = input_value
+          .checked_sub(output_value + fee_with_change)
+          .unwrap_or_default();
+
+        if excess >= change.minimal_non_dust() {
+          with_change.output.last_mut().unwrap().value = excess;
+          return Ok((with_change, fee_with_change, weight_with_change.to_wu()));
+        }
+
+        return Ok((transaction, input_value - output_value, weight.to_wu()));
+      }
+
+      let Some((outpoint, value)) = cardinals.next() else {
+        bail!(
+          "wallet does not contain enough cardinal UTXOs, please add additional funds to wallet."
+        );
+      };
+
+      transaction.input.push(Self::txin(*outpoint));
+      input_value += *value;
+    }
+  }
+
+  fn txin(previous_output: OutPoint) -> TxIn {
+    TxIn {
+      previous_output,
+      script_sig: ScriptBuf::new(),
+      sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+      witness: Witness::new(),
+    }
+  }
+
+  fn sends(units: &[&Unit]) -> Vec<usize> {
+    let mut sends = units
+      .iter()
+      .flat_map(|unit| unit.sends.iter().copied())
+      .collect::<Vec<usize>>();
+
+    sends.sort();
+
+    sends
+  }
+
+  /// Estimates the weight of `transaction` once its inputs are signed,
+  /// assuming that they are all taproot key path spends.
+  fn estimate_weight(transaction: &Transaction) -> Weight {
+    let mut transaction = transaction.clone();
+
+    for txin in &mut transaction.input {
+      txin.witness = Witness::from_slice(&[&[0; 64]]);
+    }
+
+    transaction.weight()
+  }
+}
@@ -0,0 +1,108 @@
+use {super::*, bitcoin::hashes::sha256};
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SendfileUnchecked {
+  sends: Vec<SendUnchecked>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SendUnchecked {
+  address: Address<NetworkUnchecked>,
+  asset: Outgoing,
+}
+
+pub(crate) struct Sendfile {
+  pub(crate) sends: Vec<Send>,
+}
+
+pub(crate) struct Send {
+  pub(crate) address: Address,
+  pub(crate) asset: Outgoing,
+}
+
+impl Sendfile {
+  pub(crate) fn load(path: &Path, network: Network) -> Result<Self> {
+    let contents = fs::read(path)?;
+
+    let unchecked = serde_yaml::from_slice::<SendfileUnchecked>(&contents)?;
+
+    ensure!(
+      !unchecked.sends.is_empty(),
+      "send file must contain at least one send"
+    );
+
+    let mut sends = Vec::new();
+
+    for (i, send) in unchecked.sends.into_iter().enumerate() {
+      ensure!(
+        !matches!(send.asset, Outgoing::Rune { .. }),
+        "send {i} sends runes, use `ord wallet split` to send runes to multiple recipients",
+      );
+
+      sends.push(Send {
+        address: send.address.require_network(network)?,
+        asset: send.asset,
+      });
+    }
+
+    Ok(Self { sends })
+  }
+
+  /// Returns the keys under which progress of each send is recorded. Keys
+  /// commit to the index, address, and asset of a send, so that progress
+  /// survives edits to the send file which do not change earlier sends.
+  pub(crate) fn keys(&self) -> Vec<[u8; 32]> {
+    self
+      .sends
+      .iter()
+      .enumerate()
+      .map(|(i, send)| {
+        sha256::Hash::hash(format!("{i} {} {}", send.address, send.asset).as_bytes())
+          .to_byte_array()
+      })
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn example_send_file_is_valid() {
+    Sendfile::load("sends.yaml".as_ref(), Network::Bitcoin).unwrap();
+  }
+
+  #[test]
+  fn keys_commit_to_index_address_and_asset() {
+    let address = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+      .parse::<Address<NetworkUnchecked>>()
+      .unwrap()
+      .assume_checked();
+
+    let sendfile = Sendfile {
+      sends: vec![
+        Send {
+          address: address.clone(),
+          asset: "1 btc".parse().unwrap(),
+        },
+        Send {
+          address: address.clone(),
+          asset: "1 btc".parse().unwrap(),
+        },
+        Send {
+          address,
+          asset: "2 btc".parse().unwrap(),
+        },
+      ],
+    };
+
+    let keys = sendfile.keys();
+
+    assert_ne!(keys[0], keys[1]);
+    assert_ne!(keys[1], keys[2]);
+    assert_eq!(keys, sendfile.keys());
+  }
+}
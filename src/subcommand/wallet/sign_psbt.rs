@@ -0,0 +1,125 @@
+use super::*;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub psbt: String,
+  pub complete: bool,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct SignPsbt {
+  #[arg(long, help = "Sign <PSBT> with wallet keys.")]
+  psbt: String,
+}
+
+impl SignPsbt {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    let psbt = base64_decode(&self.psbt).context("failed to base64 decode PSBT")?;
+
+    let psbt = Psbt::deserialize(&psbt).context("failed to deserialize PSBT")?;
+
+    let tx = &psbt.unsigned_tx;
+
+    let mut input_value = Amount::ZERO;
+
+    let mut offsets = BTreeMap::new();
+
+    for (txin, input) in tx.input.iter().zip(&psbt.inputs) {
+      let outpoint = txin.previous_output;
+
+      let value = if let Some(output) = wallet.utxos().get(&outpoint) {
+        ensure! {
+          wallet.annotations_match(outpoint, input)?,
+          "input {outpoint} inscriptions or runes do not match PSBT annotations",
+        }
+
+        output.value
+      } else if let Some(output) = &input.witness_utxo {
+        output.value
+      } else {
+        bail!("input {outpoint} is not in wallet and has no witness UTXO");
+      };
+
+      offsets.insert(outpoint, input_value);
+
+      input_value += value;
+    }
+
+    let output_value = tx.output.iter().map(|output| output.value).sum::<Amount>();
+
+    // inscriptions on sats which would be paid as fees are lost
+    for (satpoint, inscriptions) in wallet.inscriptions() {
+      let Some(offset) = offsets.get(&satpoint.outpoint) else {
+        continue;
+      };
+
+      ensure! {
+        offset.to_sat() + satpoint.offset < output_value.to_sat(),
+        "inscription {} would be lost to fees",
+        inscriptions[0],
+      }
+    }
+
+    // runes in inputs of a transaction with a cenotaph are burned
+    if let Some(runic_outputs) = wallet.get_runic_outputs()? {
+      if let Some(outpoint) = tx
+        .input
+        .iter()
+        .map(|txin| txin.previous_output)
+        .find(|outpoint| runic_outputs.contains(outpoint))
+      {
+        ensure! {
+          !matches!(Runestone::decipher(tx), Some(Artifact::Cenotaph(_))),
+          "runes in input {outpoint} would be burned by cenotaph",
+        }
+      }
+    }
+
+    for (vout, matches) in wallet
+      .output_annotations_match(&psbt)?
+      .into_iter()
+      .enumerate()
+    {
+      ensure! {
+        matches,
+        "output {vout} inscriptions or runes do not match PSBT annotations",
+      }
+    }
+
+    // a PSBT for a transaction planned by this wallet must carry the planned
+    // annotations
+    if let Some(plan) = wallet.load_plan(tx.compute_txid())? {
+      let inputs_match = plan
+        .psbt
+        .inputs
+        .iter()
+        .zip(&psbt.inputs)
+        .all(|(planned, input)| planned.proprietary == input.proprietary);
+
+      let outputs_match = plan
+        .psbt
+        .outputs
+        .iter()
+        .zip(&psbt.outputs)
+        .all(|(planned, output)| planned.proprietary == output.proprietary);
+
+      ensure! {
+        inputs_match && outputs_match,
+        "PSBT does not match transaction {} planned by wallet",
+        tx.compute_txid(),
+      }
+    }
+
+    let result = wallet.bitcoin_client().wallet_process_psbt(
+      &base64_encode(&psbt.serialize()),
+      Some(true),
+      None,
+      None,
+    )?;
+
+    Ok(Some(Box::new(Output {
+      psbt: result.psbt,
+      complete: result.complete,
+    })))
+  }
+}
@@ -6,6 +6,7 @@ pub(crate) use {
   block::BlockHtml,
   children::ChildrenHtml,
   clock::ClockSvg,
+  collection::CollectionHtml,
   collections::CollectionsHtml,
   home::HomeHtml,
   iframe::Iframe,
@@ -22,6 +23,7 @@ pub(crate) use {
   rare::RareTxt,
   rune_not_found::RuneNotFoundHtml,
   sat::SatHtml,
+  search::SearchHtml,
 };
 
 pub use {
@@ -34,6 +36,7 @@ pub mod block;
 pub mod blocks;
 mod children;
 mod clock;
+mod collection;
 pub mod collections;
 mod home;
 mod iframe;
@@ -50,6 +53,7 @@ pub mod rune;
 pub mod rune_not_found;
 pub mod runes;
 pub mod sat;
+mod search;
 pub mod status;
 pub mod transaction;
 
@@ -67,14 +71,39 @@ where
     Self { content, config }
   }
 
-  fn og_image(&self) -> String {
-    if let Some(domain) = &self.config.domain {
-      format!("https://{domain}/static/favicon.png")
+  /// Open Graph images must be absolute URLs, so preview images are omitted
+  /// if the server's domain is not known.
+  fn og_image(&self) -> Option<String> {
+    match (&self.config.domain, self.content.preview_image()) {
+      (Some(domain), Some(path)) => Some(format!("https://{domain}{path}")),
+      (Some(domain), None) => Some(format!("https://{domain}/static/favicon.png")),
+      (None, Some(_)) => None,
+      (None, None) => Some("https://ordinals.com/static/favicon.png".into()),
+    }
+  }
+
+  fn twitter_card(&self) -> &'static str {
+    if self.config.domain.is_some() && self.content.preview_image().is_some() {
+      "summary_large_image"
     } else {
-      "https://ordinals.com/static/favicon.png".into()
+      "summary"
     }
   }
 
+  /// Structured data serialized for embedding in a `<script>` element, with
+  /// characters which could close the element escaped.
+  fn json_ld(&self) -> Option<String> {
+    Some(
+      self
+        .content
+        .structured_data()?
+        .to_string()
+        .replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('&', "\\u0026"),
+    )
+  }
+
   fn superscript(&self) -> String {
     if self.config.chain == Chain::Mainnet {
       "beta".into()
@@ -87,6 +116,20 @@ where
 pub trait PageContent: Display + 'static {
   fn title(&self) -> String;
 
+  fn description(&self) -> Option<String> {
+    None
+  }
+
+  /// Path of an image to show in link previews.
+  fn preview_image(&self) -> Option<String> {
+    None
+  }
+
+  /// JSON-LD structured data describing the page's subject.
+  fn structured_data(&self) -> Option<serde_json::Value> {
+    None
+  }
+
   fn page(self, server_config: Arc<ServerConfig>) -> PageHtml<Self>
   where
     Self: Sized,
@@ -163,6 +206,76 @@ mod tests {
     );
   }
 
+  struct Bar;
+
+  impl Display for Bar {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+      write!(f, "<h1>Bar</h1>")
+    }
+  }
+
+  impl PageContent for Bar {
+    fn title(&self) -> String {
+      "Bar".to_string()
+    }
+
+    fn description(&self) -> Option<String> {
+      Some("A bar".into())
+    }
+
+    fn preview_image(&self) -> Option<String> {
+      Some("/preview/bar".into())
+    }
+
+    fn structured_data(&self) -> Option<serde_json::Value> {
+      Some(serde_json::json!({ "name": "</script>&" }))
+    }
+  }
+
+  #[test]
+  fn page_structured_data() {
+    assert_regex_match!(
+      Bar.page(Arc::new(ServerConfig {
+        chain: Chain::Mainnet,
+        domain: None,
+        ..default()
+      })),
+      r#".*<meta property=og:title content='Bar'>
+    <meta property=og:description content='A bar'>
+    <meta name=description content='A bar'>
+    <meta property=twitter:card content=summary>
+    <script type=application/ld\+json>\{"name":"\\u003c/script\\u003e\\u0026"\}</script>
+    <title>Bar</title>.*"#
+    );
+  }
+
+  #[test]
+  fn page_preview_image_without_domain_is_omitted() {
+    assert!(!Bar
+      .page(Arc::new(ServerConfig {
+        chain: Chain::Mainnet,
+        domain: None,
+        ..default()
+      }))
+      .to_string()
+      .contains("og:image"));
+  }
+
+  #[test]
+  fn page_preview_image_with_domain() {
+    assert_regex_match!(
+      Bar.page(Arc::new(ServerConfig {
+        chain: Chain::Mainnet,
+        domain: Some("ordinals.com".into()),
+        ..default()
+      })),
+      r".*<meta name=description content='A bar'>
+    <meta property=og:image content='https://ordinals.com/preview/bar'>
+    <meta property=twitter:card content=summary_large_image>
+    <script .*"
+    );
+  }
+
   #[test]
   fn page_mainnet() {
     assert_regex_match!(
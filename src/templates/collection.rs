@@ -0,0 +1,153 @@
+use {super::*, crate::index::CollectionOrder};
+
+#[derive(Boilerplate)]
+pub(crate) struct CollectionHtml {
+  pub(crate) collection: api::Collection,
+  pub(crate) traits: Vec<String>,
+  pub(crate) transfers: bool,
+}
+
+impl CollectionHtml {
+  fn link(&self, order: &CollectionOrder, page: usize) -> String {
+    let mut link = format!("/collection/{}", self.collection.parent);
+
+    if page > 0 {
+      link.push_str(&format!("/{page}"));
+    }
+
+    if *order != CollectionOrder::Inscribed {
+      link.push_str(&format!(
+        "?order={}",
+        urlencoding::encode(&order.to_string())
+      ));
+    }
+
+    link
+  }
+
+  fn orders(&self) -> Vec<(String, CollectionOrder)> {
+    let mut orders = vec![("inscribed".into(), CollectionOrder::Inscribed)];
+
+    if self.transfers {
+      orders.push(("transferred".into(), CollectionOrder::Transferred));
+    }
+
+    for key in &self.traits {
+      orders.push((key.clone(), CollectionOrder::Trait(key.clone())));
+    }
+
+    orders
+  }
+}
+
+impl PageContent for CollectionHtml {
+  fn title(&self) -> String {
+    format!("Inscription {} Collection", self.collection.parent_number)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn collection() -> api::Collection {
+    api::Collection {
+      children: vec![inscription_id(2), inscription_id(3)],
+      first_child_height: Some(2),
+      holders: Some(1),
+      last_child_height: Some(5),
+      more: true,
+      order: CollectionOrder::Inscribed,
+      page: 0,
+      parent: inscription_id(1),
+      parent_number: 0,
+      total_children: 102,
+    }
+  }
+
+  #[test]
+  fn first_page() {
+    assert_regex_match!(
+      CollectionHtml {
+        collection: collection(),
+        traits: vec!["eyes".into()],
+        transfers: true,
+      },
+      "
+        <h1><a href=/inscription/1{64}i1>Inscription 0</a> Collection</h1>
+        <dl>
+          <dt>children</dt>
+          <dd>102</dd>
+          <dt>first child height</dt>
+          <dd><a href=/block/2>2</a></dd>
+          <dt>last child height</dt>
+          <dd><a href=/block/5>5</a></dd>
+          <dt>holders</dt>
+          <dd>1</dd>
+        </dl>
+        <div class=center>
+          inscribed
+          <a href=/collection/1{64}i1\\?order=transferred>transferred</a>
+          <a href=/collection/1{64}i1\\?order=trait%3Aeyes>eyes</a>
+        </div>
+        <div class=thumbnails>
+          <a href=/inscription/2{64}i2><iframe .* src=/preview/2{64}i2></iframe></a>
+          <a href=/inscription/3{64}i3><iframe .* src=/preview/3{64}i3></iframe></a>
+        </div>
+        <div class=center>
+        prev
+        <a class=next href=/collection/1{64}i1/1>next</a>
+        </div>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn transferred_order_requires_transfers_index() {
+    assert_regex_match!(
+      CollectionHtml {
+        collection: collection(),
+        traits: Vec::new(),
+        transfers: false,
+      },
+      "
+        .*
+        <div class=center>
+          inscribed
+        </div>
+        .*
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn pagination_preserves_order() {
+    assert_regex_match!(
+      CollectionHtml {
+        collection: api::Collection {
+          more: false,
+          order: CollectionOrder::Transferred,
+          page: 1,
+          ..collection()
+        },
+        traits: Vec::new(),
+        transfers: true,
+      },
+      "
+        .*
+        <div class=center>
+          <a href=/collection/1{64}i1>inscribed</a>
+          transferred
+        </div>
+        .*
+        <div class=center>
+        <a class=prev href=/collection/1{64}i1\\?order=transferred>prev</a>
+        next
+        </div>
+      "
+      .unindent()
+    );
+  }
+}
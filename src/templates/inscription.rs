@@ -25,6 +25,40 @@ impl PageContent for InscriptionHtml {
   fn title(&self) -> String {
     format!("Inscription {}", self.number)
   }
+
+  fn description(&self) -> Option<String> {
+    Some(match self.inscription.content_type() {
+      Some(content_type) => format!(
+        "Inscription {} ({content_type}), inscribed in block {}",
+        self.number, self.height
+      ),
+      None => format!(
+        "Inscription {}, inscribed in block {}",
+        self.number, self.height
+      ),
+    })
+  }
+
+  fn preview_image(&self) -> Option<String> {
+    Some(format!("/preview/{}", self.id))
+  }
+
+  fn structured_data(&self) -> Option<serde_json::Value> {
+    let mut data = serde_json::json!({
+      "@context": "https://schema.org",
+      "@type": "CreativeWork",
+      "dateCreated": self.timestamp.to_rfc3339(),
+      "description": self.description(),
+      "identifier": self.id,
+      "name": self.title(),
+    });
+
+    if let Some(content_type) = self.inscription.content_type() {
+      data["encodingFormat"] = content_type.into();
+    }
+
+    Some(data)
+  }
 }
 
 impl InscriptionHtml {
@@ -327,6 +361,7 @@ mod tests {
             </div>
             <div class=center>
               <a href=/children/1{64}i1>all \\(2\\)</a>
+              <a href=/collection/1{64}i1>collection</a>
             </div>
           </dd>
           <dt>id</dt>
@@ -397,6 +432,7 @@ mod tests {
             </div>
             <div class=center>
               <a href=/children/1{64}i1>all \\(1\\)</a>
+              <a href=/collection/1{64}i1>collection</a>
             </div>
           </dd>
           <dt>id</dt>
@@ -34,6 +34,28 @@ impl PageContent for RuneHtml {
   fn title(&self) -> String {
     format!("Rune {}", self.entry.spaced_rune)
   }
+
+  fn description(&self) -> Option<String> {
+    Some(format!(
+      "Rune {} ({}), etched in block {}",
+      self.entry.spaced_rune, self.id, self.entry.block
+    ))
+  }
+
+  fn preview_image(&self) -> Option<String> {
+    self.parent.map(|parent| format!("/preview/{parent}"))
+  }
+
+  fn structured_data(&self) -> Option<serde_json::Value> {
+    Some(serde_json::json!({
+      "@context": "https://schema.org",
+      "@type": "Thing",
+      "alternateName": self.entry.spaced_rune.rune.to_string(),
+      "description": self.description(),
+      "identifier": self.id,
+      "name": self.title(),
+    }))
+  }
 }
 
 #[cfg(test)]
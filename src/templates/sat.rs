@@ -13,6 +13,34 @@ impl PageContent for SatHtml {
   fn title(&self) -> String {
     format!("Sat {}", self.sat)
   }
+
+  fn description(&self) -> Option<String> {
+    Some(format!(
+      "Sat {} ({}), mined in block {}, rarity {}",
+      self.sat,
+      self.sat.name(),
+      self.sat.height(),
+      self.sat.rarity()
+    ))
+  }
+
+  fn preview_image(&self) -> Option<String> {
+    self
+      .inscriptions
+      .first()
+      .map(|inscription_id| format!("/preview/{inscription_id}"))
+  }
+
+  fn structured_data(&self) -> Option<serde_json::Value> {
+    Some(serde_json::json!({
+      "@context": "https://schema.org",
+      "@type": "Thing",
+      "alternateName": self.sat.name(),
+      "description": self.description(),
+      "identifier": self.sat.n().to_string(),
+      "name": self.title(),
+    }))
+  }
 }
 
 #[cfg(test)]
@@ -0,0 +1,68 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct SearchHtml {
+  pub(crate) inscriptions: Vec<InscriptionId>,
+  pub(crate) query: String,
+  pub(crate) runes: Vec<SpacedRune>,
+  pub(crate) sat: Option<Sat>,
+}
+
+impl PageContent for SearchHtml {
+  fn title(&self) -> String {
+    format!("Search: {}", self.query)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn results() {
+    assert_regex_match!(
+      SearchHtml {
+        inscriptions: vec![inscription_id(1)],
+        query: "foo".into(),
+        runes: vec![SpacedRune {
+          rune: Rune(26),
+          spacers: 1
+        }],
+        sat: None,
+      },
+      "
+        <h1>Search results for foo</h1>
+        <h2>Runes</h2>
+        <ul>
+          <li><a href=/rune/A•A>A•A</a></li>
+        </ul>
+        <h2>Inscriptions</h2>
+        <div class=thumbnails>
+          <a href=/inscription/1{64}i1><iframe .* src=/preview/1{64}i1></iframe></a>
+        </div>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn no_results() {
+    assert_regex_match!(
+      SearchHtml {
+        inscriptions: Vec::new(),
+        query: "<foo>".into(),
+        runes: Vec::new(),
+        sat: Some(Sat(0)),
+      },
+      "
+        <h1>Search results for &lt;foo&gt;</h1>
+        <dl>
+          <dt>sat</dt>
+          <dd><a href=/sat/0>nvtdijuwxlp</a></dd>
+        </dl>
+        <p>No inscriptions or runes found.</p>
+      "
+      .unindent()
+    );
+  }
+}
@@ -5,6 +5,7 @@ pub struct TransactionHtml {
   pub chain: Chain,
   pub etching: Option<SpacedRune>,
   pub inscription_count: u32,
+  pub sat_flow: Option<api::SatFlow>,
   pub transaction: Transaction,
   pub txid: Txid,
 }
@@ -49,6 +50,7 @@ mod tests {
         chain: Chain::Mainnet,
         etching: None,
         inscription_count: 0,
+        sat_flow: None,
         txid: transaction.compute_txid(),
         transaction,
       }.to_string(),
@@ -4,17 +4,26 @@ use {
   bitcoin::{
     bip32::{ChildNumber, DerivationPath, Xpriv},
     psbt::Psbt,
-    secp256k1::Secp256k1,
+    secp256k1::{Secp256k1, SecretKey},
+    PrivateKey,
   },
   bitcoincore_rpc::json::ImportDescriptors,
-  entry::{EtchingEntry, EtchingEntryValue},
+  entry::{
+    AssetsEntry, AssetsEntryValue, EtchingEntry, EtchingEntryValue, PlanEntry, PlanEntryValue,
+  },
   fee_rate::FeeRate,
-  index::entry::Entry,
+  index::{allocate_runes, entry::Entry, Lot},
   indicatif::{ProgressBar, ProgressStyle},
   log::log_enabled,
-  miniscript::descriptor::{DescriptorSecretKey, DescriptorXKey, Wildcard},
-  redb::{Database, DatabaseError, ReadableTable, RepairSession, StorageError, TableDefinition},
-  std::sync::Once,
+  miniscript::{
+    descriptor::{DescriptorPublicKey, DescriptorSecretKey, DescriptorXKey, Wildcard, WshInner},
+    Terminal,
+  },
+  redb::{
+    Database, DatabaseError, ReadableTable, RepairSession, StorageError, TableDefinition,
+    TableError,
+  },
+  std::{collections::HashMap, sync::Once},
   transaction_builder::TransactionBuilder,
 };
 
@@ -25,8 +34,20 @@ pub mod wallet_constructor;
 
 const SCHEMA_VERSION: u64 = 1;
 
+define_table! { COMMIT_TXID_TO_RECOVERY_KEY, &[u8; 32], &[u8; 32] }
+define_table! { OUTPOINT_TO_ASSETS, &[u8; 36], AssetsEntryValue }
 define_table! { RUNE_TO_ETCHING, u128, EtchingEntryValue }
+define_table! { SEND_TO_TXID, &[u8; 32], &[u8; 32] }
 define_table! { STATISTICS, u64, u64 }
+define_table! { TXID_TO_PLAN, &[u8; 32], PlanEntryValue }
+
+/// Proprietary PSBT key prefix for annotations added to PSBTs planned by
+/// watch-only and multisig wallets.
+const PSBT_PROPRIETARY_PREFIX: &[u8] = b"ord";
+/// Inscriptions in an input, as a JSON array of inscription IDs.
+const PSBT_INSCRIPTIONS_SUBTYPE: u8 = 0;
+/// Rune balances in an input, as a JSON object of runes to piles.
+const PSBT_RUNES_SUBTYPE: u8 = 1;
 
 #[derive(Copy, Clone)]
 pub(crate) enum Statistic {
@@ -61,6 +82,51 @@ pub struct ListDescriptorsResult {
   pub descriptors: Vec<Descriptor>,
 }
 
+/// How wallet outputs are locked, determined from the wallet's descriptors.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Custody {
+  /// Single-key taproot, `tr(KEY)`.
+  Taproot,
+  /// Multisig taproot script path, `tr(KEY,multi_a(…))`.
+  TaprootMultisig,
+  /// Multisig P2WSH, `wsh(multi(…))` or `wsh(sortedmulti(…))`.
+  WitnessScriptHashMultisig,
+}
+
+impl Custody {
+  fn from_descriptor(descriptor: &miniscript::Descriptor<DescriptorPublicKey>) -> Option<Self> {
+    match descriptor {
+      miniscript::Descriptor::Tr(tr) if tr.tap_tree().is_none() => Some(Self::Taproot),
+      miniscript::Descriptor::Tr(tr)
+        if tr
+          .iter_scripts()
+          .all(|(_depth, script)| matches!(script.node, Terminal::MultiA(_))) =>
+      {
+        Some(Self::TaprootMultisig)
+      }
+      miniscript::Descriptor::Wsh(wsh) => match wsh.as_inner() {
+        WshInner::SortedMulti(_) => Some(Self::WitnessScriptHashMultisig),
+        WshInner::Ms(script) if matches!(script.node, Terminal::Multi(_)) => {
+          Some(Self::WitnessScriptHashMultisig)
+        }
+        WshInner::Ms(_) => None,
+      },
+      _ => None,
+    }
+  }
+
+  pub(crate) fn multisig(self) -> bool {
+    self != Self::Taproot
+  }
+
+  pub(crate) fn address_type(self) -> bitcoincore_rpc::json::AddressType {
+    match self {
+      Self::Taproot | Self::TaprootMultisig => bitcoincore_rpc::json::AddressType::Bech32m,
+      Self::WitnessScriptHashMultisig => bitcoincore_rpc::json::AddressType::Bech32,
+    }
+  }
+}
+
 #[derive(Debug, PartialEq)]
 pub(crate) enum Maturity {
   BelowMinimumHeight(u64),
@@ -72,6 +138,7 @@ pub(crate) enum Maturity {
 
 pub(crate) struct Wallet {
   bitcoin_client: Client,
+  custody: Custody,
   database: Database,
   has_rune_index: bool,
   has_sat_index: bool,
@@ -83,6 +150,7 @@ pub(crate) struct Wallet {
   inscriptions: BTreeMap<SatPoint, Vec<InscriptionId>>,
   locked_utxos: BTreeMap<OutPoint, TxOut>,
   settings: Settings,
+  watch_only: bool,
 }
 
 impl Wallet {
@@ -216,6 +284,21 @@ impl Wallet {
     Ok(inscription)
   }
 
+  pub(crate) fn get_output(&self, output: OutPoint) -> Result<Option<api::Output>> {
+    let response = self
+      .ord_client
+      .get(self.rpc_url.join(&format!("/output/{output}")).unwrap())
+      .send()?;
+
+    if response.status() == StatusCode::NOT_FOUND {
+      return Ok(None);
+    }
+
+    let response = response.error_for_status()?;
+
+    Ok(Some(serde_json::from_str(&response.text()?)?))
+  }
+
   pub(crate) fn inscription_exists(&self, inscription_id: InscriptionId) -> Result<bool> {
     Ok(
       !self
@@ -309,14 +392,20 @@ impl Wallet {
     &self,
     rune: Rune,
   ) -> Result<Option<(RuneId, RuneEntry, Option<InscriptionId>)>> {
+    self.query_rune(&SpacedRune { rune, spacers: 0 }.to_string())
+  }
+
+  pub(crate) fn get_rune_by_id(
+    &self,
+    id: RuneId,
+  ) -> Result<Option<(RuneId, RuneEntry, Option<InscriptionId>)>> {
+    self.query_rune(&id.to_string())
+  }
+
+  fn query_rune(&self, query: &str) -> Result<Option<(RuneId, RuneEntry, Option<InscriptionId>)>> {
     let response = self
       .ord_client
-      .get(
-        self
-          .rpc_url
-          .join(&format!("/rune/{}", SpacedRune { rune, spacers: 0 }))
-          .unwrap(),
-      )
+      .get(self.rpc_url.join(&format!("/rune/{query}")).unwrap())
       .send()?;
 
     if response.status() == StatusCode::NOT_FOUND {
@@ -334,12 +423,29 @@ impl Wallet {
     Ok(
       self
         .bitcoin_client
-        .call::<Address<NetworkUnchecked>>("getrawchangeaddress", &["bech32m".into()])
+        .call::<Address<NetworkUnchecked>>(
+          "getrawchangeaddress",
+          &[serde_json::to_value(self.custody.address_type())?],
+        )
         .context("could not get change addresses from wallet")?
         .require_network(self.chain().network())?,
     )
   }
 
+  pub(crate) fn is_mine(&self, script_pubkey: &Script) -> Result<bool> {
+    let Ok(address) = self.chain().address_from_script(script_pubkey) else {
+      return Ok(false);
+    };
+
+    Ok(
+      self
+        .bitcoin_client()
+        .get_address_info(&address)?
+        .is_mine
+        .unwrap_or_default(),
+    )
+  }
+
   pub(crate) fn has_sat_index(&self) -> bool {
     self.has_sat_index
   }
@@ -352,6 +458,20 @@ impl Wallet {
     self.settings.chain()
   }
 
+  /// Whether spending commands return PSBTs to be signed externally, either
+  /// because the wallet has no private keys, or because it is multisig.
+  pub(crate) fn watch_only(&self) -> bool {
+    self.watch_only
+  }
+
+  pub(crate) fn signs_externally(&self) -> bool {
+    self.watch_only || self.custody.multisig()
+  }
+
+  pub(crate) fn custody(&self) -> Custody {
+    self.custody
+  }
+
   pub(crate) fn integration_test(&self) -> bool {
     self.settings.integration_test()
   }
@@ -466,22 +586,28 @@ impl Wallet {
     })
   }
 
-  fn check_descriptors(wallet_name: &str, descriptors: Vec<Descriptor>) -> Result<Vec<Descriptor>> {
-    let tr = descriptors
-      .iter()
-      .filter(|descriptor| descriptor.desc.starts_with("tr("))
-      .count();
+  fn check_descriptors(
+    wallet_name: &str,
+    descriptors: Vec<Descriptor>,
+  ) -> Result<(Vec<Descriptor>, Custody)> {
+    let secp = Secp256k1::new();
 
-    let rawtr = descriptors
+    // `rawtr` descriptors hold the private keys of inscription commit
+    // outputs, and are not parsed by miniscript
+    let custody = descriptors
       .iter()
-      .filter(|descriptor| descriptor.desc.starts_with("rawtr("))
-      .count();
+      .filter(|descriptor| !descriptor.desc.starts_with("rawtr("))
+      .map(|descriptor| {
+        miniscript::Descriptor::parse_descriptor(&secp, &descriptor.desc)
+          .ok()
+          .and_then(|(descriptor, _key_map)| Custody::from_descriptor(&descriptor))
+      })
+      .collect::<Option<Vec<Custody>>>();
 
-    if tr != 2 || descriptors.len() != 2 + rawtr {
-      bail!("wallet \"{}\" contains unexpected output descriptors, and does not appear to be an `ord` wallet, create a new wallet with `ord wallet create`", wallet_name);
+    match custody.as_deref() {
+      Some([receive, change]) if receive == change => Ok((descriptors, *receive)),
+      _ => bail!("wallet \"{}\" contains unexpected output descriptors, and does not appear to be an `ord` wallet, create a new wallet with `ord wallet create`", wallet_name),
     }
-
-    Ok(descriptors)
   }
 
   pub(crate) fn initialize_from_descriptors(
@@ -491,9 +617,37 @@ impl Wallet {
   ) -> Result {
     let client = Self::check_version(settings.bitcoin_rpc_client(Some(name.clone()))?)?;
 
-    let descriptors = Self::check_descriptors(&name, descriptors)?;
+    let (descriptors, _custody) = Self::check_descriptors(&name, descriptors)?;
+
+    let secp = Secp256k1::new();
+
+    let mut private = 0;
+
+    let spending = descriptors
+      .iter()
+      .filter(|descriptor| !descriptor.desc.starts_with("rawtr("))
+      .collect::<Vec<&Descriptor>>();
+
+    for descriptor in &spending {
+      let (_descriptor, key_map) =
+        miniscript::Descriptor::parse_descriptor(&secp, &descriptor.desc)
+          .with_context(|| format!("invalid descriptor `{}`", descriptor.desc))?;
 
-    client.create_wallet(&name, None, Some(true), None, None)?;
+      if !key_map.is_empty() {
+        private += 1;
+      }
+    }
+
+    ensure!(
+      private == 0 || private == spending.len(),
+      "descriptors must either all contain private keys, or all contain only public keys",
+    );
+
+    // wallets restored from public descriptors are watch-only, and spending
+    // commands return PSBTs to be signed externally
+    let watch_only = private == 0;
+
+    client.create_wallet(&name, Some(watch_only), Some(true), None, None)?;
 
     let descriptors = descriptors
       .into_iter()
@@ -520,6 +674,90 @@ impl Wallet {
     Ok(())
   }
 
+  /// Creates a watch-only wallet from an extended public key, optionally with
+  /// key origin, e.g. `[fingerprint/86'/0'/0']xpub…`, using the same
+  /// descriptors as wallets created from a mnemonic.
+  pub(crate) fn initialize_watch_only(
+    name: String,
+    settings: &Settings,
+    key: &str,
+    timestamp: bitcoincore_rpc::json::Timestamp,
+  ) -> Result {
+    let mut descriptors = Vec::new();
+
+    for change in [false, true] {
+      let public_key = format!("{}/{}/*", key.trim(), u8::from(change))
+        .parse::<DescriptorPublicKey>()
+        .context("invalid extended public key")?;
+
+      ensure!(
+        matches!(public_key, DescriptorPublicKey::XPub(_)),
+        "key must be an extended public key",
+      );
+
+      descriptors.push(Descriptor {
+        desc: miniscript::descriptor::Descriptor::new_tr(public_key, None)?.to_string(),
+        timestamp,
+        active: true,
+        internal: Some(change),
+        range: None,
+        next: None,
+      });
+    }
+
+    Self::initialize_from_descriptors(name, settings, descriptors)
+  }
+
+  /// Creates a multisig wallet from a taproot or P2WSH multisig descriptor
+  /// whose keys derive receive and change addresses with `/<0;1>/*`. Keys may
+  /// be private, for a cosigner, or public, for a watch-only coordinator.
+  pub(crate) fn initialize_multisig(
+    name: String,
+    settings: &Settings,
+    descriptor: &str,
+    timestamp: bitcoincore_rpc::json::Timestamp,
+  ) -> Result {
+    let descriptor = descriptor.trim();
+
+    let descriptor = descriptor
+      .split_once('#')
+      .map_or(descriptor, |(descriptor, _checksum)| descriptor);
+
+    ensure!(
+      descriptor.contains("/<0;1>/*"),
+      "multisig descriptor keys must derive receive and change addresses with `/<0;1>/*`",
+    );
+
+    let secp = Secp256k1::new();
+
+    let mut descriptors = Vec::new();
+
+    for change in [false, true] {
+      let (descriptor, key_map) = miniscript::Descriptor::parse_descriptor(
+        &secp,
+        &descriptor.replace("<0;1>", &u8::from(change).to_string()),
+      )
+      .context("invalid multisig descriptor")?;
+
+      ensure!(
+        Custody::from_descriptor(&descriptor).is_some_and(Custody::multisig),
+        "descriptor must be a `tr` descriptor with `multi_a` script paths, or a `wsh` descriptor \
+        with `multi` or `sortedmulti`",
+      );
+
+      descriptors.push(Descriptor {
+        desc: descriptor.to_string_with_secret(&key_map),
+        timestamp,
+        active: true,
+        internal: Some(change),
+        range: None,
+        next: None,
+      });
+    }
+
+    Self::initialize_from_descriptors(name, settings, descriptors)
+  }
+
   pub(crate) fn initialize(
     name: String,
     settings: &Settings,
@@ -710,7 +948,10 @@ impl Wallet {
         let mut tx = database.begin_write()?;
         tx.set_quick_repair(true);
 
+        tx.open_table(OUTPOINT_TO_ASSETS)?;
         tx.open_table(RUNE_TO_ETCHING)?;
+        tx.open_table(SEND_TO_TXID)?;
+        tx.open_table(TXID_TO_PLAN)?;
 
         tx.open_table(STATISTICS)?
           .insert(&Statistic::Schema.key(), &SCHEMA_VERSION)?;
@@ -785,6 +1026,519 @@ impl Wallet {
     )
   }
 
+  /// Annotates the inputs of `psbt` with the inscriptions and runes they
+  /// contain, and its outputs with the inscriptions and runes they receive,
+  /// so that external signers can check what they are spending and where it
+  /// goes.
+  pub(crate) fn annotate_psbt(&self, psbt: &mut Psbt) -> Result {
+    for (txin, input) in psbt.unsigned_tx.input.iter().zip(&mut psbt.inputs) {
+      let Some(output) = self.output_info.get(&txin.previous_output) else {
+        continue;
+      };
+
+      for (subtype, value) in Self::annotations(output)? {
+        if let Some(value) = value {
+          input
+            .proprietary
+            .insert(Self::annotation_key(subtype), value);
+        }
+      }
+    }
+
+    for (output, annotations) in psbt
+      .outputs
+      .iter_mut()
+      .zip(self.output_annotations(&psbt.unsigned_tx)?)
+    {
+      for (subtype, value) in annotations {
+        if let Some(value) = value {
+          output
+            .proprietary
+            .insert(Self::annotation_key(subtype), value);
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Checks the annotations of each output of `psbt` against the inscriptions
+  /// and runes that output would receive, returning whether each matches.
+  pub(crate) fn output_annotations_match(&self, psbt: &Psbt) -> Result<Vec<bool>> {
+    Ok(
+      psbt
+        .outputs
+        .iter()
+        .zip(self.output_annotations(&psbt.unsigned_tx)?)
+        .map(|(output, annotations)| {
+          annotations.into_iter().all(|(subtype, value)| {
+            output.proprietary.get(&Self::annotation_key(subtype)) == value.as_ref()
+          })
+        })
+        .collect(),
+    )
+  }
+
+  /// Returns annotations for the inscriptions and runes each output of `tx`
+  /// would receive. Inscriptions are assigned to outputs by sat position, and
+  /// runes are allocated as the indexer would allocate them, assuming that
+  /// mints succeed. Inscriptions on sats paid as fees, and runes burned by
+  /// cenotaphs, are not assigned to any output.
+  fn output_annotations(&self, tx: &Transaction) -> Result<Vec<[(u8, Option<Vec<u8>>); 2]>> {
+    let mut inscriptions = vec![Vec::<InscriptionId>::new(); tx.output.len()];
+    let mut unallocated = HashMap::<RuneId, Lot>::new();
+    let mut runes = BTreeMap::<RuneId, (SpacedRune, u8, Option<char>)>::new();
+
+    let mut start = 0;
+
+    for txin in &tx.input {
+      let outpoint = txin.previous_output;
+
+      let output = match self.output_info.get(&outpoint) {
+        Some(output) => output.clone(),
+        None => self
+          .get_output(outpoint)?
+          .ok_or_else(|| anyhow!("input {outpoint} not found"))?,
+      };
+
+      for inscription_id in output.inscriptions.iter().flatten() {
+        let offset = match self.inscription_info.get(inscription_id) {
+          Some(inscription) => inscription.satpoint.offset,
+          None => {
+            self
+              .get_inscription(*inscription_id)?
+              .ok_or_else(|| anyhow!("inscription {inscription_id} not found"))?
+              .satpoint
+              .offset
+          }
+        };
+
+        let mut sat = start + offset;
+
+        for (vout, tx_out) in tx.output.iter().enumerate() {
+          if sat < tx_out.value.to_sat() {
+            inscriptions[vout].push(*inscription_id);
+            break;
+          }
+
+          sat -= tx_out.value.to_sat();
+        }
+      }
+
+      for (spaced_rune, pile) in output.runes.iter().flatten() {
+        let (id, _entry, _parent) = self
+          .get_rune(spaced_rune.rune)?
+          .ok_or_else(|| anyhow!("rune `{spaced_rune}` has not been etched"))?;
+
+        *unallocated.entry(id).or_default() += pile.amount;
+
+        runes.insert(id, (*spaced_rune, pile.divisibility, pile.symbol));
+      }
+
+      start += output.value;
+    }
+
+    let artifact = Runestone::decipher(tx);
+
+    if let Some(Artifact::Runestone(Runestone { mint: Some(id), .. })) = &artifact {
+      if let Some((id, entry, _parent)) = self.get_rune_by_id(*id)? {
+        if let Some(amount) = entry.terms.and_then(|terms| terms.amount) {
+          *unallocated.entry(id).or_default() += amount;
+
+          runes.insert(id, (entry.spaced_rune, entry.divisibility, entry.symbol));
+        }
+      }
+    }
+
+    let (allocated, _burned) = allocate_runes(tx, artifact.as_ref(), unallocated, None);
+
+    inscriptions
+      .into_iter()
+      .zip(allocated)
+      .map(|(inscriptions, allocated)| {
+        let allocated = allocated
+          .into_iter()
+          .filter(|(_id, lot)| lot.0 > 0)
+          .map(|(id, lot)| {
+            let (spaced_rune, divisibility, symbol) = runes[&id];
+            (
+              spaced_rune,
+              Pile {
+                amount: lot.0,
+                divisibility,
+                symbol,
+              },
+            )
+          })
+          .collect::<BTreeMap<SpacedRune, Pile>>();
+
+        Ok([
+          (
+            PSBT_INSCRIPTIONS_SUBTYPE,
+            (!inscriptions.is_empty())
+              .then(|| serde_json::to_vec(&inscriptions))
+              .transpose()?,
+          ),
+          (
+            PSBT_RUNES_SUBTYPE,
+            (!allocated.is_empty())
+              .then(|| serde_json::to_vec(&allocated))
+              .transpose()?,
+          ),
+        ])
+      })
+      .collect()
+  }
+
+  /// Checks that the annotations of `input`, which spends `outpoint`, match
+  /// the inscriptions and runes that the ord server reports `outpoint` as
+  /// containing.
+  pub(crate) fn annotations_match(
+    &self,
+    outpoint: OutPoint,
+    input: &bitcoin::psbt::Input,
+  ) -> Result<bool> {
+    let Some(output) = self.get_output(outpoint)? else {
+      bail!("input {outpoint} not found");
+    };
+
+    Ok(
+      Self::annotations(&output)?
+        .into_iter()
+        .all(|(subtype, value)| {
+          input.proprietary.get(&Self::annotation_key(subtype)) == value.as_ref()
+        }),
+    )
+  }
+
+  fn annotations(output: &api::Output) -> Result<[(u8, Option<Vec<u8>>); 2]> {
+    Ok([
+      (
+        PSBT_INSCRIPTIONS_SUBTYPE,
+        output
+          .inscriptions
+          .as_ref()
+          .filter(|inscriptions| !inscriptions.is_empty())
+          .map(serde_json::to_vec)
+          .transpose()?,
+      ),
+      (
+        PSBT_RUNES_SUBTYPE,
+        output
+          .runes
+          .as_ref()
+          .filter(|runes| !runes.is_empty())
+          .map(serde_json::to_vec)
+          .transpose()?,
+      ),
+    ])
+  }
+
+  fn annotation_key(subtype: u8) -> bitcoin::psbt::raw::ProprietaryKey {
+    bitcoin::psbt::raw::ProprietaryKey {
+      prefix: PSBT_PROPRIETARY_PREFIX.to_vec(),
+      subtype,
+      key: Vec::new(),
+    }
+  }
+
+  /// Prepares `psbt` to be signed externally, adding UTXO and key derivation
+  /// information and annotations. If `save` is true, the PSBT is recorded, so
+  /// that `ord wallet finalize` can check that the signed PSBT matches it.
+  /// `reveal` is broadcast after the PSBT's transaction.
+  pub(crate) fn plan_psbt(
+    &self,
+    psbt: &Psbt,
+    reveal: Option<&Transaction>,
+    save: bool,
+  ) -> Result<String> {
+    let result = self.bitcoin_client().wallet_process_psbt(
+      &base64_encode(&psbt.serialize()),
+      Some(false),
+      None,
+      None,
+    )?;
+
+    let mut psbt = Psbt::deserialize(&base64_decode(&result.psbt)?)?;
+
+    self.annotate_psbt(&mut psbt)?;
+
+    if save {
+      let mut wtx = self.database.begin_write()?;
+      wtx.set_quick_repair(true);
+
+      wtx.open_table(TXID_TO_PLAN)?.insert(
+        &psbt.unsigned_tx.compute_txid().store(),
+        PlanEntry {
+          psbt: psbt.clone(),
+          reveal: reveal.cloned(),
+        }
+        .store(),
+      )?;
+
+      wtx.commit()?;
+    }
+
+    Ok(base64_encode(&psbt.serialize()))
+  }
+
+  /// Records the recovery key for commit transaction `commit`. Wallets which
+  /// sign externally cannot import private keys into Bitcoin Core, so recovery
+  /// keys are kept in the wallet database instead.
+  pub(crate) fn save_recovery_key(&self, commit: Txid, recovery_key: &SecretKey) -> Result {
+    let mut wtx = self.database.begin_write()?;
+    wtx.set_quick_repair(true);
+
+    wtx
+      .open_table(COMMIT_TXID_TO_RECOVERY_KEY)?
+      .insert(&commit.store(), &recovery_key.secret_bytes())?;
+
+    wtx.commit()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn recovery_keys(&self) -> Result<Vec<PrivateKey>> {
+    let rtx = self.database.begin_read()?;
+
+    let table = match rtx.open_table(COMMIT_TXID_TO_RECOVERY_KEY) {
+      Ok(table) => table,
+      Err(TableError::TableDoesNotExist(_)) => return Ok(Vec::new()),
+      Err(err) => return Err(err.into()),
+    };
+
+    table
+      .iter()?
+      .map(|result| {
+        let (_commit, recovery_key) = result?;
+        Ok(PrivateKey::new(
+          SecretKey::from_slice(recovery_key.value())?,
+          self.chain().network(),
+        ))
+      })
+      .collect()
+  }
+
+  pub(crate) fn load_plan(&self, txid: Txid) -> Result<Option<PlanEntry>> {
+    let rtx = self.database.begin_read()?;
+
+    let table = match rtx.open_table(TXID_TO_PLAN) {
+      Ok(table) => table,
+      Err(TableError::TableDoesNotExist(_)) => return Ok(None),
+      Err(err) => return Err(err.into()),
+    };
+
+    Ok(
+      table
+        .get(&txid.store())?
+        .map(|result| PlanEntry::load(result.value())),
+    )
+  }
+
+  pub(crate) fn clear_plan(&self, txid: Txid) -> Result {
+    let mut wtx = self.database.begin_write()?;
+    wtx.set_quick_repair(true);
+
+    wtx.open_table(TXID_TO_PLAN)?.remove(&txid.store())?;
+    wtx.commit()?;
+
+    Ok(())
+  }
+
+  /// Records that the sends with keys `sends` are made by transaction `txid`.
+  pub(crate) fn save_sends(&self, sends: &[[u8; 32]], txid: Txid) -> Result {
+    let mut wtx = self.database.begin_write()?;
+    wtx.set_quick_repair(true);
+
+    {
+      let mut table = wtx.open_table(SEND_TO_TXID)?;
+
+      for send in sends {
+        table.insert(send, &txid.store())?;
+      }
+    }
+
+    wtx.commit()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn load_sends(&self, sends: &[[u8; 32]]) -> Result<Vec<Option<Txid>>> {
+    let rtx = self.database.begin_read()?;
+
+    let table = match rtx.open_table(SEND_TO_TXID) {
+      Ok(table) => table,
+      Err(TableError::TableDoesNotExist(_)) => return Ok(vec![None; sends.len()]),
+      Err(err) => return Err(err.into()),
+    };
+
+    sends
+      .iter()
+      .map(|send| Ok(table.get(send)?.map(|txid| Txid::load(*txid.value()))))
+      .collect()
+  }
+
+  pub(crate) fn clear_sends(&self, sends: &[[u8; 32]]) -> Result {
+    let mut wtx = self.database.begin_write()?;
+    wtx.set_quick_repair(true);
+
+    {
+      let mut table = wtx.open_table(SEND_TO_TXID)?;
+
+      for send in sends {
+        table.remove(send)?;
+      }
+    }
+
+    wtx.commit()?;
+
+    Ok(())
+  }
+
+  /// Saves the inscriptions and runes in unspent wallet outputs, so that they
+  /// are known after the outputs are spent. Outputs without inscriptions or
+  /// runes are saved too, so that a missing entry means that the assets in an
+  /// output are unknown. Unconfirmed outputs, which the ord server has not
+  /// yet indexed, are skipped.
+  pub(crate) fn save_assets(&self) -> Result {
+    let mempool = self
+      .bitcoin_client()
+      .get_raw_mempool()?
+      .into_iter()
+      .collect::<HashSet<Txid>>();
+
+    self.save_output_assets(
+      self
+        .output_info
+        .keys()
+        .filter(|outpoint| !mempool.contains(&outpoint.txid))
+        .copied(),
+    )
+  }
+
+  /// Like `save_assets`, but only saves the assets in the wallet outputs
+  /// spent by `transaction`, so it can be called before every broadcast.
+  pub(crate) fn save_spent_assets(&self, transaction: &Transaction) -> Result {
+    let mut outpoints = Vec::new();
+
+    for txin in &transaction.input {
+      let outpoint = txin.previous_output;
+
+      if !self.output_info.contains_key(&outpoint) {
+        continue;
+      }
+
+      let confirmed = self
+        .bitcoin_client()
+        .get_tx_out(&outpoint.txid, outpoint.vout, Some(true))?
+        .is_some_and(|tx_out| tx_out.confirmations > 0);
+
+      if confirmed {
+        outpoints.push(outpoint);
+      }
+    }
+
+    self.save_output_assets(outpoints)
+  }
+
+  fn save_output_assets(&self, outpoints: impl IntoIterator<Item = OutPoint>) -> Result {
+    let mut entries = BTreeMap::new();
+
+    for outpoint in outpoints {
+      let mut entry = AssetsEntry::default();
+
+      for (satpoint, ids) in self.inscriptions.range(
+        SatPoint {
+          outpoint,
+          offset: 0,
+        }..=SatPoint {
+          outpoint,
+          offset: u64::MAX,
+        },
+      ) {
+        entry
+          .inscriptions
+          .extend(ids.iter().map(|id| (satpoint.offset, *id)));
+      }
+
+      if let Some(runes) = self
+        .output_info
+        .get(&outpoint)
+        .and_then(|info| info.runes.as_ref())
+      {
+        entry.runes = runes.iter().map(|(rune, pile)| (*rune, *pile)).collect();
+      }
+
+      entries.insert(outpoint, entry);
+    }
+
+    if entries.is_empty() {
+      return Ok(());
+    }
+
+    let mut wtx = self.database.begin_write()?;
+    wtx.set_quick_repair(true);
+
+    {
+      let mut table = wtx.open_table(OUTPOINT_TO_ASSETS)?;
+
+      for (outpoint, entry) in entries {
+        table.insert(&outpoint.store(), entry.store())?;
+      }
+    }
+
+    wtx.commit()?;
+
+    Ok(())
+  }
+
+  /// Removes saved assets of outputs of transactions not in `transactions`,
+  /// which can no longer appear in the wallet's history.
+  pub(crate) fn prune_assets(&self, transactions: &HashSet<Txid>) -> Result {
+    let mut wtx = self.database.begin_write()?;
+    wtx.set_quick_repair(true);
+
+    {
+      let mut table = wtx.open_table(OUTPOINT_TO_ASSETS)?;
+
+      let mut pruned = Vec::new();
+
+      for result in table.iter()? {
+        let (outpoint, _entry) = result?;
+
+        let outpoint = OutPoint::load(*outpoint.value());
+
+        if !transactions.contains(&outpoint.txid) {
+          pruned.push(outpoint);
+        }
+      }
+
+      for outpoint in pruned {
+        table.remove(&outpoint.store())?;
+      }
+    }
+
+    wtx.commit()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn load_assets(&self, outpoint: OutPoint) -> Result<Option<AssetsEntry>> {
+    let rtx = self.database.begin_read()?;
+
+    let table = match rtx.open_table(OUTPOINT_TO_ASSETS) {
+      Ok(table) => table,
+      Err(TableError::TableDoesNotExist(_)) => return Ok(None),
+      Err(err) => return Err(err.into()),
+    };
+
+    Ok(
+      table
+        .get(&outpoint.store())?
+        .map(|result| AssetsEntry::load(result.value())),
+    )
+  }
+
   pub(super) fn sign_and_broadcast_transaction(
     &self,
     unsigned_transaction: Transaction,
@@ -793,7 +1547,41 @@ impl Wallet {
   ) -> Result<(Txid, String, u64)> {
     let unspent_outputs = self.utxos();
 
-    let (txid, psbt) = if dry_run {
+    let (txid, psbt) = self.sign_and_broadcast(&unsigned_transaction, dry_run, burn_amount)?;
+
+    let mut fee = 0;
+    for txin in unsigned_transaction.input.iter() {
+      let Some(txout) = unspent_outputs.get(&txin.previous_output) else {
+        panic!("input {} not found in utxos", txin.previous_output);
+      };
+      fee += txout.value.to_sat();
+    }
+
+    for txout in unsigned_transaction.output.iter() {
+      fee = fee.checked_sub(txout.value.to_sat()).unwrap();
+    }
+
+    Ok((txid, psbt, fee))
+  }
+
+  /// Signs and broadcasts `unsigned_transaction`, or, if the wallet signs
+  /// externally, plans it so that it can be finalized later.
+  pub(crate) fn sign_and_broadcast(
+    &self,
+    unsigned_transaction: &Transaction,
+    dry_run: bool,
+    burn_amount: Option<Amount>,
+  ) -> Result<(Txid, String)> {
+    Ok(if self.signs_externally() {
+      (
+        unsigned_transaction.compute_txid(),
+        self.plan_psbt(
+          &Psbt::from_unsigned_tx(unsigned_transaction.clone())?,
+          None,
+          !dry_run,
+        )?,
+      )
+    } else if dry_run {
       let psbt = self
         .bitcoin_client()
         .wallet_process_psbt(
@@ -823,21 +1611,7 @@ impl Wallet {
         .ok_or_else(|| anyhow!("unable to sign transaction"))?;
 
       (self.send_raw_transaction(&signed_tx, burn_amount)?, psbt)
-    };
-
-    let mut fee = 0;
-    for txin in unsigned_transaction.input.iter() {
-      let Some(txout) = unspent_outputs.get(&txin.previous_output) else {
-        panic!("input {} not found in utxos", txin.previous_output);
-      };
-      fee += txout.value.to_sat();
-    }
-
-    for txout in unsigned_transaction.output.iter() {
-      fee = fee.checked_sub(txout.value.to_sat()).unwrap();
-    }
-
-    Ok((txid, psbt, fee))
+    })
   }
 
   pub(crate) fn send_raw_transaction<R: bitcoincore_rpc::RawTx>(
@@ -845,7 +1619,11 @@ impl Wallet {
     tx: R,
     burn_amount: Option<Amount>,
   ) -> Result<Txid> {
-    let mut arguments = vec![tx.raw_hex().into()];
+    let raw_hex = tx.raw_hex();
+
+    self.save_spent_assets(&consensus::encode::deserialize_hex(&raw_hex)?)?;
+
+    let mut arguments = vec![raw_hex.into()];
 
     if let Some(burn_amount) = burn_amount {
       arguments.push(serde_json::Value::Null);
@@ -90,6 +90,10 @@ impl Plan {
       ))));
     }
 
+    if wallet.signs_externally() {
+      return self.plan(wallet, commit_tx, reveal_tx, recovery_key_pair, total_fees);
+    }
+
     let signed_commit_tx = wallet
       .bitcoin_client()
       .sign_raw_transaction_with_wallet(&commit_tx, None, None)?
@@ -125,6 +129,9 @@ impl Plan {
       Self::backup_recovery_key(wallet, recovery_key_pair)?;
     }
 
+    wallet.save_spent_assets(&commit_tx)?;
+    wallet.save_spent_assets(&reveal_tx)?;
+
     let commit_txid = wallet
       .bitcoin_client()
       .send_raw_transaction(&signed_commit_tx)?;
@@ -183,6 +190,71 @@ impl Plan {
     }
   }
 
+  /// Plans commit and reveal transactions for a watch-only or multisig
+  /// wallet. The commit transaction is returned as a PSBT to be signed
+  /// externally. The reveal transaction's commit input is signed with the
+  /// ephemeral key, so if the reveal transaction has no other inputs, it is
+  /// broadcast by `ord wallet finalize` after the commit transaction.
+  /// Otherwise, it is also returned as a PSBT to be signed externally and
+  /// finalized after the commit transaction.
+  fn plan(
+    &self,
+    wallet: &Wallet,
+    commit_tx: Transaction,
+    reveal_tx: Transaction,
+    recovery_key_pair: TweakedKeypair,
+    total_fees: u64,
+  ) -> SubcommandResult {
+    ensure!(
+      self.etching.is_none(),
+      "etching runes is not supported with watch-only or multisig wallets",
+    );
+
+    // the commit transaction may be broadcast as soon as the PSBT is signed,
+    // so the recovery key must be stored before the PSBT is returned
+    if !self.no_backup {
+      wallet.save_recovery_key(
+        commit_tx.compute_txid(),
+        &recovery_key_pair.to_inner().secret_key(),
+      )?;
+    }
+
+    let commit_input = self.parent_info.len() + self.reveal_satpoints.len();
+
+    let reveal_psbt = if reveal_tx.input.len() > 1 {
+      let mut psbt = Psbt::from_unsigned_tx(Self::remove_witnesses(reveal_tx.clone()))?;
+
+      let commit_output = reveal_tx.input[commit_input].previous_output;
+
+      psbt.inputs[commit_input].witness_utxo =
+        Some(commit_tx.output[usize::try_from(commit_output.vout).unwrap()].clone());
+
+      psbt.inputs[commit_input].final_script_witness =
+        Some(reveal_tx.input[commit_input].witness.clone());
+
+      Some(wallet.plan_psbt(&psbt, None, true)?)
+    } else {
+      None
+    };
+
+    let commit_psbt = wallet.plan_psbt(
+      &Psbt::from_unsigned_tx(Self::remove_witnesses(commit_tx.clone()))?,
+      reveal_psbt.is_none().then_some(&reveal_tx),
+      true,
+    )?;
+
+    Ok(Some(Box::new(self.output(
+      commit_tx.compute_txid(),
+      Some(commit_psbt),
+      reveal_tx.compute_txid(),
+      false,
+      reveal_psbt,
+      total_fees,
+      self.inscriptions.clone(),
+      None,
+    ))))
+  }
+
   fn remove_witnesses(mut transaction: Transaction) -> Transaction {
     for txin in transaction.input.iter_mut() {
       txin.witness = Witness::new();
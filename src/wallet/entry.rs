@@ -36,6 +36,63 @@ impl Entry for EtchingEntry {
   }
 }
 
+/// A transaction planned by a watch-only or multisig wallet, awaiting external
+/// signatures. `reveal` is a signed inscription reveal transaction, which is
+/// broadcast once the planned commit transaction has been broadcast.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PlanEntry {
+  pub psbt: Psbt,
+  pub reveal: Option<Transaction>,
+}
+
+pub(super) type PlanEntryValue = (
+  Vec<u8>,         // psbt
+  Option<Vec<u8>>, // reveal
+);
+
+impl Entry for PlanEntry {
+  type Value = PlanEntryValue;
+
+  fn load((psbt, reveal): PlanEntryValue) -> Self {
+    Self {
+      psbt: Psbt::deserialize(&psbt).unwrap(),
+      reveal: reveal.map(|reveal| consensus::encode::deserialize::<Transaction>(&reveal).unwrap()),
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    (
+      self.psbt.serialize(),
+      self
+        .reveal
+        .map(|reveal| consensus::encode::serialize(&reveal)),
+    )
+  }
+}
+
+/// Inscriptions, with their offsets, and rune balances in a wallet output,
+/// saved while the output is unspent, since the index forgets them once the
+/// output is spent.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct AssetsEntry {
+  pub inscriptions: Vec<(u64, InscriptionId)>,
+  pub runes: Vec<(SpacedRune, Pile)>,
+}
+
+pub(super) type AssetsEntryValue = Vec<u8>;
+
+impl Entry for AssetsEntry {
+  type Value = AssetsEntryValue;
+
+  fn load(value: AssetsEntryValue) -> Self {
+    serde_json::from_slice(&value).unwrap()
+  }
+
+  fn store(self) -> Self::Value {
+    serde_json::to_vec(&self).unwrap()
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -102,4 +159,54 @@ mod tests {
     assert_eq!(entry.clone().store(), value);
     assert_eq!(EtchingEntry::load(value), entry);
   }
+
+  #[test]
+  fn plan_entry() {
+    let transaction = Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: OutPoint::null(),
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::MAX,
+        witness: Witness::new(),
+      }],
+      output: Vec::new(),
+    };
+
+    let psbt = Psbt::from_unsigned_tx(transaction.clone()).unwrap();
+
+    let entry = PlanEntry {
+      psbt: psbt.clone(),
+      reveal: Some(transaction.clone()),
+    };
+
+    let value = (
+      psbt.serialize(),
+      Some(consensus::encode::serialize(&transaction)),
+    );
+
+    assert_eq!(entry.clone().store(), value);
+    assert_eq!(PlanEntry::load(value), entry);
+  }
+
+  #[test]
+  fn assets_entry() {
+    let entry = AssetsEntry {
+      inscriptions: vec![(1, inscription_id(1))],
+      runes: vec![(
+        SpacedRune {
+          rune: Rune(99246114928149462),
+          spacers: 1,
+        },
+        Pile {
+          amount: u128::MAX,
+          divisibility: 2,
+          symbol: Some('¢'),
+        },
+      )],
+    };
+
+    assert_eq!(AssetsEntry::load(entry.clone().store()), entry);
+  }
 }
@@ -120,8 +120,8 @@ pub struct TransactionBuilder {
 type Result<T> = std::result::Result<T, Error>;
 
 impl TransactionBuilder {
-  const ADDITIONAL_INPUT_VBYTES: usize = 57;
-  const ADDITIONAL_OUTPUT_VBYTES: usize = 43;
+  pub(crate) const ADDITIONAL_INPUT_VBYTES: usize = 57;
+  pub(crate) const ADDITIONAL_OUTPUT_VBYTES: usize = 43;
   const SCHNORR_SIGNATURE_SIZE: usize = 64;
   pub(crate) const MAX_POSTAGE: Amount = Amount::from_sat(2 * 10_000);
 
@@ -191,6 +191,170 @@ impl TransactionBuilder {
       .build()
   }
 
+  /// Returns a builder for a replacement of a transaction with `inputs` and
+  /// `outputs`, whose values must be in `amounts`.
+  pub fn replacement(
+    inputs: Vec<OutPoint>,
+    outputs: Vec<TxOut>,
+    inscriptions: BTreeMap<SatPoint, Vec<InscriptionId>>,
+    amounts: BTreeMap<OutPoint, TxOut>,
+    locked_utxos: BTreeSet<OutPoint>,
+    runic_utxos: BTreeSet<OutPoint>,
+    change: [Address; 2],
+    fee_rate: FeeRate,
+    network: Network,
+  ) -> Self {
+    Self {
+      utxos: amounts
+        .keys()
+        .filter(|outpoint| !inputs.contains(outpoint))
+        .cloned()
+        .collect(),
+      amounts,
+      change_addresses: change.iter().cloned().collect(),
+      fee_rate,
+      outgoing: SatPoint {
+        outpoint: inputs[0],
+        offset: 0,
+      },
+      inputs,
+      inscriptions,
+      locked_utxos,
+      network,
+      outputs,
+      recipient: ScriptBuf::new(),
+      runic_utxos,
+      target: Target::Postage,
+      unused_change_addresses: change.to_vec(),
+    }
+  }
+
+  /// Builds a replacement for a transaction of `vsize` vbytes which paid
+  /// `replaced_fee`. The replacement pays at least the builder's fee rate,
+  /// and, as required by BIP 125 rule 4, at least `replaced_fee` plus
+  /// `incremental_fee_rate` for its own size. The fee is deducted from output
+  /// `change`, if it can pay it, and otherwise cardinal UTXOs are added, with
+  /// any excess sent to `change`, or to a new change output.
+  pub fn build_replacement(
+    mut self,
+    mut vsize: usize,
+    change: Option<usize>,
+    replaced_fee: Amount,
+    incremental_fee_rate: FeeRate,
+  ) -> Result<Transaction> {
+    let fee_rate = self.fee_rate;
+
+    let required = |vsize: usize| {
+      max(
+        fee_rate.fee(vsize),
+        replaced_fee + incremental_fee_rate.fee(vsize),
+      )
+    };
+
+    let output_value = self
+      .outputs
+      .iter()
+      .map(|tx_out| tx_out.value)
+      .sum::<Amount>();
+
+    let mut input_value = self
+      .inputs
+      .iter()
+      .map(|outpoint| self.amounts[outpoint].value)
+      .sum::<Amount>();
+
+    if let Some(vout) = change {
+      let deficit = (output_value + required(vsize))
+        .checked_sub(input_value)
+        .unwrap_or_default();
+
+      let output = &mut self.outputs[vout];
+
+      if output.value >= deficit + output.script_pubkey.minimal_non_dust() {
+        tprintln!("deducted {deficit} sat replacement fee from change output");
+        output.value -= deficit;
+        return self.build_replaced(vsize, required(vsize));
+      }
+    } else {
+      vsize += Self::ADDITIONAL_OUTPUT_VBYTES;
+    }
+
+    let excess = loop {
+      let deficit = (output_value + required(vsize + Self::ADDITIONAL_INPUT_VBYTES))
+        .checked_sub(input_value)
+        .unwrap_or_default();
+
+      let (utxo, value) = self.select_cardinal_utxo(deficit.to_sat(), false)?;
+
+      tprintln!("added {value} sat input to cover {deficit} sat replacement fee deficit");
+
+      self.inputs.push(utxo);
+      input_value += value;
+      vsize += Self::ADDITIONAL_INPUT_VBYTES;
+
+      if let Some(excess) = input_value.checked_sub(output_value + required(vsize)) {
+        break excess;
+      }
+    };
+
+    match change {
+      Some(vout) => self.outputs[vout].value += excess,
+      None => {
+        let script_pubkey = self
+          .unused_change_addresses
+          .pop()
+          .unwrap_or_else(|| panic!("not enough change addresses"))
+          .script_pubkey();
+
+        if excess >= script_pubkey.minimal_non_dust() {
+          self.outputs.push(TxOut {
+            value: excess,
+            script_pubkey,
+          });
+        }
+      }
+    }
+
+    self.build_replaced(vsize, required(vsize))
+  }
+
+  fn build_replaced(self, vsize: usize, required: Amount) -> Result<Transaction> {
+    let transaction = Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: self
+        .inputs
+        .iter()
+        .map(|outpoint| TxIn {
+          previous_output: *outpoint,
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+          witness: Witness::new(),
+        })
+        .collect(),
+      output: self.outputs,
+    };
+
+    let input_value = self
+      .inputs
+      .iter()
+      .map(|outpoint| self.amounts[outpoint].value)
+      .sum::<Amount>();
+
+    let output_value = transaction
+      .output
+      .iter()
+      .map(|tx_out| tx_out.value)
+      .sum::<Amount>();
+
+    assert!(
+      input_value.checked_sub(output_value).unwrap() >= required,
+      "invariant: replacement pays required fee for {vsize} vbytes",
+    );
+
+    Ok(transaction)
+  }
+
   fn select_outgoing(mut self) -> Result<Self> {
     let dust_limit = self
       .unused_change_addresses
@@ -2196,4 +2360,149 @@ mod tests {
       outpoint(2),
     );
   }
+
+  #[test]
+  fn replacement_fee_is_deducted_from_change() {
+    let amounts = vec![(outpoint(1), tx_out(20_000, address(0)))];
+
+    let replacement = TransactionBuilder::replacement(
+      vec![outpoint(1)],
+      vec![
+        tx_out(5_000, recipient_address()),
+        tx_out(14_900, change(0)),
+      ],
+      BTreeMap::new(),
+      amounts.into_iter().collect(),
+      BTreeSet::new(),
+      BTreeSet::new(),
+      [change(1), change(2)],
+      FeeRate::try_from(2.0).unwrap(),
+      Network::Testnet,
+    )
+    .build_replacement(
+      100,
+      Some(1),
+      Amount::from_sat(100),
+      FeeRate::try_from(1.0).unwrap(),
+    )
+    .unwrap();
+
+    pretty_assert_eq!(
+      replacement,
+      Transaction {
+        version: Version(2),
+        lock_time: LockTime::ZERO,
+        input: vec![tx_in(outpoint(1))],
+        output: vec![
+          tx_out(5_000, recipient_address()),
+          tx_out(14_800, change(0))
+        ],
+      }
+    );
+  }
+
+  #[test]
+  fn replacement_pays_incremental_relay_fee() {
+    let amounts = vec![(outpoint(1), tx_out(20_000, address(0)))];
+
+    let replacement = TransactionBuilder::replacement(
+      vec![outpoint(1)],
+      vec![
+        tx_out(5_000, recipient_address()),
+        tx_out(14_900, change(0)),
+      ],
+      BTreeMap::new(),
+      amounts.into_iter().collect(),
+      BTreeSet::new(),
+      BTreeSet::new(),
+      [change(1), change(2)],
+      FeeRate::try_from(1.5).unwrap(),
+      Network::Testnet,
+    )
+    .build_replacement(
+      100,
+      Some(1),
+      Amount::from_sat(100),
+      FeeRate::try_from(1.0).unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(replacement.output[1].value, Amount::from_sat(14_800));
+  }
+
+  #[test]
+  fn replacement_adds_cardinal_input_and_change_output() {
+    let amounts = vec![
+      (outpoint(1), tx_out(5_100, address(0))),
+      (outpoint(2), tx_out(10_000, address(0))),
+    ];
+
+    let replacement = TransactionBuilder::replacement(
+      vec![outpoint(1)],
+      vec![tx_out(5_000, recipient_address())],
+      BTreeMap::new(),
+      amounts.into_iter().collect(),
+      BTreeSet::new(),
+      BTreeSet::new(),
+      [change(1), change(2)],
+      FeeRate::try_from(2.0).unwrap(),
+      Network::Testnet,
+    )
+    .build_replacement(
+      100,
+      None,
+      Amount::from_sat(100),
+      FeeRate::try_from(1.0).unwrap(),
+    )
+    .unwrap();
+
+    let vsize = 100
+      + TransactionBuilder::ADDITIONAL_INPUT_VBYTES
+      + TransactionBuilder::ADDITIONAL_OUTPUT_VBYTES;
+
+    pretty_assert_eq!(
+      replacement,
+      Transaction {
+        version: Version(2),
+        lock_time: LockTime::ZERO,
+        input: vec![tx_in(outpoint(1)), tx_in(outpoint(2))],
+        output: vec![
+          tx_out(5_000, recipient_address()),
+          tx_out(
+            15_100 - 5_000 - 2 * u64::try_from(vsize).unwrap(),
+            change(2)
+          ),
+        ],
+      }
+    );
+  }
+
+  #[test]
+  fn replacement_does_not_add_runic_inputs() {
+    let amounts = vec![
+      (outpoint(1), tx_out(5_100, address(0))),
+      (outpoint(2), tx_out(10_000, address(0))),
+    ];
+
+    assert_eq!(
+      TransactionBuilder::replacement(
+        vec![outpoint(1)],
+        vec![tx_out(5_000, recipient_address())],
+        BTreeMap::new(),
+        amounts.into_iter().collect(),
+        BTreeSet::new(),
+        [outpoint(2)].into_iter().collect(),
+        [change(1), change(2)],
+        FeeRate::try_from(2.0).unwrap(),
+        Network::Testnet,
+      )
+      .build_replacement(
+        100,
+        None,
+        Amount::from_sat(100),
+        FeeRate::try_from(1.0).unwrap()
+      ),
+      Err(Error::NotEnoughCardinalUtxos),
+    );
+  }
 }
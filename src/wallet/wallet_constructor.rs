@@ -46,6 +46,9 @@ impl WalletConstructor {
   pub(crate) fn build(self) -> Result<Wallet> {
     let database = Wallet::open_database(&self.name, &self.settings)?;
 
+    let custody;
+    let watch_only;
+
     let bitcoin_client = {
       let client =
         Wallet::check_version(self.settings.bitcoin_rpc_client(Some(self.name.clone()))?)?;
@@ -77,14 +80,14 @@ impl WalletConstructor {
         }
       }
 
-      if client.get_wallet_info()?.private_keys_enabled {
-        Wallet::check_descriptors(
-          &self.name,
-          client
-            .call::<ListDescriptorsResult>("listdescriptors", &[serde_json::Value::Null])?
-            .descriptors,
-        )?;
-      }
+      watch_only = !client.get_wallet_info()?.private_keys_enabled;
+
+      (_, custody) = Wallet::check_descriptors(
+        &self.name,
+        client
+          .call::<ListDescriptorsResult>("listdescriptors", &[serde_json::Value::Null])?
+          .descriptors,
+      )?;
 
       client
     };
@@ -124,8 +127,9 @@ impl WalletConstructor {
 
     let status = self.get_server_status()?;
 
-    Ok(Wallet {
+    let wallet = Wallet {
       bitcoin_client,
+      custody,
       database,
       has_rune_index: status.rune_index,
       has_sat_index: status.sat_index,
@@ -137,7 +141,10 @@ impl WalletConstructor {
       rpc_url: self.rpc_url,
       settings: self.settings,
       utxos,
-    })
+      watch_only,
+    };
+
+    Ok(wallet)
   }
 
   fn get_output_info(&self, outputs: Vec<OutPoint>) -> Result<BTreeMap<OutPoint, api::Output>> {
@@ -25,7 +25,7 @@ addEventListener("DOMContentLoaded", () => {
     }
   });
 
-  const search = document.querySelector('form[action="/search"]');
+  const search = document.querySelector('form[action$="/search"]');
   const query = search.querySelector('input[name="query"]');
 
   search.addEventListener('submit', (e) => {
@@ -7,7 +7,7 @@ const definition = await import(`https://cdn.jsdelivr.net/gh/highlightjs/cdn-rel
 
 hljs.registerLanguage(language, definition.default);
 
-const response = await fetch(`/content/${inscription}`);
+const response = await fetch(`../content/${inscription}`);
 const text = await response.text();
 const code = document.querySelector('code');
 
@@ -4,7 +4,7 @@ pdfjs.GlobalWorkerOptions.workerSrc = 'https://cdn.jsdelivr.net/npm/pdfjs-dist@3
 
 let canvas = document.querySelector('canvas');
 
-let pdf = await pdfjs.getDocument(`/content/${canvas.dataset.inscription}`).promise;
+let pdf = await pdfjs.getDocument(`../content/${canvas.dataset.inscription}`).promise;
 
 let page = await pdf.getPage(1);
 
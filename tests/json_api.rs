@@ -526,6 +526,7 @@ fn get_transaction() {
       chain: Chain::Mainnet,
       etching: None,
       inscription_count: 0,
+      sat_flow: None,
       transaction,
       txid,
     }
@@ -25,7 +25,12 @@ fn default() {
   "index_cache_size": \d+,
   "index_runes": false,
   "index_sats": false,
+  "index_script_hashes": false,
+  "index_search": false,
+  "index_spent_sats": false,
+  "index_traits": false,
   "index_transactions": false,
+  "index_transfers": false,
   "integration_test": false,
   "no_index_inscriptions": false,
   "server_password": null,
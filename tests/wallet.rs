@@ -1,16 +1,23 @@
 use super::*;
 
+mod accelerate;
 mod addresses;
 mod authentication;
 mod balance;
 mod batch_command;
+mod bump;
 mod burn;
 mod cardinals;
+mod consolidate_runes;
 mod create;
 mod dump;
+mod extract;
+mod finalize;
+mod history;
 mod inscribe;
 mod inscriptions;
 mod label;
+mod listing;
 mod mint;
 mod offer;
 mod outputs;
@@ -24,6 +31,8 @@ mod runics;
 mod sats;
 mod selection;
 mod send;
+mod send_many;
 mod sign;
+mod sign_psbt;
 mod split;
 mod transactions;
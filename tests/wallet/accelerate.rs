@@ -0,0 +1,205 @@
+use super::*;
+
+type Accelerate = ord::subcommand::wallet::accelerate::Output;
+
+#[test]
+fn accelerate_incoming_inscription() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let address = CommandBuilder::new("wallet receive")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<ord::subcommand::wallet::receive::Output>()
+    .addresses
+    .into_iter()
+    .next()
+    .unwrap();
+
+  let txid = core.broadcast_tx(TransactionTemplate {
+    inputs: &[(
+      1,
+      0,
+      0,
+      envelope(&[b"ord", &[1], b"text/plain;charset=utf-8", &[], b"foo"]),
+    )],
+    recipient: Some(address.assume_checked()),
+    ..default()
+  });
+
+  let inscription = InscriptionId { txid, index: 0 };
+
+  let accelerate = CommandBuilder::new(format!("wallet accelerate --fee-rate 10 {txid}"))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Accelerate>();
+
+  assert_eq!(accelerate.parent, txid);
+
+  let mempool = core.mempool();
+
+  assert_eq!(mempool.len(), 2);
+
+  let (parent, child) = (&mempool[0], &mempool[1]);
+
+  assert_eq!(child.compute_txid(), accelerate.txid);
+  assert_eq!(child.input[0].previous_output, OutPoint { txid, vout: 0 });
+  assert_eq!(child.output[0].value.to_sat(), 10_000);
+
+  assert!(accelerate.fee >= 10 * u64::try_from(parent.vsize() + child.vsize()).unwrap());
+
+  core.mine_blocks(1);
+
+  let child = accelerate.txid;
+
+  ord.assert_response_regex(
+    format!("/inscription/{inscription}"),
+    format!(".*<dt>location</dt>\\s*<dd><a class=collapse href=/satpoint/{child}:0:0>{child}:0:0</a></dd>.*"),
+  );
+}
+
+#[test]
+fn accelerate_rune_transfer() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  etch(&core, &ord, Rune(RUNE));
+
+  let address = CommandBuilder::new("--regtest wallet receive")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<ord::subcommand::wallet::receive::Output>()
+    .addresses
+    .into_iter()
+    .next()
+    .unwrap()
+    .assume_checked();
+
+  let send = CommandBuilder::new(format!(
+    "--regtest --index-runes wallet send --fee-rate 1 {address} 500:{}",
+    Rune(RUNE),
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  let accelerate = CommandBuilder::new(format!(
+    "--regtest --index-runes wallet accelerate --fee-rate 10 {}",
+    send.txid
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Accelerate>();
+
+  let child = &core.mempool()[1];
+
+  assert_eq!(child.compute_txid(), accelerate.txid);
+  assert_eq!(child.input[0].previous_output.txid, send.txid);
+  assert!(child.output[0].value <= Amount::from_sat(2 * 10_000));
+
+  let parent = &core.mempool()[0];
+
+  let Some(Artifact::Runestone(runestone)) = Runestone::decipher(parent) else {
+    panic!("send transaction has no runestone");
+  };
+
+  let default = runestone.pointer.unwrap_or_else(|| {
+    parent
+      .output
+      .iter()
+      .position(|output| !output.script_pubkey.is_op_return())
+      .unwrap()
+      .try_into()
+      .unwrap()
+  });
+
+  assert!(runestone
+    .edicts
+    .iter()
+    .map(|edict| edict.output)
+    .chain([default])
+    .all(|vout| vout != child.input[0].previous_output.vout));
+
+  core.mine_blocks(1);
+
+  let balance = CommandBuilder::new("--regtest --index-runes wallet balance")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Balance>();
+
+  assert_eq!(
+    balance.runes,
+    Some(
+      [(
+        SpacedRune {
+          rune: Rune(RUNE),
+          spacers: 0
+        },
+        "1000".parse().unwrap()
+      )]
+      .into_iter()
+      .collect()
+    ),
+  );
+}
+
+#[test]
+fn accelerate_requires_fee_rate_above_package_fee_rate() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let send = CommandBuilder::new(
+    "wallet send --fee-rate 10 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 1btc",
+  )
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  CommandBuilder::new(format!("wallet accelerate --fee-rate 5 {}", send.txid))
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr(format!(
+      "error: transaction {} already pays fee rate of at least 5 sat/vB\n",
+      send.txid
+    ))
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn accelerate_requires_wallet_output() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let txid = core.broadcast_tx(TransactionTemplate {
+    inputs: &[(1, 0, 0, Witness::new())],
+    ..default()
+  });
+
+  CommandBuilder::new(format!("wallet accelerate --fee-rate 10 {txid}"))
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr(format!(
+      "error: transaction {txid} has no unspent outputs belonging to wallet\n"
+    ))
+    .run_and_extract_stdout();
+}
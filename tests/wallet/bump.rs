@@ -0,0 +1,213 @@
+use super::*;
+
+type Bump = ord::subcommand::wallet::bump::Output;
+
+#[test]
+fn bumped_inscription_send_keeps_inscription_destination() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let (inscription, _) = inscribe(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let send = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 {inscription}"
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  let original = core.mempool()[0].clone();
+
+  let bump = CommandBuilder::new(format!("wallet bump --fee-rate 10 {}", send.txid))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Bump>();
+
+  assert_eq!(bump.original, send.txid);
+  assert_eq!(bump.reveal, None);
+  assert!(bump.fee > send.fee);
+
+  let mempool = core.mempool();
+
+  assert_eq!(mempool.len(), 1);
+
+  let replacement = &mempool[0];
+
+  assert_eq!(replacement.compute_txid(), bump.txid);
+  assert_eq!(replacement.output[0], original.output[0]);
+  assert_eq!(
+    replacement.input[..original.input.len()]
+      .iter()
+      .map(|txin| txin.previous_output)
+      .collect::<Vec<OutPoint>>(),
+    original
+      .input
+      .iter()
+      .map(|txin| txin.previous_output)
+      .collect::<Vec<OutPoint>>(),
+  );
+
+  core.mine_blocks(1);
+
+  let txid = bump.txid;
+
+  ord.assert_response_regex(
+    format!("/inscription/{inscription}"),
+    format!(".*<dt>location</dt>\\s*<dd><a class=collapse href=/satpoint/{txid}:0:0>{txid}:0:0</a></dd>.*"),
+  );
+}
+
+#[test]
+fn bumped_commit_rebuilds_reveal() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let batch = CommandBuilder::new("wallet inscribe --fee-rate 1 --file foo.txt")
+    .write("foo.txt", "FOO")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Batch>();
+
+  let bump = CommandBuilder::new(format!("wallet bump --fee-rate 10 {}", batch.commit))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Bump>();
+
+  let reveal = bump.reveal.unwrap();
+
+  assert_ne!(bump.txid, batch.commit);
+  assert_ne!(reveal, batch.reveal);
+
+  let mempool = core.mempool();
+
+  assert_eq!(mempool.len(), 2);
+  assert_eq!(mempool[0].compute_txid(), bump.txid);
+  assert_eq!(mempool[1].compute_txid(), reveal);
+  assert_eq!(mempool[1].input[0].previous_output.txid, bump.txid);
+
+  let commit = mempool[0].output[0].value.to_sat();
+
+  let reveal_fee = commit - mempool[1].output[0].value.to_sat();
+
+  assert!(reveal_fee >= 10 * u64::try_from(mempool[1].vsize()).unwrap());
+
+  core.mine_blocks(1);
+
+  ord.assert_response_regex(
+    format!("/inscription/{reveal}i0"),
+    ".*<h1>Inscription 0</h1>.*",
+  );
+}
+
+#[test]
+fn bump_fee_rate_must_be_higher() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let send =
+    CommandBuilder::new("wallet send --fee-rate 5 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 1btc")
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output::<Send>();
+
+  CommandBuilder::new(format!("wallet bump --fee-rate 4 {}", send.txid))
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr(format!(
+      "error: fee rate 4 sat/vB is not higher than fee rate of transaction {}\n",
+      send.txid
+    ))
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn bump_pays_incremental_relay_fee() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let send =
+    CommandBuilder::new("wallet send --fee-rate 5 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 1btc")
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output::<Send>();
+
+  let bump = CommandBuilder::new(format!("wallet bump --fee-rate 5.5 {}", send.txid))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Bump>();
+
+  let mempool = core.mempool();
+
+  assert_eq!(mempool.len(), 1);
+  assert_eq!(mempool[0].compute_txid(), bump.txid);
+
+  assert!(bump.fee >= send.fee + u64::try_from(mempool[0].vsize()).unwrap());
+}
+
+#[test]
+fn bump_reveal_is_refused() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let batch = CommandBuilder::new("wallet inscribe --fee-rate 1 --file foo.txt")
+    .write("foo.txt", "FOO")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Batch>();
+
+  CommandBuilder::new(format!("wallet bump --fee-rate 10 {}", batch.reveal))
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr(format!(
+      "error: transaction {} is an inscription reveal, bump commit transaction {} instead\n",
+      batch.reveal, batch.commit,
+    ))
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn bump_requires_transaction_in_mempool() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  let txid = core.mine_blocks(1)[0].txdata[0].compute_txid();
+
+  CommandBuilder::new(format!("wallet bump --fee-rate 10 {txid}"))
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr(format!("error: transaction {txid} is not in mempool\n"))
+    .run_and_extract_stdout();
+}
@@ -0,0 +1,313 @@
+use super::*;
+
+type ConsolidateRunes = ord::subcommand::wallet::consolidate_runes::Output;
+
+fn split(core: &mockcore::Handle, ord: &TestServer, runes: &[Rune], outputs: usize) {
+  let address = CommandBuilder::new("--regtest wallet receive")
+    .core(core)
+    .ord(ord)
+    .run_and_deserialize_output::<ord::subcommand::wallet::receive::Output>()
+    .addresses
+    .into_iter()
+    .next()
+    .unwrap()
+    .require_network(Network::Regtest)
+    .unwrap();
+
+  let mut splitfile = String::from("outputs:\n");
+
+  for _ in 0..outputs {
+    splitfile.push_str(&format!("- address: {address}\n  runes:\n"));
+    for rune in runes {
+      splitfile.push_str(&format!("    {rune}: 100\n"));
+    }
+  }
+
+  CommandBuilder::new("--regtest wallet split --fee-rate 1 --splits splits.yaml")
+    .core(core)
+    .ord(ord)
+    .write("splits.yaml", splitfile)
+    .run_and_deserialize_output::<ord::subcommand::wallet::split::Output>();
+
+  core.mine_blocks(1);
+}
+
+fn outputs_by_runes(
+  core: &mockcore::Handle,
+  ord: &TestServer,
+) -> BTreeMap<Vec<SpacedRune>, Vec<OutPoint>> {
+  let mut runes = BTreeMap::<OutPoint, Vec<SpacedRune>>::new();
+
+  for (spaced_rune, outputs) in CommandBuilder::new("--regtest --index-runes balances")
+    .core(core)
+    .ord(ord)
+    .run_and_deserialize_output::<Balances>()
+    .runes
+  {
+    for outpoint in outputs.keys() {
+      runes.entry(*outpoint).or_default().push(spaced_rune);
+    }
+  }
+
+  let mut outputs = BTreeMap::<Vec<SpacedRune>, Vec<OutPoint>>::new();
+
+  for (outpoint, runes) in runes {
+    outputs.entry(runes).or_default().push(outpoint);
+  }
+
+  outputs
+}
+
+#[test]
+fn requires_rune_index() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  CommandBuilder::new("wallet consolidate-runes --fee-rate 1")
+    .core(&core)
+    .ord(&ord)
+    .expected_stderr(
+      "error: `ord wallet consolidate-runes` requires index created with `--index-runes`\n",
+    )
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn nothing_to_consolidate() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  etch(&core, &ord, Rune(RUNE));
+
+  CommandBuilder::new("--regtest wallet consolidate-runes --fee-rate 1")
+    .core(&core)
+    .ord(&ord)
+    .expected_stderr("error: no runic outputs to consolidate\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn consolidate_into_single_output() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let rune = Rune(RUNE);
+  let spaced_rune = SpacedRune { rune, spacers: 0 };
+
+  etch(&core, &ord, rune);
+
+  split(&core, &ord, &[rune], 4);
+
+  assert_eq!(outputs_by_runes(&core, &ord)[&vec![spaced_rune]].len(), 5);
+
+  let balance = CommandBuilder::new("--regtest wallet balance")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Balance>();
+
+  let output = CommandBuilder::new("--regtest wallet consolidate-runes --fee-rate 1")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<ConsolidateRunes>();
+
+  assert_eq!(output.transactions.len(), 1);
+  assert_eq!(output.inputs, 5);
+  assert_eq!(output.outputs, 1);
+  assert_eq!(output.fee, output.transactions[0].fee);
+  assert_eq!(output.fee_saved, 4 * 57);
+
+  core.mine_blocks_with_subsidy(1, 0);
+
+  pretty_assert_eq!(
+    CommandBuilder::new("--regtest --index-runes balances")
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output::<Balances>(),
+    Balances {
+      runes: [(
+        spaced_rune,
+        [(
+          OutPoint {
+            txid: output.transactions[0].txid,
+            vout: 1,
+          },
+          Pile {
+            amount: 1000,
+            divisibility: 0,
+            symbol: Some('¢'),
+          },
+        )]
+        .into(),
+      )]
+      .into(),
+    }
+  );
+
+  let after = CommandBuilder::new("--regtest wallet balance")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Balance>();
+
+  assert_eq!(after.runes, balance.runes);
+  assert_eq!(after.ordinal, balance.ordinal);
+  assert_eq!(after.runic, Some(10000));
+}
+
+#[test]
+fn consolidate_into_multiple_outputs() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let rune = Rune(RUNE);
+  let spaced_rune = SpacedRune { rune, spacers: 0 };
+
+  etch(&core, &ord, rune);
+
+  split(&core, &ord, &[rune], 4);
+
+  let output = CommandBuilder::new("--regtest wallet consolidate-runes --fee-rate 1 --outputs 2")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<ConsolidateRunes>();
+
+  assert_eq!(output.inputs, 5);
+  assert_eq!(output.outputs, 2);
+
+  core.mine_blocks(1);
+
+  let txid = output.transactions[0].txid;
+
+  pretty_assert_eq!(
+    CommandBuilder::new("--regtest --index-runes balances")
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output::<Balances>(),
+    Balances {
+      runes: [(
+        spaced_rune,
+        [
+          (
+            OutPoint { txid, vout: 1 },
+            Pile {
+              amount: 500,
+              divisibility: 0,
+              symbol: Some('¢'),
+            },
+          ),
+          (
+            OutPoint { txid, vout: 2 },
+            Pile {
+              amount: 500,
+              divisibility: 0,
+              symbol: Some('¢'),
+            },
+          ),
+        ]
+        .into(),
+      )]
+      .into(),
+    }
+  );
+}
+
+#[test]
+fn consolidate_per_rune() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let a = SpacedRune {
+    rune: Rune(RUNE),
+    spacers: 0,
+  };
+
+  let b = SpacedRune {
+    rune: Rune(RUNE + 1),
+    spacers: 0,
+  };
+
+  etch(&core, &ord, a.rune);
+  etch(&core, &ord, b.rune);
+
+  split(&core, &ord, &[a.rune], 2);
+  split(&core, &ord, &[b.rune], 2);
+  split(&core, &ord, &[a.rune, b.rune], 1);
+
+  let before = outputs_by_runes(&core, &ord);
+
+  assert!(before[&vec![a]].len() > 1);
+  assert!(before[&vec![b]].len() > 1);
+  assert!(!before[&vec![a, b]].is_empty());
+
+  let output = CommandBuilder::new("--regtest wallet consolidate-runes --fee-rate 1 --per-rune")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<ConsolidateRunes>();
+
+  assert_eq!(output.transactions.len(), 2);
+  assert_eq!(
+    output.inputs,
+    before[&vec![a]].len() + before[&vec![b]].len()
+  );
+  assert_eq!(output.outputs, 2);
+
+  core.mine_blocks(1);
+
+  let after = outputs_by_runes(&core, &ord);
+
+  assert_eq!(after[&vec![a]].len(), 1);
+  assert_eq!(after[&vec![b]].len(), 1);
+  assert_eq!(after[&vec![a, b]], before[&vec![a, b]]);
+
+  assert_eq!(
+    CommandBuilder::new("--regtest wallet balance")
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output::<Balance>()
+      .runes,
+    Some([(a, "1000".parse().unwrap()), (b, "1000".parse().unwrap())].into()),
+  );
+}
+
+#[test]
+fn consolidate_respects_max_weight() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let rune = Rune(RUNE);
+
+  etch(&core, &ord, rune);
+
+  split(&core, &ord, &[rune], 5);
+
+  let output = CommandBuilder::new(
+    "--regtest wallet consolidate-runes --fee-rate 1 --dry-run --max-weight 1200",
+  )
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<ConsolidateRunes>();
+
+  assert!(output.transactions.len() > 1);
+  assert_eq!(output.outputs, output.transactions.len());
+
+  assert!(core.mempool().is_empty());
+}
@@ -0,0 +1,188 @@
+use {super::*, ord::subcommand::wallet::extract::Extracted};
+
+type Extract = ord::subcommand::wallet::extract::Output;
+
+#[test]
+fn extract_sat() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let sat = Sat(50 * COIN_VALUE);
+
+  let output = CommandBuilder::new(format!("wallet extract --fee-rate 1 {}", sat.name()))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Extract>();
+
+  assert_eq!(
+    output.extracted,
+    [Extracted {
+      sat,
+      satpoint: SatPoint {
+        outpoint: OutPoint {
+          txid: output.txid,
+          vout: 0,
+        },
+        offset: 0,
+      },
+    }],
+  );
+
+  let tx = &core.mempool()[0];
+
+  assert_eq!(tx.compute_txid(), output.txid);
+  assert_eq!(tx.input.len(), 1);
+  assert_eq!(tx.output.len(), 2);
+  assert_eq!(
+    tx.output[0].value,
+    tx.output[0].script_pubkey.minimal_non_dust()
+  );
+  assert_eq!(
+    tx.output[1].value.to_sat(),
+    50 * COIN_VALUE - tx.output[0].value.to_sat() - output.fee,
+  );
+
+  core.mine_blocks(1);
+
+  assert!(sats(&core, &ord).iter().any(|rare| rare.sat == sat
+    && rare.output
+      == OutPoint {
+        txid: output.txid,
+        vout: 0,
+      }
+    && rare.offset == 0));
+
+  CommandBuilder::new(format!("wallet extract --fee-rate 1 {}", sat.name()))
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr("error: sats are already in their own outputs\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn extract_rarity() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(3);
+
+  let output = CommandBuilder::new("wallet extract --fee-rate 1 uncommon")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Extract>();
+
+  let mut sats = output
+    .extracted
+    .iter()
+    .map(|extracted| extracted.sat)
+    .collect::<Vec<Sat>>();
+
+  sats.sort();
+
+  assert_eq!(
+    sats,
+    [
+      Sat(50 * COIN_VALUE),
+      Sat(100 * COIN_VALUE),
+      Sat(150 * COIN_VALUE)
+    ],
+  );
+
+  let tx = &core.mempool()[0];
+
+  assert_eq!(tx.input.len(), 3);
+  assert_eq!(tx.output.len(), 6);
+
+  for extracted in &output.extracted {
+    let txout = &tx.output[usize::try_from(extracted.satpoint.outpoint.vout).unwrap()];
+    assert_eq!(extracted.satpoint.offset, 0);
+    assert_eq!(txout.value, txout.script_pubkey.minimal_non_dust());
+  }
+}
+
+#[test]
+fn extract_sats_from_tsv() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let output = CommandBuilder::new("wallet extract --fee-rate 1 sats.tsv")
+    .write(
+      "sats.tsv",
+      format!("{}\n{}\n", 50 * COIN_VALUE, 50 * COIN_VALUE + 1000),
+    )
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Extract>();
+
+  let txid = output.txid;
+
+  assert_eq!(
+    output.extracted,
+    [
+      Extracted {
+        sat: Sat(50 * COIN_VALUE),
+        satpoint: SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 0,
+        },
+      },
+      Extracted {
+        sat: Sat(50 * COIN_VALUE + 1000),
+        satpoint: SatPoint {
+          outpoint: OutPoint { txid, vout: 2 },
+          offset: 0,
+        },
+      },
+    ],
+  );
+
+  assert_eq!(core.mempool()[0].output[1].value.to_sat(), 1000 - 330);
+}
+
+#[test]
+fn extract_sat_not_in_wallet() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new("wallet extract --fee-rate 1 0")
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr("error: could not find sat `0` in wallet outputs\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn extract_requires_sat_index() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  CommandBuilder::new("wallet extract --fee-rate 1 uncommon")
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr("error: extract requires index created with `--index-sats` flag\n")
+    .run_and_extract_stdout();
+}
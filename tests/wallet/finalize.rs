@@ -0,0 +1,331 @@
+use {
+  super::*,
+  bitcoin::{
+    bip32::{DerivationPath, Xpriv, Xpub},
+    secp256k1::Secp256k1,
+    taproot::Signature,
+  },
+};
+
+type Finalize = ord::subcommand::wallet::finalize::Output;
+
+fn create_watch_only_wallet(core: &mockcore::Handle) {
+  let secp = Secp256k1::new();
+
+  let master = Xpriv::new_master(Network::Bitcoin, &[0; 64]).unwrap();
+
+  let path = "m/86'/0'/0'".parse::<DerivationPath>().unwrap();
+
+  let xpub = Xpub::from_priv(&secp, &master.derive_priv(&secp, &path).unwrap());
+
+  CommandBuilder::new("wallet restore --from xpub")
+    .stdin(format!("[{}/86'/0'/0']{xpub}", master.fingerprint(&secp)).into())
+    .core(core)
+    .run_and_extract_stdout();
+
+  assert!(core.state().watch_only_wallets.contains("ord"));
+
+  let descriptors = core.descriptors();
+
+  assert_eq!(descriptors.len(), 2);
+  assert!(descriptors
+    .iter()
+    .all(|descriptor| descriptor.starts_with("tr(") && descriptor.contains(&xpub.to_string())));
+}
+
+fn sign(psbt: &str) -> String {
+  let mut psbt = Psbt::deserialize(&base64_decode(psbt).unwrap()).unwrap();
+
+  for input in &mut psbt.inputs {
+    if input.final_script_witness.is_none() {
+      input.tap_key_sig = Some(Signature::from_slice(&[1; 64]).unwrap());
+    }
+  }
+
+  base64_encode(&psbt.serialize())
+}
+
+#[test]
+fn watch_only_send_returns_unsigned_psbt_which_can_be_finalized() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_watch_only_wallet(&core);
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  core.mine_blocks(1);
+
+  let send =
+    CommandBuilder::new("wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 1btc")
+      .temp_dir(tempdir.clone())
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output::<Send>();
+
+  assert!(core.mempool().is_empty());
+
+  let psbt = Psbt::deserialize(&base64_decode(&send.psbt).unwrap()).unwrap();
+
+  assert_eq!(psbt.unsigned_tx.compute_txid(), send.txid);
+
+  assert!(psbt
+    .inputs
+    .iter()
+    .all(|input| input.final_script_witness.is_none() && input.witness_utxo.is_some()));
+
+  CommandBuilder::new(format!("wallet finalize --psbt {}", send.psbt))
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr(format!(
+      "error: input {} is not signed\n",
+      psbt.unsigned_tx.input[0].previous_output
+    ))
+    .run_and_extract_stdout();
+
+  let finalize = CommandBuilder::new(format!("wallet finalize --psbt {}", sign(&send.psbt)))
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Finalize>();
+
+  assert_eq!(finalize.txid, send.txid);
+  assert_eq!(finalize.reveal, None);
+
+  assert_eq!(core.mempool().len(), 1);
+  assert_eq!(core.mempool()[0].compute_txid(), send.txid);
+
+  CommandBuilder::new(format!("wallet finalize --psbt {}", sign(&send.psbt)))
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr(format!(
+      "error: transaction {} was not planned by wallet\n",
+      send.txid
+    ))
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn watch_only_dry_run_is_not_planned() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_watch_only_wallet(&core);
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  core.mine_blocks(1);
+
+  let send = CommandBuilder::new(
+    "wallet send --fee-rate 1 --dry-run bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 1btc",
+  )
+  .temp_dir(tempdir.clone())
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  CommandBuilder::new(format!("wallet finalize --psbt {}", sign(&send.psbt)))
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr(format!(
+      "error: transaction {} was not planned by wallet\n",
+      send.txid
+    ))
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn watch_only_inscribe_broadcasts_reveal_after_commit() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_watch_only_wallet(&core);
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  core.mine_blocks(1);
+
+  let batch = CommandBuilder::new("wallet inscribe --fee-rate 1 --file foo.txt")
+    .temp_dir(tempdir.clone())
+    .write("foo.txt", "FOO")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Batch>();
+
+  assert!(core.mempool().is_empty());
+  assert!(!batch.reveal_broadcast);
+  assert_eq!(batch.reveal_psbt, None);
+
+  let finalize = CommandBuilder::new(format!(
+    "wallet finalize --psbt {}",
+    sign(batch.commit_psbt.as_ref().unwrap())
+  ))
+  .temp_dir(tempdir.clone())
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Finalize>();
+
+  assert_eq!(finalize.txid, batch.commit);
+  assert_eq!(finalize.reveal, Some(batch.reveal));
+
+  core.mine_blocks(1);
+
+  let inscription = batch.inscriptions[0].id;
+
+  let inscriptions = CommandBuilder::new("wallet inscriptions")
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Inscriptions>();
+
+  assert_eq!(inscriptions.len(), 1);
+  assert_eq!(inscriptions[0].inscription, inscription);
+
+  let send = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 {inscription}"
+  ))
+  .temp_dir(tempdir.clone())
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  let psbt = Psbt::deserialize(&base64_decode(&send.psbt).unwrap()).unwrap();
+
+  let annotated = psbt
+    .inputs
+    .iter()
+    .filter_map(|input| {
+      input
+        .proprietary
+        .iter()
+        .find(|(key, _value)| key.prefix == b"ord" && key.subtype == 0)
+        .map(|(_key, value)| serde_json::from_slice::<Vec<InscriptionId>>(value).unwrap())
+    })
+    .collect::<Vec<Vec<InscriptionId>>>();
+
+  assert_eq!(annotated, [[inscription]]);
+}
+
+#[test]
+fn watch_only_wallet_may_not_etch() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  let secp = Secp256k1::new();
+
+  let master = Xpriv::new_master(Network::Regtest, &[0; 64]).unwrap();
+
+  let path = "m/86'/1'/0'".parse::<DerivationPath>().unwrap();
+
+  let xpub = Xpub::from_priv(&secp, &master.derive_priv(&secp, &path).unwrap());
+
+  CommandBuilder::new("--regtest wallet restore --from xpub")
+    .stdin(xpub.to_string().into())
+    .core(&core)
+    .run_and_extract_stdout();
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new("--regtest --index-runes wallet batch --fee-rate 0 --batch batch.yaml")
+    .write("inscription.txt", "FOO")
+    .write(
+      "batch.yaml",
+      serde_yaml::to_string(&batch::File {
+        etching: Some(batch::Etching {
+          divisibility: 0,
+          rune: SpacedRune {
+            rune: Rune(RUNE),
+            spacers: 0,
+          },
+          supply: "1000".parse().unwrap(),
+          premine: "1000".parse().unwrap(),
+          symbol: '¢',
+          terms: None,
+          turbo: false,
+        }),
+        inscriptions: vec![batch::Entry {
+          file: Some("inscription.txt".into()),
+          ..default()
+        }],
+        ..default()
+      })
+      .unwrap(),
+    )
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr("error: etching runes is not supported with watch-only or multisig wallets\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn watch_only_finalize_sends_reveal_if_commit_was_already_broadcast() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_watch_only_wallet(&core);
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  core.mine_blocks(1);
+
+  let batch = CommandBuilder::new("wallet inscribe --fee-rate 1 --file foo.txt")
+    .temp_dir(tempdir.clone())
+    .write("foo.txt", "FOO")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Batch>();
+
+  let descriptors = CommandBuilder::new("wallet dump")
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .stderr_regex(".*")
+    .run_and_deserialize_output::<ListDescriptorsResult>();
+
+  assert_eq!(
+    descriptors
+      .descriptors
+      .iter()
+      .filter(|descriptor| descriptor.desc.starts_with("rawtr("))
+      .count(),
+    1,
+  );
+
+  let commit_psbt = sign(batch.commit_psbt.as_ref().unwrap());
+
+  let commit = Psbt::deserialize(&base64_decode(&commit_psbt).unwrap())
+    .unwrap()
+    .unsigned_tx;
+
+  core.state().mempool.push(commit);
+
+  let finalize = CommandBuilder::new(format!("wallet finalize --psbt {commit_psbt}"))
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Finalize>();
+
+  assert_eq!(finalize.txid, batch.commit);
+  assert_eq!(finalize.reveal, Some(batch.reveal));
+
+  assert_eq!(
+    core
+      .mempool()
+      .iter()
+      .map(|tx| tx.compute_txid())
+      .collect::<Vec<Txid>>(),
+    [batch.commit, batch.reveal],
+  );
+}
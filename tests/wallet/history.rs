@@ -0,0 +1,303 @@
+use {
+  super::*,
+  ord::subcommand::wallet::history::{Output, Transfer},
+};
+
+type History = Vec<Output>;
+
+#[test]
+fn history_includes_received_and_sent_bitcoin() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  CommandBuilder::new("wallet create")
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Create>();
+
+  core.mine_blocks(1);
+
+  let coinbase = core.tx(1, 0).compute_txid();
+
+  let send =
+    CommandBuilder::new("wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 1btc")
+      .temp_dir(tempdir.clone())
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output::<Send>();
+
+  core.mine_blocks(1);
+
+  let history = CommandBuilder::new("wallet history")
+    .temp_dir(tempdir)
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<History>();
+
+  let received = history
+    .iter()
+    .find(|entry| entry.transaction == coinbase)
+    .unwrap();
+
+  pretty_assert_eq!(
+    *received,
+    Output {
+      transaction: coinbase,
+      height: Some(1),
+      timestamp: Some(1),
+      confirmations: 2,
+      received: 50 * COIN_VALUE,
+      sent: 0,
+      fee: None,
+      inscriptions_received: Vec::new(),
+      inscriptions_sent: Vec::new(),
+      runes_received: BTreeMap::new(),
+      runes_sent: BTreeMap::new(),
+      labels: Vec::new(),
+      assets_unknown: false,
+    }
+  );
+
+  let sent = history
+    .iter()
+    .find(|entry| entry.transaction == send.txid)
+    .unwrap();
+
+  pretty_assert_eq!(
+    *sent,
+    Output {
+      transaction: send.txid,
+      height: Some(2),
+      timestamp: Some(2),
+      confirmations: 1,
+      received: 0,
+      sent: COIN_VALUE,
+      fee: Some(send.fee),
+      inscriptions_received: Vec::new(),
+      inscriptions_sent: Vec::new(),
+      runes_received: BTreeMap::new(),
+      runes_sent: BTreeMap::new(),
+      labels: Vec::new(),
+      assets_unknown: false,
+    }
+  );
+}
+
+#[test]
+fn history_includes_inscriptions_with_counterparties() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  CommandBuilder::new("wallet create")
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Create>();
+
+  core.mine_blocks(1);
+
+  let output = CommandBuilder::new("wallet inscribe --fee-rate 1 --file foo.txt")
+    .temp_dir(tempdir.clone())
+    .write("foo.txt", "FOO")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Batch>();
+
+  core.mine_blocks(1);
+
+  let inscription = output.inscriptions[0].id;
+
+  let address = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+
+  let send = CommandBuilder::new(format!("wallet send --fee-rate 1 {address} {inscription}"))
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Send>();
+
+  core.mine_blocks(1);
+
+  let history = CommandBuilder::new("wallet history")
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<History>();
+
+  let reveal = history
+    .iter()
+    .find(|entry| entry.transaction == output.reveal)
+    .unwrap();
+
+  assert_eq!(
+    reveal.inscriptions_received,
+    [Transfer {
+      inscription,
+      counterparty: None,
+    }],
+  );
+
+  let sent = history
+    .iter()
+    .find(|entry| entry.transaction == send.txid)
+    .unwrap();
+
+  assert_eq!(
+    sent.inscriptions_sent,
+    [Transfer {
+      inscription,
+      counterparty: Some(address.parse().unwrap()),
+    }],
+  );
+  assert_eq!(sent.sent, 10_000 - send.fee);
+  assert_eq!(sent.fee, Some(send.fee));
+
+  let csv = CommandBuilder::new("wallet history --csv")
+    .temp_dir(tempdir)
+    .core(&core)
+    .ord(&ord)
+    .stdout_regex(".*")
+    .run_and_extract_stdout();
+
+  let lines = csv.lines().collect::<Vec<&str>>();
+
+  assert_eq!(
+    lines[0],
+    "transaction,height,timestamp,confirmations,received,sent,fee,inscriptions_received,\
+    inscriptions_sent,runes_received,runes_sent,labels,assets_unknown",
+  );
+
+  assert_eq!(lines.len(), history.len() + 1);
+
+  assert!(lines.contains(
+    &format!(
+      "{},3,3,1,0,{},{},,{inscription}:{address},,,,false",
+      send.txid,
+      10_000 - send.fee,
+      send.fee,
+    )
+    .as_str()
+  ));
+}
+
+#[test]
+fn history_includes_runes_with_divisibility() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  CommandBuilder::new("--regtest wallet create")
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Create>();
+
+  let rune = Rune(RUNE);
+  let spaced_rune = SpacedRune { rune, spacers: 1 };
+
+  batch(
+    &core,
+    &ord,
+    batch::File {
+      etching: Some(batch::Etching {
+        supply: "100.0".parse().unwrap(),
+        divisibility: 1,
+        terms: None,
+        premine: "100.0".parse().unwrap(),
+        rune: spaced_rune,
+        symbol: '¢',
+        turbo: false,
+      }),
+      inscriptions: vec![batch::Entry {
+        file: Some("inscription.jpeg".into()),
+        ..default()
+      }],
+      ..default()
+    },
+  );
+
+  let send = CommandBuilder::new(format!(
+    "--regtest wallet send --fee-rate 1 bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw 25.5:{spaced_rune}",
+  ))
+  .temp_dir(tempdir.clone())
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  core.mine_blocks(1);
+
+  let history = CommandBuilder::new("--regtest wallet history")
+    .temp_dir(tempdir)
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<History>();
+
+  let sent = history
+    .iter()
+    .find(|entry| entry.transaction == send.txid)
+    .unwrap();
+
+  assert_eq!(
+    sent.runes_sent,
+    [(spaced_rune, "25.5".parse().unwrap())].into(),
+  );
+  assert!(sent.runes_received.is_empty());
+  assert_eq!(sent.fee, Some(send.fee));
+}
+
+#[test]
+fn history_marks_unindexed_outputs_as_unknown() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  CommandBuilder::new("wallet create")
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Create>();
+
+  core.mine_blocks(1);
+
+  let coinbase = core.tx(1, 0).compute_txid();
+
+  let send =
+    CommandBuilder::new("wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 1btc")
+      .temp_dir(tempdir.clone())
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output::<Send>();
+
+  let history = CommandBuilder::new("wallet history")
+    .temp_dir(tempdir)
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<History>();
+
+  assert!(
+    !history
+      .iter()
+      .find(|entry| entry.transaction == coinbase)
+      .unwrap()
+      .assets_unknown
+  );
+
+  // the change output of the unconfirmed send is not yet indexed
+  assert!(
+    history
+      .iter()
+      .find(|entry| entry.transaction == send.txid)
+      .unwrap()
+      .assets_unknown
+  );
+}
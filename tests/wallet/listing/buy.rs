@@ -0,0 +1,200 @@
+use super::*;
+
+type Buy = ord::subcommand::wallet::listing::buy::Output;
+type Create = ord::subcommand::wallet::listing::create::Output;
+
+#[test]
+fn bought_listing_works() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let postage = 9000;
+
+  let (inscription, _txid) = inscribe_with_options(&core, &ord, Some(postage), 0);
+
+  let create = CommandBuilder::new(format!(
+    "wallet listing create --inscription {inscription} --amount 1btc"
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Create>();
+
+  let seller_addresses = core.state().clear_wallet_addresses();
+
+  core.mine_blocks(3);
+
+  let buy = CommandBuilder::new(format!(
+    "wallet listing buy --inscription {inscription} --amount 1btc --fee-rate 1 --psbt {} --dry-run",
+    create.psbt
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Buy>();
+
+  assert!(core.mempool().is_empty());
+  assert!(buy.fee > 0);
+
+  let buy = CommandBuilder::new(format!(
+    "wallet listing buy --inscription {inscription} --amount 1btc --fee-rate 1 --psbt {}",
+    create.psbt
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Buy>();
+
+  let tx = core.mine_blocks(1)[0].txdata[1].clone();
+
+  assert_eq!(tx.compute_txid(), buy.txid);
+
+  let inscriptions = CommandBuilder::new("wallet inscriptions")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Inscriptions>();
+
+  assert_eq!(inscriptions.len(), 1);
+  assert_eq!(inscriptions[0].inscription, inscription);
+  assert_eq!(inscriptions[0].location.outpoint.txid, buy.txid);
+  assert_eq!(inscriptions[0].location.outpoint.vout, 1);
+  assert_eq!(inscriptions[0].location.offset, 0);
+
+  let balance = CommandBuilder::new("wallet balance")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Balance>();
+
+  // fees are paid to the buyer, who mines the block
+  assert_eq!(balance.total, 4 * 50 * COIN_VALUE - COIN_VALUE);
+
+  core.state().clear_wallet_addresses();
+
+  for address in seller_addresses {
+    core.state().add_wallet_address(address);
+  }
+
+  let inscriptions = CommandBuilder::new("wallet inscriptions")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Inscriptions>();
+
+  assert!(inscriptions.is_empty());
+
+  assert!(core.state().utxos.contains_key(&OutPoint {
+    txid: buy.txid,
+    vout: 2
+  }));
+
+  assert_eq!(tx.output[1].value.to_sat(), postage);
+  assert_eq!(tx.output[2].value.to_sat(), COIN_VALUE + postage);
+}
+
+#[test]
+fn listing_amount_must_match() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, _txid) = inscribe_with_options(&core, &ord, Some(9000), 0);
+
+  let create = CommandBuilder::new(format!(
+    "wallet listing create --inscription {inscription} --amount 1btc"
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Create>();
+
+  core.state().clear_wallet_addresses();
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new(format!(
+    "wallet listing buy --inscription {inscription} --amount 0.5btc --fee-rate 1 --psbt {}",
+    create.psbt
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_exit_code(1)
+  .expected_stderr(
+    "error: unexpected listing payment of 1.00009000 BTC, expected 0.50000000 BTC plus 0.00009000 BTC postage\n",
+  )
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn listing_inscription_must_match() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, _txid) = inscribe_with_options(&core, &ord, Some(9000), 0);
+
+  let create = CommandBuilder::new(format!(
+    "wallet listing create --inscription {inscription} --amount 1btc"
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Create>();
+
+  core.state().clear_wallet_addresses();
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new(format!(
+    "wallet listing buy --inscription 6fb976ab49dcec017f1e201e84395983204ae1a7c2abf7ced0a85d692e442799i0 --amount 1btc --fee-rate 1 --psbt {}",
+    create.psbt
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_exit_code(1)
+  .expected_stderr(format!("error: unexpected listed inscription {inscription}\n"))
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn listing_input_must_be_signed() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, txid) = inscribe_with_options(&core, &ord, Some(9000), 0);
+
+  core.state().clear_wallet_addresses();
+
+  core.mine_blocks(1);
+
+  let psbt = Psbt::from_unsigned_tx(Transaction {
+    version: Version(2),
+    lock_time: LockTime::ZERO,
+    input: vec![TxIn {
+      previous_output: OutPoint { txid, vout: 0 },
+      script_sig: ScriptBuf::new(),
+      sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+      witness: Witness::new(),
+    }],
+    output: vec![TxOut {
+      value: Amount::from_sat(COIN_VALUE + 9000),
+      script_pubkey: ScriptBuf::new(),
+    }],
+  })
+  .unwrap();
+
+  CommandBuilder::new(format!(
+    "wallet listing buy --inscription {inscription} --amount 1btc --fee-rate 1 --psbt {}",
+    base64_encode(&psbt.serialize())
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_exit_code(1)
+  .expected_stderr(format!(
+    "error: listing input {txid}:0 must be signed with SIGHASH_SINGLE|ANYONECANPAY\n"
+  ))
+  .run_and_extract_stdout();
+}
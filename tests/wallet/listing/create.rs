@@ -0,0 +1,66 @@
+use super::*;
+
+type Create = ord::subcommand::wallet::listing::create::Output;
+
+#[test]
+fn created_listing_is_signed_with_sighash_single_anyonecanpay() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let postage = 9000;
+
+  let (inscription, txid) = inscribe_with_options(&core, &ord, Some(postage), 0);
+
+  let create = CommandBuilder::new(format!(
+    "wallet listing create --inscription {inscription} --amount 1btc"
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Create>();
+
+  assert_eq!(create.inscription, inscription);
+  assert_eq!(create.payment, COIN_VALUE + postage);
+
+  let psbt = Psbt::deserialize(&base64_decode(&create.psbt).unwrap()).unwrap();
+
+  assert_eq!(psbt.unsigned_tx.input.len(), 1);
+  assert_eq!(
+    psbt.unsigned_tx.input[0].previous_output,
+    OutPoint { txid, vout: 0 }
+  );
+
+  assert_eq!(psbt.unsigned_tx.output.len(), 1);
+  assert_eq!(
+    psbt.unsigned_tx.output[0].value.to_sat(),
+    COIN_VALUE + postage
+  );
+
+  let witness = psbt.inputs[0].final_script_witness.as_ref().unwrap();
+
+  assert_eq!(witness.len(), 1);
+  assert_eq!(witness.nth(0).unwrap().len(), 65);
+  assert_eq!(witness.nth(0).unwrap().last(), Some(&0x83));
+}
+
+#[test]
+fn inscription_must_be_in_wallet() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  CommandBuilder::new(
+    "wallet listing create --inscription 6fb976ab49dcec017f1e201e84395983204ae1a7c2abf7ced0a85d692e442799i0 --amount 1btc",
+  )
+  .core(&core)
+  .ord(&ord)
+  .expected_exit_code(1)
+  .expected_stderr(
+    "error: inscription 6fb976ab49dcec017f1e201e84395983204ae1a7c2abf7ced0a85d692e442799i0 not in wallet\n",
+  )
+  .run_and_extract_stdout();
+}
@@ -631,3 +631,171 @@ fn seller_input_must_not_be_signed() {
   ))
   .run_and_extract_stdout();
 }
+
+#[test]
+fn accepted_rune_offer_works() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let etched = etch(&core, &ord, Rune(RUNE));
+
+  let location = etched.output.rune.unwrap().location.unwrap();
+
+  let seller_addresses = core.state().clear_wallet_addresses();
+
+  core.mine_blocks(1);
+
+  let create = CommandBuilder::new(format!(
+    "--regtest --index-runes wallet offer create --rune 600:{} --utxo {location} --amount 1btc --fee-rate 1",
+    Rune(RUNE),
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Create>();
+
+  let buyer_addresses = core.state().clear_wallet_addresses();
+
+  for address in seller_addresses {
+    core.state().add_wallet_address(address);
+  }
+
+  let accept = CommandBuilder::new(format!(
+    "--regtest --index-runes wallet offer accept --rune 600:{} --amount 1btc --psbt {}",
+    Rune(RUNE),
+    create.psbt,
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Accept>();
+
+  core.mine_blocks(1);
+
+  let balances = CommandBuilder::new("--regtest --index-runes balances")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<ord::subcommand::balances::Output>();
+
+  pretty_assert_eq!(
+    balances.runes[&SpacedRune::new(Rune(RUNE), 0)],
+    [
+      (
+        OutPoint {
+          txid: accept.txid,
+          vout: 0,
+        },
+        Pile {
+          amount: 600,
+          divisibility: 0,
+          symbol: Some('¢'),
+        },
+      ),
+      (
+        OutPoint {
+          txid: accept.txid,
+          vout: 1,
+        },
+        Pile {
+          amount: 400,
+          divisibility: 0,
+          symbol: Some('¢'),
+        },
+      ),
+    ]
+    .into_iter()
+    .collect(),
+  );
+
+  for address in buyer_addresses {
+    core.state().add_wallet_address(address);
+  }
+
+  let tx = core.tx_by_id(accept.txid);
+
+  assert!(core.state().is_wallet_address(
+    &Address::from_script(&tx.output[0].script_pubkey, Network::Regtest).unwrap()
+  ));
+}
+
+#[test]
+fn rune_offer_may_not_misroute_runes() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let etched = etch(&core, &ord, Rune(RUNE));
+
+  let location = etched.output.rune.unwrap().location.unwrap();
+
+  let seller_addresses = core.state().clear_wallet_addresses();
+
+  core.mine_blocks(1);
+
+  let create = CommandBuilder::new(format!(
+    "--regtest --index-runes wallet offer create --rune 600:{} --utxo {location} --amount 1btc --fee-rate 1",
+    Rune(RUNE),
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Create>();
+
+  core.state().clear_wallet_addresses();
+
+  for address in seller_addresses {
+    core.state().add_wallet_address(address);
+  }
+
+  let mut psbt = Psbt::deserialize(&base64_decode(&create.psbt).unwrap()).unwrap();
+
+  psbt.unsigned_tx.output[2].script_pubkey = Runestone {
+    edicts: vec![Edict {
+      id: etched.id,
+      amount: 0,
+      output: 0,
+    }],
+    ..default()
+  }
+  .encipher();
+
+  CommandBuilder::new(format!(
+    "--regtest --index-runes wallet offer accept --rune 600:{} --amount 1btc --psbt {} --dry-run",
+    Rune(RUNE),
+    base64_encode(&psbt.serialize()),
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_exit_code(1)
+  .expected_stderr(format!(
+    "error: offer sends 1000 units of rune {} from outgoing input {location}, expected 600\n",
+    etched.id,
+  ))
+  .run_and_extract_stdout();
+
+  psbt.unsigned_tx.output[2].script_pubkey = Runestone {
+    edicts: vec![Edict {
+      id: etched.id,
+      amount: 600,
+      output: 0,
+    }],
+    pointer: Some(2),
+    ..default()
+  }
+  .encipher();
+
+  CommandBuilder::new(format!(
+    "--regtest --index-runes wallet offer accept --rune 600:{} --amount 1btc --psbt {} --dry-run",
+    Rune(RUNE),
+    base64_encode(&psbt.serialize()),
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_exit_code(1)
+  .expected_stderr(format!(
+    "error: offer burns runes from outgoing input {location}\n"
+  ))
+  .run_and_extract_stdout();
+}
@@ -45,7 +45,7 @@ fn created_offer_is_correct() {
     address,
   );
 
-  assert_eq!(create.inscription, inscription);
+  assert_eq!(create.inscription, Some(inscription));
 
   let psbt = Psbt::deserialize(&base64_decode(&create.psbt).unwrap()).unwrap();
 
@@ -185,3 +185,33 @@ fn inscription_must_have_valid_address() {
   .expected_exit_code(1)
   .run_and_extract_stdout();
 }
+
+#[test]
+fn rune_offer_utxo_must_hold_offered_amount() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let etched = etch(&core, &ord, Rune(RUNE));
+
+  let location = etched.output.rune.unwrap().location.unwrap();
+
+  core.state().clear_wallet_addresses();
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new(format!(
+    "--regtest --index-runes wallet offer create --rune 1001:{} --utxo {location} --amount 1btc --fee-rate 1",
+    Rune(RUNE),
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_exit_code(1)
+  .expected_stderr(format!(
+    "error: utxo {location} holds 1000\u{A0}¢ `{}`, less than offered amount\n",
+    Rune(RUNE),
+  ))
+  .run_and_extract_stdout();
+}
@@ -0,0 +1,320 @@
+use super::*;
+
+type SendMany = ord::subcommand::wallet::send_many::Output;
+
+#[test]
+fn send_many_sends_inscriptions_sats_and_bitcoin_in_one_transaction() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (first, first_reveal) = inscribe(&core, &ord);
+  let (second, second_reveal) = inscribe(&core, &ord);
+
+  let sat = sats(&core, &ord)
+    .into_iter()
+    .find(|rare| rare.output.txid != first_reveal && rare.output.txid != second_reveal)
+    .unwrap()
+    .sat;
+
+  let output = CommandBuilder::new("wallet send-many --fee-rate 1 --sends sends.yaml")
+    .write(
+      "sends.yaml",
+      format!(
+        "
+sends:
+- address: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+  asset: {first}
+- address: bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv
+  asset: {second}
+- address: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+  asset: {}
+- address: bc1p5d7rjq7g6rdk2yhzks9smlaqtedr4dekq08ge8ztwac72sfr9rusxg3297
+  asset: 1 btc
+",
+        sat.name(),
+      ),
+    )
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<SendMany>();
+
+  assert!(output.completed.is_empty());
+  assert_eq!(output.transactions.len(), 1);
+  assert_eq!(output.transactions[0].sends, [0, 1, 2, 3]);
+
+  let mempool = core.mempool();
+
+  assert_eq!(mempool.len(), 1);
+
+  let tx = &mempool[0];
+
+  assert_eq!(tx.compute_txid(), output.transactions[0].txid);
+
+  let scripts = tx
+    .output
+    .iter()
+    .map(|txout| txout.script_pubkey.clone())
+    .collect::<Vec<ScriptBuf>>();
+
+  for address in [
+    "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+    "bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv",
+    "bc1p5d7rjq7g6rdk2yhzks9smlaqtedr4dekq08ge8ztwac72sfr9rusxg3297",
+  ] {
+    assert!(scripts.contains(
+      &address
+        .parse::<Address<NetworkUnchecked>>()
+        .unwrap()
+        .assume_checked()
+        .script_pubkey()
+    ));
+  }
+
+  core.mine_blocks(1);
+
+  let txid = output.transactions[0].txid;
+
+  for inscription in [first, second] {
+    ord.assert_response_regex(
+      format!("/inscription/{inscription}"),
+      format!(".*<dt>location</dt>\\s*<dd><a class=collapse href=/satpoint/{txid}:\\d+:0>{txid}:\\d+:0</a></dd>.*"),
+    );
+  }
+
+  assert!(!sats(&core, &ord).iter().any(|rare| rare.sat == sat));
+}
+
+#[test]
+fn send_many_splits_sends_into_transactions_under_max_weight() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(3);
+
+  let output =
+    CommandBuilder::new("wallet send-many --fee-rate 1 --max-weight 700 --sends sends.yaml")
+      .write(
+        "sends.yaml",
+        "
+sends:
+- address: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+  asset: 1 btc
+- address: bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv
+  asset: 2 btc
+- address: bc1p5d7rjq7g6rdk2yhzks9smlaqtedr4dekq08ge8ztwac72sfr9rusxg3297
+  asset: 3 btc
+",
+      )
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output::<SendMany>();
+
+  assert!(output.transactions.len() > 1);
+
+  assert_eq!(
+    output
+      .transactions
+      .iter()
+      .flat_map(|sent| sent.sends.iter().copied())
+      .collect::<Vec<usize>>(),
+    [0, 1, 2],
+  );
+
+  let mempool = core.mempool();
+
+  assert_eq!(mempool.len(), output.transactions.len());
+
+  for (tx, sent) in mempool.iter().zip(&output.transactions) {
+    assert_eq!(tx.compute_txid(), sent.txid);
+    assert!(tx.weight().to_wu() <= 700);
+  }
+}
+
+#[test]
+fn send_many_dry_run_does_not_record_progress() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let sends = "
+sends:
+- address: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+  asset: 1 btc
+- address: bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv
+  asset: 2 btc
+";
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  let dry_run = CommandBuilder::new("wallet send-many --dry-run --fee-rate 1 --sends sends.yaml")
+    .temp_dir(tempdir.clone())
+    .write("sends.yaml", sends)
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<SendMany>();
+
+  assert_eq!(dry_run.transactions.len(), 1);
+  assert!(core.mempool().is_empty());
+
+  let output = CommandBuilder::new("wallet send-many --fee-rate 1 --sends sends.yaml")
+    .temp_dir(tempdir.clone())
+    .write("sends.yaml", sends)
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<SendMany>();
+
+  assert!(output.completed.is_empty());
+  assert_eq!(output.transactions.len(), 1);
+  assert_eq!(core.mempool().len(), 1);
+}
+
+#[test]
+fn send_many_skips_completed_sends() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  let sends = "
+sends:
+- address: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+  asset: 1 btc
+- address: bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv
+  asset: 2 btc
+";
+
+  let output = CommandBuilder::new("wallet send-many --fee-rate 1 --sends sends.yaml")
+    .temp_dir(tempdir.clone())
+    .write("sends.yaml", sends)
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<SendMany>();
+
+  let txid = output.transactions[0].txid;
+
+  let output = CommandBuilder::new("wallet send-many --fee-rate 1 --sends sends.yaml")
+    .temp_dir(tempdir.clone())
+    .write("sends.yaml", sends)
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<SendMany>();
+
+  assert_eq!(
+    output.completed,
+    [(0, txid), (1, txid)].into_iter().collect()
+  );
+  assert!(output.transactions.is_empty());
+  assert_eq!(core.mempool().len(), 1);
+}
+
+#[test]
+fn send_many_keeps_progress_when_sends_are_appended() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(2);
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  let sends = "
+sends:
+- address: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+  asset: 1 btc
+";
+
+  let output = CommandBuilder::new("wallet send-many --fee-rate 1 --sends sends.yaml")
+    .temp_dir(tempdir.clone())
+    .write("sends.yaml", sends)
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<SendMany>();
+
+  let txid = output.transactions[0].txid;
+
+  let output = CommandBuilder::new("wallet send-many --fee-rate 1 --sends sends.yaml")
+    .temp_dir(tempdir.clone())
+    .write(
+      "sends.yaml",
+      format!("# appended a send\n{sends}- address: bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv\n  asset: 2 btc\n"),
+    )
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<SendMany>();
+
+  assert_eq!(output.completed, [(0, txid)].into_iter().collect());
+  assert_eq!(output.transactions.len(), 1);
+  assert_eq!(output.transactions[0].sends, [1]);
+  assert_eq!(core.mempool().len(), 2);
+}
+
+#[test]
+fn send_many_refuses_runes() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  CommandBuilder::new("wallet send-many --fee-rate 1 --sends sends.yaml")
+    .write(
+      "sends.yaml",
+      "
+sends:
+- address: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+  asset: 5:FOO
+",
+    )
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr(
+      "error: send 0 sends runes, use `ord wallet split` to send runes to multiple recipients\n",
+    )
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn send_many_refuses_dust_amounts() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new("wallet send-many --fee-rate 1 --sends sends.yaml")
+    .write(
+      "sends.yaml",
+      "
+sends:
+- address: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+  asset: 1 btc
+- address: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+  asset: 1 sat
+",
+    )
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr("error: send 1 amount 0.00000001 BTC below dust threshold 0.00000294 BTC\n")
+    .run_and_extract_stdout();
+}
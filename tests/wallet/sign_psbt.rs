@@ -0,0 +1,280 @@
+use {
+  super::*,
+  bitcoin::{
+    bip32::{DerivationPath, Xpriv, Xpub},
+    secp256k1::Secp256k1,
+  },
+};
+
+type Finalize = ord::subcommand::wallet::finalize::Output;
+type SignPsbt = ord::subcommand::wallet::sign_psbt::Output;
+
+fn multisig_descriptor() -> String {
+  let secp = Secp256k1::new();
+
+  let path = "m/48'/0'/0'/2'".parse::<DerivationPath>().unwrap();
+
+  let keys = [1, 2, 3]
+    .map(|seed| {
+      Xpriv::new_master(Network::Bitcoin, &[seed; 64])
+        .unwrap()
+        .derive_priv(&secp, &path)
+        .unwrap()
+    })
+    .to_vec();
+
+  format!(
+    "wsh(sortedmulti(2,{}/<0;1>/*,{}/<0;1>/*,{}/<0;1>/*))",
+    keys[0],
+    Xpub::from_priv(&secp, &keys[1]),
+    Xpub::from_priv(&secp, &keys[2]),
+  )
+}
+
+fn create_multisig_wallet(core: &mockcore::Handle) {
+  CommandBuilder::new("wallet restore --from multisig")
+    .stdin(multisig_descriptor().into())
+    .core(core)
+    .run_and_extract_stdout();
+
+  assert!(!core.state().watch_only_wallets.contains("ord"));
+
+  let descriptors = core.descriptors();
+
+  assert_eq!(descriptors.len(), 2);
+  assert!(descriptors
+    .iter()
+    .all(|descriptor| descriptor.starts_with("wsh(sortedmulti(2,")));
+  assert!(descriptors
+    .iter()
+    .any(|descriptor| descriptor.contains("/0/*")));
+  assert!(descriptors
+    .iter()
+    .any(|descriptor| descriptor.contains("/1/*")));
+}
+
+#[test]
+fn multisig_send_is_cosigned_and_finalized() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_multisig_wallet(&core);
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  core.mine_blocks(1);
+
+  let send =
+    CommandBuilder::new("wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 1btc")
+      .temp_dir(tempdir.clone())
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output::<Send>();
+
+  assert!(core.mempool().is_empty());
+
+  let signed = CommandBuilder::new(format!("wallet sign-psbt --psbt {}", send.psbt))
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<SignPsbt>();
+
+  assert_ne!(signed.psbt, send.psbt);
+
+  let finalize = CommandBuilder::new(format!("wallet finalize --psbt {}", signed.psbt))
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Finalize>();
+
+  assert_eq!(finalize.txid, send.txid);
+
+  assert_eq!(core.mempool().len(), 1);
+  assert_eq!(core.mempool()[0].compute_txid(), send.txid);
+}
+
+#[test]
+fn multisig_descriptor_must_derive_receive_and_change_addresses() {
+  let core = mockcore::spawn();
+
+  CommandBuilder::new("wallet restore --from multisig")
+    .stdin(multisig_descriptor().replace("<0;1>", "0").into())
+    .core(&core)
+    .expected_exit_code(1)
+    .expected_stderr(
+      "error: multisig descriptor keys must derive receive and change addresses with `/<0;1>/*`\n",
+    )
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn multisig_descriptor_must_be_multisig() {
+  let core = mockcore::spawn();
+
+  let secp = Secp256k1::new();
+
+  let xpub = Xpub::from_priv(
+    &secp,
+    &Xpriv::new_master(Network::Bitcoin, &[0; 64]).unwrap(),
+  );
+
+  CommandBuilder::new("wallet restore --from multisig")
+    .stdin(format!("tr({xpub}/<0;1>/*)").into())
+    .core(&core)
+    .expected_exit_code(1)
+    .expected_stderr(
+      "error: descriptor must be a `tr` descriptor with `multi_a` script paths, or a `wsh` \
+      descriptor with `multi` or `sortedmulti`\n",
+    )
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn sign_psbt_checks_inscriptions() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_multisig_wallet(&core);
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  core.mine_blocks(1);
+
+  let batch = CommandBuilder::new("wallet inscribe --fee-rate 1 --file foo.txt")
+    .temp_dir(tempdir.clone())
+    .write("foo.txt", "FOO")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Batch>();
+
+  let signed = CommandBuilder::new(format!(
+    "wallet sign-psbt --psbt {}",
+    batch.commit_psbt.unwrap()
+  ))
+  .temp_dir(tempdir.clone())
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<SignPsbt>();
+
+  let finalize = CommandBuilder::new(format!("wallet finalize --psbt {}", signed.psbt))
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Finalize>();
+
+  assert_eq!(finalize.reveal, Some(batch.reveal));
+
+  core.mine_blocks(1);
+
+  let inscription = batch.inscriptions[0].id;
+
+  let send = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 {inscription}"
+  ))
+  .temp_dir(tempdir.clone())
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  let psbt = Psbt::deserialize(&base64_decode(&send.psbt).unwrap()).unwrap();
+
+  let outpoint = psbt
+    .unsigned_tx
+    .input
+    .iter()
+    .zip(&psbt.inputs)
+    .find(|(_txin, input)| !input.proprietary.is_empty())
+    .unwrap()
+    .0
+    .previous_output;
+
+  {
+    let mut psbt = psbt.clone();
+
+    for input in &mut psbt.inputs {
+      input.proprietary.clear();
+    }
+
+    CommandBuilder::new(format!(
+      "wallet sign-psbt --psbt {}",
+      base64_encode(&psbt.serialize())
+    ))
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr(format!(
+      "error: input {outpoint} inscriptions or runes do not match PSBT annotations\n"
+    ))
+    .run_and_extract_stdout();
+  }
+
+  {
+    let mut psbt = psbt.clone();
+
+    for output in &mut psbt.unsigned_tx.output {
+      output.value = Amount::ZERO;
+    }
+
+    CommandBuilder::new(format!(
+      "wallet sign-psbt --psbt {}",
+      base64_encode(&psbt.serialize())
+    ))
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr(format!(
+      "error: inscription {inscription} would be lost to fees\n"
+    ))
+    .run_and_extract_stdout();
+  }
+
+  {
+    let mut psbt = psbt.clone();
+
+    for output in &mut psbt.outputs {
+      output.proprietary.clear();
+    }
+
+    CommandBuilder::new(format!(
+      "wallet sign-psbt --psbt {}",
+      base64_encode(&psbt.serialize())
+    ))
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr("error: output 0 inscriptions or runes do not match PSBT annotations\n")
+    .run_and_extract_stdout();
+  }
+
+  {
+    let mut psbt = psbt.clone();
+
+    psbt.inputs[0].proprietary.insert(
+      bitcoin::psbt::raw::ProprietaryKey {
+        prefix: b"ord".to_vec(),
+        subtype: 2,
+        key: Vec::new(),
+      },
+      Vec::new(),
+    );
+
+    CommandBuilder::new(format!(
+      "wallet sign-psbt --psbt {}",
+      base64_encode(&psbt.serialize())
+    ))
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr(format!(
+      "error: PSBT does not match transaction {} planned by wallet\n",
+      send.txid,
+    ))
+    .run_and_extract_stdout();
+  }
+}